@@ -0,0 +1,7 @@
+create table starboard
+(
+    original_id          bigint primary key not null,
+    guild_id             bigint             not null,
+    starboard_channel_id bigint             not null,
+    starboard_message_id bigint             not null
+);
@@ -0,0 +1,2 @@
+alter table message alter column encrypted_content drop not null;
+alter table message add column content_hash bytea;
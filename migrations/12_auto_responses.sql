@@ -0,0 +1,12 @@
+create table autoResponse
+(
+    id               serial primary key not null,
+    guild_id         bigint             not null,
+    trigger          text               not null,
+    response         text               not null,
+    -- if the whole message has to match instead of only containing the trigger
+    exact            bool               not null,
+    cooldown_seconds bigint             not null
+);
+
+create index auto_response_guild_index on autoResponse (guild_id);
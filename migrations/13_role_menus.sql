@@ -0,0 +1,10 @@
+create table roleMenu
+(
+    message_id bigint primary key not null,
+    guild_id   bigint             not null,
+    channel_id bigint             not null,
+    -- the emoji, role and label of every entry in the menu
+    options    jsonb              not null
+);
+
+create index role_menu_guild_index on roleMenu (guild_id);
@@ -0,0 +1,2 @@
+-- activity is counted per guild over a range of (snowflake) message ids
+create index message_guild_id_index on message (guild_id, id);
@@ -0,0 +1,7 @@
+-- the message we last pinned in each auto pin channel, so it can be unpinned again when the next one comes in
+create table auto_pin
+(
+    channel_id bigint primary key not null,
+    guild_id   bigint             not null,
+    message_id bigint             not null
+);
@@ -0,0 +1,8 @@
+create table messageReactions
+(
+    message_id bigint not null,
+    guild_id   bigint not null,
+    emoji      text   not null,
+    count      bigint not null default 0,
+    primary key (message_id, emoji)
+);
@@ -0,0 +1,2 @@
+-- finding the first message of users, and checking nobody talked before a point, for the alt lookup
+create index message_guild_author_id_index on message (guild_id, author_id, id);
@@ -0,0 +1,9 @@
+-- users we have seen talk in a guild, so the first message greeting only ever goes out once per user
+create table first_message
+(
+    guild_id bigint  not null,
+    user_id  bigint  not null,
+    -- false for users that already had messages stored before they got tracked here
+    greeted  boolean not null,
+    primary key (guild_id, user_id)
+);
@@ -0,0 +1,3 @@
+-- history brought over from other bots keeps its original timestamps, and the reason they came with (encrypted with the guild key)
+alter table history add column imported boolean not null default false;
+alter table history add column encrypted_reason bytea;
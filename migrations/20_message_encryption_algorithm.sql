@@ -0,0 +1,2 @@
+-- which AEAD the content of a message was encrypted with, 0 is AES-256-GCM which everything before this used
+alter table message add column encryption_algorithm smallint not null default 0;
@@ -0,0 +1,24 @@
+-- past names of users, usernames are the same everywhere so they don't belong to any guild
+create table username_change
+(
+    id         serial primary key not null,
+    user_id    bigint             not null,
+    old_name   text               not null,
+    new_name   text               not null,
+    changed_at timestamptz        not null default now()
+);
+
+create index username_change_user_index on username_change (user_id);
+
+-- nicknames only exist within a guild and are encrypted with its key, null when there was no nickname
+create table nickname_change
+(
+    id                 serial primary key not null,
+    guild_id           bigint             not null,
+    user_id            bigint             not null,
+    encrypted_old_name bytea              null,
+    encrypted_new_name bytea              null,
+    changed_at         timestamptz        not null default now()
+);
+
+create index nickname_change_guild_user_index on nickname_change (guild_id, user_id);
@@ -0,0 +1,3 @@
+-- reasons can be corrected after the fact, keep track of who last did so and when
+alter table history add column reason_edited_by bigint;
+alter table history add column reason_edited_at timestamptz;
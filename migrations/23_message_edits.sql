@@ -0,0 +1,14 @@
+-- edits bump the revision, content encrypted for a revision uses it in the nonce so no two versions share one
+alter table message add column revision int not null default 0;
+
+-- the versions edited messages had before, stored the same way the message content was at the time
+create table message_edit
+(
+    message_id           bigint      not null,
+    revision             int         not null,
+    encrypted_content    bytea,
+    encryption_algorithm smallint    not null,
+    content_hash         bytea,
+    edited_at            timestamptz not null default now(),
+    primary key (message_id, revision)
+);
@@ -0,0 +1,7 @@
+-- copies of attachments for guilds that opted in, encrypted with the guild key like message content
+create table attachment_blob
+(
+    attachment_id        bigint   not null primary key,
+    encrypted_content    bytea    not null,
+    encryption_algorithm smallint not null
+);
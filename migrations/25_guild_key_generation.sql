@@ -0,0 +1,2 @@
+-- bumped every time the key of a guild is replaced, the main key encrypts each generation under its own nonce
+alter table guildconfig add column key_generation int not null default 0;
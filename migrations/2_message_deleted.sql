@@ -0,0 +1,2 @@
+alter table message
+    add column deleted bool not null default false;
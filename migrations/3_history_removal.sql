@@ -0,0 +1,10 @@
+create table historyRemoval
+(
+    id         serial primary key not null,
+    history_id int                not null references history (id) unique,
+    guild_id   bigint             not null,
+    removed_by bigint             not null,
+    removed_at timestamptz        not null default now()
+);
+
+create index history_removal_guild_index on historyRemoval (guild_id);
@@ -0,0 +1,6 @@
+-- guilds currently in raid mode, with the verification level to go back to once it's turned off
+create table raid_mode
+(
+    guild_id              bigint primary key not null,
+    previous_verification smallint
+);
@@ -0,0 +1,12 @@
+create table note
+(
+    id                serial primary key not null,
+    guild_id          bigint             not null,
+    user_id           bigint             not null,
+    mod_id            bigint             not null,
+    nonce_id          bigint             not null,
+    encrypted_content bytea              not null,
+    created_at        timestamptz        not null default now()
+);
+
+create index note_guild_user_index on note (guild_id, user_id);
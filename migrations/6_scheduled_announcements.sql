@@ -0,0 +1,14 @@
+create table scheduledAnnouncement
+(
+    id         serial primary key not null,
+    guild_id   bigint             not null,
+    channel_id bigint             not null,
+    creator_id bigint             not null,
+    cron       varchar(255)       not null,
+    message    text               not null,
+    -- unix timestamp (seconds) of the next time this should be posted
+    next_run   bigint             not null
+);
+
+create index scheduled_announcement_guild_index on scheduledAnnouncement (guild_id);
+create index scheduled_announcement_next_run_index on scheduledAnnouncement (next_run);
@@ -0,0 +1,2 @@
+-- attachments are only linked to their guild through the message table, index the join key
+create index attachment_message_index on attachment (message_id);
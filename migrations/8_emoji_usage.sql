@@ -0,0 +1,7 @@
+create table emojiUsage
+(
+    guild_id bigint not null,
+    emoji_id bigint not null,
+    uses     bigint not null default 0,
+    primary key (guild_id, emoji_id)
+);
@@ -0,0 +1,2 @@
+alter table message
+    add column referenced_message_id bigint;
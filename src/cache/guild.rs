@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 use twilight_model::guild::{DefaultMessageNotificationLevel, Guild, PartialGuild, PremiumTier, VerificationLevel};
 use twilight_model::id::{ChannelId, GuildId, RoleId, UserId};
 
-use super::{is_default, Cache, CachedChannel, CachedEmoji, CachedMember, CachedRole};
+use super::{is_default, Cache, CachedChannel, CachedEmoji, CachedMember, CachedPresence, CachedRole};
+use crate::error::PermissionError;
 
 #[derive(Debug)]
 pub struct CachedGuild {
@@ -43,6 +44,10 @@ pub struct CachedGuild {
 
     //own fields
     pub complete: AtomicBool,
+    //too big to request all members up front, they get cached as they are needed
+    pub lazy: AtomicBool,
+    //only filled when presence tracking is enabled, offline members are left out
+    pub presences: RwLock<HashMap<UserId, CachedPresence>>,
     pub member_count: AtomicU64, //own field because we do not rely on the guild create info for this but rather the
 }
 
@@ -74,6 +79,8 @@ impl From<Guild> for CachedGuild {
             premium_subscription_count: guild.premium_subscription_count.unwrap_or(0),
             preferred_locale: guild.preferred_locale,
             complete: AtomicBool::new(false),
+            lazy: AtomicBool::new(false),
+            presences: RwLock::new(HashMap::new()),
             member_count: AtomicU64::new(0),
         };
 
@@ -140,6 +147,9 @@ impl CachedGuild {
             premium_subscription_count: cold_guild.premium_subscription_count,
             preferred_locale: cold_guild.preferred_locale,
             complete: AtomicBool::new(true),
+            lazy: AtomicBool::new(false),
+            // not worth storing, the presence updates fill these back in soon enough
+            presences: RwLock::new(HashMap::new()),
             member_count: AtomicU64::new(cold_guild.members.len() as u64),
         };
 
@@ -175,6 +185,7 @@ impl CachedGuild {
         guild
     }
 
+    /// Builds the updated version of this guild, anything the update doesn't include is carried over.
     pub fn update(&self, other: &PartialGuild) -> Self {
         let mut guild = CachedGuild {
             id: other.id,
@@ -194,21 +205,42 @@ impl CachedGuild {
             unavailable: false,
             members: RwLock::new(HashMap::new()),
             channels: RwLock::new(HashMap::new()),
-            max_presences: other.max_presences,
-            max_members: other.max_members,
+            max_presences: other.max_presences.or(self.max_presences),
+            max_members: other.max_members.or(self.max_members),
             description: other.description.clone(),
             banner: other.banner.clone(),
             premium_tier: other.premium_tier,
-            premium_subscription_count: other.premium_subscription_count.unwrap_or(0),
+            premium_subscription_count: other
+                .premium_subscription_count
+                .unwrap_or(self.premium_subscription_count),
             preferred_locale: other.preferred_locale.clone(),
             complete: AtomicBool::new(self.complete.load(Ordering::SeqCst)),
+            lazy: AtomicBool::new(self.lazy.load(Ordering::SeqCst)),
+            presences: RwLock::new(
+                self.presences
+                    .read()
+                    .expect("Guild inner presence cache got poisoned!")
+                    .clone(),
+            ),
             member_count: AtomicU64::new(self.member_count.load(Ordering::SeqCst)),
         };
 
         {
             let mut roles = guild.roles.write().expect("Guild inner roles cache got poisoned!");
-            for role in other.roles.values() {
-                roles.insert(role.id, Arc::new(CachedRole::from_role(role)));
+            if other.roles.is_empty() {
+                // there is always an everyone role, no roles means they weren't part of the update
+                for role in self
+                    .roles
+                    .read()
+                    .expect("Guild inner roles cache got poisoned!")
+                    .values()
+                {
+                    roles.insert(role.id, role.clone());
+                }
+            } else {
+                for role in other.roles.values() {
+                    roles.insert(role.id, Arc::new(CachedRole::from_role(role)));
+                }
             }
         }
 
@@ -244,6 +276,19 @@ impl CachedGuild {
         guild
     }
 
+    /// If only some members are cached, others need to be fetched from the api when they are needed.
+    pub fn is_lazily_cached(&self) -> bool {
+        self.lazy.load(Ordering::SeqCst)
+    }
+
+    pub fn get_presence(&self, user_id: &UserId) -> Option<CachedPresence> {
+        self.presences
+            .read()
+            .expect("Guild inner presence cache got poisoned!")
+            .get(user_id)
+            .cloned()
+    }
+
     pub fn get_role(&self, role_id: &RoleId) -> Option<Arc<CachedRole>> {
         self.roles
             .read()
@@ -252,6 +297,51 @@ impl CachedGuild {
             .cloned()
     }
 
+    /// The position of the highest role a member has, 0 (the everyone role) if they have none.
+    pub fn top_role_position(&self, member: &CachedMember) -> i64 {
+        let roles = self.roles.read().expect("Global role cache got poisoned!");
+        member
+            .roles
+            .iter()
+            .filter_map(|role_id| roles.get(role_id))
+            .map(|role| role.position)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Where a member ranks in the role hierarchy, the owner outranks everyone regardless of their roles.
+    pub fn hierarchy_position(&self, member: &CachedMember) -> i64 {
+        if member.user_id == self.owner_id {
+            i64::MAX
+        } else {
+            self.top_role_position(member)
+        }
+    }
+
+    /// Checks if the role hierarchy allows an actor to moderate a target, with the bot carrying it out.
+    ///
+    /// Both need a top role strictly above the target's, a bot that isn't cached ranks at the bottom.
+    pub fn can_act_on(
+        &self,
+        actor: &CachedMember,
+        target: &CachedMember,
+        bot: Option<&CachedMember>,
+    ) -> Result<(), PermissionError> {
+        if target.user_id == self.owner_id {
+            return Err(PermissionError::TargetIsOwner);
+        }
+
+        let target_position = self.top_role_position(target);
+        if self.hierarchy_position(actor) <= target_position {
+            return Err(PermissionError::AuthorTooLow);
+        }
+        if bot.map_or(0, |bot| self.top_role_position(bot)) <= target_position {
+            return Err(PermissionError::BotTooLow);
+        }
+
+        Ok(())
+    }
+
     pub fn get_icon_url(&self, animated: bool) -> Option<String> {
         match &self.icon {
             Some(icon) => {
@@ -469,3 +559,116 @@ impl From<Arc<CachedGuild>> for ColdStorageGuild {
         csg
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CachedGuild;
+    use serde_json::json;
+    use twilight_model::guild::{Guild, PartialGuild};
+    use twilight_model::id::{RoleId, UserId};
+
+    fn role(id: &str, name: &str, position: i64) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "color": 0,
+            "hoist": false,
+            "managed": false,
+            "mentionable": false,
+            "permissions": "104324673",
+            "position": position
+        })
+    }
+
+    fn guild_payload() -> serde_json::Value {
+        json!({
+            "id": "365498559174410241",
+            "name": "GearBot testing",
+            "icon": "a_1269e74af4df7417b13759eae50c83dc",
+            "splash": null,
+            "discovery_splash": null,
+            "owner_id": "106354106196570112",
+            "region": "europe",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 1,
+            "default_message_notifications": 1,
+            "explicit_content_filter": 2,
+            "mfa_level": 0,
+            "application_id": null,
+            "system_channel_id": null,
+            "system_channel_flags": 0,
+            "rules_channel_id": null,
+            "widget_enabled": false,
+            "widget_channel_id": null,
+            "vanity_url_code": null,
+            "description": null,
+            "banner": null,
+            "premium_tier": 1,
+            "premium_subscription_count": 4,
+            "preferred_locale": "en-US",
+            "max_members": 250000,
+            "max_presences": null,
+            "features": ["ANIMATED_ICON"],
+            "roles": [role("365498559174410241", "@everyone", 0), role("365500223482691584", "Moderators", 1)],
+            "emojis": [],
+            "joined_at": "2017-09-30T10:12:56.000000+00:00",
+            "large": false,
+            "lazy": false,
+            "unavailable": false,
+            "member_count": 3,
+            "members": [],
+            "channels": [],
+            "presences": [],
+            "voice_states": []
+        })
+    }
+
+    #[test]
+    fn update_refreshes_metadata() {
+        let guild: Guild = serde_json::from_value(guild_payload()).unwrap();
+        let cached = CachedGuild::from(guild);
+
+        // a new name, icon and owner, without the member limit or boost count
+        let mut update = guild_payload();
+        let fields = update.as_object_mut().unwrap();
+        fields.insert("name".to_string(), json!("GearBot testing grounds"));
+        fields.insert("icon".to_string(), json!("8342729096ea3675442027381ff50dfe"));
+        fields.insert("owner_id".to_string(), json!("140130139605434369"));
+        fields.insert(
+            "roles".to_string(),
+            json!([
+                role("365498559174410241", "@everyone", 0),
+                role("365500223482691585", "Admins", 1)
+            ]),
+        );
+        fields.remove("max_members");
+        fields.remove("premium_subscription_count");
+        let update: PartialGuild = serde_json::from_value(update).unwrap();
+
+        let updated = cached.update(&update);
+        assert_eq!(updated.name, "GearBot testing grounds");
+        assert_eq!(updated.icon.as_deref(), Some("8342729096ea3675442027381ff50dfe"));
+        assert_eq!(updated.owner_id, UserId(140130139605434369));
+        assert_eq!(updated.max_members, Some(250000));
+        assert_eq!(updated.premium_subscription_count, 4);
+        assert!(updated.get_role(&RoleId(365500223482691584)).is_none());
+        assert_eq!(updated.get_role(&RoleId(365500223482691585)).unwrap().name, "Admins");
+    }
+
+    #[test]
+    fn update_without_roles_keeps_them() {
+        let guild: Guild = serde_json::from_value(guild_payload()).unwrap();
+        let cached = CachedGuild::from(guild);
+
+        let mut update = guild_payload();
+        update.as_object_mut().unwrap().insert("roles".to_string(), json!([]));
+        let update: PartialGuild = serde_json::from_value(update).unwrap();
+
+        let updated = cached.update(&update);
+        assert_eq!(
+            updated.get_role(&RoleId(365500223482691584)).unwrap().name,
+            "Moderators"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use twilight_model::guild::Member;
+use twilight_model::guild::{Member, PartialMember};
 use twilight_model::id::{RoleId, UserId};
 
 use super::{is_default, Cache, CachedUser};
@@ -39,6 +40,19 @@ impl CachedMember {
         }
     }
 
+    pub fn from_partial_member(user_id: UserId, member: &PartialMember) -> Self {
+        CachedMember {
+            user_id,
+            nickname: member.nick.clone(),
+            roles: member.roles.clone(),
+            joined_at: member.joined_at.clone(),
+            // not included in partial members, it gets filled in with the next member update
+            boosting_since: None,
+            server_deafened: member.deaf,
+            server_muted: member.mute,
+        }
+    }
+
     pub fn update(&self, member: &MemberUpdate) -> Self {
         CachedMember {
             user_id: member.user.id,
@@ -51,6 +65,14 @@ impl CachedMember {
         }
     }
 
+    /// When the member joined the guild, if discord told us and it's in a format we understand.
+    pub fn join_time(&self) -> Option<DateTime<Utc>> {
+        let joined_at = self.joined_at.as_ref()?;
+        DateTime::parse_from_str(joined_at, "%FT%T%.f%z")
+            .ok()
+            .map(|joined| joined.with_timezone(&Utc))
+    }
+
     pub fn user(&self, cache: &Cache) -> Arc<CachedUser> {
         cache
             .get_user(self.user_id)
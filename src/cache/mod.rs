@@ -7,7 +7,8 @@ use log::{debug, info, trace, warn};
 use twilight_gateway::Event;
 use twilight_model::channel::{Channel, GuildChannel, PrivateChannel};
 use twilight_model::gateway::payload::RequestGuildMembers;
-use twilight_model::gateway::presence::{ActivityType, Status};
+use twilight_model::gateway::presence::{Activity, Status, UserOrId};
+use twilight_model::guild::{Member, PartialMember, Permissions, Role};
 use twilight_model::id::{ChannelId, EmojiId, GuildId, UserId};
 use twilight_model::user::User;
 
@@ -15,6 +16,7 @@ mod channel;
 mod emoji;
 mod guild;
 mod member;
+mod presence;
 mod role;
 mod user;
 
@@ -22,6 +24,7 @@ pub use channel::CachedChannel;
 pub use emoji::CachedEmoji;
 pub use guild::{CachedGuild, ColdStorageGuild};
 pub use member::CachedMember;
+pub use presence::CachedPresence;
 pub use role::CachedRole;
 pub use user::CachedUser;
 
@@ -30,9 +33,19 @@ use crate::database::Redis;
 use crate::error::{ColdResumeError, DatabaseError};
 use crate::{gearbot_error, gearbot_important, gearbot_info, gearbot_warn};
 
+/// Nonce used when re-requesting the members of a guild after resetting its cache.
+pub const CACHE_RESET_NONCE: &str = "cache_reset";
+
+/// Nonce used when requesting the members of a lazily cached guild in the background.
+pub const LAZY_CHUNK_NONCE: &str = "lazy_chunk";
+
 pub struct Cache {
     //cluster info
     cluster_id: u64,
+    //guilds with more members than this don't get them all requested when they come in
+    lazy_member_threshold: Option<u64>,
+    //presences aren't tracked at all without a limit
+    max_presences: Option<usize>,
 
     //cache
     pub guilds: RwLock<HashMap<GuildId, Arc<CachedGuild>>>,
@@ -41,6 +54,8 @@ pub struct Cache {
     pub dm_channels_by_user: RwLock<HashMap<UserId, Arc<CachedChannel>>>,
     pub users: RwLock<HashMap<UserId, Arc<CachedUser>>>,
     pub emoji: RwLock<HashMap<EmojiId, Arc<CachedEmoji>>>,
+    //our own effective permissions, per guild and channel
+    bot_permissions: RwLock<HashMap<GuildId, HashMap<ChannelId, Permissions>>>,
     //is this even possible to get accurate across multiple clusters?
     pub filling: AtomicBool,
 
@@ -52,15 +67,23 @@ pub struct Cache {
 }
 
 impl Cache {
-    pub fn new(cluster_id: u64, stats: Arc<BotStats>) -> Self {
+    pub fn new(
+        cluster_id: u64,
+        stats: Arc<BotStats>,
+        lazy_member_threshold: Option<u64>,
+        max_presences: Option<usize>,
+    ) -> Self {
         Cache {
             cluster_id,
+            lazy_member_threshold,
+            max_presences,
             guilds: RwLock::new(HashMap::new()),
             guild_channels: RwLock::new(HashMap::new()),
             private_channels: RwLock::new(HashMap::new()),
             dm_channels_by_user: RwLock::new(HashMap::new()),
             users: RwLock::new(HashMap::new()),
             emoji: RwLock::new(HashMap::new()),
+            bot_permissions: RwLock::new(HashMap::new()),
             filling: AtomicBool::new(true),
             unavailable_guilds: RwLock::new(vec![]),
             expected: RwLock::new(vec![]),
@@ -77,6 +100,10 @@ impl Cache {
             .clear();
         self.users.write().expect("Global users cache got poisoned!").clear();
         self.emoji.write().expect("Global emoji cache got poisoned").clear();
+        self.bot_permissions
+            .write()
+            .expect("Global bot permissions cache got poisoned!")
+            .clear();
         self.filling.store(true, Ordering::SeqCst);
         self.private_channels
             .write()
@@ -110,6 +137,25 @@ impl Cache {
                     self.nuke_guild_cache(cached_guild)
                 }
                 let guild = CachedGuild::from(e.0.clone());
+                let lazy = self
+                    .lazy_member_threshold
+                    .map_or(false, |threshold| e.member_count.unwrap_or(0) > threshold);
+                guild.lazy.store(lazy, Ordering::SeqCst);
+
+                if let Some(max) = self.max_presences {
+                    let mut presences = guild
+                        .presences
+                        .write()
+                        .expect("Guild inner presence cache got poisoned!");
+                    for (user_id, presence) in &e.presences {
+                        if presences.len() >= max {
+                            break;
+                        }
+                        if let Some(cached) = CachedPresence::new(presence.status, &presence.activities) {
+                            presences.insert(*user_id, cached);
+                        }
+                    }
+                }
 
                 {
                     //fine to always grab a write lock here, we update from the main loop itself
@@ -149,18 +195,27 @@ impl Cache {
                     .role_count
                     .add(guild.roles.read().expect("Guild inner roles cache got poisoned!").len() as i64);
 
-                //we usually don't need this mutable but acquire a write lock regardless to prevent potential deadlocks
-                let mut list = self.unavailable_guilds.write().unwrap();
-                if let Some(index) = list.iter().position(|id| id.0 == guild.id.0) {
-                    list.remove(index);
-                    gearbot_info!("Guild {}, ``{}`` is available again!", guild.name, guild.id);
+                {
+                    //we usually don't need this mutable but acquire a write lock regardless to prevent potential deadlocks
+                    let mut list = self.unavailable_guilds.write().unwrap();
+                    if let Some(index) = list.iter().position(|id| id.0 == guild.id.0) {
+                        list.remove(index);
+                        gearbot_info!("Guild {}, ``{}`` is available again!", guild.name, guild.id);
+                    }
                 }
 
                 self.guilds
                     .write()
                     .expect("Global guild cache got poisoned!")
                     .insert(e.id, Arc::new(guild));
-                self.stats.guild_counts.partial.inc();
+                if lazy {
+                    // nothing to wait for, the members will come in as they are needed
+                    debug!("Caching the members of {} lazily", e.id);
+                    self.stats.guild_counts.lazy.inc();
+                    self.initial_guild_loaded(shard_id, &ctx).await;
+                } else {
+                    self.stats.guild_counts.partial.inc();
+                }
             }
             Event::GuildUpdate(update) => {
                 trace!("Receive guild update for {} ({})", update.name, update.id);
@@ -178,6 +233,10 @@ impl Cache {
                         self.stats
                             .role_count
                             .add(guild.roles.read().expect("Guild inner role cache got poisoned!").len() as i64);
+                        self.guilds
+                            .write()
+                            .expect("Global guild cache got poisoned!")
+                            .insert(guild.id, Arc::new(guild));
                     }
                     None => {
                         gearbot_warn!(
@@ -226,7 +285,23 @@ impl Cache {
                             }
                         }
                         self.stats.user_counts.total.add(count);
-                        if (chunk.chunk_count - 1) == chunk.chunk_index && chunk.nonce.is_none() {
+                        let last_chunk = (chunk.chunk_count - 1) == chunk.chunk_index;
+                        if last_chunk && chunk.nonce.as_deref() == Some(CACHE_RESET_NONCE) {
+                            debug!("Finished re-processing all chunks for {} ({})", guild.name, guild.id.0);
+                            guild.complete.store(true, Ordering::SeqCst);
+                            self.stats.guild_counts.partial.dec();
+                            self.stats.guild_counts.loaded.inc();
+                        } else if last_chunk && chunk.nonce.as_deref() == Some(LAZY_CHUNK_NONCE) {
+                            debug!(
+                                "Finished processing background chunks for {} ({})",
+                                guild.name, guild.id.0
+                            );
+                            guild.complete.store(true, Ordering::SeqCst);
+                            if guild.lazy.swap(false, Ordering::SeqCst) {
+                                self.stats.guild_counts.lazy.dec();
+                                self.stats.guild_counts.loaded.inc();
+                            }
+                        } else if last_chunk && chunk.nonce.is_none() {
                             debug!(
                                 "Finished processing all chunks for {} ({}). {:?} guilds to go!",
                                 guild.name,
@@ -234,52 +309,9 @@ impl Cache {
                                 self.stats.guild_counts.partial.get()
                             );
                             guild.complete.store(true, Ordering::SeqCst);
-                            let shard_missing = self
-                                .missing_per_shard
-                                .read()
-                                .expect("Global shard state tracking got poisoned!")
-                                .get(&shard_id)
-                                .unwrap()
-                                .fetch_sub(1, Ordering::Relaxed);
-                            if shard_missing == 1 {
-                                //this shard is ready
-                                info!("All guilds cached for shard {}", shard_id);
-                                if chunk.nonce.is_none() && self.shard_cached(shard_id) {
-                                    let c = ctx.clone();
-                                    tokio::spawn(async move {
-                                        if let Err(e) = c
-                                            .set_shard_activity(
-                                                shard_id,
-                                                Status::Online,
-                                                ActivityType::Watching,
-                                                String::from("the gears turn"),
-                                            )
-                                            .await
-                                        {
-                                            gearbot_error!(
-                                                "Failed to set shard activity for shard {}: {}",
-                                                shard_id,
-                                                e
-                                            );
-                                        }
-                                    });
-                                }
-                            }
                             self.stats.guild_counts.partial.dec();
                             self.stats.guild_counts.loaded.inc();
-                            // if we where at 1 we are now at 0
-                            if self.stats.guild_counts.partial.get() == 0
-                                && self.filling.load(Ordering::Relaxed)
-                                && ctx
-                                    .shard_states
-                                    .read()
-                                    .await
-                                    .values()
-                                    .all(|state| matches!(state, ShardState::Ready))
-                            {
-                                gearbot_important!("Initial cache filling completed for cluster {}!", self.cluster_id);
-                                self.filling.store(false, Ordering::SeqCst);
-                            }
+                            self.initial_guild_loaded(shard_id, &ctx).await;
                         }
                     }
                     None => {
@@ -498,6 +530,16 @@ impl Cache {
                 }
             }
 
+            Event::PresenceUpdate(update) => {
+                let user_id = match &update.user {
+                    UserOrId::User(user) => user.id,
+                    UserOrId::UserId { id } => *id,
+                };
+                if let Some(guild) = self.get_guild(&update.guild_id) {
+                    self.update_presence(&guild, user_id, update.status, &update.activities);
+                }
+            }
+
             Event::MemberRemove(event) => {
                 debug!("{} left {}", event.user.id, event.guild_id);
                 match self.get_guild(&event.guild_id) {
@@ -508,6 +550,11 @@ impl Cache {
                         .remove(&event.user.id)
                     {
                         Some(member) => {
+                            guild
+                                .presences
+                                .write()
+                                .expect("Guild inner presence cache got poisoned!")
+                                .remove(&member.user_id);
                             let count = member.user(self).mutual_servers.fetch_sub(1, Ordering::SeqCst) - 1;
 
                             debug!("{} is now in {} mutual servers", member.user_id, count);
@@ -583,8 +630,23 @@ impl Cache {
                 ),
             },
 
+            Event::MessageCreate(msg) => {
+                // lazily cached guilds only know the members that came up before, messages tell us about the rest
+                if let (Some(guild_id), Some(member)) = (msg.guild_id, &msg.member) {
+                    if let Some(guild) = self.get_guild(&guild_id) {
+                        if guild.is_lazily_cached() {
+                            self.insert_partial_member(&guild, &msg.author, member);
+                        }
+                    }
+                }
+            }
+
             _ => {}
         };
+
+        if let Some(guild_id) = bot_permissions_affected(event, &ctx) {
+            self.forget_bot_permissions(guild_id);
+        }
     }
 
     fn guild_unavailable(&self, guild: &Arc<CachedGuild>) {
@@ -597,7 +659,46 @@ impl Cache {
         list.push(guild.id);
     }
 
+    /// Counts a guild as done for the initial cache filling of its shard, showing the status once the whole shard is.
+    async fn initial_guild_loaded(&self, shard_id: u64, ctx: &Arc<BotContext>) {
+        let shard_missing = self
+            .missing_per_shard
+            .read()
+            .expect("Global shard state tracking got poisoned!")
+            .get(&shard_id)
+            .unwrap()
+            .fetch_sub(1, Ordering::Relaxed);
+        if shard_missing == 1 {
+            //this shard is ready
+            info!("All guilds cached for shard {}", shard_id);
+            if self.shard_cached(shard_id) {
+                let c = ctx.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = c.show_current_status(shard_id).await {
+                        gearbot_error!("Failed to set shard activity for shard {}: {}", shard_id, e);
+                    }
+                });
+            }
+        }
+
+        // if we where at 1 we are now at 0
+        if self.stats.guild_counts.partial.get() == 0
+            && self.filling.load(Ordering::Relaxed)
+            && ctx
+                .shard_states
+                .read()
+                .await
+                .values()
+                .all(|state| matches!(state, ShardState::Ready))
+        {
+            gearbot_important!("Initial cache filling completed for cluster {}!", self.cluster_id);
+            self.filling.store(false, Ordering::SeqCst);
+        }
+    }
+
     fn nuke_guild_cache(&self, guild: &Arc<CachedGuild>) {
+        self.forget_bot_permissions(guild.id);
+
         {
             let mut channels = self
                 .guild_channels
@@ -644,13 +745,134 @@ impl Cache {
             .expect("Global guild cache got poisoned!")
             .remove(&guild.id);
 
-        if !guild.complete.load(Ordering::SeqCst) {
+        if guild.is_lazily_cached() {
+            self.stats.guild_counts.lazy.dec();
+        } else if !guild.complete.load(Ordering::SeqCst) {
             self.stats.guild_counts.partial.dec();
         } else {
             self.stats.guild_counts.loaded.dec();
         }
     }
 
+    /// Throws out the cached channels, roles and members of a guild, replacing the channels and roles with freshly fetched ones.
+    ///
+    /// The guild is marked as incomplete until its members are re-requested with ``CACHE_RESET_NONCE``.
+    pub fn reset_guild(&self, guild: &Arc<CachedGuild>, channels: &[GuildChannel], roles: &[Role]) {
+        {
+            let mut global_channels = self
+                .guild_channels
+                .write()
+                .expect("Global guild channels cache got poisoned!");
+            let mut guild_channels = guild
+                .channels
+                .write()
+                .expect("Guild inner channels cache got poisoned!");
+            for channel_id in guild_channels.keys() {
+                global_channels.remove(channel_id);
+            }
+            self.stats.channel_count.sub(guild_channels.len() as i64);
+            guild_channels.clear();
+
+            for channel in channels {
+                let arced = Arc::new(CachedChannel::from_guild_channel(channel, guild.id));
+                guild_channels.insert(arced.get_id(), arced.clone());
+                global_channels.insert(arced.get_id(), arced);
+            }
+            self.stats.channel_count.add(guild_channels.len() as i64);
+        }
+
+        {
+            let mut guild_roles = guild.roles.write().expect("Guild inner roles cache got poisoned!");
+            self.stats.role_count.sub(guild_roles.len() as i64);
+            guild_roles.clear();
+            for role in roles {
+                guild_roles.insert(role.id, Arc::new(CachedRole::from_role(role)));
+            }
+            self.stats.role_count.add(guild_roles.len() as i64);
+        }
+
+        {
+            let mut users = self.users.write().expect("Global user cache got poisoned!");
+            let mut members = guild.members.write().expect("Guild inner members cache got poisoned!");
+            for member in members.values() {
+                if let Some(user) = users.get(&member.user_id) {
+                    let count = user.mutual_servers.fetch_sub(1, Ordering::SeqCst) - 1;
+                    if count == 0 {
+                        users.remove(&member.user_id);
+                        self.stats.user_counts.unique.dec();
+                    }
+                }
+            }
+            self.stats.user_counts.total.sub(members.len() as i64);
+            members.clear();
+        }
+
+        if guild.complete.swap(false, Ordering::SeqCst) {
+            self.stats.guild_counts.loaded.dec();
+            self.stats.guild_counts.partial.inc();
+        } else if guild.lazy.swap(false, Ordering::SeqCst) {
+            // all members get requested again, so it's no longer lazy once they are in
+            self.stats.guild_counts.lazy.dec();
+            self.stats.guild_counts.partial.inc();
+        }
+    }
+
+    /// Caches a member that was fetched from the api rather than received over the gateway.
+    /// Whether member presences are being kept track of.
+    pub fn tracks_presences(&self) -> bool {
+        self.max_presences.is_some()
+    }
+
+    fn update_presence(&self, guild: &CachedGuild, user_id: UserId, status: Status, activities: &[Activity]) {
+        let max = match self.max_presences {
+            Some(max) => max,
+            None => return,
+        };
+        let mut presences = guild
+            .presences
+            .write()
+            .expect("Guild inner presence cache got poisoned!");
+        match CachedPresence::new(status, activities) {
+            // once full only the members we already track are kept up to date
+            Some(presence) if presences.len() < max || presences.contains_key(&user_id) => {
+                presences.insert(user_id, presence);
+            }
+            Some(_) => {}
+            None => {
+                presences.remove(&user_id);
+            }
+        }
+    }
+
+    pub fn insert_member(&self, guild: &CachedGuild, member: &Member) -> Arc<CachedMember> {
+        let mut members = guild.members.write().expect("Guild inner members cache got poisoned!");
+        if let Some(cached) = members.get(&member.user.id) {
+            return cached.clone();
+        }
+
+        let user = self.get_or_insert_user(&member.user);
+        user.mutual_servers.fetch_add(1, Ordering::SeqCst);
+        let cached = Arc::new(CachedMember::from_member(member));
+        members.insert(member.user.id, cached.clone());
+        self.stats.user_counts.total.inc();
+        cached
+    }
+
+    /// Caches a member from the partial member discord includes in messages, unless it's already known.
+    pub fn insert_partial_member(&self, guild: &CachedGuild, user: &User, member: &PartialMember) -> Arc<CachedMember> {
+        let mut members = guild.members.write().expect("Guild inner members cache got poisoned!");
+        if let Some(cached) = members.get(&user.id) {
+            return cached.clone();
+        }
+
+        let cached_user = self.get_or_insert_user(user);
+        cached_user.mutual_servers.fetch_add(1, Ordering::SeqCst);
+        let cached = Arc::new(CachedMember::from_partial_member(user.id, member));
+        members.insert(user.id, cached.clone());
+        self.stats.user_counts.total.inc();
+        cached
+    }
+
     pub fn insert_private_channel(&self, private_channel: &PrivateChannel) -> Arc<CachedChannel> {
         let channel = CachedChannel::from_private(private_channel, self);
         let arced = Arc::new(channel);
@@ -695,6 +917,31 @@ impl Cache {
         }
     }
 
+    pub fn get_bot_permissions(&self, guild_id: GuildId, channel_id: ChannelId) -> Option<Permissions> {
+        self.bot_permissions
+            .read()
+            .expect("Global bot permissions cache got poisoned!")
+            .get(&guild_id)
+            .and_then(|channels| channels.get(&channel_id))
+            .copied()
+    }
+
+    pub fn insert_bot_permissions(&self, guild_id: GuildId, channel_id: ChannelId, permissions: Permissions) {
+        self.bot_permissions
+            .write()
+            .expect("Global bot permissions cache got poisoned!")
+            .entry(guild_id)
+            .or_insert_with(HashMap::new)
+            .insert(channel_id, permissions);
+    }
+
+    pub fn forget_bot_permissions(&self, guild_id: GuildId) {
+        self.bot_permissions
+            .write()
+            .expect("Global bot permissions cache got poisoned!")
+            .remove(&guild_id);
+    }
+
     pub fn get_channel(&self, channel_id: ChannelId) -> Option<Arc<CachedChannel>> {
         match self
             .guild_channels
@@ -1092,3 +1339,26 @@ pub trait DiscordUser {
 pub trait Named {
     fn get_name(&self) -> &str;
 }
+
+/// The guild our cached permissions need to be recalculated for after this event, if any.
+fn bot_permissions_affected(event: &Event, ctx: &BotContext) -> Option<GuildId> {
+    match event {
+        Event::GuildUpdate(update) => Some(update.id),
+        Event::ChannelUpdate(channel) => channel_guild_id(&channel.0),
+        Event::ChannelDelete(channel) => channel_guild_id(&channel.0),
+        Event::MemberUpdate(event) if event.user.id == ctx.bot_user.id => Some(event.guild_id),
+        Event::MemberChunk(chunk) if chunk.members.contains_key(&ctx.bot_user.id) => Some(chunk.guild_id),
+        Event::RoleUpdate(event) => Some(event.guild_id),
+        Event::RoleDelete(event) => Some(event.guild_id),
+        _ => None,
+    }
+}
+
+fn channel_guild_id(channel: &Channel) -> Option<GuildId> {
+    match channel {
+        Channel::Guild(GuildChannel::Category(category)) => category.guild_id,
+        Channel::Guild(GuildChannel::Text(text)) => text.guild_id,
+        Channel::Guild(GuildChannel::Voice(voice)) => voice.guild_id,
+        _ => None,
+    }
+}
@@ -0,0 +1,42 @@
+use twilight_model::gateway::presence::{Activity, ActivityType, Status};
+
+use crate::utils::text;
+
+/// Activities are only shown as a hint, long custom statuses get cut off.
+const MAX_ACTIVITY_LENGTH: usize = 128;
+
+#[derive(Clone, Debug)]
+pub struct CachedPresence {
+    pub status: Status,
+    pub activity: Option<String>,
+}
+
+impl CachedPresence {
+    /// Offline (and invisible) members aren't worth keeping around, there is nothing to show for them.
+    pub fn new(status: Status, activities: &[Activity]) -> Option<Self> {
+        if matches!(status, Status::Offline | Status::Invisible) {
+            return None;
+        }
+
+        let activity = activities.first().map(|activity| {
+            let shown = match (&activity.kind, &activity.state) {
+                // custom statuses have a placeholder as name, the actual text is in the state
+                (ActivityType::Custom, Some(state)) => state,
+                _ => &activity.name,
+            };
+            text::truncate(shown, MAX_ACTIVITY_LENGTH).to_string()
+        });
+
+        Some(CachedPresence { status, activity })
+    }
+
+    /// Name of the status for the translation selectors.
+    pub fn status_name(&self) -> &'static str {
+        match self.status {
+            Status::Online => "online",
+            Status::Idle => "idle",
+            Status::DoNotDisturb => "dnd",
+            Status::Offline | Status::Invisible => "offline",
+        }
+    }
+}
@@ -84,10 +84,15 @@ impl CachedUser {
                 avatar,
                 if avatar.starts_with("a_") { "gif" } else { "png" }
             ),
-            None => format!(
-                "https://cdn.discordapp.com/embed/avatar/{}.png",
-                &self.discriminator.parse::<u16>().unwrap() % 5
-            ),
+            None => self.default_avatar_url(),
         }
     }
+
+    /// The avatar discord shows for users that didn't upload one.
+    pub fn default_avatar_url(&self) -> String {
+        format!(
+            "https://cdn.discordapp.com/embed/avatars/{}.png",
+            &self.discriminator.parse::<u16>().unwrap() % 5
+        )
+    }
 }
@@ -0,0 +1,74 @@
+use std::sync::atomic::Ordering;
+
+use twilight_model::id::GuildId;
+
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+
+/// Shows what the cache holds for a guild, and if it (or its shard) is still waiting on data.
+pub async fn cache_stats(mut ctx: CommandContext) -> CommandResult {
+    let guild_id = if ctx.parser.has_next() {
+        let id = ctx.parser.get_id()?;
+        GuildId(id)
+    } else {
+        ctx.get_guild()?.id
+    };
+
+    let bot_context = &ctx.bot_context;
+    let shard_id = (guild_id.0 >> 22) % bot_context.scheme_info.total_shards;
+    // not there when the shard was cold resumed, everything was restored in one go then
+    let shard_missing = bot_context
+        .cache
+        .missing_per_shard
+        .read()
+        .expect("Global shard state tracking got poisoned!")
+        .get(&shard_id)
+        .map(|missing| missing.load(Ordering::Relaxed));
+    let shard_status = match shard_missing {
+        Some(0) | None => String::from("all guilds cached"),
+        Some(missing) => format!("still waiting on {} guilds", missing),
+    };
+
+    let guild = match bot_context.cache.get_guild(&guild_id) {
+        Some(guild) => guild,
+        None => {
+            ctx.reply_raw(format!(
+                "Guild {} is not in the cache of this cluster (shard {}: {})",
+                guild_id, shard_id, shard_status
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let members = guild
+        .members
+        .read()
+        .expect("Guild inner members cache got poisoned!")
+        .len();
+    let roles = guild.roles.read().expect("Guild inner roles cache got poisoned!").len();
+    let channels = guild
+        .channels
+        .read()
+        .expect("Guild inner channels cache got poisoned!")
+        .len();
+
+    ctx.reply_raw(format!(
+        "Cache of **{}** (``{}``)\n**Members**: {} (guild reports {})\n**Roles**: {}\n**Channels**: {}\n**Emoji**: {}\n**Complete**: {}\n**Lazy**: {}\n**Unavailable**: {}\n**Shard {}**: {}",
+        guild.name,
+        guild.id,
+        members,
+        guild.member_count.load(Ordering::Relaxed),
+        roles,
+        channels,
+        guild.emoji.len(),
+        guild.complete.load(Ordering::SeqCst),
+        guild.lazy.load(Ordering::SeqCst),
+        guild.unavailable,
+        shard_id,
+        shard_status
+    ))
+    .await?;
+
+    Ok(())
+}
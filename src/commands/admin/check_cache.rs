@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 
 use log::info;
-use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+use twilight_embed_builder::EmbedFieldBuilder;
 use twilight_model::id::{GuildId, UserId};
 
 use crate::core::CommandContext;
@@ -81,7 +81,8 @@ pub async fn check_cache(ctx: CommandContext) -> CommandResult {
         info!("{}", out);
         out = String::from("Too long, see console");
     }
-    let e = EmbedBuilder::new()
+    let e = ctx
+        .embed_builder()?
         .field(
             EmbedFieldBuilder::new(
                 "Unique users metric",
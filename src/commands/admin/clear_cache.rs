@@ -0,0 +1,76 @@
+use twilight_model::gateway::payload::RequestGuildMembers;
+use twilight_model::id::GuildId;
+
+use crate::cache::CACHE_RESET_NONCE;
+use crate::core::CommandContext;
+use crate::error::{CommandResult, DatabaseError, OtherFailure};
+use crate::gearbot_important;
+
+pub async fn clear_cache(mut ctx: CommandContext) -> CommandResult {
+    let guild_id = if ctx.parser.has_next() {
+        let id = ctx.parser.get_id()?;
+        GuildId(id)
+    } else {
+        ctx.get_guild()?.id
+    };
+
+    let bot_context = &ctx.bot_context;
+    let guild = match bot_context.cache.get_guild(&guild_id) {
+        Some(guild) => guild,
+        None => {
+            ctx.reply_raw(format!("Guild {} is not in the cache of this cluster", guild_id))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // fetch before touching anything so a failure here leaves the old cache intact
+    let channels = bot_context.http.guild_channels(guild_id).await?;
+    let roles = bot_context.http.roles(guild_id).await?;
+
+    let member_ids: Vec<_> = guild
+        .members
+        .read()
+        .expect("Guild inner members cache got poisoned!")
+        .keys()
+        .copied()
+        .collect();
+    for user_id in &member_ids {
+        bot_context
+            .datastore
+            .cache_pool
+            .delete(&format!("user:{}", user_id))
+            .await
+            .map_err(DatabaseError::from)?;
+    }
+
+    bot_context.cache.reset_guild(&guild, &channels, &roles);
+    bot_context.forget_config(guild_id).await;
+
+    let shard_id = (guild_id.0 >> 22) % bot_context.scheme_info.total_shards;
+    let data = RequestGuildMembers::builder(guild_id)
+        .nonce(CACHE_RESET_NONCE)
+        .query("", None);
+    bot_context
+        .cluster
+        .command(shard_id, &data)
+        .await
+        .map_err(|e| OtherFailure::ShardOrCluster(e.to_string()))?;
+
+    gearbot_important!(
+        "Cache of guild {} was cleared by {}",
+        guild_id,
+        ctx.message.author.username
+    );
+
+    ctx.reply_raw(format!(
+        "Cleared the cache for {} ({} channels, {} roles reloaded), re-requesting {} members",
+        guild.name,
+        channels.len(),
+        roles.len(),
+        member_ids.len()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::core::{CommandContext, ERROR_WINDOW};
+use crate::error::CommandResult;
+
+/// Don't let an unresponsive database hold up the whole report.
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Everything there is to know about how this cluster is doing, in one go.
+pub async fn health(ctx: CommandContext) -> CommandResult {
+    let bot_context = &ctx.bot_context;
+    let datastore = &bot_context.datastore;
+    let stats = &bot_context.stats;
+
+    let postgres = ping(datastore.ping()).await;
+    let (pool_size, pool_idle) = datastore.pool_usage();
+    let postgres = format!("{} (pool: {} connections, {} idle)", postgres, pool_size, pool_idle);
+    let redis = ping(datastore.cache_pool.ping()).await;
+
+    let latencies = bot_context.get_shard_latencies().await;
+    let mut shards = String::new();
+    {
+        let states = bot_context.shard_states.read().await;
+        let mut shard_ids: Vec<_> = states.keys().copied().collect();
+        shard_ids.sort_unstable();
+        for shard_id in shard_ids {
+            let (latency, degraded) = latencies
+                .iter()
+                .find(|(id, _, _)| *id == shard_id)
+                .map_or((None, false), |(_, latency, degraded)| (*latency, *degraded));
+            let latency = match latency {
+                Some(latency) => format!("{}ms", latency.as_millis()),
+                None => String::from("unknown"),
+            };
+            shards += &format!(
+                "\n{:>4} | {:<14} | {:>8}{}",
+                shard_id,
+                format!("{:?}", states[&shard_id]),
+                latency,
+                if degraded { " (degraded)" } else { "" }
+            );
+        }
+    }
+
+    let (queued_logs, log_capacity) = bot_context.log_queue_depth();
+    let logpump = format!(
+        "{} of {} queued, {} overflowed, {} given up on",
+        queued_logs,
+        log_capacity,
+        stats.logpump_stats.overflowed_logs.get(),
+        stats.logpump_stats.dropped_logs.get()
+    );
+
+    let guilds = &stats.guild_counts;
+    let cache = format!(
+        "{} guilds ({} partial, {} lazy, {} in an outage), {} users ({} unique), {} channels, {} roles, {} emoji",
+        guilds.loaded.get(),
+        guilds.partial.get(),
+        guilds.lazy.get(),
+        guilds.outage.get(),
+        stats.user_counts.total.get(),
+        stats.user_counts.unique.get(),
+        stats.channel_count.get(),
+        stats.role_count.get(),
+        stats.emoji_count.get()
+    );
+
+    let (command_errors, event_errors) = bot_context.recent_error_counts().await;
+
+    let output = format!(
+        "Health of cluster {}\n**Postgres**: {}\n**Redis**: {}\n**Logpump**: {}\n**Cache**: {}\n**Errors in the last {} minutes**: {} commands, {} events\n**Shards**:\n```{}\n```",
+        bot_context.scheme_info.cluster_id,
+        postgres,
+        redis,
+        logpump,
+        cache,
+        ERROR_WINDOW.as_secs() / 60,
+        command_errors,
+        event_errors,
+        shards
+    );
+    ctx.reply_raw_or_file(output, "health.txt").await?;
+
+    Ok(())
+}
+
+async fn ping<E: Display>(ping: impl Future<Output = Result<Duration, E>>) -> String {
+    match tokio::time::timeout(PING_TIMEOUT, ping).await {
+        Ok(Ok(latency)) => format!("reachable, {}ms", latency.as_millis()),
+        Ok(Err(e)) => format!("unreachable ({})", e),
+        Err(_) => format!("no answer within {} seconds", PING_TIMEOUT.as_secs()),
+    }
+}
@@ -0,0 +1,80 @@
+use crate::commands::meta::nodes::CommandGroup;
+use crate::commands::ROOT_NODE;
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::gearbot_important;
+
+/// Disables a command in every guild on all clusters, for when it misbehaves and a fix isn't out yet.
+pub async fn disable_globally(mut ctx: CommandContext) -> CommandResult {
+    toggle_globally(&mut ctx, true).await
+}
+
+pub async fn enable_globally(mut ctx: CommandContext) -> CommandResult {
+    toggle_globally(&mut ctx, false).await
+}
+
+pub async fn list_globally_disabled(ctx: CommandContext) -> CommandResult {
+    let commands = ctx.bot_context.globally_disabled_commands().await;
+    if commands.is_empty() {
+        ctx.reply_raw("No commands are disabled globally").await?;
+    } else {
+        let commands = commands
+            .iter()
+            .map(|command| format!("``{}``", command))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ctx.reply_raw(format!("Commands disabled globally: {}", commands))
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn toggle_globally(ctx: &mut CommandContext, disable: bool) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_lowercase();
+
+    // it's the real name that gets checked when a command is used
+    let name = match ROOT_NODE.find_command(&input) {
+        // we'd lock ourselves out of turning it back on
+        Some(node) if node.group == CommandGroup::BotAdmin => {
+            ctx.reply_raw(format!(
+                "``{}`` is a bot admin command, those can't be disabled",
+                node.name
+            ))
+            .await?;
+            return Ok(());
+        }
+        Some(node) => node.name.clone(),
+        // commands that were removed since can still be taken off the list
+        None if !disable => input,
+        None => {
+            ctx.reply_raw(format!("There is no ``{}`` command", input)).await?;
+            return Ok(());
+        }
+    };
+
+    if !ctx.bot_context.set_globally_disabled(&name, disable).await? {
+        ctx.reply_raw(if disable {
+            format!("``{}`` is already disabled globally", name)
+        } else {
+            format!("``{}`` isn't disabled globally", name)
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let author = &ctx.message.author.username;
+    if disable {
+        gearbot_important!("{} disabled the {} command globally", author, name);
+        ctx.reply_raw(format!(
+            "``{}`` is now disabled in every server, other clusters pick this up within 10 seconds",
+            name
+        ))
+        .await?;
+    } else {
+        gearbot_important!("{} enabled the {} command globally again", author, name);
+        ctx.reply_raw(format!("``{}`` can be used again", name)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::core::{CommandContext, MaintenanceState};
+use crate::error::CommandResult;
+use crate::gearbot_important;
+
+/// Toggles maintenance mode, ``logs`` also pauses message logging while it is active.
+pub async fn maintenance(mut ctx: CommandContext) -> CommandResult {
+    if ctx.bot_context.is_in_maintenance().await {
+        ctx.bot_context.set_maintenance(None).await?;
+        gearbot_important!("{} ended maintenance mode", ctx.message.author.username);
+        ctx.reply_raw("Maintenance mode is over, commands and message logging are back")
+            .await?;
+        return Ok(());
+    }
+
+    let pause_message_logs = ctx.parser.has_next() && ctx.parser.get_next()?.eq_ignore_ascii_case("logs");
+    ctx.bot_context
+        .set_maintenance(Some(MaintenanceState { pause_message_logs }))
+        .await?;
+
+    gearbot_important!(
+        "{} started maintenance mode{}",
+        ctx.message.author.username,
+        if pause_message_logs {
+            " with message logging paused"
+        } else {
+            ""
+        }
+    );
+    ctx.reply_raw(if pause_message_logs {
+        "Maintenance mode is on and message logging is paused, use this command again to end it"
+    } else {
+        "Maintenance mode is on, use this command again to end it"
+    })
+    .await?;
+
+    Ok(())
+}
@@ -1,5 +1,35 @@
+pub use cache_stats::cache_stats;
 pub use check_cache::check_cache;
+pub use clear_cache::clear_cache;
+pub use health::health;
+pub use kill_switch::{disable_globally, enable_globally, list_globally_disabled};
+pub use maintenance::maintenance;
+pub use profile::{set_avatar, set_username};
+pub use reload_logging::reload_logging;
+pub use reload_translations::reload_translations;
 pub use restart::restart;
+pub use rotate_guild_key::rotate_guild_key;
+pub use rotate_keys::rotate_keys;
+pub use shards::shards;
+pub use test_webhooks::test_webhooks;
+pub use validate_translations::validate_translations;
+pub use verify_messages::verify_messages;
+pub use webhook_status::webhook_status;
 
+mod cache_stats;
 mod check_cache;
+mod clear_cache;
+mod health;
+mod kill_switch;
+mod maintenance;
+mod profile;
+mod reload_logging;
+mod reload_translations;
 mod restart;
+mod rotate_guild_key;
+mod rotate_keys;
+mod shards;
+mod test_webhooks;
+mod validate_translations;
+mod verify_messages;
+mod webhook_status;
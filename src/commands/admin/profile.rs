@@ -0,0 +1,132 @@
+use hyper::StatusCode;
+use twilight_http::Error;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::gearbot_important;
+use crate::utils;
+
+/// Discord refuses avatars that are any bigger than this.
+const MAX_AVATAR_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Replaces our avatar with the image attached to the command.
+pub async fn set_avatar(ctx: CommandContext) -> CommandResult {
+    let attachment = match ctx.message.attachments.first() {
+        Some(attachment) => attachment,
+        None => {
+            ctx.reply_raw("Attach the image to use as avatar").await?;
+            return Ok(());
+        }
+    };
+
+    if attachment.size > MAX_AVATAR_SIZE {
+        ctx.reply_raw(format!(
+            "That image is too big, avatars can be at most {}MB",
+            MAX_AVATAR_SIZE / 1024 / 1024
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let image = match utils::download_bytes(&attachment.url).await {
+        Ok(image) => image,
+        Err(e) => {
+            ctx.reply_raw(format!("Unable to download the image: {}", e)).await?;
+            return Ok(());
+        }
+    };
+
+    // going by what's in the file, the extension could be anything
+    let format = match image_format(&image) {
+        Some(format) => format,
+        None => {
+            ctx.reply_raw("Avatars have to be a png, jpeg or gif image").await?;
+            return Ok(());
+        }
+    };
+
+    let data = format!("data:image/{};base64,{}", format, base64::encode(&image));
+    let result = ctx.bot_context.http.update_current_user().avatar(data).await;
+    if let Err(e) = result {
+        ctx.reply_raw(format!("Discord refused the new avatar: {}", describe_error(&e)))
+            .await?;
+        return Ok(());
+    }
+
+    refresh_profile(&ctx).await?;
+    gearbot_important!("{} changed the avatar", ctx.message.author.username);
+    ctx.reply_raw("Avatar updated").await?;
+
+    Ok(())
+}
+
+/// Changes our username, discord only allows this twice an hour.
+pub async fn set_username(mut ctx: CommandContext) -> CommandResult {
+    let username = ctx.parser.get_remaining();
+    if username.is_empty() {
+        return Err(ParseError::MissingArgument.into());
+    }
+
+    // validate before spending one of the few changes we get
+    let request = match ctx.bot_context.http.update_current_user().username(username.as_str()) {
+        Ok(request) => request,
+        Err(_) => {
+            ctx.reply_raw("Usernames have to be between 2 and 32 characters long")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if let Err(wait) = ctx.bot_context.bot_user.claim_username_change() {
+        ctx.reply_raw(format!(
+            "Discord only allows 2 username changes per hour, try again in {} minutes",
+            wait.as_secs() / 60 + 1
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if let Err(e) = request.await {
+        ctx.reply_raw(format!("Discord refused the new username: {}", describe_error(&e)))
+            .await?;
+        return Ok(());
+    }
+
+    refresh_profile(&ctx).await?;
+    gearbot_important!(
+        "{} changed the username to ``{}``",
+        ctx.message.author.username,
+        username
+    );
+    ctx.reply_raw(format!("Username is now ``{}``", username)).await?;
+
+    Ok(())
+}
+
+async fn refresh_profile(ctx: &CommandContext) -> CommandResult {
+    let profile = ctx.bot_context.http.current_user().await?;
+    ctx.bot_context.bot_user.set_profile(profile);
+    Ok(())
+}
+
+fn describe_error(error: &Error) -> String {
+    match error {
+        Error::Response { status, .. } if *status == StatusCode::TOO_MANY_REQUESTS => {
+            String::from("changing it too often, try again later")
+        }
+        e => e.to_string(),
+    }
+}
+
+/// Recognizes the image formats discord takes for avatars by their signature.
+fn image_format(image: &[u8]) -> Option<&'static str> {
+    if image.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if image.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpeg")
+    } else if image.starts_with(b"GIF87a") || image.starts_with(b"GIF89a") {
+        Some("gif")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,30 @@
+use crate::core::{logging, BotConfig, CommandContext};
+use crate::error::CommandResult;
+use crate::gearbot_important;
+
+/// Re-reads the log filter from the config file and applies it without a restart.
+pub async fn reload_logging(ctx: CommandContext) -> CommandResult {
+    let logging_config = match BotConfig::reload_logging() {
+        Ok(logging_config) => logging_config,
+        Err(e) => {
+            ctx.reply_raw(format!("Unable to load the config file: {}", e)).await?;
+            return Ok(());
+        }
+    };
+
+    let filter = logging_config.filter.unwrap_or_else(|| String::from("info"));
+    if let Err(e) = logging::set_log_filter(&filter) {
+        ctx.reply_raw(format!("Invalid log filter ``{}``: {}", filter, e))
+            .await?;
+        return Ok(());
+    }
+
+    gearbot_important!(
+        "{} changed the log filter to ``{}``",
+        ctx.message.author.username,
+        filter
+    );
+    ctx.reply_raw(format!("Log filter is now ``{}``", filter)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::gearbot_important;
+use crate::translation;
+
+/// Re-reads the translation files from disk, keeping the current ones when anything is wrong with them.
+pub async fn reload_translations(ctx: CommandContext) -> CommandResult {
+    let fallback = ctx.bot_context.get_translations().fallback().clone();
+    let translations = match translation::read_translations(&fallback) {
+        Ok(translations) => translations,
+        Err(e) => {
+            ctx.reply_raw(format!(
+                "Unable to load the translations, the current ones are kept: {}",
+                e
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // guilds using a language that vanished would no longer get any replies
+    let current = ctx.bot_context.get_translations();
+    let missing: Vec<String> = current
+        .languages()
+        .filter(|lang| !translations.languages().any(|new| new == *lang))
+        .map(|lang| format!("``{}``", lang))
+        .collect();
+    if !missing.is_empty() {
+        ctx.reply_raw(format!(
+            "Not reloading, these languages would be gone: {}",
+            missing.join(", ")
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let count = translations.languages().count();
+    ctx.bot_context.set_translations(translations);
+
+    gearbot_important!("{} reloaded the translations", ctx.message.author.username);
+    ctx.reply_raw(format!("Reloaded the translations for {} languages", count))
+        .await?;
+
+    Ok(())
+}
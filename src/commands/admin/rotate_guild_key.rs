@@ -0,0 +1,46 @@
+use twilight_model::id::GuildId;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, DatabaseError};
+use crate::gearbot_important;
+
+/// Gives a guild a new encryption key, for when the old one might have gotten out.
+pub async fn rotate_guild_key(mut ctx: CommandContext) -> CommandResult {
+    let guild_id = GuildId(ctx.parser.get_id()?);
+
+    let report = match ctx.bot_context.datastore.rotate_guild_encryption_key(guild_id).await {
+        Ok(report) => report,
+        // the old key is still in place, it's up to whoever runs this to sort that content out first
+        Err(e @ DatabaseError::UnreadableDuringRotation { .. }) => {
+            ctx.reply_raw(e).await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    gearbot_important!(
+        "Encryption key of guild {} replaced by {}: re-encrypted {} messages, {} edits, {} attachments, {} other",
+        guild_id,
+        ctx.message.author.username,
+        report.messages,
+        report.edits,
+        report.attachments,
+        report.other
+    );
+
+    let mut output = format!(
+        "Guild {} has a new encryption key, re-encrypted {} messages, {} earlier versions of edited messages, \
+        {} attachments and {} notes, case reasons and nicknames",
+        guild_id, report.messages, report.edits, report.attachments, report.other
+    );
+    if report.hashes_cleared > 0 {
+        output += &format!(
+            "\nCleared {} content hashes, these were salted with the old key",
+            report.hashes_cleared
+        );
+    }
+
+    ctx.reply_raw_or_file(output, "guild_key_rotation.txt").await?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::gearbot_important;
+
+pub async fn rotate_keys(ctx: CommandContext) -> CommandResult {
+    let report = ctx.bot_context.datastore.rotate_guild_keys().await?;
+
+    gearbot_important!(
+        "Guild key rotation initiated by {}: {} rotated, {} already current, {} failed",
+        ctx.message.author.username,
+        report.rotated,
+        report.current,
+        report.failed.len()
+    );
+
+    let mut output = format!(
+        "Rotated {} guild keys, {} were already using the current main key",
+        report.rotated, report.current
+    );
+
+    if !report.failed.is_empty() {
+        let failed = report
+            .failed
+            .iter()
+            .map(|guild_id| guild_id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        output += &format!(
+            "\nUnable to decrypt the keys of {} guilds with either main key: {}",
+            report.failed.len(),
+            failed
+        );
+    }
+
+    ctx.reply_raw_or_file(output, "key_rotation.txt").await?;
+
+    Ok(())
+}
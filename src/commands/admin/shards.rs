@@ -0,0 +1,37 @@
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+
+pub async fn shards(ctx: CommandContext) -> CommandResult {
+    let latencies = ctx.bot_context.get_shard_latencies().await;
+    let states = ctx.bot_context.shard_states.read().await;
+
+    let mut output = format!("Shards of cluster {}:\n```", ctx.bot_context.scheme_info.cluster_id);
+    let mut shard_ids: Vec<_> = states.keys().copied().collect();
+    shard_ids.sort_unstable();
+
+    for shard_id in shard_ids {
+        let (latency, degraded) = latencies
+            .iter()
+            .find(|(id, _, _)| *id == shard_id)
+            .map_or((None, false), |(_, latency, degraded)| (*latency, *degraded));
+
+        let latency = match latency {
+            Some(latency) => format!("{}ms", latency.as_millis()),
+            None => String::from("unknown"),
+        };
+
+        output += &format!(
+            "\n{:>4} | {:<14} | {:>8}{}",
+            shard_id,
+            format!("{:?}", states[&shard_id]),
+            latency,
+            if degraded { " (degraded)" } else { "" }
+        );
+    }
+    output += "\n```";
+    drop(states);
+
+    ctx.reply_raw_or_file(output, "shards.txt").await?;
+
+    Ok(())
+}
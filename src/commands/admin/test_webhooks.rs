@@ -0,0 +1,51 @@
+use hyper::StatusCode;
+use twilight_http::Error;
+
+use crate::core::logging::{self, WebhookTestFailure};
+use crate::core::{BotConfig, CommandContext};
+use crate::error::CommandResult;
+
+/// Sends a test message through each logging webhook in the config file and reports which ones work.
+pub async fn test_webhooks(ctx: CommandContext) -> CommandResult {
+    let logging_config = match BotConfig::reload_logging() {
+        Ok(logging_config) => logging_config,
+        Err(e) => {
+            ctx.reply_raw(format!("Unable to load the config file: {}", e)).await?;
+            return Ok(());
+        }
+    };
+
+    let webhooks = [
+        ("important_logs", &logging_config.important_logs),
+        ("info_logs", &logging_config.info_logs),
+    ];
+
+    let mut report = String::new();
+    for (name, url) in webhooks.iter() {
+        let result = logging::test_webhook(&ctx.bot_context.http, url, &ctx.bot_context.bot_user.profile()).await;
+        let outcome = match result {
+            Ok(()) => String::from("working"),
+            Err(failure) => describe_failure(failure),
+        };
+        report += &format!("``{}``: {}\n", name, outcome);
+    }
+
+    ctx.reply_raw(report).await?;
+
+    Ok(())
+}
+
+fn describe_failure(failure: WebhookTestFailure) -> String {
+    match failure {
+        WebhookTestFailure::InvalidUrl(e) => format!("invalid webhook url ({})", e),
+        WebhookTestFailure::Execution(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => {
+            String::from("the webhook no longer exists (404)")
+        }
+        WebhookTestFailure::Execution(Error::Response { status, .. })
+            if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN =>
+        {
+            format!("missing permissions to use the webhook ({})", status.as_u16())
+        }
+        WebhookTestFailure::Execution(e) => format!("sending failed: {}", e),
+    }
+}
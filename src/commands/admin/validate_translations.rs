@@ -0,0 +1,37 @@
+use unic_langid::LanguageIdentifier;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation;
+
+/// Reports what is missing, extra or broken in the translation files of a language.
+pub async fn validate_translations(mut ctx: CommandContext) -> CommandResult {
+    let lang: LanguageIdentifier = ctx
+        .parser
+        .get_next()?
+        .parse()
+        .map_err(|_| ParseError::WrongArgumentType(String::from("language identifier")))?;
+
+    let report = match translation::validate_locale(&lang) {
+        Ok(report) => report,
+        Err(e) => {
+            ctx.reply_raw(format!("Unable to read the translations for ``{}``: {}", lang, e))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if report.is_clean() {
+        ctx.reply_raw(format!("The translations for ``{}`` are complete and valid", lang))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.reply_raw_or_file(
+        format!("Translation report for ``{}``:\n```\n{}```", lang, report),
+        &format!("translations_{}.txt", lang),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use twilight_model::id::GuildId;
+
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+
+/// Checks if the stored messages of a guild still decrypt with its key, either all of them or only the newest ones.
+pub async fn verify_messages(mut ctx: CommandContext) -> CommandResult {
+    let guild_id = GuildId(ctx.parser.get_id()?);
+    let limit = if ctx.parser.has_next() {
+        Some(ctx.parser.get_i64()?.max(1))
+    } else {
+        None
+    };
+
+    let report = ctx.bot_context.datastore.verify_guild_messages(guild_id, limit).await?;
+
+    let mut output = format!(
+        "Checked {} messages of guild {}: {} decrypted fine, {} failed, {} stored without content",
+        report.verified + report.failed + report.skipped,
+        guild_id,
+        report.verified,
+        report.failed,
+        report.skipped
+    );
+
+    if !report.failed_samples.is_empty() {
+        let samples = report
+            .failed_samples
+            .iter()
+            .map(|message_id| message_id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        output += &format!("\nSome of the messages that failed: {}", samples);
+    }
+
+    ctx.reply_raw_or_file(output, "message_verification.txt").await?;
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use hyper::StatusCode;
+use twilight_http::Error;
+use twilight_model::id::ChannelId;
+
+use crate::core::{logpump, CommandContext};
+use crate::error::{CommandResult, ParseError};
+use crate::utils::matchers;
+
+/// Reports the webhook stored for a channel and if it still exists, ``recreate`` replaces it with a new one.
+pub async fn webhook_status(mut ctx: CommandContext) -> CommandResult {
+    // the channel can be in any guild, so it doesn't have to resolve in this one
+    let input = ctx.parser.get_next()?;
+    let channel_id = matchers::get_channel_mention(input)
+        .or_else(|| input.parse().ok())
+        .map(ChannelId)
+        .ok_or_else(|| ParseError::WrongArgumentType(String::from("channel")))?;
+    let recreate = match ctx.parser.get_next().ok() {
+        None => false,
+        Some(arg) if arg.eq_ignore_ascii_case("recreate") => true,
+        Some(_) => return Err(ParseError::WrongArgumentType(String::from("recreate")).into()),
+    };
+
+    let datastore = &ctx.bot_context.datastore;
+    let (webhook_id, token) = match datastore.get_webhook_parts(channel_id).await? {
+        Some(parts) => parts,
+        None if recreate => return recreate_webhook(&ctx, channel_id).await,
+        None => {
+            ctx.reply_raw(format!("There is no webhook stored for <#{}>", channel_id))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let exists = match ctx.bot_context.http.webhook(webhook_id).token(token).await {
+        Ok(found) => found.is_some(),
+        Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => false,
+        Err(e) => {
+            ctx.reply_raw(format!(
+                "Webhook ``{}`` is stored for <#{}>, but checking if it still exists failed: {}",
+                webhook_id, channel_id, e
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if recreate {
+        datastore.remove_webhook(channel_id).await?;
+        return recreate_webhook(&ctx, channel_id).await;
+    }
+
+    let reply = if exists {
+        format!(
+            "Webhook ``{}`` is stored for <#{}> and still exists",
+            webhook_id, channel_id
+        )
+    } else {
+        format!(
+            "Webhook ``{}`` is stored for <#{}> but no longer exists, ``webhook {} recreate`` replaces it",
+            webhook_id, channel_id, channel_id
+        )
+    };
+    ctx.reply_raw(reply).await?;
+
+    Ok(())
+}
+
+async fn recreate_webhook(ctx: &CommandContext, channel_id: ChannelId) -> CommandResult {
+    let reply = match logpump::get_webhook(&ctx.bot_context, &channel_id).await {
+        Ok(Some((webhook_id, _))) => format!("Created webhook ``{}`` for <#{}>", webhook_id, channel_id),
+        Ok(None) => format!(
+            "No webhook is stored for <#{}> anymore, but I lack the permissions to create a new one",
+            channel_id
+        ),
+        Err(e) => format!("Failed to create a new webhook for <#{}>: {}", channel_id, e),
+    };
+    ctx.reply_raw(reply).await?;
+
+    Ok(())
+}
@@ -2,14 +2,12 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use chrono::Utc;
-use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+use twilight_embed_builder::EmbedFieldBuilder;
 
 use crate::core::CommandContext;
 use crate::error::{CommandResult, OtherFailure};
 use crate::translation::{FluArgs, GearBotString};
-use crate::utils::{self, Emoji};
-
-const ABOUT_EMBED_COLOR: u32 = 0x00_cea2;
+use crate::utils::Emoji;
 
 pub async fn about(ctx: CommandContext) -> CommandResult {
     let stats = &ctx.bot_context.stats;
@@ -40,7 +38,10 @@ pub async fn about(ctx: CommandContext) -> CommandResult {
         .add("gearGold", Emoji::GearGold.for_chat())
         .add("gearIron", Emoji::GearIron.for_chat())
         .add("cluster_id", ctx.bot_context.scheme_info.cluster_id)
-        .add("uptime", utils::age(ctx.bot_context.start_time, Utc::now(), 4))
+        .add(
+            "uptime",
+            ctx.format_duration(Utc::now() - ctx.bot_context.start_time, 4),
+        )
         .add("start_time", ctx.bot_context.start_time.to_rfc2822())
         .add("version", stats.version)
         .add("shards", ctx.bot_context.scheme_info.total_shards)
@@ -57,9 +58,9 @@ pub async fn about(ctx: CommandContext) -> CommandResult {
 
     let description = ctx.translate_with_args(GearBotString::AboutDescription, &args);
 
-    let embed = EmbedBuilder::new()
+    let embed = ctx
+        .embed_builder()?
         .description(description)?
-        .color(ABOUT_EMBED_COLOR)?
         .timestamp(Utc::now().to_rfc3339())
         .field(
             EmbedFieldBuilder::new("Support Server", "[Click Here](https://discord.gg/PfwZmgU)")?
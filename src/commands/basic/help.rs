@@ -1,12 +1,165 @@
+use std::sync::Arc;
+
+use crate::commands::meta::nodes::CommandNode;
+use crate::commands::ROOT_NODE;
 use crate::core::CommandContext;
 use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
 
-pub async fn help(ctx: CommandContext) -> CommandResult {
-    if let Some(_thing) = ctx.parser.peek() {
+pub async fn help(mut ctx: CommandContext) -> CommandResult {
+    if ctx.parser.has_next() {
         // user is asking about something
+        command_help(ctx).await
     } else {
         // list everything
+        overview(ctx).await
+    }
+}
+
+async fn overview(ctx: CommandContext) -> CommandResult {
+    let mut groups = String::new();
+    for group in &ROOT_NODE.groups {
+        let commands = ROOT_NODE
+            .by_group
+            .get(group)
+            .into_iter()
+            .flatten()
+            .filter(|node| can_use(&ctx, node, true))
+            .map(|node| format!("``{}``", node.name))
+            .collect::<Vec<_>>();
+        if commands.is_empty() {
+            continue;
+        }
+
+        let args = FluArgs::with_capacity(2)
+            .add("group", group.get_name())
+            .add("commands", commands.join(", "))
+            .generate();
+        groups += "\n";
+        groups += &ctx.translate_with_args(GearBotString::HelpGroup, &args);
+    }
+
+    if groups.is_empty() {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::HelpNothingAvailable, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearinfo", Emoji::Info.for_chat())
+        .add("groups", groups)
+        .generate();
+    ctx.reply(GearBotString::HelpOverview, args).await?;
+
+    Ok(())
+}
+
+async fn command_help(mut ctx: CommandContext) -> CommandResult {
+    let mut path = vec![];
+    let mut found: Option<Arc<CommandNode>> = None;
+    while ctx.parser.has_next() {
+        let part = ctx.parser.get_next()?.to_lowercase();
+        let nodes = match &found {
+            Some(node) => &node.sub_nodes,
+            None => &ROOT_NODE.all_commands,
+        };
+        let node = match nodes.get(&part) {
+            // commands people can't use stay hidden, no need to advertise them
+            Some(node) if can_use(&ctx, node, found.is_none()) => Arc::clone(node),
+            _ => {
+                path.push(part);
+                let args = FluArgs::with_capacity(2)
+                    .add("gearno", Emoji::No.for_chat())
+                    .add("command", path.join(" "))
+                    .generate();
+                ctx.reply(GearBotString::HelpUnknownCommand, args).await?;
+                return Ok(());
+            }
+        };
+        path.push(node.name.clone());
+        found = Some(node);
+    }
+
+    // there always is one, the overview is shown when nothing was asked about
+    let node = match found {
+        Some(node) => node,
+        None => return Ok(()),
+    };
+
+    let mut details = vec![match node.description {
+        Some(description) => ctx.translate(description),
+        None => ctx.translate(GearBotString::HelpNoDescription),
+    }];
+
+    if let Some(usage) = node.usage {
+        let args = FluArgs::with_capacity(2)
+            .add("command", path.join(" "))
+            .add("usage", ctx.translate(usage))
+            .generate();
+        details.push(ctx.translate_with_args(GearBotString::HelpUsage, &args));
+    }
+
+    if !node.aliases.is_empty() {
+        let aliases = node
+            .aliases
+            .iter()
+            .map(|alias| format!("``{}``", alias))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let args = FluArgs::with_capacity(1).add("aliases", aliases).generate();
+        details.push(ctx.translate_with_args(GearBotString::HelpAliases, &args));
+    }
+
+    let subcommands = node
+        .node_list
+        .iter()
+        .filter(|sub_node| can_use(&ctx, sub_node, false))
+        .map(|sub_node| format!("``{}``", sub_node.name))
+        .collect::<Vec<_>>();
+    if !subcommands.is_empty() {
+        let args = FluArgs::with_capacity(1)
+            .add("subcommands", subcommands.join(", "))
+            .generate();
+        details.push(ctx.translate_with_args(GearBotString::HelpSubcommands, &args));
+    }
+
+    if let Some(cooldown) = node.cooldown {
+        let cooldown = chrono::Duration::seconds(cooldown.as_secs() as i64);
+        let args = FluArgs::with_capacity(1)
+            .add("duration", ctx.format_duration(cooldown, 2))
+            .generate();
+        details.push(ctx.translate_with_args(GearBotString::HelpCooldown, &args));
+    }
+
+    if node.guild_only {
+        details.push(ctx.translate(GearBotString::HelpGuildOnly));
     }
 
+    let details: String = details.iter().map(|line| format!("\n{}", line)).collect();
+    let args = FluArgs::with_capacity(3)
+        .add("gearinfo", Emoji::Info.for_chat())
+        .add("command", path.join(" "))
+        .add("details", details)
+        .generate();
+    ctx.reply(GearBotString::HelpCommand, args).await?;
+
     Ok(())
 }
+
+/// Mirrors the checks the parser does before running a command, so only commands that would actually run are shown.
+///
+/// Disabling commands and running them in DMs goes by the top level command, subcommands only need the permission.
+fn can_use(ctx: &CommandContext, node: &CommandNode, top_level: bool) -> bool {
+    if !ctx.permissions.contains(node.command_permission) {
+        return false;
+    }
+    if !top_level {
+        return true;
+    }
+
+    match ctx.get_config() {
+        Ok(config) => !(node.can_be_disabled() && config.disabled_commands.contains(&node.name)),
+        Err(_) => node.works_in_dms(),
+    }
+}
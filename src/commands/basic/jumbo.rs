@@ -0,0 +1,23 @@
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{matchers, Emoji};
+
+pub async fn jumbo(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_string();
+
+    // only a single custom emoji, unicode ones have no image of their own to show
+    let mut parts = matchers::get_emoji_parts(&input);
+    let emoji = match parts.pop() {
+        Some(emoji) if parts.is_empty() && emoji.for_chat() == input => emoji,
+        _ => {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::JumboInvalid, args).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.reply_raw(emoji.url()).await?;
+
+    Ok(())
+}
@@ -1,13 +1,17 @@
 pub use about::about;
 pub use coinflip::coinflip;
 pub use help::help;
+pub use jumbo::jumbo;
 pub use ping::ping;
 pub use quote::quote;
+pub use snowflake::snowflake;
 pub use uid::uid;
 
 mod about;
 mod coinflip;
 mod help;
+mod jumbo;
 mod ping;
 mod quote;
+mod snowflake;
 mod uid;
@@ -1,24 +1,30 @@
 use twilight_model::id::MessageId;
 
 use crate::core::CommandContext;
-use crate::error::{CommandResult, ParseError};
+use crate::error::CommandResult;
 use crate::translation::{FluArgs, GearBotString};
 use crate::utils;
 
 pub async fn quote(mut ctx: CommandContext) -> CommandResult {
-    let msg_id = ctx
-        .parser
-        .get_next()?
-        .parse::<u64>()
-        .map_err(|_| ParseError::MissingArgument)?;
+    if !ctx.check_content_storage().await? {
+        return Ok(());
+    }
+
+    let msg_id = ctx.parser.get_id()?;
 
-    let guild_id = ctx.get_guild().id;
+    let guild_id = ctx.get_guild()?.id;
 
     match ctx.bot_context.fetch_user_message(MessageId(msg_id), guild_id).await? {
-        Some(msg) => {
-            let message = utils::clean(&msg.content, true, true, false, false);
-            ctx.reply_raw(message).await?;
-        }
+        Some(msg) => match msg.content {
+            Some(content) => {
+                let message = utils::clean(&content, true, true, false, false);
+                ctx.reply_raw(message).await?;
+            }
+            None => {
+                let args = FluArgs::with_capacity(0).generate();
+                ctx.reply(GearBotString::QuoteContentNotStored, args).await?;
+            }
+        },
         None => {
             let args = FluArgs::with_capacity(0).generate();
             ctx.reply(GearBotString::QuoteNotFound, args).await?;
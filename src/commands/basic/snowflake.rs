@@ -0,0 +1,40 @@
+use chrono::Utc;
+
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+pub async fn snowflake(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_string();
+    let now = Utc::now();
+
+    // everything discord hands out carries its creation time, ids from before discord or the future are made up
+    let created_at = input
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id >> 22 > 0)
+        .map(utils::snowflake_timestamp)
+        .filter(|created_at| *created_at <= now);
+
+    let created_at = match created_at {
+        Some(created_at) => created_at,
+        None => {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("input", input.replace('`', "ˋ"))
+                .generate();
+            ctx.reply(GearBotString::SnowflakeInvalid, args).await?;
+            return Ok(());
+        }
+    };
+
+    let args = FluArgs::with_capacity(3)
+        .add("id", input)
+        .add("created_at", created_at.format("%F %T UTC").to_string())
+        .add("age", ctx.format_duration(now - created_at, 2))
+        .generate();
+    ctx.reply(GearBotString::SnowflakeInfo, args).await?;
+
+    Ok(())
+}
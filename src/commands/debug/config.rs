@@ -1,18 +1,58 @@
-use crate::core::{CommandContext, GuildConfig};
-use crate::error::{CommandResult, DatabaseError};
+use chrono_tz::Tz;
+use twilight_embed_builder::ImageSource;
+use twilight_model::guild::Permissions;
+use twilight_model::id::{ChannelId, RoleId};
+
+use crate::commands::meta::nodes::CommandGroup;
+use crate::commands::ROOT_NODE;
+use crate::core::reactors::gen_config_page;
+use crate::core::{
+    AutomodActionKind, CommandContext, ConfigExport, EmbedFooterConfig, ErrorVerbosity, GuildConfig, LogCategory,
+    LogChannelConfig, LogStyle, Reactor, ResponseCleanup, SystemMessageStorage, MAX_DELETE_GRACE,
+    MAX_EMBED_FOOTER_LENGTH, MAX_MESSAGE_LENGTH, MAX_NICKNAME_LENGTH, MAX_RESPONSE_CLEANUP_DELAY,
+};
+use crate::error::{CommandResult, DatabaseError, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+/// Exported configs are small, anything bigger than this is not something we made.
+const MAX_IMPORT_SIZE: u64 = 256 * 1024;
+
+/// Shows the current settings of the guild in a paged embed, with how to change them.
+pub async fn config_overview(ctx: CommandContext) -> CommandResult {
+    let config = ctx.get_config()?;
+    let page = gen_config_page(0, &config)?;
+
+    let message = ctx.reply_embed(page).await?;
+    Reactor::new_config().save(&ctx.bot_context, message.id).await?;
+    for emoji in &[Emoji::Left, Emoji::Right] {
+        ctx.bot_context
+            .http
+            .create_reaction(message.channel_id, message.id, emoji.to_reaction())
+            .await?;
+    }
+
+    Ok(())
+}
 
 pub async fn get_config(ctx: CommandContext) -> CommandResult {
     let stringified_config = serde_json::to_string(&ctx.get_config()?).map_err(DatabaseError::Deserializing)?;
 
-    ctx.reply_raw(stringified_config).await?;
+    ctx.reply_raw_or_file(stringified_config, "config.json").await?;
 
     Ok(())
 }
 
 pub async fn get_config_pretty(ctx: CommandContext) -> CommandResult {
     let stringified_config = serde_json::to_string_pretty(&ctx.get_config()?).map_err(DatabaseError::Deserializing)?;
+    let formatted = format!("```json\n{}```", stringified_config);
 
-    ctx.reply_raw(format!("```json\n{}```", stringified_config)).await?;
+    // the codeblock is only for readability in chat, don't put it in the file
+    if formatted.chars().count() <= MAX_MESSAGE_LENGTH {
+        ctx.reply_raw(formatted).await?;
+    } else {
+        ctx.reply_raw_or_file(stringified_config, "config.json").await?;
+    }
 
     Ok(())
 }
@@ -30,3 +70,1105 @@ pub async fn reset_config(ctx: CommandContext) -> CommandResult {
     ctx.reply_raw("Config reset to default").await?;
     Ok(())
 }
+
+pub async fn export_config(ctx: CommandContext) -> CommandResult {
+    let guild = ctx.get_guild()?;
+    let config = match ctx.bot_context.datastore.export_guild_config(guild.id.0).await? {
+        Some(config) => config,
+        None => (*ctx.get_config()?).clone(),
+    };
+
+    let export = ConfigExport::new(guild, config);
+    let stringified_export = serde_json::to_string_pretty(&export).map_err(DatabaseError::Serializing)?;
+    ctx.reply_raw_or_file(stringified_export, "config_export.json").await?;
+
+    Ok(())
+}
+
+pub async fn import_config(mut ctx: CommandContext) -> CommandResult {
+    let input = match ctx.message.attachments.first() {
+        Some(attachment) if attachment.size <= MAX_IMPORT_SIZE => utils::download_text(&attachment.url).await.ok(),
+        Some(_) => None,
+        None => Some(ctx.parser.get_remaining()),
+    };
+
+    let export = match input.and_then(|input| serde_json::from_str::<ConfigExport>(&input).ok()) {
+        Some(export) => export,
+        None => {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::ConfigImportInvalid, args).await?;
+            return Ok(());
+        }
+    };
+
+    let guild = ctx.get_guild()?;
+    let (config, skipped) = export.into_config_for(guild);
+    ctx.bot_context
+        .datastore
+        .import_guild_config(guild.id.0, &config)
+        .await?;
+    ctx.bot_context.invalidate_config(guild.id).await;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("count", skipped.len())
+        .add("skipped", skipped.join(", ").replace('`', "ˋ"))
+        .generate();
+    if skipped.is_empty() {
+        ctx.reply_success(GearBotString::ConfigImported, args).await?;
+    } else {
+        // the skipped settings are worth a mention, even in quiet mode
+        ctx.reply(GearBotString::ConfigImportedWithSkips, args).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn set_embed_color(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?;
+    let hex = input.trim_start_matches('#').trim_start_matches("0x");
+
+    let color = match u32::from_str_radix(hex, 16) {
+        Ok(color) if color <= 0xFF_FFFF => color,
+        _ => return Err(ParseError::WrongArgumentType(String::from("hex color (like #00cea2)")).into()),
+    };
+
+    let mut config = (*ctx.get_config()?).clone();
+    config.embed_color = Some(color);
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!("Embed color updated to #{:06x}", color)).await?;
+    Ok(())
+}
+
+pub async fn set_timezone(mut ctx: CommandContext) -> CommandResult {
+    let timezone = ctx
+        .parser
+        .get_next()?
+        .parse::<Tz>()
+        .map_err(|_| ParseError::WrongArgumentType(String::from("timezone name (like Europe/Brussels)")))?;
+
+    let mut config = (*ctx.get_config()?).clone();
+    config.timezone = Some(timezone.name().to_string());
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!("Timezone updated to {}", timezone.name()))
+        .await?;
+    Ok(())
+}
+
+pub async fn set_embed_footer(mut ctx: CommandContext) -> CommandResult {
+    let text = ctx.parser.get_remaining();
+    let mut config = (*ctx.get_config()?).clone();
+
+    if text.is_empty() {
+        let reply = match &config.embed_footer {
+            Some(footer) => format!("The embed footer is currently set to: {}", footer.text),
+            None => String::from("No embed footer is set"),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    if text.eq_ignore_ascii_case("off") {
+        config.embed_footer = None;
+        ctx.set_config(config).await?;
+        ctx.reply_raw("Embed footer removed").await?;
+        return Ok(());
+    }
+
+    if text.chars().count() > MAX_EMBED_FOOTER_LENGTH {
+        ctx.reply_raw(format!(
+            "{} The embed footer can be at most {} characters long",
+            Emoji::No.for_chat(),
+            MAX_EMBED_FOOTER_LENGTH
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let icon_url = config.embed_footer.take().and_then(|footer| footer.icon_url);
+    config.embed_footer = Some(EmbedFooterConfig { text, icon_url });
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw("Embed footer updated").await?;
+    Ok(())
+}
+
+pub async fn set_embed_footer_icon(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_string();
+    let mut config = (*ctx.get_config()?).clone();
+
+    let footer = match config.embed_footer.as_mut() {
+        Some(footer) => footer,
+        None => {
+            ctx.reply_raw(format!(
+                "{} Set an embed footer text before giving it an icon",
+                Emoji::No.for_chat()
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if input.eq_ignore_ascii_case("off") {
+        footer.icon_url = None;
+    } else if input.starts_with("https://") && ImageSource::url(input.as_str()).is_ok() {
+        footer.icon_url = Some(input);
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("https image link")).into());
+    }
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw("Embed footer icon updated").await?;
+    Ok(())
+}
+
+/// Shows or changes how long command responses stay around before they get cleaned up.
+pub async fn set_response_cleanup(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let reply = match &config.response_cleanup {
+            Some(cleanup) if cleanup.include_command => format!(
+                "Command responses and the commands themselves are deleted after {} seconds",
+                cleanup.delay_seconds
+            ),
+            Some(cleanup) => format!("Command responses are deleted after {} seconds", cleanup.delay_seconds),
+            None => String::from("Command responses are not cleaned up"),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    if input.eq_ignore_ascii_case("off") {
+        config.response_cleanup = None;
+        ctx.set_config(config).await?;
+        ctx.reply_raw("Command responses will no longer be cleaned up").await?;
+        return Ok(());
+    }
+
+    let delay_seconds = input
+        .parse::<u64>()
+        .ok()
+        .filter(|delay| *delay > 0 && *delay <= MAX_RESPONSE_CLEANUP_DELAY)
+        .ok_or_else(|| ParseError::WrongArgumentType(format!("number between 1 and {}", MAX_RESPONSE_CLEANUP_DELAY)))?;
+    let include_command = ctx.parser.has_next() && ctx.parser.get_next()?.eq_ignore_ascii_case("command");
+    config.response_cleanup = Some(ResponseCleanup {
+        delay_seconds,
+        include_command,
+    });
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!("Responses will be deleted after {} seconds", delay_seconds))
+        .await?;
+    Ok(())
+}
+
+/// Shows or changes how long delete logs are held back, in case the author reposts the message.
+pub async fn set_delete_grace(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let reply = match config.message_logs.delete_grace {
+            Some(grace) => format!(
+                "Deletes are logged after {} seconds, unless the author reposts the message before that",
+                grace
+            ),
+            None => String::from("Deletes are logged right away"),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    if input.eq_ignore_ascii_case("off") {
+        config.message_logs.delete_grace = None;
+        ctx.set_config(config).await?;
+        ctx.reply_raw("Deletes will be logged right away").await?;
+        return Ok(());
+    }
+
+    let grace = input
+        .parse::<u64>()
+        .ok()
+        .filter(|grace| *grace > 0 && *grace <= MAX_DELETE_GRACE)
+        .ok_or_else(|| ParseError::WrongArgumentType(format!("number between 1 and {}", MAX_DELETE_GRACE)))?;
+    config.message_logs.delete_grace = Some(grace);
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!(
+        "Deletes will be logged after {} seconds, unless the author reposts the message before that",
+        grace
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes the nickname the bot goes by in this guild.
+pub async fn set_bot_nickname(mut ctx: CommandContext) -> CommandResult {
+    let nickname = ctx.parser.get_remaining();
+    let mut config = (*ctx.get_config()?).clone();
+
+    if nickname.is_empty() {
+        let reply = match &config.bot_nickname {
+            Some(nickname) => format!("My nickname is set to: {}", nickname),
+            None => String::from("No nickname is set for me"),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    let nickname = if nickname.eq_ignore_ascii_case("off") {
+        None
+    } else if nickname.chars().count() > MAX_NICKNAME_LENGTH {
+        ctx.reply_raw(format!(
+            "{} Nicknames can be at most {} characters long",
+            Emoji::No.for_chat(),
+            MAX_NICKNAME_LENGTH
+        ))
+        .await?;
+        return Ok(());
+    } else {
+        Some(nickname)
+    };
+
+    config.bot_nickname = nickname.clone();
+    ctx.set_config(config).await?;
+
+    if !ctx.bot_has_guild_permissions(Permissions::CHANGE_NICKNAME) {
+        ctx.reply_raw(format!(
+            "{} Nickname saved, but I'm not allowed to change my nickname here so it won't show until I am",
+            Emoji::Warn.for_chat()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.get_guild()?.id;
+    ctx.bot_context
+        .apply_bot_nickname(guild_id, nickname.as_deref())
+        .await?;
+
+    let reply = match nickname {
+        Some(nickname) => format!("I'll go by {} from now on", nickname),
+        None => String::from("Nickname removed"),
+    };
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+/// Shows or changes if command responses use embeds or plain text.
+pub async fn set_plaintext_responses(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(if config.plaintext_responses {
+            "Command responses are sent as plain text"
+        } else {
+            "Command responses use embeds"
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.plaintext_responses = if input.eq_ignore_ascii_case("on") {
+        true
+    } else if input.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("on or off")).into());
+    };
+    let plaintext = config.plaintext_responses;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(if plaintext {
+        "Command responses will be sent as plain text from now on"
+    } else {
+        "Command responses will use embeds again"
+    })
+    .await?;
+    Ok(())
+}
+
+pub async fn set_quiet_mode(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(if config.quiet_mode {
+            "Quiet mode is on, successful commands only get a reaction"
+        } else {
+            "Quiet mode is off, successful commands get a reply"
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.quiet_mode = if input.eq_ignore_ascii_case("on") {
+        true
+    } else if input.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("on or off")).into());
+    };
+    let quiet = config.quiet_mode;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(if quiet {
+        "Quiet mode enabled, successful commands will only get a reaction from now on"
+    } else {
+        "Quiet mode disabled, successful commands will get a reply again"
+    })
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes if command messages get deleted after they were handled.
+pub async fn set_delete_commands(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(if config.delete_command_messages {
+            "Command messages are deleted after they were handled"
+        } else {
+            "Command messages are left alone"
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.delete_command_messages = if input.eq_ignore_ascii_case("on") {
+        true
+    } else if input.eq_ignore_ascii_case("off") {
+        false
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("on or off")).into());
+    };
+    let delete = config.delete_command_messages;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(if delete {
+        "Command messages will be deleted after they were handled from now on, as long as I have permission to"
+    } else {
+        "Command messages will no longer be deleted"
+    })
+    .await?;
+    Ok(())
+}
+
+pub async fn set_error_verbosity(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(match config.error_verbosity {
+            ErrorVerbosity::Generic => "Command errors only show a generic message and an error id",
+            ErrorVerbosity::Detailed => "Command errors show what went wrong, along with an error id",
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.error_verbosity = if input.eq_ignore_ascii_case("generic") {
+        ErrorVerbosity::Generic
+    } else if input.eq_ignore_ascii_case("detailed") {
+        ErrorVerbosity::Detailed
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("generic or detailed")).into());
+    };
+    let verbosity = config.error_verbosity;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(match verbosity {
+        ErrorVerbosity::Generic => "Command errors will only show a generic message from now on",
+        ErrorVerbosity::Detailed => "Command errors will show what went wrong from now on",
+    })
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes if system messages without content (joins, boosts, pins, ...) get stored.
+pub async fn set_system_messages(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(match config.message_logs.empty_system_messages {
+            SystemMessageStorage::Marker => "Empty system messages are stored without content",
+            SystemMessageStorage::Skip => "Empty system messages are not stored",
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.message_logs.empty_system_messages = if input.eq_ignore_ascii_case("marker") {
+        SystemMessageStorage::Marker
+    } else if input.eq_ignore_ascii_case("skip") {
+        SystemMessageStorage::Skip
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("marker or skip")).into());
+    };
+    let storage = config.message_logs.empty_system_messages;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(match storage {
+        SystemMessageStorage::Marker => "Empty system messages will be stored without content from now on",
+        SystemMessageStorage::Skip => "Empty system messages will no longer be stored",
+    })
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes if message content gets stored at all, turning it off keeps only who sent what when.
+pub async fn set_content_storage(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(if config.message_logs.disable_content_storage {
+            "Message content storage is off, only who sent which message when is stored"
+        } else {
+            "Message content storage is on"
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.message_logs.disable_content_storage = if input.eq_ignore_ascii_case("on") {
+        false
+    } else if input.eq_ignore_ascii_case("off") {
+        true
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("on or off")).into());
+    };
+    let disabled = config.message_logs.disable_content_storage;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(if disabled {
+        "Message content will no longer be stored, content that was already stored is kept"
+    } else {
+        "Message content will be stored again from now on"
+    })
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes the channel members get welcomed in with an image as they join.
+pub async fn set_welcome_image(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let reply = match config.welcome_image.channel {
+            Some(channel_id) => format!("Members get welcomed with an image in <#{}>", channel_id),
+            None => String::from("Welcome images are off"),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    let channel_id = match ctx.parser.peek() {
+        Some(input) if input.eq_ignore_ascii_case("off") => None,
+        _ => Some(ctx.parser.get_channel()?.get_id()),
+    };
+    config.welcome_image.channel = channel_id;
+    ctx.set_config(config).await?;
+
+    let channel_id = match channel_id {
+        Some(channel_id) => channel_id,
+        None => {
+            ctx.reply_raw("Welcome images turned off").await?;
+            return Ok(());
+        }
+    };
+    ctx.reply_raw(format!("Members will be welcomed with an image in <#{}>", channel_id))
+        .await?;
+
+    let needed = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES;
+    if !ctx.bot_has_permissions_in_channel(channel_id, needed) {
+        ctx.reply_raw(format!(
+            "{} I need permission to send messages and attach files in <#{}> to post them",
+            Emoji::Warn.for_chat(),
+            channel_id
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Changes or removes the image the avatar and name are drawn onto in welcome images.
+pub async fn set_welcome_background(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_string();
+    let guild_id = ctx.get_guild()?.id;
+    let mut config = (*ctx.get_config()?).clone();
+
+    if input.eq_ignore_ascii_case("off") {
+        config.welcome_image.background = None;
+        ctx.set_config(config).await?;
+        ctx.bot_context.forget_welcome_background(guild_id).await;
+        ctx.reply_raw("Welcome images will use a plain background").await?;
+        return Ok(());
+    }
+
+    if !input.starts_with("https://") {
+        return Err(ParseError::WrongArgumentType(String::from("https image link")).into());
+    }
+
+    // try it out right away, a broken link would otherwise only show up as plain backgrounds later on
+    if let Err(e) = ctx.bot_context.get_welcome_background(guild_id, &input).await {
+        ctx.reply_raw(format!(
+            "{} Unable to use that image as background: {}",
+            Emoji::No.for_chat(),
+            e
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    config.welcome_image.background = Some(input);
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw("Welcome image background updated").await?;
+    Ok(())
+}
+
+/// Changes the color of the name and member number on welcome images.
+pub async fn set_welcome_color(mut ctx: CommandContext) -> CommandResult {
+    let input = ctx.parser.get_next()?;
+    let hex = input.trim_start_matches('#').trim_start_matches("0x");
+
+    let color = match u32::from_str_radix(hex, 16) {
+        Ok(color) if color <= 0xFF_FFFF => color,
+        _ => return Err(ParseError::WrongArgumentType(String::from("hex color (like #ffffff)")).into()),
+    };
+
+    let mut config = (*ctx.get_config()?).clone();
+    config.welcome_image.text_color = color;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!("Welcome image text color updated to #{:06x}", color))
+        .await?;
+    Ok(())
+}
+
+/// Shows or changes if role menu messages get updated when one of their roles is deleted.
+pub async fn set_role_menu_messages(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        ctx.reply_raw(if config.keep_role_menu_messages {
+            "Role menu messages are left as they are when one of their roles gets deleted"
+        } else {
+            "Role menu messages are updated when one of their roles gets deleted"
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let input = ctx.parser.get_next()?;
+    config.keep_role_menu_messages = if input.eq_ignore_ascii_case("update") {
+        false
+    } else if input.eq_ignore_ascii_case("keep") {
+        true
+    } else {
+        return Err(ParseError::WrongArgumentType(String::from("update or keep")).into());
+    };
+    let keep = config.keep_role_menu_messages;
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(if keep {
+        "Role menu messages will be left alone when one of their roles gets deleted, the option just stops working"
+    } else {
+        "Role menu messages will drop the option of a deleted role, or be removed when no options are left"
+    })
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes the channels messages never get stored for.
+pub async fn set_storage_exclusion(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let message_logs = &config.message_logs;
+        let channels = if message_logs.excluded_channels.is_empty() {
+            String::from("No channels are excluded from message storage")
+        } else {
+            format!(
+                "Messages are never stored for {}",
+                mention_channels(&message_logs.excluded_channels)
+            )
+        };
+        let nsfw = if message_logs.exclude_nsfw_channels {
+            "Messages in NSFW channels are not stored"
+        } else {
+            "Messages in NSFW channels are stored like any other"
+        };
+        ctx.reply_raw(format!("{}\n{}", channels, nsfw)).await?;
+        return Ok(());
+    }
+
+    let nsfw = ctx
+        .parser
+        .peek()
+        .map_or(false, |input| input.eq_ignore_ascii_case("nsfw"));
+    // only channels that exist in this guild can be added, ids of removed ones can still be cleaned up
+    let channel_id = if nsfw {
+        ctx.parser.get_next()?;
+        None
+    } else {
+        let input = ctx.parser.peek().cloned().unwrap_or_default();
+        match ctx.parser.get_channel() {
+            Ok(channel) => Some(channel.get_id()),
+            Err(e) => match input.parse().ok().map(ChannelId) {
+                Some(channel_id) if config.message_logs.excluded_channels.contains(&channel_id) => Some(channel_id),
+                _ => return Err(e.into()),
+            },
+        }
+    };
+
+    let exclude = match ctx.parser.get_next()? {
+        input if input.eq_ignore_ascii_case("on") => true,
+        input if input.eq_ignore_ascii_case("off") => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("on or off")).into()),
+    };
+
+    let message_logs = &mut config.message_logs;
+    let reply = match channel_id {
+        Some(channel_id) => {
+            message_logs
+                .excluded_channels
+                .retain(|excluded| *excluded != channel_id);
+            if exclude {
+                message_logs.excluded_channels.push(channel_id);
+                format!(
+                    "Messages in <#{}> will no longer be stored, messages that were already stored are kept",
+                    channel_id
+                )
+            } else {
+                format!("Messages in <#{}> will be stored again from now on", channel_id)
+            }
+        }
+        None => {
+            message_logs.exclude_nsfw_channels = exclude;
+            if exclude {
+                String::from(
+                    "Messages in NSFW channels will no longer be stored, messages that were already stored are kept",
+                )
+            } else {
+                String::from("Messages in NSFW channels will be stored again from now on")
+            }
+        }
+    };
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+/// What to tell the user when changing which channel gets the logs of a category.
+struct LogChannelReplies {
+    list: GearBotString,
+    none: GearBotString,
+    set: GearBotString,
+    disabled: GearBotString,
+}
+
+/// Shows or changes the channel message edits and deletions get logged to.
+pub async fn set_logged_command(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        if config.logged_commands.is_empty() {
+            ctx.reply_raw("No commands are being logged").await?;
+        } else {
+            let names: Vec<String> = config
+                .logged_commands
+                .iter()
+                .map(|name| format!("``{}``", name))
+                .collect();
+            ctx.reply_raw(format!("Using these commands gets logged: {}", names.join(", ")))
+                .await?;
+        }
+        return Ok(());
+    }
+
+    let name = match ROOT_NODE.find_command(&ctx.parser.get_next()?) {
+        Some(node) => node.name.clone(),
+        None => return Err(ParseError::WrongArgumentType(String::from("command name")).into()),
+    };
+
+    let enable = match ctx.parser.get_next()? {
+        input if input.eq_ignore_ascii_case("on") => true,
+        input if input.eq_ignore_ascii_case("off") => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("on or off")).into()),
+    };
+
+    config.logged_commands.retain(|logged| logged != &name);
+    if enable {
+        config.logged_commands.push(name.clone());
+    }
+    let has_log_channel = !config.get_log_channels(&LogCategory::GENERAL).is_empty();
+    ctx.set_config(config).await?;
+
+    let reply = if !enable {
+        format!("The ``{}`` command is no longer logged", name)
+    } else if has_log_channel {
+        format!(
+            "Every successful use of the ``{}`` command will be logged from now on",
+            name
+        )
+    } else {
+        format!(
+            "Every successful use of the ``{}`` command will be logged once general logs have a channel",
+            name
+        )
+    };
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+/// Shows or changes the permission group commands require in this guild, instead of their usual permissions.
+pub async fn set_command_override(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        if config.command_overrides.is_empty() {
+            ctx.reply_raw("All commands require the permissions they normally do")
+                .await?;
+        } else {
+            let mut overrides: Vec<String> = config
+                .command_overrides
+                .iter()
+                .map(|(command, group)| format!("``{}``: {}", command, group))
+                .collect();
+            overrides.sort();
+            ctx.reply_raw(format!(
+                "These commands need a permission group (or one with a higher priority):\n{}",
+                overrides.join("\n")
+            ))
+            .await?;
+        }
+        return Ok(());
+    }
+
+    let node = match ROOT_NODE.find_command(&ctx.parser.get_next()?) {
+        Some(node) => node,
+        None => return Err(ParseError::WrongArgumentType(String::from("command name")).into()),
+    };
+    if node.group == CommandGroup::BotAdmin {
+        ctx.reply_raw(format!("The permissions for ``{}`` can't be changed", node.name))
+            .await?;
+        return Ok(());
+    }
+
+    let group_name = ctx.parser.get_remaining();
+    if group_name.is_empty() {
+        let reply = match config.command_overrides.get(&node.name) {
+            Some(group) => format!("``{}`` can be used by {} and up", node.name, group),
+            None => format!("``{}`` requires the permissions it normally does", node.name),
+        };
+        ctx.reply_raw(reply).await?;
+        return Ok(());
+    }
+
+    let reply = if group_name.eq_ignore_ascii_case("default") {
+        config.command_overrides.remove(&node.name);
+        format!("``{}`` requires the permissions it normally does again", node.name)
+    } else {
+        let group = match config
+            .permission_groups
+            .iter()
+            .find(|group| group.name.eq_ignore_ascii_case(&group_name))
+        {
+            Some(group) => group.name.clone(),
+            None => return Err(ParseError::WrongArgumentType(String::from("permission group name")).into()),
+        };
+        let reply = format!("``{}`` can now be used by {} and up", node.name, group);
+        config.command_overrides.insert(node.name.clone(), group);
+        reply
+    };
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+/// Shows or changes which automod action wins when a message breaks several rules at once.
+pub async fn set_automod_order(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    let input = ctx.parser.get_remaining();
+    if input.trim().is_empty() {
+        let order: Vec<&str> = config.automod.action_order().iter().map(|kind| kind.name()).collect();
+        ctx.reply_raw(format!(
+            "When a message breaks several automod rules, the first of these actions wins: {}",
+            order.join(" > ")
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    config.automod.action_precedence = if input.trim().eq_ignore_ascii_case("default") {
+        vec![]
+    } else {
+        let mut precedence = vec![];
+        for name in input.split(|c: char| c == '>' || c == ',' || c.is_whitespace()) {
+            if name.is_empty() {
+                continue;
+            }
+            match AutomodActionKind::from_name(name) {
+                Some(kind) if !precedence.contains(&kind) => precedence.push(kind),
+                Some(_) => {}
+                None => {
+                    return Err(ParseError::WrongArgumentType(String::from("ban, mute, warn, delete or alert")).into())
+                }
+            }
+        }
+        precedence
+    };
+    let order: Vec<&str> = config.automod.action_order().iter().map(|kind| kind.name()).collect();
+    ctx.set_config(config).await?;
+
+    ctx.reply_raw(format!(
+        "When a message breaks several automod rules, the first of these actions wins from now on: {}",
+        order.join(" > ")
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Shows or changes the roles that don't have to wait out command cooldowns.
+pub async fn set_cooldown_bypass(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        if config.cooldown_bypass_roles.is_empty() {
+            ctx.reply_raw("No roles bypass command cooldowns").await?;
+        } else {
+            let roles: Vec<String> = config
+                .cooldown_bypass_roles
+                .iter()
+                .map(|role_id| format!("<@&{}>", role_id))
+                .collect();
+            ctx.reply_raw(format!("These roles bypass command cooldowns: {}", roles.join(", ")))
+                .await?;
+        }
+        return Ok(());
+    }
+
+    // only roles that exist in this guild can be added, ids of removed ones can still be cleaned up
+    let input = ctx.parser.peek().cloned().unwrap_or_default();
+    let role_id = match ctx.parser.get_role() {
+        Ok(role) => role.id,
+        Err(e) => match input.parse().ok().map(RoleId) {
+            Some(role_id) if config.cooldown_bypass_roles.contains(&role_id) => role_id,
+            _ => return Err(e.into()),
+        },
+    };
+
+    let enable = match ctx.parser.get_next()? {
+        input if input.eq_ignore_ascii_case("on") => true,
+        input if input.eq_ignore_ascii_case("off") => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("on or off")).into()),
+    };
+
+    config.cooldown_bypass_roles.retain(|bypassing| *bypassing != role_id);
+    if enable {
+        config.cooldown_bypass_roles.push(role_id);
+    }
+    ctx.set_config(config).await?;
+
+    let reply = if enable {
+        format!(
+            "Members with <@&{}> no longer have to wait out command cooldowns",
+            role_id
+        )
+    } else {
+        format!("Members with <@&{}> have to wait out command cooldowns again", role_id)
+    };
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+/// Shows or changes the channels commands are (not) answered in.
+pub async fn set_command_channels(mut ctx: CommandContext) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let channels = &config.command_channels;
+        let allowed = if channels.allowed.is_empty() {
+            String::from("Commands can be used in every channel")
+        } else {
+            format!("Commands can only be used in {}", mention_channels(&channels.allowed))
+        };
+        let ignored = if channels.ignored.is_empty() {
+            String::from("No channels are ignored")
+        } else {
+            format!("Commands are ignored in {}", mention_channels(&channels.ignored))
+        };
+        ctx.reply_raw(format!("{}\n{}", allowed, ignored)).await?;
+        return Ok(());
+    }
+
+    let allow = match ctx.parser.get_next()? {
+        input if input.eq_ignore_ascii_case("allow") => true,
+        input if input.eq_ignore_ascii_case("ignore") => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("allow or ignore")).into()),
+    };
+
+    // only channels that exist in this guild can be added, ids of removed ones can still be cleaned up
+    let list = if allow {
+        &mut config.command_channels.allowed
+    } else {
+        &mut config.command_channels.ignored
+    };
+    let input = ctx.parser.peek().cloned().unwrap_or_default();
+    let channel_id = match ctx.parser.get_channel() {
+        Ok(channel) => channel.get_id(),
+        Err(e) => match input.parse().ok().map(ChannelId) {
+            Some(channel_id) if list.contains(&channel_id) => channel_id,
+            _ => return Err(e.into()),
+        },
+    };
+
+    let enable = match ctx.parser.get_next()? {
+        input if input.eq_ignore_ascii_case("on") => true,
+        input if input.eq_ignore_ascii_case("off") => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("on or off")).into()),
+    };
+
+    list.retain(|listed| *listed != channel_id);
+    if enable {
+        list.push(channel_id);
+    }
+    let only_allowed = config.command_channels.allowed.is_empty();
+    ctx.set_config(config).await?;
+
+    let reply = match (allow, enable) {
+        (true, true) => format!("Commands can now be used in <#{}>", channel_id),
+        (true, false) if only_allowed => String::from("Commands can be used in every channel again"),
+        (true, false) => format!("Commands can no longer be used in <#{}>", channel_id),
+        (false, true) => format!("Commands are now ignored in <#{}>", channel_id),
+        (false, false) => format!("Commands are no longer ignored in <#{}>", channel_id),
+    };
+    ctx.reply_raw(reply).await?;
+    Ok(())
+}
+
+fn mention_channels(channels: &[ChannelId]) -> String {
+    channels
+        .iter()
+        .map(|channel_id| format!("<#{}>", channel_id))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub async fn set_message_log(mut ctx: CommandContext) -> CommandResult {
+    let replies = LogChannelReplies {
+        list: GearBotString::MessageLogChannels,
+        none: GearBotString::NoMessageLogChannel,
+        set: GearBotString::MessageLogChannelSet,
+        disabled: GearBotString::MessageLogChannelDisabled,
+    };
+    set_category_channel(&mut ctx, LogCategory::MESSAGES, replies).await
+}
+
+/// Shows or changes the channel members joining and leaving get logged to.
+pub async fn set_join_log(mut ctx: CommandContext) -> CommandResult {
+    let replies = LogChannelReplies {
+        list: GearBotString::JoinLogChannels,
+        none: GearBotString::NoJoinLogChannel,
+        set: GearBotString::JoinLogChannelSet,
+        disabled: GearBotString::JoinLogChannelDisabled,
+    };
+    set_category_channel(&mut ctx, LogCategory::MEMBERS, replies).await
+}
+
+async fn set_category_channel(
+    ctx: &mut CommandContext,
+    category: LogCategory,
+    replies: LogChannelReplies,
+) -> CommandResult {
+    let mut config = (*ctx.get_config()?).clone();
+
+    if !ctx.parser.has_next() {
+        let channels = config.get_log_channels(&category);
+        if channels.is_empty() {
+            ctx.reply(replies.none, FluArgs::with_capacity(0).generate()).await?;
+        } else {
+            let channels: Vec<String> = channels.iter().map(|id| format!("<#{}>", id)).collect();
+            let args = FluArgs::with_capacity(1)
+                .add("channels", channels.join(", "))
+                .generate();
+            ctx.reply(replies.list, args).await?;
+        }
+        return Ok(());
+    }
+
+    let channel_id = match ctx.parser.peek() {
+        Some(input) if input.eq_ignore_ascii_case("off") => None,
+        _ => Some(ctx.parser.get_channel()?.get_id()),
+    };
+
+    // only one channel gets the logs of the category, other categories stay where they are
+    for log_config in config.log_channels.values_mut() {
+        log_config.categories.retain(|existing| existing != &category);
+    }
+    config
+        .log_channels
+        .retain(|_, log_config| !log_config.categories.is_empty());
+
+    let channel_id = match channel_id {
+        Some(channel_id) => channel_id,
+        None => {
+            ctx.set_config(config).await?;
+            let args = FluArgs::with_capacity(1)
+                .add("gearyes", Emoji::Yes.for_chat())
+                .generate();
+            ctx.reply_success(replies.disabled, args).await?;
+            return Ok(());
+        }
+    };
+
+    // there is nothing to log without the messages being stored
+    if category == LogCategory::MESSAGES {
+        config.message_logs.enabled = true;
+    }
+    config
+        .log_channels
+        .entry(channel_id)
+        .or_insert_with(|| LogChannelConfig {
+            categories: vec![],
+            disabled_keys: vec![],
+            style: LogStyle::Embed,
+            filters: vec![],
+            timestamps: true,
+        })
+        .categories
+        .push(category);
+    ctx.set_config(config).await?;
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("channel_id", channel_id.to_string())
+        .generate();
+    ctx.reply_success(replies.set, args).await?;
+
+    let can_post = ctx
+        .bot_has_permissions_in_channel(channel_id, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES)
+        || ctx.bot_has_permissions_in_channel(channel_id, Permissions::VIEW_CHANNEL | Permissions::MANAGE_WEBHOOKS);
+    if !can_post {
+        let args = FluArgs::with_capacity(2)
+            .add("gearwarn", Emoji::Warn.for_chat())
+            .add("channel_id", channel_id.to_string())
+            .generate();
+        ctx.reply(GearBotString::LogChannelNoAccess, args).await?;
+    }
+
+    Ok(())
+}
@@ -2,8 +2,8 @@ use crate::core::CommandContext;
 use crate::error::CommandResult;
 
 pub async fn get_perms(mut ctx: CommandContext) -> CommandResult {
-    let member = ctx.parser.get_member_or(ctx.message.get_author_as_member()?)?;
-    let guild = ctx.get_guild();
+    let member = ctx.parser.get_member_or(ctx.message.get_author_as_member()?).await?;
+    let guild = ctx.get_guild()?;
     let config = ctx.get_config()?;
 
     ctx.reply_raw(format!(
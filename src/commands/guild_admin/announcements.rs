@@ -0,0 +1,121 @@
+use chrono::Utc;
+use twilight_model::guild::Permissions;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, text, Emoji};
+
+/// Keeps a single guild from flooding the scheduler.
+const MAX_ANNOUNCEMENTS: usize = 25;
+
+pub async fn add_announcement(mut ctx: CommandContext) -> CommandResult {
+    let channel = ctx.parser.get_channel()?;
+
+    let mut parts = Vec::with_capacity(5);
+    for _ in 0..5 {
+        parts.push(ctx.parser.get_next()?.to_string());
+    }
+    let cron = parts.join(" ");
+
+    let message = ctx.parser.get_remaining();
+    if message.is_empty() {
+        return Err(ParseError::MissingArgument.into());
+    }
+
+    let next_run = match utils::parse_cron(&cron).and_then(|schedule| schedule.after(&Utc::now()).next()) {
+        Some(next_run) => next_run,
+        None => {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("cron", cron)
+                .generate();
+            ctx.reply(GearBotString::AnnouncementInvalidCron, args).await?;
+            return Ok(());
+        }
+    };
+
+    if !ctx.bot_has_permissions_in_channel(channel.get_id(), Permissions::SEND_MESSAGES) {
+        return Err(ParseError::NoChannelAccessBot(channel.get_name().to_string()).into());
+    }
+
+    let guild_id = ctx.get_guild()?.id;
+    let datastore = &ctx.bot_context.datastore;
+    if datastore.get_announcements(guild_id).await?.len() >= MAX_ANNOUNCEMENTS {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("max", MAX_ANNOUNCEMENTS)
+            .generate();
+        ctx.reply(GearBotString::AnnouncementLimitReached, args).await?;
+        return Ok(());
+    }
+
+    let id = datastore
+        .insert_announcement(
+            guild_id,
+            channel.get_id(),
+            ctx.message.author.id,
+            &cron,
+            &message,
+            next_run.timestamp(),
+        )
+        .await?;
+
+    let args = FluArgs::with_capacity(4)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("id", id)
+        .add("channel_id", channel.get_id().to_string())
+        .add("next_run", ctx.format_time(next_run))
+        .generate();
+    ctx.reply_success(GearBotString::AnnouncementAdded, args).await?;
+
+    Ok(())
+}
+
+pub async fn list_announcements(ctx: CommandContext) -> CommandResult {
+    let announcements = ctx.bot_context.datastore.get_announcements(ctx.get_guild()?.id).await?;
+
+    if announcements.is_empty() {
+        ctx.reply(GearBotString::NoAnnouncements, FluArgs::with_capacity(0).generate())
+            .await?;
+        return Ok(());
+    }
+
+    let mut output = ctx.translate(GearBotString::AnnouncementsHeader);
+    for announcement in announcements {
+        let preview = announcement.message.replace('`', "ˋ").replace('\n', " ");
+        let preview = text::truncate_with_ellipsis(&preview, 50);
+
+        output += &format!(
+            "\n``#{}`` <#{}> ``{}``: {}",
+            announcement.id, announcement.channel_id, announcement.cron, preview
+        );
+    }
+
+    ctx.reply_raw_or_file(output, "announcements.txt").await?;
+
+    Ok(())
+}
+
+pub async fn remove_announcement(mut ctx: CommandContext) -> CommandResult {
+    let id = ctx.parser.get_entry_number()?;
+
+    let removed = ctx
+        .bot_context
+        .datastore
+        .remove_announcement(ctx.get_guild()?.id, id)
+        .await?;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .add("id", id)
+        .generate();
+    if removed {
+        ctx.reply_success(GearBotString::AnnouncementRemoved, args).await?;
+    } else {
+        ctx.reply(GearBotString::AnnouncementNotFound, args).await?;
+    }
+
+    Ok(())
+}
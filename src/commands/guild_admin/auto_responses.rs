@@ -0,0 +1,106 @@
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{text, Emoji};
+
+/// Every message in the guild is checked against all of these.
+const MAX_AUTO_RESPONSES: usize = 50;
+
+pub async fn add_auto_response(mut ctx: CommandContext) -> CommandResult {
+    let exact = match ctx.parser.get_next()?.to_lowercase().as_str() {
+        "exact" => true,
+        "contains" => false,
+        _ => return Err(ParseError::WrongArgumentType(String::from("match mode (exact or contains)")).into()),
+    };
+
+    let cooldown = ctx.parser.get_duration()?;
+
+    // triggers can contain spaces, so the response is split off by the first |
+    let remaining = ctx.parser.get_remaining();
+    let (trigger, response) = match remaining.find('|') {
+        Some(split) => (remaining[..split].trim(), remaining[split + 1..].trim()),
+        None => {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::AutoResponseMissingSeparator, args).await?;
+            return Ok(());
+        }
+    };
+
+    if trigger.is_empty() || response.is_empty() {
+        return Err(ParseError::MissingArgument.into());
+    }
+
+    let guild_id = ctx.get_guild()?.id;
+    let datastore = &ctx.bot_context.datastore;
+    if datastore.get_auto_responses(guild_id).await?.len() >= MAX_AUTO_RESPONSES {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("max", MAX_AUTO_RESPONSES)
+            .generate();
+        ctx.reply(GearBotString::AutoResponseLimitReached, args).await?;
+        return Ok(());
+    }
+
+    let id = datastore
+        .insert_auto_response(guild_id, trigger, response, exact, cooldown.num_seconds())
+        .await?;
+    ctx.bot_context.forget_auto_responses(guild_id).await;
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("id", id)
+        .generate();
+    ctx.reply_success(GearBotString::AutoResponseAdded, args).await?;
+
+    Ok(())
+}
+
+pub async fn list_auto_responses(ctx: CommandContext) -> CommandResult {
+    let responses = ctx.bot_context.get_auto_responses(ctx.get_guild()?.id).await?;
+
+    if responses.is_empty() {
+        ctx.reply(GearBotString::NoAutoResponses, FluArgs::with_capacity(0).generate())
+            .await?;
+        return Ok(());
+    }
+
+    let mut output = ctx.translate(GearBotString::AutoResponsesHeader);
+    for response in responses.iter() {
+        let preview = response.response.replace('`', "ˋ").replace('\n', " ");
+        let preview = text::truncate_with_ellipsis(&preview, 50);
+
+        output += &format!(
+            "\n``#{}`` {} ``{}`` ({}s): {}",
+            response.id,
+            if response.exact { "exact" } else { "contains" },
+            response.trigger.replace('`', "ˋ"),
+            response.cooldown_seconds,
+            preview
+        );
+    }
+
+    ctx.reply_raw_or_file(output, "auto_responses.txt").await?;
+
+    Ok(())
+}
+
+pub async fn remove_auto_response(mut ctx: CommandContext) -> CommandResult {
+    let id = ctx.parser.get_entry_number()?;
+
+    let guild_id = ctx.get_guild()?.id;
+    let removed = ctx.bot_context.datastore.remove_auto_response(guild_id, id).await?;
+    ctx.bot_context.forget_auto_responses(guild_id).await;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .add("id", id)
+        .generate();
+    if removed {
+        ctx.reply_success(GearBotString::AutoResponseRemoved, args).await?;
+    } else {
+        ctx.reply(GearBotString::AutoResponseNotFound, args).await?;
+    }
+
+    Ok(())
+}
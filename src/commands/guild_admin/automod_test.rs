@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use chrono::Duration;
+use twilight_model::guild::Permissions;
+use twilight_model::id::MessageId;
+
+use crate::core::CommandContext;
+use crate::database::structures::UserMessage;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, matchers, text, Emoji};
+
+/// Every message tested against has to be decrypted, so only a limited amount can be looked at.
+const MAX_SAMPLE_SIZE: usize = 1000;
+
+/// Spam windows can't be longer than the spam tracker remembers messages for.
+const MAX_WINDOW_SECONDS: i64 = 10 * 60;
+
+/// How many of the matching messages are shown as examples.
+const EXAMPLE_COUNT: usize = 5;
+
+/// Message content in the examples is cut off after this many characters.
+const EXAMPLE_LENGTH: usize = 80;
+
+/// A candidate automod rule, in the same shape as the automod config takes it.
+///
+/// Cross channel spam and invites aren't here, a sample of a single channel can't show the first and
+/// checking every invite would take ages.
+enum CandidateRule {
+    Extensions(Vec<String>),
+    Mentions(usize),
+    Rate { threshold: usize, window: Duration },
+    Duplicates { threshold: usize, window: Duration },
+    Links(Vec<String>),
+}
+
+impl CandidateRule {
+    /// Rules that only look at who sent what when also work for messages stored without their content.
+    fn needs_content(&self) -> bool {
+        match self {
+            CandidateRule::Extensions(_) | CandidateRule::Rate { .. } => false,
+            CandidateRule::Mentions(_) | CandidateRule::Duplicates { .. } | CandidateRule::Links(_) => true,
+        }
+    }
+}
+
+pub async fn automod_test(mut ctx: CommandContext) -> CommandResult {
+    if !ctx.check_content_storage().await? {
+        return Ok(());
+    }
+
+    let channel = ctx.parser.get_channel()?;
+    let count = ctx.parser.get_i64()?;
+    if count < 1 || count > MAX_SAMPLE_SIZE as i64 {
+        return Err(ParseError::WrongArgumentType(format!("number between 1 and {}", MAX_SAMPLE_SIZE)).into());
+    }
+    let rule = parse_rule(&mut ctx)?;
+
+    // the examples show what was said in there
+    let user_has_access = ctx.has_channel_permissions(
+        ctx.message.author.id,
+        channel.get_id(),
+        Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+    );
+    if !user_has_access {
+        return Err(ParseError::NoChannelAccessUser(channel.get_name().to_string()).into());
+    }
+
+    let mut messages = ctx
+        .bot_context
+        .datastore
+        .get_recent_channel_messages(channel.get_id(), ctx.get_guild()?.id, count)
+        .await?;
+
+    if messages.is_empty() {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("channel_id", channel.get_id().to_string())
+            .generate();
+        ctx.reply(GearBotString::AutomodTestNoMessages, args).await?;
+        return Ok(());
+    }
+
+    // spam windows run forward in time, the newest messages come back first
+    messages.reverse();
+    let checked = messages.len();
+    // messages stored in privacy mode don't have any content to match against
+    let unreadable = if rule.needs_content() {
+        messages.iter().filter(|m| m.content.is_none()).count()
+    } else {
+        0
+    };
+
+    let matching: Vec<&UserMessage> = match &rule {
+        CandidateRule::Extensions(extensions) => {
+            let ids: Vec<_> = messages.iter().map(|message| message.id).collect();
+            let blocked: HashSet<_> = ctx
+                .bot_context
+                .datastore
+                .get_attachments(&ids)
+                .await?
+                .into_iter()
+                .filter(|attachment| matchers::get_blocked_extension(&attachment.filename, extensions).is_some())
+                .map(|attachment| MessageId(attachment.message_id as u64))
+                .collect();
+            messages
+                .iter()
+                .filter(|message| blocked.contains(&message.id))
+                .collect()
+        }
+        CandidateRule::Mentions(threshold) => messages
+            .iter()
+            .filter(|message| {
+                message
+                    .content
+                    .as_deref()
+                    .map_or(false, |content| matchers::count_mentions(content) >= *threshold)
+            })
+            .collect(),
+        CandidateRule::Rate { threshold, window } => messages
+            .iter()
+            .enumerate()
+            .filter(|(i, message)| sent_within(&messages[..=*i], message, *window, |_| true) >= *threshold)
+            .map(|(_, message)| message)
+            .collect(),
+        CandidateRule::Duplicates { threshold, window } => messages
+            .iter()
+            .enumerate()
+            .filter(|(i, message)| {
+                // attachments without text all look the same, those aren't duplicates
+                let content = match message.content.as_deref().map(normalize) {
+                    Some(content) if !content.is_empty() => content,
+                    _ => return false,
+                };
+                let same_content =
+                    |other: &UserMessage| other.content.as_deref().map(normalize).as_ref() == Some(&content);
+                sent_within(&messages[..=*i], message, *window, same_content) >= *threshold
+            })
+            .map(|(_, message)| message)
+            .collect(),
+        CandidateRule::Links(allowed) => messages
+            .iter()
+            .filter(|message| {
+                message.content.as_deref().map_or(false, |content| {
+                    matchers::get_link_domains(content)
+                        .iter()
+                        .any(|domain| !matchers::is_domain_allowed(domain, allowed))
+                })
+            })
+            .collect(),
+    };
+
+    let examples: String = matching
+        .iter()
+        .take(EXAMPLE_COUNT)
+        .map(|message| {
+            let author = match ctx.bot_context.cache.get_user(message.author) {
+                Some(user) => format!("{}#{}", user.username, user.discriminator),
+                None => message.author.to_string(),
+            };
+            let snippet = text::truncate_with_ellipsis(message.content.as_deref().unwrap_or(""), EXAMPLE_LENGTH);
+            format!(
+                "\n**{}**: {}",
+                utils::clean(&author, true, true, true, true),
+                utils::clean(&snippet, true, true, true, true)
+            )
+        })
+        .collect();
+
+    let args = FluArgs::with_capacity(6)
+        .add("gearinfo", Emoji::Info.for_chat())
+        .add("count", matching.len())
+        .add("checked", checked)
+        .add("unreadable", unreadable)
+        .add("channel_id", channel.get_id().to_string())
+        .add("examples", examples)
+        .generate();
+    ctx.reply(GearBotString::AutomodTestResult, args).await?;
+
+    Ok(())
+}
+
+/// Reads the rule type and its settings, thresholds and windows work the same as in the automod config.
+fn parse_rule(ctx: &mut CommandContext) -> Result<CandidateRule, ParseError> {
+    let rule = match ctx.parser.get_next()?.to_lowercase().as_str() {
+        "extensions" => CandidateRule::Extensions(get_list(ctx)?),
+        "mentions" => CandidateRule::Mentions(get_threshold(ctx)?),
+        "rate" => CandidateRule::Rate {
+            threshold: get_threshold(ctx)?,
+            window: get_window(ctx)?,
+        },
+        "duplicates" => CandidateRule::Duplicates {
+            threshold: get_threshold(ctx)?,
+            window: get_window(ctx)?,
+        },
+        "links" => CandidateRule::Links(get_list(ctx)?),
+        _ => {
+            return Err(ParseError::WrongArgumentType(String::from(
+                "rule type (extensions, mentions, rate, duplicates or links)",
+            )))
+        }
+    };
+    Ok(rule)
+}
+
+fn get_threshold(ctx: &mut CommandContext) -> Result<usize, ParseError> {
+    let threshold = ctx.parser.get_i64()?;
+    if threshold < 1 {
+        return Err(ParseError::WrongArgumentType(String::from("threshold of at least 1")));
+    }
+    Ok(threshold as usize)
+}
+
+fn get_window(ctx: &mut CommandContext) -> Result<Duration, ParseError> {
+    let seconds = ctx.parser.get_i64()?;
+    if seconds < 1 || seconds > MAX_WINDOW_SECONDS {
+        return Err(ParseError::WrongArgumentType(format!(
+            "window between 1 and {} seconds",
+            MAX_WINDOW_SECONDS
+        )));
+    }
+    Ok(Duration::seconds(seconds))
+}
+
+/// The rest of the arguments, separated by spaces or commas.
+fn get_list(ctx: &mut CommandContext) -> Result<Vec<String>, ParseError> {
+    let list: Vec<String> = ctx
+        .parser
+        .get_remaining()
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|entry| !entry.is_empty())
+        .map(String::from)
+        .collect();
+    if list.is_empty() {
+        return Err(ParseError::MissingArgument);
+    }
+    Ok(list)
+}
+
+/// The same content as far as the duplicate spam check is concerned.
+fn normalize(content: &str) -> String {
+    content.trim().to_lowercase()
+}
+
+/// How many of the earlier messages (the message itself included) the same author sent within the window
+/// leading up to it, only counting the ones the filter lets through.
+fn sent_within(
+    earlier: &[UserMessage],
+    message: &UserMessage,
+    window: Duration,
+    filter: impl Fn(&UserMessage) -> bool,
+) -> usize {
+    let sent = utils::snowflake_timestamp(message.id.0);
+    earlier
+        .iter()
+        .rev()
+        .take_while(|other| sent - utils::snowflake_timestamp(other.id.0) <= window)
+        .filter(|other| other.author == message.author && filter(other))
+        .count()
+}
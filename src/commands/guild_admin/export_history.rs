@@ -0,0 +1,176 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::json;
+use twilight_model::id::UserId;
+
+use crate::core::{CommandContext, HISTORY_TYPES};
+use crate::database::structures::{ExportedHistoryEntry, HistoryFilter};
+use crate::error::{CommandResult, ParseError};
+use crate::parser::Parser;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{matchers, Emoji};
+
+/// Entries are fetched (and decrypted) in pages of this size, so big histories are never loaded all at once.
+const PAGE_SIZE: i64 = 500;
+
+const CSV_HEADER: &str = "case,user_id,moderator_id,type,start,end,imported,removed,reason\n";
+
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+pub async fn export_history(mut ctx: CommandContext) -> CommandResult {
+    let format = match ctx.parser.get_next()?.to_lowercase().as_str() {
+        "csv" => ExportFormat::Csv,
+        "json" => ExportFormat::Json,
+        _ => return Err(ParseError::WrongArgumentType(String::from("export format (csv or json)")).into()),
+    };
+
+    let mut filter = HistoryFilter::default();
+    while ctx.parser.has_next() {
+        let input = ctx.parser.get_next()?.to_string();
+        let (key, value) = match input.find('=') {
+            Some(split) => (input[..split].to_lowercase(), &input[split + 1..]),
+            None => return Err(invalid_filter()),
+        };
+        match key.as_str() {
+            "since" => filter.since = Some(parse_moment(value)?),
+            "until" => filter.until = Some(parse_moment(value)?),
+            "mod" | "moderator" => {
+                let moderator = matchers::get_mention(value)
+                    .or_else(|| value.parse().ok())
+                    .ok_or_else(|| ParseError::WrongArgumentType(String::from("moderator mention or id")))?;
+                filter.moderator = Some(UserId(moderator));
+            }
+            "type" => {
+                let kind = HISTORY_TYPES
+                    .iter()
+                    .find(|kind| kind.eq_ignore_ascii_case(value))
+                    .ok_or_else(|| ParseError::WrongArgumentType(HISTORY_TYPES.join(", ")))?;
+                filter.kind = Some(kind);
+            }
+            _ => return Err(invalid_filter()),
+        }
+    }
+
+    let guild_id = ctx.get_guild()?.id;
+    let limit = ctx.get_upload_limit();
+    let mut output = match format {
+        ExportFormat::Csv => String::from(CSV_HEADER),
+        ExportFormat::Json => String::from("["),
+    };
+    let mut count = 0;
+    let mut after = 0;
+    loop {
+        let page = ctx
+            .bot_context
+            .datastore
+            .get_history_page(guild_id, &filter, after, PAGE_SIZE)
+            .await?;
+
+        for entry in &page {
+            let row = match format {
+                ExportFormat::Csv => csv_row(entry),
+                ExportFormat::Json if count == 0 => format!("\n{}", json_entry(entry)),
+                ExportFormat::Json => format!(",\n{}", json_entry(entry)),
+            };
+            // leave room to close the json array
+            if output.len() + row.len() + 2 > limit {
+                let args = FluArgs::with_capacity(2)
+                    .add("gearno", Emoji::No.for_chat())
+                    .add("count", count)
+                    .generate();
+                ctx.reply(GearBotString::HistoryExportTooLarge, args).await?;
+                return Ok(());
+            }
+            output += &row;
+            count += 1;
+        }
+
+        match page.last() {
+            Some(last) if page.len() as i64 == PAGE_SIZE => after = last.id,
+            _ => break,
+        }
+    }
+
+    if count == 0 {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::HistoryExportEmpty, args).await?;
+        return Ok(());
+    }
+
+    let filename = match format {
+        ExportFormat::Csv => "history.csv",
+        ExportFormat::Json => {
+            output += "\n]";
+            "history.json"
+        }
+    };
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("count", count)
+        .generate();
+    ctx.reply_with_file(GearBotString::HistoryExported, args, filename, output.into_bytes())
+        .await?;
+
+    Ok(())
+}
+
+fn invalid_filter() -> crate::error::CommandError {
+    ParseError::WrongArgumentType(String::from("filter (since=, until=, mod= or type=)")).into()
+}
+
+/// Either a date (``2020-12-31``) or how long ago (``30d``).
+fn parse_moment(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(DateTime::from_utc(date.and_hms(0, 0, 0), Utc));
+    }
+
+    Parser::parse_duration(input).map(|ago| Utc::now() - ago)
+}
+
+fn csv_row(entry: &ExportedHistoryEntry) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{}\n",
+        entry.id,
+        entry.user_id,
+        entry.mod_id,
+        entry.kind,
+        entry.start.to_rfc3339(),
+        entry.end.map(|end| end.to_rfc3339()).unwrap_or_default(),
+        entry.imported,
+        entry.removed,
+        csv_field(entry.reason.as_deref().unwrap_or_default())
+    )
+}
+
+/// Quotes the field when needed, and defuses anything a spreadsheet would run as a formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(|c| matches!(c, '=' | '+' | '-' | '@')) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn json_entry(entry: &ExportedHistoryEntry) -> String {
+    // ids as strings, they don't fit in the numbers most json parsers use
+    json!({
+        "case": entry.id,
+        "user_id": entry.user_id.to_string(),
+        "moderator_id": entry.mod_id.to_string(),
+        "type": entry.kind,
+        "start": entry.start.to_rfc3339(),
+        "end": entry.end.map(|end| end.to_rfc3339()),
+        "imported": entry.imported,
+        "removed": entry.removed,
+        "reason": entry.reason,
+    })
+    .to_string()
+}
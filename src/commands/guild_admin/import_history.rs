@@ -0,0 +1,64 @@
+use crate::core::{CommandContext, HistoryImport};
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+/// Exports from other bots can get sizable for older communities, but not this big.
+const MAX_IMPORT_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Most entries imported in one go, bigger histories have to be split up.
+const MAX_IMPORT_ENTRIES: usize = 10_000;
+
+pub async fn import_history(ctx: CommandContext) -> CommandResult {
+    let input = match ctx.message.attachments.first() {
+        Some(attachment) if attachment.size <= MAX_IMPORT_SIZE => utils::download_text(&attachment.url).await.ok(),
+        _ => None,
+    };
+
+    let import = match input.and_then(|input| serde_json::from_str::<HistoryImport>(&input).ok()) {
+        Some(import) => import,
+        None => {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::HistoryImportInvalid, args).await?;
+            return Ok(());
+        }
+    };
+
+    if import.entries.len() > MAX_IMPORT_ENTRIES {
+        let args = FluArgs::with_capacity(3)
+            .add("gearno", Emoji::No.for_chat())
+            .add("count", import.entries.len())
+            .add("max", MAX_IMPORT_ENTRIES)
+            .generate();
+        ctx.reply(GearBotString::HistoryImportTooLarge, args).await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.get_guild()?.id;
+    let (accepted, rejected) = import.validate();
+    let imported = ctx
+        .bot_context
+        .datastore
+        .import_history_entries(guild_id, &accepted)
+        .await?;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("imported", imported)
+        .add("rejected", rejected.len())
+        .generate();
+    let mut summary = ctx.translate_with_args(GearBotString::HistoryImported, &args);
+    for entry in rejected {
+        let args = FluArgs::with_capacity(2)
+            .add("position", entry.position)
+            // problems quote the entries, don't let those ping or format anything
+            .add("problem", utils::clean(&entry.problem, true, true, false, false))
+            .generate();
+        summary += "\n";
+        summary += &ctx.translate_with_args(GearBotString::HistoryImportRejectedEntry, &args);
+    }
+
+    ctx.reply_raw_or_file(summary, "history_import.txt").await?;
+
+    Ok(())
+}
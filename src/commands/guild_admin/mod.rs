@@ -0,0 +1,17 @@
+pub use announcements::*;
+pub use auto_responses::*;
+pub use automod_test::*;
+pub use export_history::*;
+pub use import_history::*;
+pub use role_menus::*;
+pub use simulate_join::*;
+pub use toggle_commands::*;
+
+mod announcements;
+mod auto_responses;
+mod automod_test;
+mod export_history;
+mod import_history;
+mod role_menus;
+mod simulate_join;
+mod toggle_commands;
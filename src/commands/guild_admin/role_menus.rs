@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use twilight_model::guild::Permissions;
+use twilight_model::id::{MessageId, RoleId};
+
+use crate::core::CommandContext;
+use crate::database::structures::RoleMenuOption;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// Discord doesn't allow more than 20 different reactions on a single message.
+const MAX_ROLE_MENU_OPTIONS: usize = 20;
+
+pub async fn create_role_menu(mut ctx: CommandContext) -> CommandResult {
+    let channel = ctx.parser.get_channel()?;
+    let channel_id = channel.get_id();
+
+    let user_has_access = ctx.has_channel_permissions(
+        ctx.message.author.id,
+        channel_id,
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+    );
+    if !user_has_access {
+        return Err(ParseError::NoChannelAccessUser(channel.get_name().to_string()).into());
+    }
+
+    let bot_has_access = ctx.bot_has_permissions_in_channel(
+        channel_id,
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS | Permissions::ADD_REACTIONS,
+    );
+    if !bot_has_access {
+        return Err(ParseError::NoChannelAccessBot(channel.get_name().to_string()).into());
+    }
+
+    let guild = ctx.get_guild()?.clone();
+    let author = ctx.message.get_author_as_member()?;
+    let bot_position = match ctx.bot_context.cache.get_member(&guild.id, &ctx.get_bot_user().id) {
+        Some(bot_member) => guild.top_role_position(&bot_member),
+        None => 0,
+    };
+    let author_position = guild.hierarchy_position(&author);
+
+    let mut options = vec![];
+    let mut seen_emoji = HashSet::new();
+    loop {
+        let emoji = ctx.parser.get_next()?.to_string();
+        let role = ctx.parser.get_role()?;
+
+        if options.len() == MAX_ROLE_MENU_OPTIONS {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("max", MAX_ROLE_MENU_OPTIONS)
+                .generate();
+            ctx.reply(GearBotString::RoleMenuTooManyOptions, args).await?;
+            return Ok(());
+        }
+
+        if !seen_emoji.insert(emoji.clone()) {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("emoji", emoji)
+                .generate();
+            ctx.reply(GearBotString::RoleMenuDuplicateEmoji, args).await?;
+            return Ok(());
+        }
+
+        // the everyone role shares its id with the guild
+        let key = if role.id == RoleId(guild.id.0) || role.managed || role.position >= bot_position {
+            Some(GearBotString::RoleMenuUnassignableRole)
+        } else if role.position >= author_position {
+            Some(GearBotString::RoleMenuRoleTooHigh)
+        } else {
+            None
+        };
+        if let Some(key) = key {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("role", role.name.replace('`', "ˋ"))
+                .generate();
+            ctx.reply(key, args).await?;
+            return Ok(());
+        }
+
+        options.push(RoleMenuOption {
+            emoji,
+            role_id: role.id,
+            label: role.name.clone(),
+        });
+
+        if !ctx.parser.has_next() {
+            break;
+        }
+    }
+
+    let embed = ctx.bot_context.role_menu_embed(&ctx.get_config()?, &options)?;
+    let message = ctx.send_embed(embed, channel_id).await?;
+
+    let http = &ctx.bot_context.http;
+    for option in &options {
+        if http
+            .create_reaction(channel_id, message.id, option.to_reaction())
+            .await
+            .is_err()
+        {
+            // don't leave a menu behind that can't be fully used
+            http.delete_message(channel_id, message.id).await?;
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("emoji", option.emoji.clone())
+                .generate();
+            ctx.reply(GearBotString::RoleMenuInvalidEmoji, args).await?;
+            return Ok(());
+        }
+    }
+
+    ctx.bot_context
+        .datastore
+        .insert_role_menu(guild.id, channel_id, message.id, &options)
+        .await?;
+    ctx.bot_context.forget_role_menus(guild.id).await;
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("channel_id", channel_id.to_string())
+        .generate();
+    ctx.reply_success(GearBotString::RoleMenuCreated, args).await?;
+
+    Ok(())
+}
+
+pub async fn list_role_menus(ctx: CommandContext) -> CommandResult {
+    let menus = ctx.bot_context.get_role_menus(ctx.get_guild()?.id).await?;
+
+    if menus.is_empty() {
+        ctx.reply(GearBotString::NoRoleMenus, FluArgs::with_capacity(0).generate())
+            .await?;
+        return Ok(());
+    }
+
+    let mut output = ctx.translate(GearBotString::RoleMenusHeader);
+    for menu in menus.iter() {
+        let roles = menu
+            .options
+            .iter()
+            .map(|option| format!("{} <@&{}>", option.emoji, option.role_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        output += &format!("\n``{}`` in <#{}>: {}", menu.message_id, menu.channel_id, roles);
+    }
+
+    ctx.reply_raw_or_file(output, "role_menus.txt").await?;
+
+    Ok(())
+}
+
+pub async fn remove_role_menu(mut ctx: CommandContext) -> CommandResult {
+    let message_id = MessageId(ctx.parser.get_id()?);
+
+    let guild_id = ctx.get_guild()?.id;
+    let channel_id = ctx
+        .bot_context
+        .get_role_menus(guild_id)
+        .await?
+        .iter()
+        .find(|menu| menu.message_id == message_id)
+        .map(|menu| menu.channel_id);
+
+    let removed = ctx.bot_context.datastore.remove_role_menu(guild_id, message_id).await?;
+    ctx.bot_context.forget_role_menus(guild_id).await;
+
+    if let Some(channel_id) = channel_id {
+        // it might already be gone, the menu is removed either way
+        let _ = ctx.bot_context.http.delete_message(channel_id, message_id).await;
+    }
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .add("id", message_id.to_string())
+        .generate();
+    if removed {
+        ctx.reply_success(GearBotString::RoleMenuRemoved, args).await?;
+    } else {
+        ctx.reply(GearBotString::RoleMenuNotFound, args).await?;
+    }
+
+    Ok(())
+}
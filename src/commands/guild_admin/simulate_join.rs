@@ -0,0 +1,129 @@
+use twilight_model::guild::Permissions;
+
+use crate::core::{AccountAgeAction, CommandContext, LogCategory};
+use crate::error::CommandResult;
+use crate::handlers::anti_raid;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// Walks through what happens when a member joins, without actually doing any of it.
+///
+/// Nothing gets logged, kicked or assigned and the join doesn't count towards raid detection.
+pub async fn simulate_join(mut ctx: CommandContext) -> CommandResult {
+    let member = ctx.parser.get_member_or(ctx.get_author_as_member()?).await?;
+    let guild = ctx.get_guild()?;
+    let config = ctx.get_config()?;
+    let mut steps = vec![];
+
+    let channels = config.get_log_channels(&LogCategory::MEMBERS);
+    if channels.is_empty() {
+        steps.push(ctx.translate(GearBotString::SimulateJoinNotLogged));
+    } else {
+        let channels = channels
+            .iter()
+            .map(|channel_id| format!("<#{}>", channel_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let args = FluArgs::with_capacity(1).add("channels", channels).generate();
+        steps.push(ctx.translate_with_args(GearBotString::SimulateJoinLogged, &args));
+    }
+
+    let is_bot = ctx
+        .bot_context
+        .cache
+        .get_user(member.user_id)
+        .map_or(false, |user| user.bot_user);
+    let bot_permissions = ctx
+        .bot_context
+        .get_guild_permissions_for(&guild.id, &ctx.bot_context.bot_user.id);
+    let mut let_in = true;
+
+    if is_bot {
+        steps.push(ctx.translate(GearBotString::SimulateJoinBot));
+    } else {
+        let gate = &config.account_age_gate;
+        if let Some(days) = gate.min_age_days {
+            if anti_raid::fails_account_age_gate(member.user_id, gate) {
+                let args = FluArgs::with_capacity(2).add("days", days);
+                steps.push(match gate.action {
+                    AccountAgeAction::Kick => {
+                        ctx.translate_with_args(GearBotString::SimulateJoinGateKick, &args.generate())
+                    }
+                    AccountAgeAction::AssignRole(role_id) => ctx.translate_with_args(
+                        GearBotString::SimulateJoinGateRole,
+                        &args.add("role_id", role_id.to_string()).generate(),
+                    ),
+                });
+
+                let needed = anti_raid::gate_permission(&gate.action);
+                if bot_permissions.contains(needed) {
+                    let_in = gate.action != AccountAgeAction::Kick;
+                } else {
+                    steps.push(missing_permission(&ctx, needed));
+                }
+            } else {
+                steps.push(ctx.translate(GearBotString::SimulateJoinGatePassed));
+            }
+        }
+
+        // kicked members never reach the raid handling
+        if let_in && ctx.bot_context.is_raid_mode_active(guild.id).await {
+            if anti_raid::kicked_by_raid_mode(member.user_id, &config.anti_raid) {
+                steps.push(ctx.translate(GearBotString::SimulateJoinRaidKick));
+                if bot_permissions.contains(Permissions::KICK_MEMBERS) {
+                    let_in = false;
+                } else {
+                    steps.push(missing_permission(&ctx, Permissions::KICK_MEMBERS));
+                }
+            } else {
+                steps.push(ctx.translate(GearBotString::SimulateJoinRaidAllowed));
+            }
+        }
+    }
+
+    // the greeting only happens once they talk, but it's part of welcoming them all the same
+    if let Some(greeting) = config.first_message_greeting.as_ref().filter(|_| let_in) {
+        if let Some(message) = &greeting.message {
+            let args = FluArgs::with_capacity(1)
+                .add("greeting", message.replace("{user}", &format!("<@{}>", member.user_id)))
+                .generate();
+            steps.push(ctx.translate_with_args(GearBotString::SimulateJoinGreeting, &args));
+        }
+        if let Some(reaction) = &greeting.reaction {
+            let args = FluArgs::with_capacity(1).add("reaction", reaction.clone()).generate();
+            steps.push(ctx.translate_with_args(GearBotString::SimulateJoinGreetingReaction, &args));
+        }
+    }
+
+    if let Some(channel_id) = config.welcome_image.channel.filter(|_| let_in && !is_bot) {
+        // nobody gets welcomed while raid mode is on
+        if !ctx.bot_context.is_raid_mode_active(guild.id).await {
+            let args = FluArgs::with_capacity(1)
+                .add("channel_id", channel_id.to_string())
+                .generate();
+            steps.push(ctx.translate_with_args(GearBotString::SimulateJoinWelcomeImage, &args));
+
+            let needed = Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES;
+            if !ctx.bot_has_permissions_in_channel(channel_id, needed) {
+                steps.push(missing_permission(&ctx, needed));
+            }
+        }
+    }
+
+    let steps: String = steps.iter().map(|step| format!("\n{}", step)).collect();
+    let args = FluArgs::with_capacity(3)
+        .add("gearinfo", Emoji::Info.for_chat())
+        .add("user_id", member.user_id.to_string())
+        .add("steps", steps)
+        .generate();
+    ctx.reply(GearBotString::SimulateJoinResult, args).await?;
+
+    Ok(())
+}
+
+fn missing_permission(ctx: &CommandContext, permission: Permissions) -> String {
+    let args = FluArgs::with_capacity(1)
+        .add("permission", format!("{:?}", permission))
+        .generate();
+    ctx.translate_with_args(GearBotString::SimulateJoinMissingPermission, &args)
+}
@@ -0,0 +1,61 @@
+use crate::commands::ROOT_NODE;
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+pub async fn disable_command(mut ctx: CommandContext) -> CommandResult {
+    toggle_command(&mut ctx, true).await
+}
+
+pub async fn enable_command(mut ctx: CommandContext) -> CommandResult {
+    toggle_command(&mut ctx, false).await
+}
+
+async fn toggle_command(ctx: &mut CommandContext, disable: bool) -> CommandResult {
+    let input = ctx.parser.get_next()?.to_lowercase();
+    let node = match ROOT_NODE.find_command(&input) {
+        Some(node) => node,
+        None => {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("command", input)
+                .generate();
+            ctx.reply(GearBotString::UnknownCommand, args).await?;
+            return Ok(());
+        }
+    };
+
+    let mut config = (*ctx.get_config()?).clone();
+    let currently_disabled = config.disabled_commands.contains(&node.name);
+
+    let key = if !node.can_be_disabled() {
+        GearBotString::CommandCannotBeDisabled
+    } else if disable && currently_disabled {
+        GearBotString::CommandAlreadyDisabled
+    } else if !disable && !currently_disabled {
+        GearBotString::CommandNotDisabled
+    } else {
+        if disable {
+            config.disabled_commands.push(node.name.clone());
+        } else {
+            config.disabled_commands.retain(|name| name != &node.name);
+        }
+        ctx.set_config(config).await?;
+
+        if disable {
+            GearBotString::CommandNowDisabled
+        } else {
+            GearBotString::CommandNowEnabled
+        }
+    };
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .add("command", node.name.as_str())
+        .generate();
+    ctx.reply(key, args).await?;
+
+    Ok(())
+}
@@ -21,6 +21,10 @@ pub mod macros {
                 command_permission: $command_permission,
                 group: $group,
                 aliases: $a,
+                description: None,
+                usage: None,
+                cooldown: None,
+                guild_only: true,
             })
         }};
     }
@@ -60,7 +64,11 @@ pub mod macros {
                 bot_permissions: $bot_permissions,
                 command_permission: $command_permission,
                 group: $group,
-                aliases: $a
+                aliases: $a,
+                description: None,
+                usage: None,
+                cooldown: None,
+                guild_only: true,
             })
         }
         }
@@ -94,7 +102,11 @@ pub mod macros {
                 bot_permissions: $bot_permissions,
                 command_permission: $command_permission,
                 group: $group,
-                aliases: vec![]
+                aliases: vec![],
+                description: None,
+                usage: None,
+                cooldown: None,
+                guild_only: true,
             })
         }
         }
@@ -128,6 +140,10 @@ pub mod macros {
                 command_permission: $command_permission,
                 group: $group,
                 aliases: $a,
+                description: None,
+                usage: None,
+                cooldown: None,
+                guild_only: true,
                 node_list: list
             })
         }
@@ -162,6 +178,10 @@ pub mod macros {
                 command_permission: $command_permission,
                 group: $group,
                 aliases: vec![],
+                description: None,
+                usage: None,
+                cooldown: None,
+                guild_only: true,
                 node_list: list
             })
         }
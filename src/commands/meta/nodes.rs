@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bitflags::bitflags;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -9,6 +10,7 @@ use twilight_model::guild::Permissions;
 
 use crate::core::CommandContext;
 use crate::error::CommandResult;
+use crate::translation::GearBotString;
 
 pub type CommandResultOuter = Pin<Box<dyn Future<Output = CommandResult> + Send>>;
 pub type CommandHandler = Box<dyn Fn(CommandContext) -> CommandResultOuter + Send + Sync>;
@@ -20,6 +22,13 @@ pub struct RootNode {
     pub groups: Vec<CommandGroup>,
 }
 
+impl RootNode {
+    /// Looks up a command by its name or one of its aliases, so callers always end up with the real command.
+    pub fn find_command(&self, name: &str) -> Option<&Arc<CommandNode>> {
+        self.all_commands.get(&name.to_lowercase())
+    }
+}
+
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub enum CommandGroup {
     Basic,
@@ -70,6 +79,36 @@ bitflags! {
         const MISC_GROUP            = 0x004_000;
         const EMOJI_COMMAND         = 0x008_000;
         const EMOJI_LIST_COMMAND    = 0x010_000;
+        const PURGE_COMMAND         = 0x020_000;
+        const DELWARN_COMMAND       = 0x040_000;
+        const CLEARWARNINGS_COMMAND = 0x080_000;
+        const RAIDMODE_COMMAND      = 0x100_000;
+        const ADDNOTE_COMMAND       = 0x200_000;
+        const NOTES_COMMAND         = 0x400_000;
+        const DELNOTE_COMMAND       = 0x800_000;
+        const ANNOUNCEMENTS_COMMAND = 0x1_000_000;
+        const STATS_COMMAND         = 0x2_000_000;
+        const TOGGLE_COMMANDS       = 0x4_000_000;
+        const EMOJI_STATS_COMMAND   = 0x8_000_000;
+        const MASSBAN_COMMAND       = 0x10_000_000;
+        const TRANSCRIPT_COMMAND    = 0x20_000_000;
+        const AUTORESPONSE_COMMAND  = 0x40_000_000;
+        const ROLEMENU_COMMAND      = 0x80_000_000;
+        const TIMEOUT_COMMAND       = 0x100_000_000;
+        const ACTIVITY_COMMAND      = 0x200_000_000;
+        const REACTIONS_COMMAND     = 0x400_000_000;
+        const PRUNE_COMMAND         = 0x800_000_000;
+        const ALTS_COMMAND          = 0x1_000_000_000;
+        const SNOWFLAKE_COMMAND     = 0x2_000_000_000;
+        const MODSTATS_COMMAND      = 0x4_000_000_000;
+        const IMPORTHISTORY_COMMAND = 0x8_000_000_000;
+        const NAMES_COMMAND         = 0x10_000_000_000;
+        const REASON_COMMAND        = 0x20_000_000_000;
+        const ONLINE_COMMAND        = 0x40_000_000_000;
+        const AUTOMODTEST_COMMAND   = 0x80_000_000_000;
+        const SIMULATEJOIN_COMMAND  = 0x100_000_000_000;
+        const JUMBO_COMMAND         = 0x200_000_000_000;
+        const EXPORTHISTORY_COMMAND = 0x400_000_000_000;
     }
 }
 
@@ -97,4 +136,57 @@ pub struct CommandNode {
     pub command_permission: GearBotPermissions,
     pub group: CommandGroup,
     pub aliases: Vec<String>,
+    /// Explanation shown in the help, commands without one are only listed there.
+    pub description: Option<GearBotString>,
+    /// The arguments it takes, shown in the help after the command itself.
+    pub usage: Option<GearBotString>,
+    /// How long a user has to wait between uses, for commands that are heavy to run.
+    pub cooldown: Option<Duration>,
+    /// Commands that need a guild for their config, permissions or storage can't be used in DMs.
+    pub guild_only: bool,
+}
+
+impl CommandNode {
+    /// Bot admin commands, and the commands to turn others back on, are always available.
+    pub fn can_be_disabled(&self) -> bool {
+        self.group != CommandGroup::BotAdmin && self.name != "enable" && self.name != "disable"
+    }
+
+    pub fn works_in_dms(&self) -> bool {
+        !self.guild_only
+    }
+}
+
+/// Optional details of a command, set on it right after it's built and before it's added to the command tree.
+pub trait CommandOptions {
+    fn described(self, description: GearBotString) -> Self;
+    fn usage(self, usage: GearBotString) -> Self;
+    fn cooldown(self, seconds: u64) -> Self;
+    fn in_dms(self) -> Self;
+}
+
+impl CommandOptions for Arc<CommandNode> {
+    fn described(mut self, description: GearBotString) -> Self {
+        options_of(&mut self).description = Some(description);
+        self
+    }
+
+    fn usage(mut self, usage: GearBotString) -> Self {
+        options_of(&mut self).usage = Some(usage);
+        self
+    }
+
+    fn cooldown(mut self, seconds: u64) -> Self {
+        options_of(&mut self).cooldown = Some(Duration::from_secs(seconds));
+        self
+    }
+
+    fn in_dms(mut self) -> Self {
+        options_of(&mut self).guild_only = false;
+        self
+    }
+}
+
+fn options_of(node: &mut Arc<CommandNode>) -> &mut CommandNode {
+    Arc::get_mut(node).expect("Command options have to be set before the command is added to the tree!")
 }
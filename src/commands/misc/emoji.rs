@@ -4,7 +4,7 @@ use crate::error::CommandResult;
 use crate::utils::Emoji;
 
 pub async fn emoji_list(ctx: CommandContext) -> CommandResult {
-    let guild = ctx.get_guild();
+    let guild = ctx.get_guild()?;
     let guild_config = &ctx.get_config()?;
 
     let reactor = Reactor::new_emoji_list();
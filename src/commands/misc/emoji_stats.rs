@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::cache::CachedEmoji;
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+
+/// How many emoji to show for both the most and least used lists.
+const LIST_SIZE: usize = 10;
+
+pub async fn emoji_stats(ctx: CommandContext) -> CommandResult {
+    let guild = ctx.get_guild()?;
+    if guild.emoji.is_empty() {
+        ctx.reply(GearBotString::EmojiStatsNoEmoji, FluArgs::with_capacity(0).generate())
+            .await?;
+        return Ok(());
+    }
+
+    let usage: HashMap<u64, i64> = ctx
+        .bot_context
+        .datastore
+        .get_emoji_usage(guild.id)
+        .await?
+        .into_iter()
+        .map(|(emoji_id, uses)| (emoji_id as u64, uses))
+        .collect();
+
+    // emoji that were never used don't have a row yet but are the most interesting ones to prune
+    let mut ranked: Vec<(&CachedEmoji, i64)> = guild
+        .emoji
+        .iter()
+        .map(|emoji| (emoji.as_ref(), usage.get(&emoji.id.0).copied().unwrap_or(0)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+
+    let args = FluArgs::with_capacity(1).add("count", guild.emoji.len()).generate();
+    let mut output = ctx.translate_with_args(GearBotString::EmojiStatsHeader, &args);
+
+    output += "\n\n";
+    output += &ctx.translate(GearBotString::EmojiStatsMostUsed);
+    for (emoji, uses) in ranked.iter().take(LIST_SIZE) {
+        output += &format_entry(emoji, *uses);
+    }
+
+    // with only a few emoji the lists would overlap completely
+    if ranked.len() > LIST_SIZE {
+        output += "\n\n";
+        output += &ctx.translate(GearBotString::EmojiStatsLeastUsed);
+        for (emoji, uses) in ranked.iter().rev().take(LIST_SIZE) {
+            output += &format_entry(emoji, *uses);
+        }
+    }
+
+    ctx.reply_raw_or_file(output, "emoji_stats.txt").await?;
+
+    Ok(())
+}
+
+fn format_entry(emoji: &CachedEmoji, uses: i64) -> String {
+    format!(
+        "\n<{}:{}:{}> ``{}``: {}",
+        if emoji.animated { "a" } else { "" },
+        emoji.name,
+        emoji.id,
+        emoji.name,
+        uses
+    )
+}
@@ -1,3 +1,7 @@
 pub use emoji::*;
+pub use emoji_stats::*;
+pub use online::*;
 
 mod emoji;
+mod emoji_stats;
+mod online;
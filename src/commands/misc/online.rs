@@ -0,0 +1,49 @@
+use twilight_model::gateway::presence::Status;
+
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+pub async fn online(ctx: CommandContext) -> CommandResult {
+    if !ctx.bot_context.cache.tracks_presences() {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::OnlineNotTracked, args).await?;
+        return Ok(());
+    }
+
+    let guild = ctx.get_guild()?;
+    let (mut online, mut idle, mut dnd) = (0u64, 0u64, 0u64);
+    for presence in guild
+        .presences
+        .read()
+        .expect("Guild inner presence cache got poisoned!")
+        .values()
+    {
+        match presence.status {
+            Status::Online => online += 1,
+            Status::Idle => idle += 1,
+            Status::DoNotDisturb => dnd += 1,
+            Status::Offline | Status::Invisible => {}
+        }
+    }
+
+    // offline members aren't tracked, so everyone else we know of is
+    let total = guild
+        .members
+        .read()
+        .expect("Guild inner members cache got poisoned!")
+        .len() as u64;
+    let offline = total.saturating_sub(online + idle + dnd);
+
+    let args = FluArgs::with_capacity(5)
+        .add("online", online)
+        .add("idle", idle)
+        .add("dnd", dnd)
+        .add("offline", offline)
+        .add("total", total)
+        .generate();
+    ctx.reply(GearBotString::OnlineCount, args).await?;
+
+    Ok(())
+}
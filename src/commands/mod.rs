@@ -4,7 +4,8 @@ use std::sync::Arc;
 use lazy_static::lazy_static;
 use twilight_model::guild::Permissions;
 
-use crate::commands::meta::nodes::{CommandGroup, CommandNode, GearBotPermissions, RootNode};
+use crate::commands::meta::nodes::{CommandGroup, CommandNode, CommandOptions, GearBotPermissions, RootNode};
+use crate::translation::GearBotString;
 use crate::{
     command, command_with_aliases, command_with_subcommands, command_with_subcommands_and_aliases,
     command_with_subcommands_and_handler_and_aliases,
@@ -13,6 +14,7 @@ use crate::{
 mod admin;
 mod basic;
 mod debug;
+mod guild_admin;
 pub mod meta;
 mod misc;
 mod moderation;
@@ -26,38 +28,81 @@ lazy_static! {
                 Permissions::EMBED_LINKS,
                 GearBotPermissions::ABOUT_COMMAND,
                 CommandGroup::Basic
-            ),
+            )
+            .described(GearBotString::AboutCommandDescription)
+            .in_dms(),
             command!(
                 "coinflip",
                 basic::coinflip,
                 Permissions::empty(),
                 GearBotPermissions::COINFLIP_COMMAND,
                 CommandGroup::Basic
-            ),
+            )
+            .described(GearBotString::CoinflipDescription)
+            .usage(GearBotString::CoinflipUsage)
+            .in_dms(),
+            command!(
+                "help",
+                basic::help,
+                Permissions::empty(),
+                GearBotPermissions::HELP_COMMAND,
+                CommandGroup::Basic
+            )
+            .described(GearBotString::HelpDescription)
+            .usage(GearBotString::HelpCommandUsage)
+            .in_dms(),
+            command!(
+                "jumbo",
+                basic::jumbo,
+                Permissions::empty(),
+                GearBotPermissions::JUMBO_COMMAND,
+                CommandGroup::Basic
+            )
+            .described(GearBotString::JumboDescription)
+            .usage(GearBotString::JumboUsage)
+            .in_dms(),
             command!(
                 "ping",
                 basic::ping,
                 Permissions::empty(),
                 GearBotPermissions::PING_COMMAND,
                 CommandGroup::Basic
-            ),
+            )
+            .described(GearBotString::PingDescription)
+            .in_dms(),
             command!(
                 "quote",
                 basic::quote,
                 Permissions::EMBED_LINKS,
                 GearBotPermissions::QUOTE_COMMAND,
                 CommandGroup::Basic
-            ),
+            )
+            .described(GearBotString::QuoteDescription)
+            .usage(GearBotString::QuoteUsage),
+            command!(
+                "snowflake",
+                basic::snowflake,
+                Permissions::empty(),
+                GearBotPermissions::SNOWFLAKE_COMMAND,
+                CommandGroup::Basic
+            )
+            .described(GearBotString::SnowflakeDescription)
+            .usage(GearBotString::SnowflakeUsage)
+            .in_dms(),
             command!(
                 "uid",
                 basic::uid,
                 Permissions::empty(),
                 GearBotPermissions::UID_COMMAND,
                 CommandGroup::Basic
-            ),
-            command_with_subcommands_and_aliases!(
+            )
+            .described(GearBotString::UidDescription)
+            .usage(GearBotString::UidUsage)
+            .in_dms(),
+            command_with_subcommands_and_handler_and_aliases!(
                 "config",
                 vec![String::from("c")],
+                debug::config_overview,
                 Permissions::empty(),
                 GearBotPermissions::CONFIG_COMMAND, // has it's own to not cascade to write by mistake
                 CommandGroup::GuildAdmin,
@@ -76,14 +121,18 @@ lazy_static! {
                         GearBotPermissions::READ_CONFIG,
                         CommandGroup::GuildAdmin
                     )
-                ),
+                    .described(GearBotString::ConfigGetPrettyDescription)
+                )
+                .described(GearBotString::ConfigGetDescription),
                 command!(
                     "set",
                     debug::set_config,
                     Permissions::empty(),
                     GearBotPermissions::WRITE_CONFIG,
                     CommandGroup::GuildAdmin
-                ),
+                )
+                .described(GearBotString::ConfigSetDescription)
+                .usage(GearBotString::ConfigSetUsage),
                 command!(
                 "reset",
                 debug::reset_config,
@@ -91,14 +140,607 @@ lazy_static! {
                 GearBotPermissions::WRITE_CONFIG,
                 CommandGroup::GuildAdmin
                 )
-            ),
+                .described(GearBotString::ConfigResetDescription),
+                command!(
+                    "color",
+                    debug::set_embed_color,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigColorDescription)
+                .usage(GearBotString::ConfigColorUsage),
+                command!(
+                    "footer",
+                    debug::set_embed_footer,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigFooterDescription)
+                .usage(GearBotString::ConfigFooterUsage),
+                command!(
+                    "footericon",
+                    debug::set_embed_footer_icon,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigFooterIconDescription)
+                .usage(GearBotString::ConfigFooterIconUsage),
+                command!(
+                    "timezone",
+                    debug::set_timezone,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigTimezoneDescription)
+                .usage(GearBotString::ConfigTimezoneUsage),
+                command!(
+                    "cleanup",
+                    debug::set_response_cleanup,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigCleanupDescription)
+                .usage(GearBotString::ConfigCleanupUsage),
+                command!(
+                    "nickname",
+                    debug::set_bot_nickname,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigNicknameDescription)
+                .usage(GearBotString::ConfigNicknameUsage),
+                command!(
+                    "plaintext",
+                    debug::set_plaintext_responses,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigPlaintextDescription)
+                .usage(GearBotString::ConfigPlaintextUsage),
+                command!(
+                    "quiet",
+                    debug::set_quiet_mode,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigQuietDescription)
+                .usage(GearBotString::ConfigQuietUsage),
+                command!(
+                    "deletecommands",
+                    debug::set_delete_commands,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigDeleteCommandsDescription)
+                .usage(GearBotString::ConfigDeleteCommandsUsage),
+                command!(
+                    "errors",
+                    debug::set_error_verbosity,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigErrorsDescription)
+                .usage(GearBotString::ConfigErrorsUsage),
+                command!(
+                    "systemmessages",
+                    debug::set_system_messages,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigSystemMessagesDescription)
+                .usage(GearBotString::ConfigSystemMessagesUsage),
+                command!(
+                    "contentstorage",
+                    debug::set_content_storage,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigContentStorageDescription)
+                .usage(GearBotString::ConfigContentStorageUsage),
+                command!(
+                    "welcomeimage",
+                    debug::set_welcome_image,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigWelcomeImageDescription)
+                .usage(GearBotString::ConfigWelcomeImageUsage),
+                command!(
+                    "welcomebackground",
+                    debug::set_welcome_background,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigWelcomeBackgroundDescription)
+                .usage(GearBotString::ConfigWelcomeBackgroundUsage),
+                command!(
+                    "welcomecolor",
+                    debug::set_welcome_color,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigWelcomeColorDescription)
+                .usage(GearBotString::ConfigWelcomeColorUsage),
+                command!(
+                    "storageexclude",
+                    debug::set_storage_exclusion,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigStorageExcludeDescription)
+                .usage(GearBotString::ConfigStorageExcludeUsage),
+                command!(
+                    "rolemenumessages",
+                    debug::set_role_menu_messages,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigRoleMenuMessagesDescription)
+                .usage(GearBotString::ConfigRoleMenuMessagesUsage),
+                command!(
+                    "deletegrace",
+                    debug::set_delete_grace,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigDeleteGraceDescription)
+                .usage(GearBotString::ConfigDeleteGraceUsage),
+                command!(
+                    "logcommand",
+                    debug::set_logged_command,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigLogCommandDescription)
+                .usage(GearBotString::ConfigLogCommandUsage),
+                command!(
+                    "commandlevel",
+                    debug::set_command_override,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigCommandLevelDescription)
+                .usage(GearBotString::ConfigCommandLevelUsage),
+                command!(
+                    "automodorder",
+                    debug::set_automod_order,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigAutomodOrderDescription)
+                .usage(GearBotString::ConfigAutomodOrderUsage),
+                command!(
+                    "cooldownbypass",
+                    debug::set_cooldown_bypass,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigCooldownBypassDescription)
+                .usage(GearBotString::ConfigCooldownBypassUsage),
+                command!(
+                    "commandchannels",
+                    debug::set_command_channels,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigCommandChannelsDescription)
+                .usage(GearBotString::ConfigCommandChannelsUsage),
+                command!(
+                    "joinlog",
+                    debug::set_join_log,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigJoinLogDescription)
+                .usage(GearBotString::ConfigJoinLogUsage),
+                command!(
+                    "messagelog",
+                    debug::set_message_log,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigMessageLogDescription)
+                .usage(GearBotString::ConfigMessageLogUsage),
+                command!(
+                    "export",
+                    debug::export_config,
+                    Permissions::empty(),
+                    GearBotPermissions::READ_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigExportDescription),
+                command!(
+                    "import",
+                    debug::import_config,
+                    Permissions::empty(),
+                    GearBotPermissions::WRITE_CONFIG,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::ConfigImportDescription)
+                .usage(GearBotString::ConfigImportUsage)
+            )
+            .described(GearBotString::ConfigDescription),
+            command_with_subcommands!(
+                "announcement",
+                GearBotPermissions::ANNOUNCEMENTS_COMMAND,
+                CommandGroup::GuildAdmin,
+                command!(
+                    "add",
+                    guild_admin::add_announcement,
+                    Permissions::empty(),
+                    GearBotPermissions::ANNOUNCEMENTS_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AnnouncementAddDescription)
+                .usage(GearBotString::AnnouncementAddUsage),
+                command!(
+                    "list",
+                    guild_admin::list_announcements,
+                    Permissions::empty(),
+                    GearBotPermissions::ANNOUNCEMENTS_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AnnouncementListDescription),
+                command!(
+                    "remove",
+                    guild_admin::remove_announcement,
+                    Permissions::empty(),
+                    GearBotPermissions::ANNOUNCEMENTS_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AnnouncementRemoveDescription)
+                .usage(GearBotString::AnnouncementRemoveUsage)
+            )
+            .described(GearBotString::AnnouncementDescription),
+            command_with_subcommands!(
+                "autoresponse",
+                GearBotPermissions::AUTORESPONSE_COMMAND,
+                CommandGroup::GuildAdmin,
+                command!(
+                    "add",
+                    guild_admin::add_auto_response,
+                    Permissions::empty(),
+                    GearBotPermissions::AUTORESPONSE_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AutoResponseAddDescription)
+                .usage(GearBotString::AutoResponseAddUsage),
+                command!(
+                    "list",
+                    guild_admin::list_auto_responses,
+                    Permissions::empty(),
+                    GearBotPermissions::AUTORESPONSE_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AutoResponseListDescription),
+                command!(
+                    "remove",
+                    guild_admin::remove_auto_response,
+                    Permissions::empty(),
+                    GearBotPermissions::AUTORESPONSE_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::AutoResponseRemoveDescription)
+                .usage(GearBotString::AutoResponseRemoveUsage)
+            )
+            .described(GearBotString::AutoResponseDescription),
+            command_with_subcommands!(
+                "rolemenu",
+                GearBotPermissions::ROLEMENU_COMMAND,
+                CommandGroup::GuildAdmin,
+                command!(
+                    "create",
+                    guild_admin::create_role_menu,
+                    Permissions::MANAGE_ROLES | Permissions::ADD_REACTIONS | Permissions::EMBED_LINKS,
+                    GearBotPermissions::ROLEMENU_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::RoleMenuCreateDescription)
+                .usage(GearBotString::RoleMenuCreateUsage),
+                command!(
+                    "list",
+                    guild_admin::list_role_menus,
+                    Permissions::empty(),
+                    GearBotPermissions::ROLEMENU_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::RoleMenuListDescription),
+                command!(
+                    "remove",
+                    guild_admin::remove_role_menu,
+                    Permissions::empty(),
+                    GearBotPermissions::ROLEMENU_COMMAND,
+                    CommandGroup::GuildAdmin
+                )
+                .described(GearBotString::RoleMenuRemoveDescription)
+                .usage(GearBotString::RoleMenuRemoveUsage)
+            )
+            .described(GearBotString::RoleMenuDescription),
+            command!(
+                "disable",
+                guild_admin::disable_command,
+                Permissions::empty(),
+                GearBotPermissions::TOGGLE_COMMANDS,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::DisableDescription)
+            .usage(GearBotString::DisableUsage),
+            command!(
+                "enable",
+                guild_admin::enable_command,
+                Permissions::empty(),
+                GearBotPermissions::TOGGLE_COMMANDS,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::EnableDescription)
+            .usage(GearBotString::EnableUsage),
+            command!(
+                "importhistory",
+                guild_admin::import_history,
+                Permissions::empty(),
+                GearBotPermissions::IMPORTHISTORY_COMMAND,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::ImportHistoryDescription),
+            command!(
+                "exporthistory",
+                guild_admin::export_history,
+                Permissions::ATTACH_FILES,
+                GearBotPermissions::EXPORTHISTORY_COMMAND,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::ExportHistoryDescription)
+            .usage(GearBotString::ExportHistoryUsage)
+            .cooldown(60),
+            command!(
+                "automodtest",
+                guild_admin::automod_test,
+                Permissions::empty(),
+                GearBotPermissions::AUTOMODTEST_COMMAND,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::AutomodTestDescription)
+            .usage(GearBotString::AutomodTestUsage)
+            .cooldown(30),
+            command!(
+                "simulatejoin",
+                guild_admin::simulate_join,
+                Permissions::empty(),
+                GearBotPermissions::SIMULATEJOIN_COMMAND,
+                CommandGroup::GuildAdmin
+            )
+            .described(GearBotString::SimulateJoinDescription)
+            .usage(GearBotString::SimulateJoinUsage),
             command!(
                 "userinfo",
                 moderation::userinfo,
                 Permissions::EMBED_LINKS,
                 GearBotPermissions::USERINFO_COMMAND,
                 CommandGroup::Moderation
-            ),
+            )
+            .described(GearBotString::UserinfoDescription)
+            .usage(GearBotString::UserinfoUsage),
+            command_with_subcommands_and_handler_and_aliases!(
+                "purge",
+                vec![String::from("clean")],
+                moderation::purge,
+                Permissions::MANAGE_MESSAGES | Permissions::READ_MESSAGE_HISTORY,
+                GearBotPermissions::PURGE_COMMAND,
+                CommandGroup::Moderation,
+                command!(
+                    "until",
+                    moderation::purge_until,
+                    Permissions::MANAGE_MESSAGES | Permissions::READ_MESSAGE_HISTORY,
+                    GearBotPermissions::PURGE_COMMAND,
+                    CommandGroup::Moderation
+                )
+                .described(GearBotString::PurgeUntilDescription)
+                .usage(GearBotString::PurgeUntilUsage)
+            )
+            .described(GearBotString::PurgeDescription)
+            .usage(GearBotString::PurgeUsage),
+            command!(
+                "delwarn",
+                moderation::delwarn,
+                Permissions::empty(),
+                GearBotPermissions::DELWARN_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::DelwarnDescription)
+            .usage(GearBotString::DelwarnUsage),
+            command!(
+                "reason",
+                moderation::reason,
+                Permissions::empty(),
+                GearBotPermissions::REASON_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::ReasonDescription)
+            .usage(GearBotString::ReasonUsage),
+            command!(
+                "clearwarnings",
+                moderation::clearwarnings,
+                Permissions::empty(),
+                GearBotPermissions::CLEARWARNINGS_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::ClearWarningsDescription)
+            .usage(GearBotString::ClearWarningsUsage),
+            command!(
+                "addnote",
+                moderation::addnote,
+                Permissions::empty(),
+                GearBotPermissions::ADDNOTE_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::AddNoteDescription)
+            .usage(GearBotString::AddNoteUsage),
+            command!(
+                "notes",
+                moderation::notes,
+                Permissions::empty(),
+                GearBotPermissions::NOTES_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::NotesDescription)
+            .usage(GearBotString::NotesUsage),
+            command!(
+                "delnote",
+                moderation::delnote,
+                Permissions::empty(),
+                GearBotPermissions::DELNOTE_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::DelNoteDescription)
+            .usage(GearBotString::DelNoteUsage),
+            command!(
+                "alts",
+                moderation::alts,
+                Permissions::empty(),
+                GearBotPermissions::ALTS_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::AltsDescription)
+            .usage(GearBotString::AltsUsage),
+            command!(
+                "names",
+                moderation::names,
+                Permissions::empty(),
+                GearBotPermissions::NAMES_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::NamesDescription)
+            .usage(GearBotString::NamesUsage),
+            command!(
+                "modstats",
+                moderation::modstats,
+                Permissions::empty(),
+                GearBotPermissions::MODSTATS_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::ModStatsDescription)
+            .usage(GearBotString::ModStatsUsage),
+            command!(
+                "massban",
+                moderation::massban,
+                Permissions::BAN_MEMBERS,
+                GearBotPermissions::MASSBAN_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::MassBanDescription)
+            .usage(GearBotString::MassBanUsage),
+            command!(
+                "massunban",
+                moderation::massunban,
+                Permissions::BAN_MEMBERS,
+                GearBotPermissions::MASSBAN_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::MassUnbanDescription)
+            .usage(GearBotString::MassUnbanUsage),
+            command!(
+                "prune",
+                moderation::prune,
+                Permissions::KICK_MEMBERS,
+                GearBotPermissions::PRUNE_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::PruneDescription)
+            .usage(GearBotString::PruneUsage),
+            command!(
+                "raidmode",
+                moderation::raidmode,
+                Permissions::empty(),
+                GearBotPermissions::RAIDMODE_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::RaidModeDescription)
+            .usage(GearBotString::RaidModeUsage),
+            command!(
+                "timeout",
+                moderation::timeout,
+                Permissions::empty(),
+                GearBotPermissions::TIMEOUT_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::TimeoutDescription)
+            .usage(GearBotString::TimeoutUsage),
+            command!(
+                "untimeout",
+                moderation::untimeout,
+                Permissions::empty(),
+                GearBotPermissions::TIMEOUT_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::UntimeoutDescription)
+            .usage(GearBotString::UntimeoutUsage),
+            command!(
+                "transcript",
+                moderation::transcript,
+                Permissions::ATTACH_FILES,
+                GearBotPermissions::TRANSCRIPT_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::TranscriptDescription)
+            .usage(GearBotString::TranscriptUsage)
+            .cooldown(60),
+            command!(
+                "activity",
+                moderation::channel_activity,
+                Permissions::empty(),
+                GearBotPermissions::ACTIVITY_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::ActivityDescription)
+            .usage(GearBotString::ActivityUsage),
+            command!(
+                "reactions",
+                moderation::message_reactions,
+                Permissions::empty(),
+                GearBotPermissions::REACTIONS_COMMAND,
+                CommandGroup::Moderation
+            )
+            .described(GearBotString::ReactionsDescription)
+            .usage(GearBotString::ReactionsUsage),
+            command_with_subcommands!(
+                "stats",
+                GearBotPermissions::STATS_COMMAND,
+                CommandGroup::Moderation,
+                command!(
+                    "attachments",
+                    moderation::attachment_stats,
+                    Permissions::empty(),
+                    GearBotPermissions::STATS_COMMAND,
+                    CommandGroup::Moderation
+                )
+                .described(GearBotString::StatsAttachmentsDescription)
+            )
+            .described(GearBotString::StatsDescription),
             command_with_subcommands!(
                 "check",
                 GearBotPermissions::BOT_ADMIN,
@@ -110,23 +752,224 @@ lazy_static! {
                     GearBotPermissions::BOT_ADMIN,
                     CommandGroup::BotAdmin
                 )
-            ),
+                .described(GearBotString::CheckCacheDescription)
+            )
+            .described(GearBotString::CheckDescription),
+            command!(
+                "cachestats",
+                admin::cache_stats,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::CacheStatsDescription)
+            .usage(GearBotString::CacheStatsUsage),
+            command!(
+                "clearcache",
+                admin::clear_cache,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::ClearCacheDescription)
+            .usage(GearBotString::ClearCacheUsage),
+            command!(
+                "maintenance",
+                admin::maintenance,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::MaintenanceDescription)
+            .usage(GearBotString::MaintenanceUsage),
+            command_with_subcommands!(
+                "killswitch",
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin,
+                command!(
+                    "disable",
+                    admin::disable_globally,
+                    Permissions::empty(),
+                    GearBotPermissions::BOT_ADMIN,
+                    CommandGroup::BotAdmin
+                )
+                .described(GearBotString::KillswitchDisableDescription)
+                .usage(GearBotString::KillswitchDisableUsage),
+                command!(
+                    "enable",
+                    admin::enable_globally,
+                    Permissions::empty(),
+                    GearBotPermissions::BOT_ADMIN,
+                    CommandGroup::BotAdmin
+                )
+                .described(GearBotString::KillswitchEnableDescription)
+                .usage(GearBotString::KillswitchEnableUsage),
+                command!(
+                    "list",
+                    admin::list_globally_disabled,
+                    Permissions::empty(),
+                    GearBotPermissions::BOT_ADMIN,
+                    CommandGroup::BotAdmin
+                )
+                .described(GearBotString::KillswitchListDescription)
+            )
+            .described(GearBotString::KillswitchDescription),
+            command!(
+                "reload_logging",
+                admin::reload_logging,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::ReloadLoggingDescription),
+            command!(
+                "test_webhooks",
+                admin::test_webhooks,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::TestWebhooksDescription),
+            command!(
+                "set_avatar",
+                admin::set_avatar,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::SetAvatarDescription),
+            command!(
+                "set_username",
+                admin::set_username,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::SetUsernameDescription)
+            .usage(GearBotString::SetUsernameUsage),
+            command!(
+                "rotate_keys",
+                admin::rotate_keys,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::RotateKeysDescription),
+            command!(
+                "rotate_guild_key",
+                admin::rotate_guild_key,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::RotateGuildKeyDescription)
+            .usage(GearBotString::RotateGuildKeyUsage),
+            command!(
+                "verify_messages",
+                admin::verify_messages,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::VerifyMessagesDescription)
+            .usage(GearBotString::VerifyMessagesUsage),
+            command!(
+                "translations",
+                admin::validate_translations,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::TranslationsDescription)
+            .usage(GearBotString::TranslationsUsage),
+            command!(
+                "reload_translations",
+                admin::reload_translations,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::ReloadTranslationsDescription),
+            command!(
+                "webhook",
+                admin::webhook_status,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::WebhookDescription)
+            .usage(GearBotString::WebhookUsage),
+            command!(
+                "shards",
+                admin::shards,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::ShardsDescription),
+            command!(
+                "health",
+                admin::health,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::HealthDescription),
             command!(
                 "redis_test",
                 admin::restart,
                 Permissions::empty(),
                 GearBotPermissions::BOT_ADMIN,
                 CommandGroup::BotAdmin
-            ),
+            )
+            .described(GearBotString::RedisTestDescription),
             command!(
             "perms",
             debug::get_perms,
             Permissions::empty(),
             GearBotPermissions::BOT_ADMIN,
             CommandGroup::BotAdmin
-            ),
-            command!("test", debug::test, Permissions::empty(), GearBotPermissions::BOT_ADMIN, CommandGroup::BotAdmin),
-            command_with_subcommands!("emoji", GearBotPermissions::EMOJI_COMMAND, CommandGroup::Misc, command!("list", misc::emoji_list, Permissions::EMBED_LINKS, GearBotPermissions::EMOJI_LIST_COMMAND, CommandGroup::Misc))
+            )
+            .described(GearBotString::PermsDescription)
+            .usage(GearBotString::PermsUsage),
+            command!(
+                "test",
+                debug::test,
+                Permissions::empty(),
+                GearBotPermissions::BOT_ADMIN,
+                CommandGroup::BotAdmin
+            )
+            .described(GearBotString::TestDescription),
+            command_with_subcommands!(
+                "emoji",
+                GearBotPermissions::EMOJI_COMMAND,
+                CommandGroup::Misc,
+                command!(
+                    "list",
+                    misc::emoji_list,
+                    Permissions::EMBED_LINKS,
+                    GearBotPermissions::EMOJI_LIST_COMMAND,
+                    CommandGroup::Misc
+                )
+                .described(GearBotString::EmojiListDescription)
+            )
+            .described(GearBotString::EmojiDescription),
+            command!(
+                "emojistats",
+                misc::emoji_stats,
+                Permissions::empty(),
+                GearBotPermissions::EMOJI_STATS_COMMAND,
+                CommandGroup::Misc
+            )
+            .described(GearBotString::EmojiStatsDescription),
+            command!(
+                "online",
+                misc::online,
+                Permissions::empty(),
+                GearBotPermissions::ONLINE_COMMAND,
+                CommandGroup::Misc
+            )
+            .described(GearBotString::OnlineDescription)
         ];
 
         let mut all_commands = HashMap::new();
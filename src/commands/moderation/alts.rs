@@ -0,0 +1,67 @@
+use chrono::Duration;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+/// How close to the first message of the user others need to have started talking, unless told otherwise.
+const DEFAULT_WINDOW_HOURS: i64 = 1;
+
+/// Anything wider than this turns up regular members rather than alts.
+const MAX_WINDOW_DAYS: i64 = 1;
+
+/// The most possible alts listed at once.
+const MAX_CANDIDATES: i64 = 25;
+
+pub async fn alts(mut ctx: CommandContext) -> CommandResult {
+    let user = ctx.parser.get_user().await?;
+    let window = if ctx.parser.has_next() {
+        ctx.parser.get_duration()?
+    } else {
+        Duration::hours(DEFAULT_WINDOW_HOURS)
+    };
+    if window > Duration::days(MAX_WINDOW_DAYS) {
+        return Err(ParseError::WrongArgumentType(String::from("duration of at most 1d")).into());
+    }
+    let window_text = ctx.format_duration(window, 3);
+
+    let guild_id = ctx.get_guild()?.id;
+    let candidates = ctx
+        .bot_context
+        .datastore
+        .find_alt_candidates(guild_id, user.id, window, MAX_CANDIDATES)
+        .await?;
+
+    if candidates.is_empty() {
+        let args = FluArgs::with_capacity(3)
+            .add("gearno", Emoji::No.for_chat())
+            .add("window", window_text)
+            .add("user_id", user.id.to_string())
+            .generate();
+        ctx.reply(GearBotString::AltsEmpty, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(2)
+        .add("window", window_text)
+        .add("user_id", user.id.to_string())
+        .generate();
+    let mut output = ctx.translate_with_args(GearBotString::AltsHeader, &args);
+    output += "\n";
+    for (user_id, first_message, shared_channels) in candidates {
+        let args = FluArgs::with_capacity(3)
+            .add("user_id", user_id.to_string())
+            .add(
+                "first_message",
+                ctx.format_time(utils::snowflake_timestamp(first_message as u64)),
+            )
+            .add("shared_channels", shared_channels)
+            .generate();
+        output += "\n";
+        output += &ctx.translate_with_args(GearBotString::AltsEntry, &args);
+    }
+    ctx.reply_raw_or_file(output, "alts.txt").await?;
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::collections::{BTreeSet, HashSet};
+use std::time::{Duration, Instant};
+
+use twilight_model::id::UserId;
+
+use crate::core::logpump::LogType;
+use crate::core::CommandContext;
+use crate::error::{CommandError, CommandResult};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::bulk::BulkOutcome;
+use crate::utils::{self, matchers, Emoji};
+
+/// The most users a single mass action is allowed to handle.
+const MAX_TARGETS: usize = 1000;
+
+/// Id lists are plain text, anything bigger than this is not what we're looking for.
+const MAX_ATTACHMENT_SIZE: u64 = 256 * 1024;
+
+/// How often the progress message gets updated while working through the list.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+pub async fn massban(mut ctx: CommandContext) -> CommandResult {
+    mass_action(&mut ctx, MassAction::Ban).await
+}
+
+pub async fn massunban(mut ctx: CommandContext) -> CommandResult {
+    mass_action(&mut ctx, MassAction::Unban).await
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MassAction {
+    Ban,
+    Unban,
+}
+
+async fn mass_action(ctx: &mut CommandContext, action: MassAction) -> CommandResult {
+    // whatever is left once the ids are taken out is the reason
+    let reason = matchers::strip_ids(&ctx.parser.get_remaining());
+    if !ctx.require_reason(&reason).await? {
+        return Ok(());
+    }
+
+    let targets = match collect_targets(ctx).await? {
+        Some(targets) => targets,
+        None => return Ok(()),
+    };
+
+    let guild_id = ctx.get_guild()?.id;
+    let http = &ctx.bot_context.http;
+
+    // one request for the ban list is a lot cheaper than finding out per user
+    let banned: HashSet<UserId> = http.bans(guild_id).await?.into_iter().map(|ban| ban.user.id).collect();
+
+    let (todo, skipped): (Vec<UserId>, Vec<UserId>) = targets.into_iter().partition(|user_id| match action {
+        MassAction::Ban => !banned.contains(user_id),
+        MassAction::Unban => banned.contains(user_id),
+    });
+
+    let progress_args = |done: usize| {
+        FluArgs::with_capacity(2)
+            .add("done", done)
+            .add("total", todo.len())
+            .generate()
+    };
+    let progress = ctx.reply(GearBotString::MassActionProgress, progress_args(0)).await?;
+    let mut last_update = Instant::now();
+
+    let mut audit_reason = format!("Mass action by {}", ctx.message.author.full_name_with_id());
+    if !reason.is_empty() {
+        audit_reason += &format!(": {}", reason);
+    }
+    let audit_reason: String = audit_reason.chars().take(utils::MAX_AUDIT_REASON_LENGTH).collect();
+
+    let guild = ctx.get_guild()?;
+    let author = ctx.message.get_author_as_member()?;
+    let bot = ctx.bot_context.cache.get_member(&guild_id, &ctx.get_bot_user().id);
+
+    let mut outcome = BulkOutcome::default();
+    for (done, user_id) in todo.iter().enumerate() {
+        // only members can outrank anyone, banning users that already left is always fine
+        let member = ctx.bot_context.cache.get_member(&guild_id, user_id);
+        let hierarchy = match (&member, action) {
+            (Some(member), MassAction::Ban) => guild.can_act_on(&author, member, bot.as_deref()),
+            _ => Ok(()),
+        };
+        if let Err(e) = hierarchy {
+            outcome.failure(*user_id, e);
+            continue;
+        }
+
+        // the http client takes care of waiting for the rate limits between these
+        let result = match action {
+            MassAction::Ban => http.create_ban(guild_id, *user_id).reason(audit_reason.clone()).await,
+            MassAction::Unban => http.delete_ban(guild_id, *user_id).reason(audit_reason.clone()).await,
+        };
+
+        outcome.record_http(*user_id, result);
+
+        if last_update.elapsed() >= PROGRESS_INTERVAL {
+            last_update = Instant::now();
+            let text = ctx.translate_with_args(GearBotString::MassActionProgress, &progress_args(done + 1));
+            ctx.update_message(text, progress.channel_id, progress.id).await?;
+        }
+    }
+
+    let succeeded = outcome.succeeded();
+    if succeeded > 0 {
+        let log_type = match action {
+            MassAction::Ban => LogType::MassBan { count: succeeded },
+            MassAction::Unban => LogType::MassUnban { count: succeeded },
+        };
+        ctx.log(log_type, Some(ctx.message.channel.get_id()), ctx.message.author.id);
+    }
+
+    let key = match action {
+        MassAction::Ban => GearBotString::MassBanDone,
+        MassAction::Unban => GearBotString::MassUnbanDone,
+    };
+    let args = FluArgs::with_capacity(4)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("succeeded", succeeded)
+        .add("skipped", skipped.len())
+        .add("failed", outcome.failed())
+        .generate();
+    let summary = ctx.translate_with_args(key, &args) + &outcome.failure_report(ctx);
+
+    ctx.reply_raw_or_file(summary, "mass_action.txt").await?;
+
+    Ok(())
+}
+
+/// Gathers the unique user ids from the arguments and an optional text file attachment.
+///
+/// Returns ``None`` when the user was already told why there is nothing to do.
+async fn collect_targets(ctx: &mut CommandContext) -> Result<Option<Vec<UserId>>, CommandError> {
+    let mut input = ctx.parser.get_remaining();
+
+    if let Some(attachment) = ctx.message.attachments.first() {
+        if attachment.size > MAX_ATTACHMENT_SIZE {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::MassActionAttachmentInvalid, args).await?;
+            return Ok(None);
+        }
+
+        let content = match utils::download_text(&attachment.url).await {
+            Ok(content) => content,
+            Err(e) => {
+                log::debug!("Failed to download mass action list {}: {}", attachment.url, e);
+                let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+                ctx.reply(GearBotString::MassActionAttachmentInvalid, args).await?;
+                return Ok(None);
+            }
+        };
+        input.push('\n');
+        input += &content;
+    }
+
+    // sorted and deduplicated so the order of processing is predictable
+    let own_ids = [ctx.message.author.id, ctx.get_bot_user().id];
+    let targets: BTreeSet<UserId> = matchers::get_ids(&input)
+        .into_iter()
+        .map(UserId)
+        .filter(|user_id| !own_ids.contains(user_id))
+        .collect();
+
+    if targets.is_empty() {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::MassActionNoTargets, args).await?;
+        return Ok(None);
+    }
+
+    if targets.len() > MAX_TARGETS {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("max", MAX_TARGETS)
+            .generate();
+        ctx.reply(GearBotString::MassActionTooManyTargets, args).await?;
+        return Ok(None);
+    }
+
+    Ok(Some(targets.into_iter().collect()))
+}
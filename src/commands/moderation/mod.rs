@@ -1,3 +1,29 @@
+pub use alts::alts;
+pub use mass_ban::*;
+pub use modstats::modstats;
+pub use names::names;
+pub use notes::*;
+pub use prune::prune;
+pub use purge::*;
+pub use raidmode::raidmode;
+pub use reason::reason;
+pub use stats::*;
+pub use timeout::*;
+pub use transcript::transcript;
 pub use userinfo::userinfo;
+pub use warnings::*;
 
+mod alts;
+mod mass_ban;
+mod modstats;
+mod names;
+mod notes;
+mod prune;
+mod purge;
+mod raidmode;
+mod reason;
+mod stats;
+mod timeout;
+mod transcript;
 mod userinfo;
+mod warnings;
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// The window activity is counted over, unless told otherwise.
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+/// Anything older than this is more about who used to be on the team than current activity.
+const MAX_WINDOW_DAYS: i64 = 365;
+
+/// How many moderators the leaderboard shows.
+const LEADERBOARD_SIZE: usize = 10;
+
+pub async fn modstats(mut ctx: CommandContext) -> CommandResult {
+    let window = if ctx.parser.has_next() {
+        ctx.parser.get_duration()?
+    } else {
+        Duration::days(DEFAULT_WINDOW_DAYS)
+    };
+    if window > Duration::days(MAX_WINDOW_DAYS) {
+        return Err(ParseError::WrongArgumentType(String::from("duration of at most 365d")).into());
+    }
+    let window_text = ctx.format_duration(window, 3);
+
+    let guild_id = ctx.get_guild()?.id;
+    let activity = ctx
+        .bot_context
+        .datastore
+        .get_moderator_activity(guild_id, Utc::now() - window)
+        .await?;
+
+    if activity.is_empty() {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("window", window_text)
+            .generate();
+        ctx.reply(GearBotString::ModStatsEmpty, args).await?;
+        return Ok(());
+    }
+
+    let mut per_moderator: HashMap<i64, Vec<(String, i64)>> = HashMap::new();
+    for (mod_id, kind, count) in activity {
+        per_moderator.entry(mod_id).or_default().push((kind, count));
+    }
+    let mut leaderboard: Vec<_> = per_moderator
+        .into_iter()
+        .map(|(mod_id, mut actions)| {
+            actions.sort_by(|(_, a), (_, b)| b.cmp(a));
+            let total: i64 = actions.iter().map(|(_, count)| count).sum();
+            (mod_id, total, actions)
+        })
+        .collect();
+    leaderboard.sort_by(|(_, a, _), (_, b, _)| b.cmp(a));
+
+    let args = FluArgs::with_capacity(1).add("window", window_text).generate();
+    let mut entries = vec![ctx.translate_with_args(GearBotString::ModStatsTitle, &args)];
+    for (position, (mod_id, total, actions)) in leaderboard.into_iter().take(LEADERBOARD_SIZE).enumerate() {
+        let breakdown = actions
+            .iter()
+            .map(|(kind, count)| format!("{}: {}", kind, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let args = FluArgs::with_capacity(4)
+            .add("position", position + 1)
+            .add("moderator", mod_id.to_string())
+            .add("total", total)
+            .add("breakdown", breakdown)
+            .generate();
+        entries.push(ctx.translate_with_args(GearBotString::ModStatsEntry, &args));
+    }
+
+    let embed = ctx.embed_builder()?.description(entries.join("\n\n"))?.build()?;
+    ctx.reply_embed(embed).await?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::core::CommandContext;
+use crate::database::structures::NameChange;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+pub async fn names(mut ctx: CommandContext) -> CommandResult {
+    let user = ctx.parser.get_user().await?;
+    let guild_id = ctx.get_guild()?.id;
+
+    let datastore = &ctx.bot_context.datastore;
+    let usernames = datastore.get_username_changes(user.id).await?;
+    let nicknames = datastore.get_nickname_changes(guild_id, user.id).await?;
+
+    if usernames.is_empty() && nicknames.is_empty() {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("user_id", user.id.to_string())
+            .generate();
+        ctx.reply(GearBotString::NamesEmpty, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(1).add("user_id", user.id.to_string()).generate();
+    let mut output = ctx.translate_with_args(GearBotString::NamesHeader, &args);
+    add_section(&ctx, &mut output, GearBotString::NamesUsernames, &usernames);
+    add_section(&ctx, &mut output, GearBotString::NamesNicknames, &nicknames);
+    ctx.reply_raw_or_file(output, "names.txt").await?;
+
+    Ok(())
+}
+
+fn add_section(ctx: &CommandContext, output: &mut String, title: GearBotString, changes: &[NameChange]) {
+    if changes.is_empty() {
+        return;
+    }
+
+    *output += "\n\n";
+    *output += &ctx.translate(title);
+    for change in changes {
+        *output += "\n";
+        *output += &format_change(ctx, change);
+    }
+}
+
+fn format_change(ctx: &CommandContext, change: &NameChange) -> String {
+    // names are picked by the users themselves, don't let them format or ping anything
+    let show = |name: &Option<String>| match name {
+        Some(name) => utils::clean(name, true, true, true, false),
+        None => ctx.translate(GearBotString::NamesNoNickname),
+    };
+
+    let args = FluArgs::with_capacity(3)
+        .add("changed_at", ctx.format_time(change.changed_at))
+        .add("old_name", show(&change.old_name))
+        .add("new_name", show(&change.new_name))
+        .generate();
+    ctx.translate_with_args(GearBotString::NamesEntry, &args)
+}
@@ -0,0 +1,92 @@
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// Notes are meant to be short, anything longer belongs in a proper case writeup.
+const MAX_NOTE_LENGTH: usize = 1000;
+
+pub async fn addnote(mut ctx: CommandContext) -> CommandResult {
+    let user = ctx.parser.get_user().await?;
+    let content = ctx.parser.get_remaining();
+
+    if content.is_empty() {
+        return Err(ParseError::MissingArgument.into());
+    }
+
+    if content.chars().count() > MAX_NOTE_LENGTH {
+        return Err(ParseError::WrongArgumentType(format!("note of at most {} characters", MAX_NOTE_LENGTH)).into());
+    }
+
+    let note_id = ctx
+        .bot_context
+        .datastore
+        .insert_note(
+            ctx.get_guild()?.id,
+            user.id,
+            ctx.message.author.id,
+            ctx.message.id,
+            &content,
+        )
+        .await?;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("note_id", note_id)
+        .add("user_id", user.id.to_string())
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::NoteAdded, args).await?;
+
+    Ok(())
+}
+
+pub async fn notes(mut ctx: CommandContext) -> CommandResult {
+    let user = ctx.parser.get_user().await?;
+    let notes = ctx
+        .bot_context
+        .datastore
+        .get_notes(ctx.get_guild()?.id, user.id)
+        .await?;
+
+    if notes.is_empty() {
+        let args = FluArgs::with_capacity(1).add("user_id", user.id.to_string()).generate();
+        ctx.reply(GearBotString::NoNotes, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(2)
+        .add("count", notes.len())
+        .add("user_id", user.id.to_string())
+        .generate();
+    let mut output = ctx.translate_with_args(GearBotString::NotesHeader, &args);
+    for note in notes {
+        output += &format!("\n``#{}`` <@{}>: {}", note.id, note.moderator, note.content);
+    }
+
+    ctx.reply_raw_or_file(output, "notes.txt").await?;
+
+    Ok(())
+}
+
+pub async fn delnote(mut ctx: CommandContext) -> CommandResult {
+    let note_id = ctx.parser.get_entry_number()?;
+
+    let removed = ctx
+        .bot_context
+        .datastore
+        .remove_note(ctx.get_guild()?.id, note_id)
+        .await?;
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .add("note_id", note_id)
+        .generate();
+    if removed {
+        ctx.reply_success(GearBotString::NoteRemoved, args).await?;
+    } else {
+        ctx.reply(GearBotString::NoteNotFound, args).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::core::logpump::LogType;
+use crate::core::CommandContext;
+use crate::error::{CommandResult, DatabaseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// The range of inactivity discord accepts for prunes.
+const MIN_PRUNE_DAYS: i64 = 1;
+const MAX_PRUNE_DAYS: i64 = 30;
+
+/// Seconds a previewed prune stays available to be confirmed.
+const CONFIRMATION_TTL: u32 = 60;
+
+pub async fn prune(mut ctx: CommandContext) -> CommandResult {
+    let days = ctx.parser.get_i64()?;
+    if days < MIN_PRUNE_DAYS || days > MAX_PRUNE_DAYS {
+        let args = FluArgs::with_capacity(3)
+            .add("gearno", Emoji::No.for_chat())
+            .add("min", MIN_PRUNE_DAYS)
+            .add("max", MAX_PRUNE_DAYS)
+            .generate();
+        ctx.reply(GearBotString::PruneInvalidDays, args).await?;
+        return Ok(());
+    }
+    let days = days as u64;
+
+    let confirmed = ctx.parser.has_next() && ctx.parser.get_next()?.eq_ignore_ascii_case("confirm");
+    let guild_id = ctx.get_guild()?.id;
+    let prefix = ctx.get_config()?.prefix.clone();
+    let cache = &ctx.bot_context.datastore.cache_pool;
+    // confirmations are per moderator, so nobody can confirm a prune someone else only looked at
+    let key = format!("prune_confirm:{}:{}", guild_id, ctx.message.author.id);
+
+    if !confirmed {
+        let count = ctx
+            .bot_context
+            .http
+            .guild_prune_count(guild_id)
+            .days(days)
+            .unwrap() // validated above
+            .await?
+            .pruned;
+
+        if count == 0 {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("days", days)
+                .generate();
+            ctx.reply(GearBotString::PruneNothing, args).await?;
+            return Ok(());
+        }
+
+        cache.set(&key, &days, Some(CONFIRMATION_TTL)).await?;
+        let args = FluArgs::with_capacity(4)
+            .add("gearwarn", Emoji::Warn.for_chat())
+            .add("count", count)
+            .add("days", days)
+            .add("prefix", prefix)
+            .generate();
+        ctx.reply(GearBotString::PrunePreview, args).await?;
+        return Ok(());
+    }
+
+    if cache.get::<u64>(&key).await? != Some(days) {
+        let args = FluArgs::with_capacity(3)
+            .add("gearno", Emoji::No.for_chat())
+            .add("days", days)
+            .add("prefix", prefix)
+            .generate();
+        ctx.reply(GearBotString::PruneNotPending, args).await?;
+        return Ok(());
+    }
+    cache.delete(&key).await.map_err(DatabaseError::from)?;
+
+    let count = ctx
+        .bot_context
+        .http
+        .create_guild_prune(guild_id)
+        .days(days)
+        .unwrap() // validated above
+        .compute_prune_count(true)
+        .await?
+        .map_or(0, |prune| prune.pruned);
+
+    ctx.log(
+        LogType::MembersPruned { count, days },
+        Some(ctx.message.channel.get_id()),
+        ctx.message.author.id,
+    );
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("count", count)
+        .add("days", days)
+        .generate();
+    ctx.reply_and_clean_up(GearBotString::PruneDone, args).await?;
+
+    Ok(())
+}
@@ -0,0 +1,427 @@
+use std::time::{Duration as StdDuration, Instant};
+
+use chrono::{Duration, Utc};
+use regex::{Regex, RegexBuilder};
+use twilight_model::channel::Message;
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use crate::core::CommandContext;
+use crate::error::{CommandError, CommandResult, OtherFailure, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, text, Emoji};
+
+/// The upper limit of messages a single purge is allowed to look at.
+const MAX_PURGE_SIZE: usize = 1000;
+
+/// Discord refuses to bulk delete messages older than this.
+const BULK_DELETE_MAX_AGE_DAYS: i64 = 14;
+
+/// Shows what would be removed instead of removing it.
+const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// Stores the messages and DMs a transcript of them before they are removed.
+const ARCHIVE_FLAG: &str = "--archive";
+
+/// Only messages matching the regex after this prefix are removed, can be given more than once.
+const REGEX_FILTER_PREFIX: &str = "regex:";
+
+/// Longest regex accepted as a filter.
+const MAX_FILTER_LENGTH: usize = 200;
+
+/// Caps how big a filter regex can get once compiled, anything bigger is rejected as too complex.
+const FILTER_SIZE_LIMIT: usize = 64 * 1024;
+
+/// How long matching all messages against the filters is allowed to take before giving up.
+const FILTER_MATCH_TIMEOUT: StdDuration = StdDuration::from_secs(2);
+
+/// How many of the matching messages a dry run shows.
+const DRY_RUN_SAMPLE_SIZE: usize = 5;
+
+/// Message content in the dry run sample is cut off after this many characters.
+const DRY_RUN_SNIPPET_LENGTH: usize = 60;
+
+pub async fn purge(mut ctx: CommandContext) -> CommandResult {
+    let count = ctx.parser.get_i64()?;
+
+    if count < 1 || count > MAX_PURGE_SIZE as i64 {
+        return Err(ParseError::WrongArgumentType(format!("number between 1 and {}", MAX_PURGE_SIZE)).into());
+    }
+    let count = count as usize;
+
+    let flags = match take_flags(&mut ctx) {
+        Some(flags) => flags,
+        None => return reject_filter(&ctx).await,
+    };
+    let channel_id = ctx.message.channel.get_id();
+    let messages = fetch_messages_before(&ctx, channel_id, ctx.message.id, count).await?;
+    purge_messages(&ctx, channel_id, messages, flags).await
+}
+
+pub async fn purge_until(mut ctx: CommandContext) -> CommandResult {
+    let target = ctx.parser.get_id()?;
+    let target = MessageId(target);
+    let flags = match take_flags(&mut ctx) {
+        Some(flags) => flags,
+        None => return reject_filter(&ctx).await,
+    };
+    let channel_id = ctx.message.channel.get_id();
+
+    // make sure the message actually lives in this channel, anything else would delete an unexpected range
+    match ctx.bot_context.http.message(channel_id, target).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(ParseError::UnknownMessage.into()),
+        Err(error) => {
+            return if error.to_string().contains("status: 404") {
+                Err(ParseError::UnknownMessage.into())
+            } else {
+                Err(CommandError::OtherFailure(OtherFailure::TwilightHttp(error)))
+            };
+        }
+    }
+
+    let messages = fetch_messages_after(&ctx, channel_id, target, ctx.message.id).await?;
+    purge_messages(&ctx, channel_id, messages, flags).await
+}
+
+#[derive(Default)]
+struct PurgeFlags {
+    dry_run: bool,
+    archive: bool,
+    /// Messages have to match all of these to be removed.
+    filters: Vec<Regex>,
+}
+
+/// Takes the ``--dry-run`` and ``--archive`` flags and ``regex:`` filters off the arguments, in whatever
+/// order they were given. ``None`` when one of the filters can't be used.
+fn take_flags(ctx: &mut CommandContext) -> Option<PurgeFlags> {
+    let mut flags = PurgeFlags::default();
+    while let Some(arg) = ctx.parser.peek() {
+        match arg.as_str() {
+            DRY_RUN_FLAG => flags.dry_run = true,
+            ARCHIVE_FLAG => flags.archive = true,
+            arg => match arg.strip_prefix(REGEX_FILTER_PREFIX) {
+                Some(pattern) => flags.filters.push(build_filter(pattern)?),
+                None => break,
+            },
+        }
+        let _ = ctx.parser.get_next();
+    }
+    Some(flags)
+}
+
+/// Compiles a filter regex, refusing anything that's too long or would compile to something huge.
+fn build_filter(pattern: &str) -> Option<Regex> {
+    if pattern.is_empty() || pattern.chars().count() > MAX_FILTER_LENGTH {
+        return None;
+    }
+
+    RegexBuilder::new(pattern)
+        .size_limit(FILTER_SIZE_LIMIT)
+        .dfa_size_limit(FILTER_SIZE_LIMIT)
+        .build()
+        .ok()
+}
+
+async fn reject_filter(ctx: &CommandContext) -> CommandResult {
+    let args = FluArgs::with_capacity(2)
+        .add("gearno", Emoji::No.for_chat())
+        .add("max", MAX_FILTER_LENGTH)
+        .generate();
+    ctx.reply(GearBotString::PurgeFilterInvalid, args).await?;
+    Ok(())
+}
+
+/// Keeps the messages matching all filters, ``None`` if that took longer than ``FILTER_MATCH_TIMEOUT``.
+fn apply_filters(messages: Vec<Message>, filters: &[Regex]) -> Option<Vec<Message>> {
+    if filters.is_empty() {
+        return Some(messages);
+    }
+
+    // matching itself runs in linear time, but a complex regex over a lot of long messages still adds up
+    let start = Instant::now();
+    let mut matching = vec![];
+    for message in messages {
+        if start.elapsed() > FILTER_MATCH_TIMEOUT {
+            return None;
+        }
+        if filters.iter().all(|filter| filter.is_match(&message.content)) {
+            matching.push(message);
+        }
+    }
+
+    Some(matching)
+}
+
+async fn purge_messages(
+    ctx: &CommandContext,
+    channel_id: ChannelId,
+    messages: Vec<Message>,
+    flags: PurgeFlags,
+) -> CommandResult {
+    let messages = match apply_filters(messages, &flags.filters) {
+        Some(messages) => messages,
+        None => {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            ctx.reply(GearBotString::PurgeFilterTimeout, args).await?;
+            return Ok(());
+        }
+    };
+
+    if flags.dry_run {
+        return preview(ctx, channel_id, &messages).await;
+    }
+
+    // only what actually gets deleted is archived, and it has to be stored before anything is gone
+    let deletable: Vec<&Message> = messages.iter().filter(|m| is_bulk_deletable(m.id)).collect();
+    if flags.archive {
+        archive(ctx, ctx.get_guild()?.id, &deletable).await?;
+    }
+
+    let ids = messages.iter().map(|m| m.id).collect();
+    let (deleted, skipped) = delete_messages(ctx, channel_id, ids).await?;
+    report(ctx, deleted, skipped).await?;
+
+    if flags.archive && deleted > 0 {
+        send_transcript(ctx, channel_id, &deletable).await?;
+    }
+
+    Ok(())
+}
+
+/// Stores the messages we don't have a copy of yet, so the purge can be looked back on later.
+async fn archive(ctx: &CommandContext, guild_id: GuildId, messages: &[&Message]) -> CommandResult {
+    let ids: Vec<MessageId> = messages.iter().map(|m| m.id).collect();
+    let stored = ctx.bot_context.datastore.get_stored_message_ids(&ids).await?;
+
+    for message in messages.iter().filter(|m| !stored.contains(&m.id)) {
+        // messages fetched over http don't know which guild they belong to
+        let mut message = (*message).clone();
+        message.guild_id = Some(guild_id);
+        ctx.bot_context.insert_message(&message, guild_id).await?;
+    }
+
+    Ok(())
+}
+
+/// DMs the moderator a transcript of what was removed, falling back to pointing at the stored copy.
+async fn send_transcript(ctx: &CommandContext, channel_id: ChannelId, messages: &[&Message]) -> CommandResult {
+    let mut messages = messages.to_vec();
+    messages.sort_by_key(|m| m.id);
+
+    let transcript: String = messages
+        .iter()
+        .map(|m| {
+            format!(
+                "[{}] {}#{} ({}): {}\n",
+                utils::snowflake_timestamp(m.id.0).format("%F %T"),
+                m.author.name,
+                m.author.discriminator,
+                m.author.id,
+                m.content
+            )
+        })
+        .collect();
+
+    let filename = format!("purge-{}.txt", channel_id);
+    let sent = match ctx.get_dm_for_author().await {
+        Ok(dm) => {
+            let args = FluArgs::with_capacity(2)
+                .add("count", messages.len())
+                .add("channel_id", channel_id.to_string())
+                .generate();
+            ctx.bot_context
+                .http
+                .create_message(dm.get_id())
+                .content(ctx.translate_with_args(GearBotString::PurgeArchiveTranscript, &args))
+                .unwrap()
+                .attachment(filename, transcript.into_bytes())
+                .await
+                .is_ok()
+        }
+        Err(_) => false,
+    };
+
+    if sent {
+        let args = FluArgs::with_capacity(1)
+            .add("gearyes", Emoji::Yes.for_chat())
+            .generate();
+        ctx.reply_success_and_clean_up(GearBotString::PurgeArchiveSent, args)
+            .await?;
+    } else {
+        let args = FluArgs::with_capacity(3)
+            .add("gearinfo", Emoji::Info.for_chat())
+            .add("prefix", ctx.get_config()?.prefix.clone())
+            .add("channel_id", channel_id.to_string())
+            .generate();
+        ctx.reply_and_clean_up(GearBotString::PurgeArchiveDmFailed, args)
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn is_bulk_deletable(id: MessageId) -> bool {
+    utils::snowflake_timestamp(id.0) > Utc::now() - Duration::days(BULK_DELETE_MAX_AGE_DAYS)
+}
+
+/// Fetches up to ``count`` messages that were sent before the given message, newest first.
+async fn fetch_messages_before(
+    ctx: &CommandContext,
+    channel_id: ChannelId,
+    before: MessageId,
+    count: usize,
+) -> Result<Vec<Message>, CommandError> {
+    let mut messages = vec![];
+    let mut cursor = before;
+
+    while messages.len() < count {
+        let limit = (count - messages.len()).min(100);
+        let batch = ctx
+            .bot_context
+            .http
+            .channel_messages(channel_id)
+            .before(cursor)
+            .limit(limit as u64)
+            .unwrap()
+            .await?;
+
+        let done = batch.len() < limit;
+        if let Some(oldest) = batch.iter().map(|m| m.id).min() {
+            cursor = oldest;
+        }
+        messages.extend(batch);
+
+        if done {
+            break;
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Fetches all messages between ``after`` and ``until`` (inclusive), capped at ``MAX_PURGE_SIZE``.
+async fn fetch_messages_after(
+    ctx: &CommandContext,
+    channel_id: ChannelId,
+    after: MessageId,
+    until: MessageId,
+) -> Result<Vec<Message>, CommandError> {
+    let mut messages = vec![];
+    let mut cursor = after;
+
+    while messages.len() < MAX_PURGE_SIZE {
+        let batch = ctx
+            .bot_context
+            .http
+            .channel_messages(channel_id)
+            .after(cursor)
+            .limit(100)
+            .unwrap()
+            .await?;
+
+        let done = batch.len() < 100;
+        if let Some(newest) = batch.iter().map(|m| m.id).max() {
+            cursor = newest;
+        }
+        messages.extend(batch.into_iter().filter(|m| m.id <= until));
+
+        if done || cursor >= until {
+            break;
+        }
+    }
+
+    messages.truncate(MAX_PURGE_SIZE);
+    Ok(messages)
+}
+
+/// Removes the messages, returning how many were deleted and how many were too old to bulk delete.
+async fn delete_messages(
+    ctx: &CommandContext,
+    channel_id: ChannelId,
+    mut ids: Vec<MessageId>,
+) -> Result<(usize, usize), CommandError> {
+    let total = ids.len();
+    ids.retain(|id| is_bulk_deletable(*id));
+    let skipped = total - ids.len();
+
+    let http = &ctx.bot_context.http;
+    for chunk in ids.chunks(100) {
+        if chunk.len() == 1 {
+            http.delete_message(channel_id, chunk[0]).await?;
+        } else {
+            http.delete_messages(channel_id, chunk.to_vec()).await?;
+        }
+    }
+
+    Ok((ids.len(), skipped))
+}
+
+/// Tells what a purge would remove, in DMs when possible so the preview doesn't clutter the channel.
+async fn preview(ctx: &CommandContext, channel_id: ChannelId, messages: &[Message]) -> CommandResult {
+    let deletable: Vec<&Message> = messages.iter().filter(|m| is_bulk_deletable(m.id)).collect();
+    let skipped = messages.len() - deletable.len();
+
+    let sample: String = deletable
+        .iter()
+        .take(DRY_RUN_SAMPLE_SIZE)
+        .map(|m| {
+            let snippet = text::truncate_with_ellipsis(&m.content, DRY_RUN_SNIPPET_LENGTH);
+            format!(
+                "\n**{}#{}**: {}",
+                m.author.name,
+                m.author.discriminator,
+                utils::clean(&snippet, true, true, true, true)
+            )
+        })
+        .collect();
+
+    let args = FluArgs::with_capacity(5)
+        .add("gearinfo", Emoji::Info.for_chat())
+        .add("count", deletable.len())
+        .add("skipped", skipped)
+        .add("channel_id", channel_id.to_string())
+        .add("sample", sample)
+        .generate();
+    let translated = ctx.translate_with_args(GearBotString::PurgeDryRun, &args);
+
+    let sent = match ctx.get_dm_for_author().await {
+        Ok(dm) => ctx
+            .bot_context
+            .http
+            .create_message(dm.get_id())
+            .content(translated.clone())
+            .unwrap()
+            .await
+            .is_ok(),
+        Err(_) => false,
+    };
+
+    if sent {
+        let args = FluArgs::with_capacity(1)
+            .add("gearyes", Emoji::Yes.for_chat())
+            .generate();
+        ctx.reply_success(GearBotString::PurgeDryRunSent, args).await?;
+    } else {
+        // DMs are closed, the channel will have to do
+        ctx.reply_raw(translated).await?;
+    }
+
+    Ok(())
+}
+
+async fn report(ctx: &CommandContext, deleted: usize, skipped: usize) -> CommandResult {
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("count", deleted)
+        .add("skipped", skipped)
+        .generate();
+
+    let key = if skipped == 0 {
+        GearBotString::PurgeDone
+    } else {
+        GearBotString::PurgeDoneSkipped
+    };
+
+    ctx.reply_and_clean_up(key, args).await?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+pub async fn raidmode(mut ctx: CommandContext) -> CommandResult {
+    let config = ctx.get_config()?;
+    let guild_id = ctx.get_guild()?.id;
+    let currently_active = ctx.bot_context.is_raid_mode_active(guild_id).await;
+
+    // no argument simply flips the current state
+    let enable = if ctx.parser.has_next() {
+        match ctx.parser.get_next()?.to_lowercase().as_str() {
+            "on" | "enable" => true,
+            "off" | "disable" => false,
+            _ => return Err(ParseError::WrongArgumentType(String::from("on or off")).into()),
+        }
+    } else {
+        !currently_active
+    };
+
+    let author = ctx.message.author.id;
+    let changed = if enable {
+        ctx.bot_context
+            .enable_raid_mode(guild_id, &config.anti_raid, author)
+            .await?
+    } else {
+        ctx.bot_context
+            .disable_raid_mode(guild_id, &config.anti_raid, author)
+            .await?
+    };
+
+    let key = match (enable, changed) {
+        (true, true) => GearBotString::RaidModeOn,
+        (false, true) => GearBotString::RaidModeOff,
+        (true, false) => GearBotString::RaidModeAlreadyOn,
+        (false, false) => GearBotString::RaidModeAlreadyOff,
+    };
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("gearno", Emoji::No.for_chat())
+        .generate();
+    ctx.reply(key, args).await?;
+
+    Ok(())
+}
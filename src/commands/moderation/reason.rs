@@ -0,0 +1,77 @@
+use twilight_model::id::UserId;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// Reasons are a summary of what happened, longer writeups belong in a note.
+const MAX_REASON_LENGTH: usize = 1000;
+
+pub async fn reason(mut ctx: CommandContext) -> CommandResult {
+    let case_id = ctx.parser.get_entry_number()?;
+    let reason = ctx.parser.get_remaining();
+
+    if reason.is_empty() {
+        return Err(ParseError::MissingArgument.into());
+    }
+
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(
+            ParseError::WrongArgumentType(format!("reason of at most {} characters", MAX_REASON_LENGTH)).into(),
+        );
+    }
+
+    let guild = ctx.get_guild()?;
+    let datastore = &ctx.bot_context.datastore;
+
+    let entry = match datastore.get_history_entry(guild.id, case_id).await? {
+        Some(entry) => entry,
+        None => {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("case", case_id)
+                .generate();
+            ctx.reply(GearBotString::ReasonCaseNotFound, args).await?;
+            return Ok(());
+        }
+    };
+
+    let moderator = UserId(entry.mod_id as u64);
+    if moderator != ctx.message.author.id {
+        let config = ctx.get_config()?;
+        let author_level = ctx
+            .bot_context
+            .get_permission_level(guild, &ctx.message.get_author_as_member()?, &config);
+        // moderators that are no longer around don't outrank anyone
+        let moderator_level = ctx
+            .bot_context
+            .cache
+            .get_member(&guild.id, &moderator)
+            .map_or(0, |member| {
+                ctx.bot_context.get_permission_level(guild, &member, &config)
+            });
+
+        if author_level <= moderator_level {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("case", case_id)
+                .generate();
+            ctx.reply(GearBotString::ReasonNotAllowed, args).await?;
+            return Ok(());
+        }
+    }
+
+    datastore
+        .update_history_reason(guild.id, case_id, &reason, ctx.message.author.id)
+        .await?;
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("case", case_id)
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::ReasonUpdated, args)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use chrono::{Duration, Utc};
+use twilight_model::id::MessageId;
+
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// How many channels the activity overview shows.
+const ACTIVITY_LIST_SIZE: i64 = 10;
+
+/// Seconds the activity counts are cached for, so repeated use doesn't keep scanning the message table.
+const ACTIVITY_CACHE_TTL: u32 = 5 * 60;
+
+pub async fn attachment_stats(ctx: CommandContext) -> CommandResult {
+    let stats = ctx
+        .bot_context
+        .datastore
+        .get_attachment_stats(ctx.get_guild()?.id)
+        .await?;
+
+    let args = FluArgs::with_capacity(4)
+        .add("total", stats.total)
+        .add("images", stats.images)
+        .add("other", stats.total - stats.images)
+        .add(
+            "stored_mb",
+            format!("{:.1}", stats.stored_bytes as f64 / (1024.0 * 1024.0)),
+        )
+        .generate();
+    ctx.reply(GearBotString::AttachmentStats, args).await?;
+
+    Ok(())
+}
+
+pub async fn channel_activity(mut ctx: CommandContext) -> CommandResult {
+    let (days, window) = if ctx.parser.has_next() {
+        match ctx.parser.get_next()?.to_lowercase().as_str() {
+            "day" | "d" => (1, GearBotString::ActivityDay),
+            "week" | "w" => (7, GearBotString::ActivityWeek),
+            _ => return Err(ParseError::WrongArgumentType(String::from("day or week")).into()),
+        }
+    } else {
+        (1, GearBotString::ActivityDay)
+    };
+    let window = ctx.translate(window);
+
+    let guild_id = ctx.get_guild()?.id;
+    let datastore = &ctx.bot_context.datastore;
+    let key = format!("channel_activity:{}:{}", guild_id, days);
+    let activity: Vec<(i64, i64)> = match datastore.cache_pool.get(&key).await? {
+        Some(activity) => activity,
+        None => {
+            let activity = datastore
+                .get_channel_activity(guild_id, Utc::now() - Duration::days(days), ACTIVITY_LIST_SIZE)
+                .await?;
+            datastore
+                .cache_pool
+                .set(&key, &activity, Some(ACTIVITY_CACHE_TTL))
+                .await?;
+            activity
+        }
+    };
+
+    if activity.is_empty() {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("window", window)
+            .generate();
+        ctx.reply(GearBotString::ActivityEmpty, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(1).add("window", window).generate();
+    let mut output = ctx.translate_with_args(GearBotString::ActivityHeader, &args);
+    output += "\n";
+    for (channel_id, messages) in activity {
+        output += &format!("\n<#{}>: {}", channel_id, messages);
+    }
+    ctx.reply_raw(output).await?;
+
+    Ok(())
+}
+
+pub async fn message_reactions(mut ctx: CommandContext) -> CommandResult {
+    let message_id = ctx.parser.get_id()?;
+
+    let summary = ctx
+        .bot_context
+        .datastore
+        .get_reaction_summary(MessageId(message_id))
+        .await?;
+
+    if summary.is_empty() {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("message_id", message_id.to_string())
+            .generate();
+        ctx.reply(GearBotString::ReactionsEmpty, args).await?;
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(1)
+        .add("message_id", message_id.to_string())
+        .generate();
+    let mut output = ctx.translate_with_args(GearBotString::ReactionsHeader, &args);
+    output += "\n";
+    for (emoji, count) in summary {
+        // custom emoji are stored by id, discord renders them from that alone
+        let emoji = match emoji.parse::<u64>() {
+            Ok(id) => format!("<:emoji:{}>", id),
+            Err(_) => emoji,
+        };
+        output += &format!("\n{} {}", emoji, count);
+    }
+    ctx.reply_raw(output).await?;
+
+    Ok(())
+}
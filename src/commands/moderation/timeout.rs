@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Utc};
+use hyper::StatusCode;
+use twilight_http::Error;
+use twilight_model::id::UserId;
+
+use crate::core::logpump::LogType;
+use crate::core::CommandContext;
+use crate::error::{CommandResult, ModerationError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+/// Discord refuses timeouts that last any longer than this.
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
+pub async fn timeout(mut ctx: CommandContext) -> CommandResult {
+    let member = ctx.parser.get_member().await?;
+    let duration = ctx.parser.get_duration()?;
+    let reason = ctx.parser.get_remaining();
+    if !ctx.require_reason(&reason).await? {
+        return Ok(());
+    }
+    ctx.check_hierarchy(&member)?;
+
+    if duration > Duration::days(MAX_TIMEOUT_DAYS) {
+        let args = FluArgs::with_capacity(2)
+            .add("gearno", Emoji::No.for_chat())
+            .add("max", MAX_TIMEOUT_DAYS)
+            .generate();
+        ctx.reply(GearBotString::TimeoutTooLong, args).await?;
+        return Ok(());
+    }
+
+    let until = Utc::now() + duration;
+    set_timeout(&ctx, member.user_id, Some(until), &reason).await?;
+
+    let guild_id = ctx.get_guild()?.id;
+    let case = ctx
+        .bot_context
+        .datastore
+        .insert_history_entry(guild_id, member.user_id, ctx.message.author.id, "mute", Some(until))
+        .await?;
+
+    let until = ctx.format_time(until);
+    ctx.log(
+        LogType::MemberTimedOut {
+            target: member.user_id.0,
+            until: until.clone(),
+            reason: reason_or_default(&ctx, reason),
+        },
+        Some(ctx.message.channel.get_id()),
+        ctx.message.author.id,
+    );
+
+    let args = FluArgs::with_capacity(4)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("user_id", member.user_id.to_string())
+        .add("until", until)
+        .add("case", case)
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::TimeoutApplied, args)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn untimeout(mut ctx: CommandContext) -> CommandResult {
+    let member = ctx.parser.get_member().await?;
+    let reason = ctx.parser.get_remaining();
+    if !ctx.require_reason(&reason).await? {
+        return Ok(());
+    }
+    ctx.check_hierarchy(&member)?;
+
+    set_timeout(&ctx, member.user_id, None, &reason).await?;
+
+    let guild_id = ctx.get_guild()?.id;
+    ctx.bot_context
+        .datastore
+        .end_active_mutes(guild_id, member.user_id)
+        .await?;
+
+    ctx.log(
+        LogType::MemberTimeoutRemoved {
+            target: member.user_id.0,
+            reason: reason_or_default(&ctx, reason),
+        },
+        Some(ctx.message.channel.get_id()),
+        ctx.message.author.id,
+    );
+
+    let args = FluArgs::with_capacity(2)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("user_id", member.user_id.to_string())
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::TimeoutRemoved, args)
+        .await?;
+
+    Ok(())
+}
+
+/// Sets or clears the timeout of a member.
+async fn set_timeout(
+    ctx: &CommandContext,
+    user_id: UserId,
+    until: Option<DateTime<Utc>>,
+    reason: &str,
+) -> CommandResult {
+    let guild_id = ctx.get_guild()?.id;
+
+    let mut audit_reason = format!("Moderator: {}", ctx.message.author.full_name_with_id());
+    if !reason.is_empty() {
+        audit_reason += &format!(" Reason: {}", reason);
+    }
+
+    match ctx
+        .bot_context
+        .set_member_timeout(guild_id, user_id, until, &audit_reason)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(Error::Response { status, .. }) if status == StatusCode::FORBIDDEN => {
+            Err(ModerationError::Refused(user_id).into())
+        }
+        Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => {
+            Err(ModerationError::TargetNotFound(user_id).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn reason_or_default(ctx: &CommandContext, reason: String) -> String {
+    if reason.is_empty() {
+        ctx.translate(GearBotString::NoReasonGiven)
+    } else {
+        reason
+    }
+}
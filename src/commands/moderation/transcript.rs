@@ -0,0 +1,335 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use chrono::{Duration, Utc};
+use twilight_model::channel::Message;
+use twilight_model::guild::Permissions;
+
+use crate::core::{CommandContext, MAX_MESSAGE_LENGTH};
+use crate::database::structures::UserMessage;
+use crate::error::{CommandResult, ParseError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, text, Emoji};
+
+/// Messages are read and decrypted this many at a time, so large windows never sit in memory all at once.
+const PAGE_SIZE: usize = 500;
+
+/// How often the progress message gets updated while a long transcript is being written.
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Room kept free below the upload limit for the closing note.
+const FOOTER_RESERVE: usize = 4 * 1024;
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;background:#36393f;color:#dcddde;margin:2em}\
+.message{margin:.4em 0}.time{color:#72767d;font-size:.8em}.author{font-weight:bold;color:#fff}\
+.reply{color:#72767d;font-size:.8em}.content{white-space:pre-wrap;margin-left:1em}.note{color:#faa61a}";
+
+#[derive(Clone, Copy, PartialEq)]
+enum TranscriptFormat {
+    Text,
+    Html,
+}
+
+impl TranscriptFormat {
+    fn parse(input: &str) -> Option<Self> {
+        if input.eq_ignore_ascii_case("text") {
+            Some(TranscriptFormat::Text)
+        } else if input.eq_ignore_ascii_case("html") {
+            Some(TranscriptFormat::Html)
+        } else {
+            None
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Text => "txt",
+            TranscriptFormat::Html => "html",
+        }
+    }
+}
+
+pub async fn transcript(mut ctx: CommandContext) -> CommandResult {
+    if !ctx.check_content_storage().await? {
+        return Ok(());
+    }
+
+    let channel = ctx.parser.get_channel()?;
+    let since = ctx.parser.get_duration()?;
+    let until_given = ctx
+        .parser
+        .peek()
+        .map_or(false, |input| TranscriptFormat::parse(input).is_none());
+    let until = if until_given {
+        ctx.parser.get_duration()?
+    } else {
+        Duration::zero()
+    };
+    let format = if ctx.parser.has_next() {
+        TranscriptFormat::parse(ctx.parser.get_next()?)
+            .ok_or_else(|| ParseError::WrongArgumentType(String::from("text or html")))?
+    } else {
+        TranscriptFormat::Text
+    };
+
+    if until >= since {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::TranscriptInvalidWindow, args).await?;
+        return Ok(());
+    }
+
+    let user_has_access = ctx.has_channel_permissions(
+        ctx.message.author.id,
+        channel.get_id(),
+        Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+    );
+    if !user_has_access {
+        return Err(ParseError::NoChannelAccessUser(channel.get_name().to_string()).into());
+    }
+
+    let now = Utc::now();
+    let guild_id = ctx.get_guild()?.id;
+    let channel_id = channel.get_id();
+    // paging skips past the last id it saw, step back one so messages from the very first moment are included
+    let mut after = utils::timestamp_snowflake(now - since).saturating_sub(1);
+    let before = utils::timestamp_snowflake(now - until);
+    let max_messages = ctx.bot_context.max_transcript_messages;
+    let datastore = &ctx.bot_context.datastore;
+
+    let mut page = datastore
+        .get_channel_messages_after(channel_id, guild_id, after, before, PAGE_SIZE as i64)
+        .await?;
+    if page.is_empty() {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        ctx.reply(GearBotString::TranscriptEmpty, args).await?;
+        return Ok(());
+    }
+
+    // a short window that was fetched in one go can just be shown in chat
+    if format == TranscriptFormat::Text && page.len() < PAGE_SIZE && page.len() <= max_messages {
+        let lines: String = page
+            .iter()
+            .map(|message| TranscriptLine::new(&ctx, message).text())
+            .collect();
+        // a code block keeps mentions in the transcript from pinging anyone
+        let fenced = format!("```\n{}```", lines);
+        if fenced.chars().count() <= MAX_MESSAGE_LENGTH {
+            ctx.reply_raw(fenced).await?;
+            return Ok(());
+        }
+    }
+
+    let title = format!("#{}", channel.get_name());
+    let mut file = TranscriptFile::create(format, &format!("transcript-{}", ctx.message.id), &title)?;
+    let size_limit = ctx.get_upload_limit().saturating_sub(FOOTER_RESERVE);
+    let mut written = 0;
+    let mut note = None;
+    let mut progress: Option<Message> = None;
+    let mut last_update = Instant::now();
+
+    loop {
+        for message in &page {
+            if written == max_messages {
+                let args = FluArgs::with_capacity(1).add("max", max_messages).generate();
+                note = Some(ctx.translate_with_args(GearBotString::TranscriptTruncated, &args));
+                break;
+            }
+
+            let entry = file.entry(&ctx, message);
+            if file.size + entry.len() > size_limit {
+                let args = FluArgs::with_capacity(1).add("count", written).generate();
+                note = Some(ctx.translate_with_args(GearBotString::TranscriptSizeTruncated, &args));
+                break;
+            }
+            file.write(&entry)?;
+            written += 1;
+        }
+
+        let last = match page.last() {
+            Some(message) => message.id.0,
+            None => break,
+        };
+        if note.is_some() || page.len() < PAGE_SIZE {
+            break;
+        }
+
+        if last_update.elapsed() >= PROGRESS_INTERVAL {
+            last_update = Instant::now();
+            let args = FluArgs::with_capacity(3)
+                .add("gearinfo", Emoji::Info.for_chat())
+                .add("channel_id", channel_id.to_string())
+                .add("count", written)
+                .generate();
+            match &progress {
+                Some(progress) => {
+                    let content = ctx.translate_with_args(GearBotString::TranscriptProgress, &args);
+                    ctx.update_message(content, progress.channel_id, progress.id).await?;
+                }
+                None => progress = Some(ctx.reply(GearBotString::TranscriptProgress, args).await?),
+            }
+        }
+
+        after = last;
+        page = datastore
+            .get_channel_messages_after(channel_id, guild_id, after, before, PAGE_SIZE as i64)
+            .await?;
+    }
+
+    let data = file.finish(note)?;
+    let args = FluArgs::with_capacity(3)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("channel_id", channel_id.to_string())
+        .add("count", written)
+        .generate();
+    let filename = format!("transcript.{}", format.extension());
+    ctx.reply_with_file(GearBotString::TranscriptDone, args, &filename, data)
+        .await?;
+
+    if let Some(progress) = progress {
+        // it's only there to show the export is still going
+        let _ = ctx
+            .bot_context
+            .http
+            .delete_message(progress.channel_id, progress.id)
+            .await;
+    }
+
+    Ok(())
+}
+
+/// Everything about a single message that ends up in a transcript.
+struct TranscriptLine {
+    id: u64,
+    time: String,
+    author: String,
+    reply: Option<u64>,
+    content: String,
+}
+
+impl TranscriptLine {
+    fn new(ctx: &CommandContext, message: &UserMessage) -> Self {
+        let mut author = match ctx.bot_context.cache.get_user(message.author) {
+            Some(user) => user.full_name_with_id(),
+            None => message.author.to_string(),
+        };
+        // our own responses shouldn't be mistaken for something a member said
+        if message.author == ctx.bot_context.bot_user.id {
+            author += " [bot response]";
+        }
+
+        TranscriptLine {
+            id: message.id.0,
+            time: utils::snowflake_timestamp(message.id.0).format("%F %T").to_string(),
+            author,
+            reply: message.referenced_message.map(|referenced| referenced.0),
+            content: match &message.content {
+                Some(content) => content.clone(),
+                None => ctx.translate(GearBotString::ContentNotStored),
+            },
+        }
+    }
+
+    fn text(&self) -> String {
+        let reply = match self.reply {
+            Some(referenced) => format!(" (in reply to {})", referenced),
+            None => String::new(),
+        };
+        format!(
+            "[{}] {}{}: {}\n",
+            self.time,
+            self.author,
+            reply,
+            self.content.replace("`", "ˋ")
+        )
+    }
+
+    fn html(&self) -> String {
+        let reply = match self.reply {
+            Some(referenced) => format!(" <span class=\"reply\">in reply to {}</span>", referenced),
+            None => String::new(),
+        };
+        format!(
+            "<div class=\"message\" id=\"{}\"><span class=\"time\">{}</span> <span class=\"author\">{}</span>{}\
+            <div class=\"content\">{}</div></div>\n",
+            self.id,
+            self.time,
+            text::escape_html(&self.author),
+            reply,
+            text::escape_html(&self.content)
+        )
+    }
+}
+
+/// The transcript is written out to a temporary file as it goes, the file is removed again when dropped.
+struct TranscriptFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    format: TranscriptFormat,
+    /// Bytes written so far.
+    size: usize,
+}
+
+impl TranscriptFile {
+    fn create(format: TranscriptFormat, name: &str, title: &str) -> io::Result<Self> {
+        let path = env::temp_dir().join(format!("{}.{}", name, format.extension()));
+        let writer = BufWriter::new(File::create(&path)?);
+        let mut file = TranscriptFile {
+            path,
+            writer,
+            format,
+            size: 0,
+        };
+
+        if format == TranscriptFormat::Html {
+            let title = text::escape_html(title);
+            file.write(&format!(
+                "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head>\n\
+                <body><h1>{}</h1>\n",
+                title, HTML_STYLE, title
+            ))?;
+        }
+
+        Ok(file)
+    }
+
+    fn entry(&self, ctx: &CommandContext, message: &UserMessage) -> String {
+        let line = TranscriptLine::new(ctx, message);
+        match self.format {
+            TranscriptFormat::Text => line.text(),
+            TranscriptFormat::Html => line.html(),
+        }
+    }
+
+    fn write(&mut self, entry: &str) -> io::Result<()> {
+        self.writer.write_all(entry.as_bytes())?;
+        self.size += entry.len();
+        Ok(())
+    }
+
+    /// Closes off the transcript with the note (if any) and reads it back in for uploading.
+    fn finish(mut self, note: Option<String>) -> io::Result<Vec<u8>> {
+        match (self.format, note) {
+            (TranscriptFormat::Text, Some(note)) => self.write(&format!("\n{}\n", note))?,
+            (TranscriptFormat::Text, None) => {}
+            (TranscriptFormat::Html, note) => {
+                if let Some(note) = note {
+                    self.write(&format!("<p class=\"note\">{}</p>\n", text::escape_html(&note)))?;
+                }
+                self.write("</body></html>\n")?;
+            }
+        }
+
+        self.writer.flush()?;
+        fs::read(&self.path)
+    }
+}
+
+impl Drop for TranscriptFile {
+    fn drop(&mut self) {
+        // nothing to be done if it's already gone
+        let _ = fs::remove_file(&self.path);
+    }
+}
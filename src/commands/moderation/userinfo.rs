@@ -3,13 +3,12 @@ use twilight_embed_builder::{EmbedAuthorBuilder, EmbedBuilder, ImageSource};
 use twilight_model::guild::Permissions;
 use twilight_model::user::UserFlags;
 
+use crate::commands::meta::nodes::GearBotPermissions;
 use crate::core::CommandContext;
 use crate::error::CommandResult;
 use crate::translation::{FluArgs, GearBotString};
 use crate::utils::{self, Emoji};
 
-const USER_INFO_COLOR: u32 = 0x00_cea2;
-
 pub async fn userinfo(mut ctx: CommandContext) -> CommandResult {
     let user = ctx.parser.get_user_or(ctx.message.author.clone()).await?;
 
@@ -100,28 +99,24 @@ pub async fn userinfo(mut ctx: CommandContext) -> CommandResult {
         "\n**User id**: {}\n**Account created on**: {}\n**Account Age**: {}\n\n",
         user.id,
         created_at.format("%A %d %B %Y (%T)"),
-        utils::age(created_at, Utc::now(), 2)
+        ctx.format_duration(Utc::now() - created_at, 2)
     );
 
-    let cached_member = ctx.get_member(&user.id);
+    let cached_member = ctx.get_member(&user.id).await?;
 
     match &cached_member {
         Some(member) => {
-            let color = match member.roles.first() {
-                Some(role) => ctx.get_role(role).unwrap().color,
-                None => USER_INFO_COLOR,
+            let color = match member.roles.first().and_then(|role| ctx.get_role(role)) {
+                Some(role) => role.color,
+                None => ctx.embed_color(),
             };
             builder = builder.color(color)?;
 
-            let (joined, ago) = match &member.joined_at {
-                Some(joined) => {
-                    let joined =
-                        DateTime::from_utc(DateTime::parse_from_str(joined, "%FT%T%.f%z").unwrap().naive_utc(), Utc);
-                    (
-                        joined.format("%A %d %B %Y (%T)").to_string(),
-                        utils::age(joined, Utc::now(), 2),
-                    )
-                }
+            let (joined, ago) = match member.join_time() {
+                Some(joined) => (
+                    joined.format("%A %d %B %Y (%T)").to_string(),
+                    ctx.format_duration(Utc::now() - joined, 2),
+                ),
                 None => ("Unknown".to_string(), "Unknown".to_string()),
             };
 
@@ -152,9 +147,25 @@ pub async fn userinfo(mut ctx: CommandContext) -> CommandResult {
                     DateTime::from_utc(DateTime::parse_from_str(s, "%FT%T%.f%z").unwrap().naive_utc(), Utc);
                 content += &format!("**Boosting this server since**: {}", since);
             }
+
+            if ctx.bot_context.cache.tracks_presences() {
+                // offline members aren't tracked, so no presence means offline
+                let presence = ctx.get_guild()?.get_presence(&user.id);
+                let status = presence.as_ref().map_or("offline", |presence| presence.status_name());
+                let mut args = FluArgs::with_capacity(2).add("status", status);
+                let key = match presence.and_then(|presence| presence.activity) {
+                    Some(activity) => {
+                        args = args.add("activity", utils::clean(&activity, true, true, false, false));
+                        GearBotString::UserinfoStatusActivity
+                    }
+                    None => GearBotString::UserinfoStatus,
+                };
+                content += "\n";
+                content += &ctx.translate_with_args(key, &args.generate());
+            }
         }
         None => {
-            builder = builder.color(USER_INFO_COLOR)?;
+            builder = builder.color(ctx.embed_color())?;
         }
     }
 
@@ -169,6 +180,23 @@ pub async fn userinfo(mut ctx: CommandContext) -> CommandResult {
         )
     }
 
+    // notes are staff only, don't leak their existence to people who can't read them
+    if ctx.permissions.contains(GearBotPermissions::NOTES_COMMAND) {
+        let notes = ctx
+            .bot_context
+            .datastore
+            .count_notes(ctx.get_guild()?.id, user.id)
+            .await?;
+        if notes > 0 {
+            let args = FluArgs::with_capacity(2)
+                .add("count", notes)
+                .add("user_id", user.id.to_string())
+                .generate();
+            content += "\n";
+            content += &ctx.translate_with_args(GearBotString::UserinfoNotes, &args);
+        }
+    }
+
     builder = builder.description(content)?;
 
     let args = FluArgs::with_capacity(1).add("userid", user.id.to_string()).generate();
@@ -0,0 +1,65 @@
+use twilight_model::id::UserId;
+
+use crate::core::CommandContext;
+use crate::error::CommandResult;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
+
+pub async fn delwarn(mut ctx: CommandContext) -> CommandResult {
+    let case_id = ctx.parser.get_entry_number()?;
+
+    let guild_id = ctx.get_guild()?.id;
+    let datastore = &ctx.bot_context.datastore;
+
+    let entry = match datastore.get_history_entry(guild_id, case_id).await? {
+        Some(entry) if entry.kind == "warning" && !entry.removed => entry,
+        _ => {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("case", case_id)
+                .generate();
+            ctx.reply(GearBotString::WarningNotFound, args).await?;
+            return Ok(());
+        }
+    };
+
+    let user_id = UserId(entry.user_id as u64);
+    datastore
+        .remove_history_entry(guild_id, case_id, ctx.message.author.id)
+        .await?;
+    let remaining = datastore.count_active_warnings(guild_id, user_id).await?;
+
+    let args = FluArgs::with_capacity(4)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("case", case_id)
+        .add("user_id", user_id.to_string())
+        .add("count", remaining)
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::WarningRemoved, args)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn clearwarnings(mut ctx: CommandContext) -> CommandResult {
+    let user = ctx.parser.get_user().await?;
+
+    let guild_id = ctx.get_guild()?.id;
+    let datastore = &ctx.bot_context.datastore;
+
+    let cleared = datastore
+        .clear_warnings(guild_id, user.id, ctx.message.author.id)
+        .await?;
+    let remaining = datastore.count_active_warnings(guild_id, user.id).await?;
+
+    let args = FluArgs::with_capacity(4)
+        .add("gearyes", Emoji::Yes.for_chat())
+        .add("cleared", cleared)
+        .add("user_id", user.id.to_string())
+        .add("count", remaining)
+        .generate();
+    ctx.reply_success_and_clean_up(GearBotString::WarningsCleared, args)
+        .await?;
+
+    Ok(())
+}
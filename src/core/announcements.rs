@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{debug, warn};
+use twilight_model::guild::Permissions;
+use twilight_model::id::{ChannelId, GuildId};
+
+use crate::core::BotContext;
+use crate::database::structures::ScheduledAnnouncement;
+use crate::error::DatabaseError;
+use crate::gearbot_error;
+use crate::utils;
+
+/// Checks for due scheduled announcements every minute and posts them.
+pub async fn run(ctx: Arc<BotContext>) {
+    log::info!("Announcement scheduler started!");
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        if let Err(e) = post_due_announcements(&ctx).await {
+            gearbot_error!("Failed to process scheduled announcements: {}", e);
+        }
+    }
+}
+
+async fn post_due_announcements(ctx: &BotContext) -> Result<(), DatabaseError> {
+    let now = Utc::now();
+
+    for announcement in ctx.datastore.get_due_announcements(now.timestamp()).await? {
+        // other clusters take care of their own guilds, and unavailable ones will be picked up once they are back
+        if ctx.cache.get_guild(&GuildId(announcement.guild_id as u64)).is_none() {
+            continue;
+        }
+
+        // schedule from now rather than from the missed run, after downtime this only posts once instead of catching up
+        let next_run = utils::parse_cron(&announcement.cron)
+            .and_then(|schedule| schedule.after(&now).next())
+            .map(|next| next.timestamp())
+            .unwrap_or(i64::MAX);
+
+        // update first so a failure while posting never results in repeated posts
+        ctx.datastore
+            .set_announcement_next_run(announcement.id, next_run)
+            .await?;
+
+        post_announcement(ctx, &announcement).await;
+    }
+
+    Ok(())
+}
+
+async fn post_announcement(ctx: &BotContext, announcement: &ScheduledAnnouncement) {
+    let channel_id = ChannelId(announcement.channel_id as u64);
+
+    if ctx.cache.get_channel(channel_id).is_none() {
+        debug!(
+            "Skipping announcement {}, channel {} no longer exists",
+            announcement.id, channel_id
+        );
+        return;
+    }
+
+    if !ctx
+        .get_bot_permissions_for_channel(channel_id)
+        .contains(Permissions::SEND_MESSAGES)
+    {
+        debug!(
+            "Skipping announcement {}, unable to send messages in {}",
+            announcement.id, channel_id
+        );
+        return;
+    }
+
+    let request = match ctx
+        .http
+        .create_message(channel_id)
+        .content(announcement.message.clone())
+    {
+        Ok(request) => request,
+        Err(e) => {
+            warn!("Announcement {} has invalid content: {}", announcement.id, e);
+            return;
+        }
+    };
+
+    if let Err(e) = ctx.queue_send(channel_id, request).await {
+        warn!("Failed to post announcement {}: {}", announcement.id, e);
+    }
+}
@@ -1,21 +1,105 @@
 use std::collections::HashMap;
-use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
 use serde::Deserialize;
+use twilight_model::gateway::Intents;
 use twilight_model::id::EmojiId;
+use unic_langid::LanguageIdentifier;
 
+use crate::database::crypto::{self, EncryptionAlgorithm, EncryptionKey};
 use crate::error::StartupError;
+use crate::gearbot_warn;
+use crate::translation::DEFAULT_LANG;
 use crate::utils::{emoji, matchers, EmojiOverride};
 
+/// Where the encrypted secrets are looked for when ``SECRETS_FILE`` isn't set.
+const DEFAULT_SECRETS_FILE: &str = "secrets.toml.enc";
+
+/// The intents used when the config doesn't specify any.
+const DEFAULT_INTENTS: [&str; 10] = [
+    "GUILDS",
+    "GUILD_MEMBERS",
+    "GUILD_BANS",
+    "GUILD_EMOJIS",
+    "GUILD_INVITES",
+    "GUILD_VOICE_STATES",
+    "GUILD_MESSAGES",
+    "GUILD_MESSAGE_REACTIONS",
+    "DIRECT_MESSAGES",
+    "DIRECT_MESSAGE_REACTIONS",
+];
+
 #[derive(Deserialize, Debug)]
 pub struct BotConfig {
     pub main_encryption_key: Vec<u8>,
+    /// The main key that was used before the current one, only needed while rotating keys.
+    #[serde(default)]
+    pub previous_encryption_key: Option<Vec<u8>>,
     pub tokens: Tokens,
     pub logging: Logging,
     pub database: Database,
     pub emoji: HashMap<String, String>,
     pub global_admins: Vec<u64>,
     pub proxy_url: Option<String>,
+    pub intents: Option<Vec<String>>,
+    /// Shards with a heartbeat latency above this (in milliseconds) defer non-critical work.
+    #[serde(default = "default_degraded_latency_threshold")]
+    pub degraded_latency_threshold: u64,
+    /// Ready shards that receive no events at all for this many seconds are considered dead and get restarted.
+    #[serde(default = "default_silent_shard_threshold")]
+    pub silent_shard_threshold: u64,
+    /// A warning is raised when discord refuses this many requests for hitting ratelimits within a minute.
+    #[serde(default = "default_ratelimit_alert_threshold")]
+    pub ratelimit_alert_threshold: usize,
+    /// How many commands are allowed to run at the same time, others wait for one of them to finish.
+    #[serde(default = "default_max_concurrent_commands")]
+    pub max_concurrent_commands: usize,
+    /// How long (in milliseconds) a command waits for its turn before the user is told to try again later.
+    #[serde(default = "default_command_queue_timeout")]
+    pub command_queue_timeout: u64,
+    /// How long (in milliseconds) commands wait for their guild to be fully cached again after an outage or restart.
+    /// They are dropped right away when not set.
+    #[serde(default)]
+    pub recovery_command_wait: Option<u64>,
+    /// How many queued messages (logs, starboard posts, ...) are sent at the same time, no queue is used when not set.
+    #[serde(default)]
+    pub max_concurrent_sends: Option<usize>,
+    /// How many incoming messages are written to storage at the same time, no limit when not set.
+    #[serde(default)]
+    pub max_concurrent_storage_writes: Option<usize>,
+    /// How many messages can wait for their turn to be stored, new ones are skipped while it's full.
+    #[serde(default = "default_max_queued_storage_writes")]
+    pub max_queued_storage_writes: usize,
+    /// How many logs can wait for the logpump, the least important ones get dropped as it fills up.
+    #[serde(default = "default_logpump_capacity")]
+    pub logpump_capacity: usize,
+    /// How the messages we send ourselves are stored, so there is a record of what the bot said.
+    #[serde(default)]
+    pub own_messages: OwnMessageStorage,
+    /// Most messages a single transcript can hold, larger windows are cut off there.
+    #[serde(default = "default_max_transcript_messages")]
+    pub max_transcript_messages: usize,
+    /// Where the prometheus metrics are served.
+    #[serde(default = "default_metrics_address")]
+    pub metrics_address: SocketAddr,
+    /// Language used for strings that aren't translated in the language that was asked for.
+    #[serde(default = "default_fallback_language")]
+    pub fallback_language: LanguageIdentifier,
+    #[serde(default)]
+    pub status: StatusRotation,
+    #[serde(default)]
+    pub member_chunking: MemberChunking,
+    /// Most member presences kept per guild, presences aren't tracked (or requested from discord) when not set.
+    #[serde(default)]
+    pub max_presences_per_guild: Option<usize>,
+    /// External service uploaded attachments can be sent to for scanning, guilds opt in through their automod config.
+    #[serde(default)]
+    pub attachment_scanning: Option<AttachmentScanning>,
+    /// Credentials for integrations, meant to be kept in the encrypted secrets file.
+    #[serde(default)]
+    pub secrets: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -27,18 +111,156 @@ pub struct Tokens {
 pub struct Logging {
     pub important_logs: String,
     pub info_logs: String,
+    /// Log levels per target in env_logger syntax, replaces the logconfig.toml spec file when set.
+    #[serde(default)]
+    pub filter: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct Database {
     pub postgres: String,
     pub redis: String,
+    /// Queries taking longer than this (in milliseconds) get logged as slow.
+    #[serde(default = "default_slow_query_threshold")]
+    pub slow_query_threshold: u64,
+    /// How many queries in a row have to fail to connect before the database is considered down.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How many seconds to hold back queries once the database is down, before trying it again.
+    #[serde(default = "default_retry_after")]
+    pub retry_after: u64,
+    /// What new message content gets encrypted with, stored messages stay readable after changing it.
+    #[serde(default)]
+    pub message_encryption: EncryptionAlgorithm,
+    /// Keeps copies of attachments so they can still be shown after being deleted, off when not set.
+    /// Guilds still have to opt in with a limit of their own.
+    #[serde(default)]
+    pub attachment_storage: Option<AttachmentStorage>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AttachmentStorage {
+    /// Attachments larger than this (in bytes) are never stored, whatever limit guilds set for themselves.
+    pub max_size: u64,
+    /// How many seconds to wait on a download before giving up on keeping a copy.
+    #[serde(default = "default_attachment_download_timeout")]
+    pub download_timeout: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatusRotation {
+    /// How many seconds each status is shown before moving on to the next one.
+    #[serde(default = "default_status_interval")]
+    pub interval: u64,
+    /// The statuses to cycle through, ``{guilds}``, ``{users}`` and ``{version}`` get filled in with the live values.
+    pub messages: Vec<StatusMessage>,
+}
+
+impl Default for StatusRotation {
+    fn default() -> Self {
+        StatusRotation {
+            interval: default_status_interval(),
+            messages: vec![
+                StatusMessage {
+                    activity: StatusActivity::Watching,
+                    text: String::from("the gears turn"),
+                },
+                StatusMessage {
+                    activity: StatusActivity::Watching,
+                    text: String::from("{guilds} servers"),
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct MemberChunking {
+    /// Guilds with more members than this only cache members as they are needed, instead of requesting all of them.
+    pub lazy_threshold: Option<u64>,
+    /// Seconds between requesting all members of a lazily cached guild in the background, never when not set.
+    pub background_interval: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AttachmentScanning {
+    /// Where the attachment info is posted to, see the config template for what gets sent and is expected back.
+    pub endpoint: String,
+    /// Sent along as bearer token when set.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// How many seconds to wait for a verdict before giving up on the scan.
+    #[serde(default = "default_scan_timeout")]
+    pub timeout: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatusMessage {
+    pub activity: StatusActivity,
+    pub text: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusActivity {
+    Playing,
+    Listening,
+    Watching,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OwnMessageStorage {
+    /// Stored like messages of any other bot, following the message log settings of the guild.
+    Regular,
+    /// Always stored, even in guilds that don't log messages or ignore bots.
+    Audit,
+    /// Never stored.
+    Skip,
+}
+
+impl Default for OwnMessageStorage {
+    fn default() -> Self {
+        OwnMessageStorage::Regular
+    }
+}
+
+fn default_metrics_address() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 9091))
+}
+
+fn default_fallback_language() -> LanguageIdentifier {
+    DEFAULT_LANG
+}
+
+fn default_status_interval() -> u64 {
+    5 * 60
+}
+
+fn default_slow_query_threshold() -> u64 {
+    100
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_retry_after() -> u64 {
+    30
+}
+
+fn default_degraded_latency_threshold() -> u64 {
+    1000
 }
 
 impl BotConfig {
+    /// The location of the config file, can be changed with the ``CONFIG_FILE`` environment variable.
+    pub fn file_location() -> String {
+        env::var("CONFIG_FILE").unwrap_or_else(|_| String::from("config.toml"))
+    }
+
     pub fn new(filename: &str) -> Result<Self, StartupError> {
-        let config_file = fs::read_to_string(filename).map_err(|_| StartupError::NoConfig)?;
-        match toml::from_str::<BotConfig>(&config_file) {
+        match Self::read_layered(filename)?.try_into::<BotConfig>() {
             Err(_) => Err(StartupError::InvalidConfig),
             Ok(c) => {
                 let mut override_map: HashMap<String, EmojiOverride> = HashMap::with_capacity(c.emoji.len());
@@ -72,4 +294,236 @@ impl BotConfig {
             }
         }
     }
+
+    /// Reads only the logging section of the config file again, so it can be applied while running.
+    pub fn reload_logging() -> Result<Logging, StartupError> {
+        #[derive(Deserialize)]
+        struct LoggingOnly {
+            logging: Logging,
+        }
+
+        let parsed: LoggingOnly = Self::read_layered(&Self::file_location())?
+            .try_into()
+            .map_err(|_| StartupError::InvalidConfig)?;
+        Ok(parsed.logging)
+    }
+
+    /// Reads the config file with the override file and then the secrets file (when they exist) laid over it.
+    ///
+    /// Tables are merged key by key, so an override only needs the values that differ. This includes maps
+    /// like ``emoji``, where an override replaces or adds single entries. Anything else, lists included, is
+    /// replaced as a whole.
+    fn read_layered(filename: &str) -> Result<toml::Value, StartupError> {
+        let config_file = fs::read_to_string(filename).map_err(|_| StartupError::NoConfig)?;
+        let mut config: toml::Value = toml::from_str(&config_file).map_err(|_| StartupError::InvalidConfig)?;
+
+        if let Some(overrides) = read_overrides(filename)? {
+            merge_toml(&mut config, overrides);
+        }
+
+        if let Some(secrets) = read_secrets()? {
+            merge_toml(&mut config, secrets);
+        }
+
+        Ok(config)
+    }
+
+    /// Encrypts a plaintext secrets file with ``SECRETS_KEY``, writing it to where it will be read from on startup.
+    pub fn encrypt_secrets(plaintext_file: &str) -> Result<String, StartupError> {
+        let plaintext = fs::read_to_string(plaintext_file)?;
+        // refuse to encrypt something that won't load later on
+        toml::from_str::<toml::Value>(&plaintext).map_err(|_| StartupError::InvalidSecrets)?;
+
+        let key = secrets_key()?;
+        let encrypted =
+            crypto::encrypt_with_random_nonce(plaintext.as_bytes(), &EncryptionKey::construct_borrowed(&key));
+
+        let location = secrets_location().0;
+        fs::write(&location, encrypted)?;
+        Ok(location)
+    }
+
+    /// Assembles the gateway intents to connect with, falling back to the defaults when none are configured.
+    pub fn get_intents(&self) -> Result<Intents, StartupError> {
+        let names: Vec<&str> = match &self.intents {
+            Some(intents) => intents.iter().map(String::as_str).collect(),
+            None => DEFAULT_INTENTS.to_vec(),
+        };
+
+        let mut intents = Intents::empty();
+        for name in names {
+            match parse_intent(name) {
+                Some(intent) => intents |= intent,
+                None => {
+                    log::error!("Unknown gateway intent in the config: {}", name);
+                    return Err(StartupError::InvalidConfig);
+                }
+            }
+        }
+
+        // no point in tracking presences if discord doesn't send them
+        if self.max_presences_per_guild.is_some() {
+            intents |= Intents::GUILD_PRESENCES;
+        }
+
+        validate_intents(intents);
+        Ok(intents)
+    }
+}
+
+/// Reads the environment specific overrides, from ``CONFIG_OVERRIDE_FILE`` or the ``.local.toml``
+/// file next to the config (``config.local.toml`` for ``config.toml``).
+fn read_overrides(filename: &str) -> Result<Option<toml::Value>, StartupError> {
+    let (location, required) = match env::var("CONFIG_OVERRIDE_FILE") {
+        Ok(location) => (PathBuf::from(location), true),
+        Err(_) => (Path::new(filename).with_extension("local.toml"), false),
+    };
+
+    let overrides = match fs::read_to_string(&location) {
+        Ok(overrides) => overrides,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && !required => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    match toml::from_str(&overrides) {
+        Ok(overrides) => Ok(Some(overrides)),
+        Err(e) => {
+            log::error!("Unable to parse the config overrides in {}: {}", location.display(), e);
+            Err(StartupError::InvalidConfig)
+        }
+    }
+}
+
+/// Where the secrets file lives, and if it was explicitly configured (and thus has to exist).
+fn secrets_location() -> (String, bool) {
+    match env::var("SECRETS_FILE") {
+        Ok(location) => (location, true),
+        Err(_) => (String::from(DEFAULT_SECRETS_FILE), false),
+    }
+}
+
+/// The key the secrets file is encrypted with, given as hex in the ``SECRETS_KEY`` environment variable.
+fn secrets_key() -> Result<Vec<u8>, StartupError> {
+    let hex = env::var("SECRETS_KEY").map_err(|_| StartupError::NoSecretsKey)?;
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(StartupError::NoSecretsKey);
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| StartupError::NoSecretsKey))
+        .collect()
+}
+
+fn read_secrets() -> Result<Option<toml::Value>, StartupError> {
+    let (location, required) = secrets_location();
+    let encrypted = match fs::read(&location) {
+        Ok(encrypted) => encrypted,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && !required => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let key = secrets_key()?;
+    let decrypted = crypto::decrypt_with_random_nonce(&encrypted, &EncryptionKey::construct_borrowed(&key))
+        .ok_or(StartupError::InvalidSecrets)?;
+
+    let secrets = String::from_utf8(decrypted)
+        .ok()
+        .and_then(|secrets| toml::from_str(&secrets).ok())
+        .ok_or(StartupError::InvalidSecrets)?;
+    Ok(Some(secrets))
+}
+
+/// Lays ``overlay`` over ``base``, tables are merged key by key and anything else replaces what was there.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn parse_intent(name: &str) -> Option<Intents> {
+    let intent = match name.to_uppercase().as_str() {
+        "GUILDS" => Intents::GUILDS,
+        "GUILD_MEMBERS" => Intents::GUILD_MEMBERS,
+        "GUILD_BANS" => Intents::GUILD_BANS,
+        "GUILD_EMOJIS" => Intents::GUILD_EMOJIS,
+        "GUILD_INTEGRATIONS" => Intents::GUILD_INTEGRATIONS,
+        "GUILD_WEBHOOKS" => Intents::GUILD_WEBHOOKS,
+        "GUILD_INVITES" => Intents::GUILD_INVITES,
+        "GUILD_VOICE_STATES" => Intents::GUILD_VOICE_STATES,
+        "GUILD_PRESENCES" => Intents::GUILD_PRESENCES,
+        "GUILD_MESSAGES" => Intents::GUILD_MESSAGES,
+        "GUILD_MESSAGE_REACTIONS" => Intents::GUILD_MESSAGE_REACTIONS,
+        "GUILD_MESSAGE_TYPING" => Intents::GUILD_MESSAGE_TYPING,
+        "DIRECT_MESSAGES" => Intents::DIRECT_MESSAGES,
+        "DIRECT_MESSAGE_REACTIONS" => Intents::DIRECT_MESSAGE_REACTIONS,
+        "DIRECT_MESSAGE_TYPING" => Intents::DIRECT_MESSAGE_TYPING,
+        _ => return None,
+    };
+    Some(intent)
+}
+
+/// Warns about features that won't work with the selected intents.
+fn validate_intents(intents: Intents) {
+    if !intents.contains(Intents::GUILDS) {
+        gearbot_warn!("The GUILDS intent is disabled, the cache will not be able to function!");
+    }
+
+    if !intents.contains(Intents::GUILD_MEMBERS) {
+        gearbot_warn!("The GUILD_MEMBERS intent is disabled, member caching and permission checks will not work!");
+    }
+
+    if !intents.contains(Intents::GUILD_MESSAGES) {
+        gearbot_warn!("The GUILD_MESSAGES intent is disabled, commands and message logging will not work in guilds!");
+    }
+
+    if !intents.contains(Intents::GUILD_MESSAGE_REACTIONS) {
+        gearbot_warn!("The GUILD_MESSAGE_REACTIONS intent is disabled, reaction based navigation will not work!");
+    }
+}
+
+fn default_max_queued_storage_writes() -> usize {
+    5000
+}
+
+fn default_logpump_capacity() -> usize {
+    10000
+}
+
+fn default_max_transcript_messages() -> usize {
+    100_000
+}
+
+fn default_silent_shard_threshold() -> u64 {
+    120
+}
+
+fn default_ratelimit_alert_threshold() -> usize {
+    10
+}
+
+fn default_max_concurrent_commands() -> usize {
+    100
+}
+
+fn default_command_queue_timeout() -> u64 {
+    5000
+}
+
+fn default_scan_timeout() -> u64 {
+    10
+}
+
+fn default_attachment_download_timeout() -> u64 {
+    10
 }
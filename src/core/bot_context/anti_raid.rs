@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use twilight_model::guild::VerificationLevel;
+use twilight_model::id::{GuildId, UserId};
+
+use super::BotContext;
+use crate::core::logpump::{LogData, LogType};
+use crate::core::{AntiRaidAction, AntiRaidConfig};
+use crate::gearbot_error;
+
+/// In memory join tracking and raid mode status of a single guild.
+///
+/// Active raid modes are also stored, so the verification level can still be restored after a restart.
+#[derive(Default)]
+pub struct RaidState {
+    joins: VecDeque<Instant>,
+    active: bool,
+    previous_verification: Option<VerificationLevel>,
+}
+
+impl BotContext {
+    /// Picks the raid modes that were active when we shut down back up.
+    pub async fn restore_raid_modes(&self) {
+        match self.datastore.get_raid_modes().await {
+            Ok(raid_modes) => {
+                let mut states = self.raid_states.write().await;
+                for (guild_id, previous_verification) in raid_modes {
+                    states.insert(
+                        guild_id,
+                        RaidState {
+                            joins: VecDeque::new(),
+                            active: true,
+                            previous_verification,
+                        },
+                    );
+                }
+            }
+            Err(e) => gearbot_error!("Failed to restore the active raid modes: {}", e),
+        }
+    }
+
+    /// Registers a new member join, returns true if this join pushed the guild over the raid threshold.
+    pub async fn register_join(&self, guild_id: GuildId, config: &AntiRaidConfig) -> bool {
+        let now = Instant::now();
+        let window = Duration::from_secs(config.window_seconds);
+
+        let mut states = self.raid_states.write().await;
+        let state = states.entry(guild_id).or_insert_with(RaidState::default);
+
+        while let Some(oldest) = state.joins.front() {
+            if now.duration_since(*oldest) > window {
+                state.joins.pop_front();
+            } else {
+                break;
+            }
+        }
+        state.joins.push_back(now);
+
+        !state.active && state.joins.len() >= config.join_threshold
+    }
+
+    pub async fn is_raid_mode_active(&self, guild_id: GuildId) -> bool {
+        match self.raid_states.read().await.get(&guild_id) {
+            Some(state) => state.active,
+            None => false,
+        }
+    }
+
+    /// Enables raid mode for the guild, returns false if it was already active.
+    pub async fn enable_raid_mode(
+        &self,
+        guild_id: GuildId,
+        config: &AntiRaidConfig,
+        source_user: UserId,
+    ) -> Result<bool, twilight_http::Error> {
+        let previous_verification = self.cache.get_guild(&guild_id).map(|guild| guild.verification_level);
+
+        {
+            let mut states = self.raid_states.write().await;
+            let state = states.entry(guild_id).or_insert_with(RaidState::default);
+            if state.active {
+                return Ok(false);
+            }
+            state.active = true;
+            state.previous_verification = previous_verification;
+        }
+
+        if config.actions.contains(&AntiRaidAction::RaiseVerification) {
+            let result = self
+                .http
+                .update_guild(guild_id)
+                .verification_level(VerificationLevel::VeryHigh)
+                .await;
+            if let Err(e) = result {
+                // couldn't raise it, don't claim raid mode is on
+                if let Some(state) = self.raid_states.write().await.get_mut(&guild_id) {
+                    state.active = false;
+                    state.previous_verification = None;
+                }
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = self.datastore.set_raid_mode(guild_id, previous_verification).await {
+            gearbot_error!("Failed to store the raid mode of guild {}: {}", guild_id, e);
+        }
+
+        self.log(LogData {
+            log_type: LogType::RaidModeEnabled,
+            guild: guild_id,
+            source_channel: None,
+            source_user,
+            failed_attempts: 0,
+        });
+
+        Ok(true)
+    }
+
+    /// Disables raid mode for the guild, returns false if it wasn't active.
+    pub async fn disable_raid_mode(
+        &self,
+        guild_id: GuildId,
+        config: &AntiRaidConfig,
+        source_user: UserId,
+    ) -> Result<bool, twilight_http::Error> {
+        let previous_verification = {
+            let mut states = self.raid_states.write().await;
+            match states.get_mut(&guild_id) {
+                Some(state) if state.active => {
+                    state.active = false;
+                    state.previous_verification.take()
+                }
+                _ => return Ok(false),
+            }
+        };
+
+        if config.actions.contains(&AntiRaidAction::RaiseVerification) {
+            if let Some(level) = previous_verification {
+                if let Err(e) = self.http.update_guild(guild_id).verification_level(level).await {
+                    // still raised, stay in raid mode so it can be disabled again
+                    if let Some(state) = self.raid_states.write().await.get_mut(&guild_id) {
+                        state.active = true;
+                        state.previous_verification = Some(level);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Some(state) = self.raid_states.write().await.get_mut(&guild_id) {
+            state.joins.clear();
+        }
+
+        if let Err(e) = self.datastore.remove_raid_mode(guild_id).await {
+            gearbot_error!("Failed to remove the stored raid mode of guild {}: {}", guild_id, e);
+        }
+
+        self.log(LogData {
+            log_type: LogType::RaidModeDisabled,
+            guild: guild_id,
+            source_channel: None,
+            source_user,
+            failed_attempts: 0,
+        });
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,118 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use twilight_http::Error;
+
+use super::BotContext;
+use crate::error::ApiRequestError;
+use crate::{gearbot_error, gearbot_important};
+
+/// How many requests in a row discord has to answer with a server error before we back off.
+const FAILURE_THRESHOLD: u32 = 10;
+
+/// How long non-critical requests are skipped before probing if the api recovered.
+const COOL_DOWN: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+pub struct ApiCircuit {
+    state: Mutex<CircuitState>,
+}
+
+enum CircuitState {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A single probe is on its way to find out if the api is back.
+    HalfOpen {
+        probe_deadline: Instant,
+    },
+}
+
+impl Default for CircuitState {
+    fn default() -> Self {
+        CircuitState::Closed { failures: 0 }
+    }
+}
+
+impl BotContext {
+    /// Sends a request that can be skipped when discord's api is having a rough time.
+    ///
+    /// Once too many of these got a server error in a row they fail right away for a while, without
+    /// bothering discord. After that a single one gets through to see if things recovered.
+    pub async fn non_critical_request<T, F>(&self, request: F) -> Result<T, ApiRequestError>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        if !self.api_circuit_allows() {
+            return Err(ApiRequestError::CircuitOpen);
+        }
+
+        let result = request.await;
+        let server_error = matches!(&result, Err(Error::Response { status, .. }) if status.is_server_error());
+        self.record_api_outcome(server_error);
+
+        Ok(result?)
+    }
+
+    fn api_circuit_allows(&self) -> bool {
+        let mut state = self.api_circuit.state.lock().expect("The api circuit got poisoned!");
+        let now = Instant::now();
+        match *state {
+            CircuitState::Closed { .. } => true,
+            // the probe never reported back, send another one
+            CircuitState::Open { until } | CircuitState::HalfOpen { probe_deadline: until } if now >= until => {
+                *state = CircuitState::HalfOpen {
+                    probe_deadline: now + COOL_DOWN,
+                };
+                self.stats.api_circuit_state.set(2);
+                true
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record_api_outcome(&self, server_error: bool) {
+        let mut state = self.api_circuit.state.lock().expect("The api circuit got poisoned!");
+        match (&*state, server_error) {
+            (CircuitState::Closed { .. }, false) => *state = CircuitState::Closed { failures: 0 },
+            (CircuitState::Closed { failures }, true) => {
+                let failures = failures + 1;
+                if failures < FAILURE_THRESHOLD {
+                    *state = CircuitState::Closed { failures };
+                    return;
+                }
+
+                *state = CircuitState::Open {
+                    until: Instant::now() + COOL_DOWN,
+                };
+                self.stats.api_circuit_state.set(1);
+                gearbot_error!(
+                    "Discord answered {} requests in a row with a server error, holding back non-critical requests for {} seconds at a time until it recovers",
+                    failures,
+                    COOL_DOWN.as_secs()
+                );
+            }
+            (CircuitState::HalfOpen { .. }, false) => {
+                *state = CircuitState::Closed { failures: 0 };
+                self.stats.api_circuit_state.set(0);
+                gearbot_important!("The discord api is answering properly again, resuming non-critical requests");
+            }
+            (CircuitState::HalfOpen { .. }, true) => {
+                *state = CircuitState::Open {
+                    until: Instant::now() + COOL_DOWN,
+                };
+                self.stats.api_circuit_state.set(1);
+                log::warn!(
+                    "The discord api is still failing, trying again in {} seconds",
+                    COOL_DOWN.as_secs()
+                );
+            }
+            // requests that were let through before it opened, nothing to learn from those
+            (CircuitState::Open { .. }, _) => {}
+        }
+    }
+}
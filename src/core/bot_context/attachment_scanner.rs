@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use twilight_model::channel::Message;
+
+use super::BotContext;
+use crate::core::AttachmentScanning;
+
+pub struct AttachmentScanner {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+}
+
+impl AttachmentScanner {
+    pub fn new(config: &AttachmentScanning) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .build()
+            .expect("Failed to build the attachment scanning client");
+        AttachmentScanner {
+            client,
+            endpoint: config.endpoint.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ScanRequest<'a> {
+    guild_id: u64,
+    channel_id: u64,
+    message_id: u64,
+    attachments: Vec<ScanAttachment<'a>>,
+}
+
+#[derive(Serialize)]
+struct ScanAttachment<'a> {
+    filename: &'a str,
+    url: &'a str,
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct ScanResponse {
+    flagged: bool,
+}
+
+pub enum ScanVerdict {
+    Clean,
+    Flagged,
+    /// The service couldn't be reached or gave an answer we don't understand.
+    Failed,
+}
+
+impl BotContext {
+    /// Asks the scanning service about the attachments of this message, everything is clean without one.
+    pub async fn scan_attachments(&self, message: &Message) -> ScanVerdict {
+        let scanner = match &self.attachment_scanner {
+            Some(scanner) => scanner,
+            None => return ScanVerdict::Clean,
+        };
+
+        let request = ScanRequest {
+            guild_id: message.guild_id.map_or(0, |guild_id| guild_id.0),
+            channel_id: message.channel_id.0,
+            message_id: message.id.0,
+            attachments: message
+                .attachments
+                .iter()
+                .map(|attachment| ScanAttachment {
+                    filename: &attachment.filename,
+                    url: &attachment.url,
+                    size: attachment.size,
+                })
+                .collect(),
+        };
+        let body = serde_json::to_string(&request).expect("Scan requests are always valid json");
+
+        let mut builder = scanner
+            .client
+            .post(&scanner.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(api_key) = &scanner.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+
+        let response = match builder.send().await.and_then(|response| response.error_for_status()) {
+            Ok(response) => response.text().await,
+            Err(e) => Err(e),
+        };
+
+        match response.map(|text| serde_json::from_str::<ScanResponse>(&text)) {
+            Ok(Ok(ScanResponse { flagged: true })) => ScanVerdict::Flagged,
+            Ok(Ok(ScanResponse { flagged: false })) => ScanVerdict::Clean,
+            Ok(Err(e)) => {
+                log::warn!("The attachment scanner answered with something unexpected: {}", e);
+                ScanVerdict::Failed
+            }
+            Err(e) => {
+                log::warn!("Failed to scan the attachments of message {}: {}", message.id, e);
+                ScanVerdict::Failed
+            }
+        }
+    }
+}
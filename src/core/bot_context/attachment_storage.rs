@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use twilight_model::channel::Attachment;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::core::{AttachmentStorage, GuildConfig};
+use crate::database::structures::ContentStorage;
+use crate::error::DatabaseError;
+
+pub struct AttachmentDownloader {
+    client: reqwest::Client,
+    max_size: u64,
+}
+
+impl AttachmentDownloader {
+    pub fn new(config: &AttachmentStorage) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.download_timeout))
+            .build()
+            .expect("Failed to build the attachment download client");
+        AttachmentDownloader {
+            client,
+            max_size: config.max_size,
+        }
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, reqwest::Error> {
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+impl BotContext {
+    /// Keeps a copy of the attachment when both the bot and the guild opted in, and it fits within both limits.
+    ///
+    /// Attachments that aren't kept only have their metadata stored, same as before.
+    pub async fn store_attachment_bytes(
+        &self,
+        attachment: &Attachment,
+        guild_id: GuildId,
+        config: &GuildConfig,
+    ) -> Result<(), DatabaseError> {
+        let downloader = match &self.attachment_downloader {
+            Some(downloader) => downloader,
+            None => return Ok(()),
+        };
+
+        // no point keeping the picture when the text next to it isn't readable either
+        let limit = match config.message_logs.max_attachment_size {
+            Some(limit) if config.message_logs.content_storage() == ContentStorage::Encrypted => {
+                limit.min(downloader.max_size)
+            }
+            _ => return Ok(()),
+        };
+
+        if attachment.size > limit {
+            log::debug!(
+                "Not keeping a copy of attachment {}, it's {} bytes while the limit is {}",
+                attachment.id,
+                attachment.size,
+                limit
+            );
+            return Ok(());
+        }
+
+        let bytes = match downloader.download(&attachment.url).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!("Failed to download attachment {} to keep a copy: {}", attachment.id, e);
+                return Ok(());
+            }
+        };
+        // don't take the size discord reported on its word
+        if bytes.len() as u64 > limit {
+            return Ok(());
+        }
+
+        self.datastore
+            .insert_attachment_blob(attachment.id, guild_id, &bytes)
+            .await
+    }
+}
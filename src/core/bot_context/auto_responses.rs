@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::database::structures::AutoResponse;
+use crate::error::DatabaseError;
+
+impl BotContext {
+    /// The auto responses of a guild, they are only loaded from the database on first use.
+    pub async fn get_auto_responses(&self, guild_id: GuildId) -> Result<Arc<Vec<AutoResponse>>, DatabaseError> {
+        // Clone the option so we can release the lock much faster
+        let responses = self.auto_responses.read().await.get(&guild_id).cloned();
+        if let Some(responses) = responses {
+            return Ok(responses);
+        }
+
+        let responses = Arc::new(self.datastore.get_auto_responses(guild_id).await?);
+        self.auto_responses
+            .write()
+            .await
+            .insert(guild_id, Arc::clone(&responses));
+
+        Ok(responses)
+    }
+
+    /// Drops the cached auto responses of a guild after they changed.
+    pub async fn forget_auto_responses(&self, guild_id: GuildId) {
+        self.auto_responses.write().await.remove(&guild_id);
+    }
+
+    /// Returns if this auto response can be sent, starting a new cooldown for it if so.
+    pub async fn check_auto_response_cooldown(&self, response: &AutoResponse) -> bool {
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(response.cooldown_seconds as u64);
+        let mut cooldowns = self.auto_response_cooldowns.write().await;
+
+        match cooldowns.get(&response.id) {
+            Some(last) if now.duration_since(*last) < cooldown => false,
+            _ => {
+                cooldowns.insert(response.id, now);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use twilight_model::id::UserId;
+use twilight_model::user::CurrentUser;
+
+/// Discord only allows this many username changes within the window.
+const MAX_USERNAME_CHANGES: usize = 2;
+const USERNAME_CHANGE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Who we are on discord. The id never changes, the rest of the profile can be updated while running.
+pub struct BotUser {
+    pub id: UserId,
+    profile: RwLock<CurrentUser>,
+    username_changes: Mutex<VecDeque<Instant>>,
+}
+
+impl BotUser {
+    pub fn new(user: CurrentUser) -> Self {
+        BotUser {
+            id: user.id,
+            profile: RwLock::new(user),
+            username_changes: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// The profile as it was after the last change.
+    pub fn profile(&self) -> CurrentUser {
+        self.profile.read().expect("The bot profile got poisoned!").clone()
+    }
+
+    pub fn set_profile(&self, user: CurrentUser) {
+        *self.profile.write().expect("The bot profile got poisoned!") = user;
+    }
+
+    /// Claims one of the username changes discord allows, returns how long to wait when they are all used up.
+    pub fn claim_username_change(&self) -> Result<(), Duration> {
+        let mut changes = self
+            .username_changes
+            .lock()
+            .expect("The username changes got poisoned!");
+        let now = Instant::now();
+        while changes
+            .front()
+            .map_or(false, |changed| now.duration_since(*changed) >= USERNAME_CHANGE_WINDOW)
+        {
+            changes.pop_front();
+        }
+
+        if changes.len() >= MAX_USERNAME_CHANGES {
+            return Err(USERNAME_CHANGE_WINDOW - now.duration_since(changes[0]));
+        }
+
+        changes.push_back(now);
+        Ok(())
+    }
+}
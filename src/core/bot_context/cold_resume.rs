@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::Utc;
 use log::info;
 use twilight_model::gateway::presence::{ActivityType, Status};
 
@@ -44,6 +45,7 @@ impl BotContext {
             guild_chunks,
             shard_count: self.scheme_info.shards_per_cluster,
             user_chunks,
+            stored_at: Utc::now().timestamp(),
         };
 
         redis_cache
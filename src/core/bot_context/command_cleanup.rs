@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use hyper::StatusCode;
+use twilight_http::Error;
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use super::BotContext;
+
+/// Commands are left up for a moment, so it's clear what the response was to.
+const COMMAND_DELETE_DELAY: Duration = Duration::from_secs(3);
+
+impl BotContext {
+    /// Deletes a command message after a short delay, for guilds that don't want them cluttering their channels.
+    ///
+    /// Without permission to do so nothing is deleted, that only gets a warning the first time for each guild.
+    pub async fn schedule_command_delete(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        can_delete: bool,
+    ) {
+        if !can_delete {
+            if self.command_delete_warned.lock().await.insert(guild_id) {
+                log::warn!(
+                    "Guild {} wants command messages deleted, but I lack permission to delete them in {}",
+                    guild_id,
+                    channel_id
+                );
+            }
+            return;
+        }
+
+        let http = self.http.clone();
+        tokio::spawn(async move {
+            tokio::time::delay_for(COMMAND_DELETE_DELAY).await;
+            match http.delete_message(channel_id, message_id).await {
+                Ok(_) => {}
+                // the author (or a response cleanup) beat us to it
+                Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => {}
+                Err(e) => log::debug!(
+                    "Failed to delete command message {} in {}: {}",
+                    message_id,
+                    channel_id,
+                    e
+                ),
+            }
+        });
+    }
+}
@@ -0,0 +1,26 @@
+use std::time::{Duration, Instant};
+
+use twilight_model::id::UserId;
+
+use super::BotContext;
+
+impl BotContext {
+    /// Puts the user on cooldown for the command, unless they already are.
+    ///
+    /// Returns how long they still have to wait when they are, the cooldown is not restarted in that case.
+    pub async fn start_command_cooldown(&self, user_id: UserId, command: &str, cooldown: Duration) -> Option<Duration> {
+        let now = Instant::now();
+        let mut cooldowns = self.command_cooldowns.lock().await;
+        // expired ones are of no use anymore, no need to let them pile up
+        cooldowns.retain(|_, until| *until > now);
+
+        let key = (user_id, command.to_string());
+        match cooldowns.get(&key) {
+            Some(until) => Some(*until - now),
+            None => {
+                cooldowns.insert(key, now + cooldown);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use prometheus::IntGauge;
+use tokio::sync::SemaphorePermit;
+
+use super::BotContext;
+
+/// A claim on one of the limited command slots, freed up again once it's dropped.
+pub struct CommandSlot<'a> {
+    _permit: SemaphorePermit<'a>,
+    in_flight: &'a IntGauge,
+}
+
+impl Drop for CommandSlot<'_> {
+    fn drop(&mut self) {
+        self.in_flight.dec();
+    }
+}
+
+impl BotContext {
+    /// Waits for one of the command slots to free up, so a flood of commands can't exhaust our resources.
+    ///
+    /// Gives up when waiting takes too long, better to tell the user we're busy than to leave them hanging.
+    pub async fn acquire_command_slot(&self) -> Option<CommandSlot<'_>> {
+        let stats = &self.stats.command_load;
+        stats.queued.inc();
+        let permit = tokio::time::timeout(self.command_queue_timeout, self.command_slots.acquire()).await;
+        stats.queued.dec();
+
+        match permit {
+            Ok(permit) => {
+                stats.in_flight.inc();
+                Some(CommandSlot {
+                    _permit: permit,
+                    in_flight: &stats.in_flight,
+                })
+            }
+            Err(_) => {
+                stats.rejected.inc();
+                None
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -7,10 +8,22 @@ use twilight_model::{
 };
 
 use super::BotContext;
-use crate::cache::CachedUser;
+use crate::cache::{CachedMember, CachedUser};
 use crate::core::GuildConfig;
-use crate::database::structures::UserMessage;
+use crate::database::structures::{BulkInsertReport, UserMessage};
 use crate::error::{DatabaseError, ParseError};
+use crate::gearbot_error;
+
+/// The Redis channel config changes are announced on.
+pub const CONFIG_INVALIDATION_CHANNEL: &str = "config-invalidations";
+
+/// Announces the config of a guild changed, clusters that have it cached should drop it.
+#[derive(Serialize, Deserialize)]
+pub struct ConfigInvalidation {
+    pub guild_id: GuildId,
+    /// The cluster that made the change, it already has the new config.
+    pub cluster_id: u64,
+}
 
 #[derive(Serialize, Deserialize)]
 #[serde(untagged)]
@@ -61,6 +74,25 @@ impl BotContext {
         }
     }
 
+    /// Looks up a member in the cache, asking discord for them if the guild is lazily cached and they aren't in yet.
+    pub async fn fetch_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<Arc<CachedMember>>, twilight_http::Error> {
+        if let Some(member) = self.cache.get_member(&guild_id, &user_id) {
+            return Ok(Some(member));
+        }
+
+        let guild = match self.cache.get_guild(&guild_id) {
+            Some(guild) if guild.is_lazily_cached() => guild,
+            _ => return Ok(None),
+        };
+
+        let member = self.http.guild_member(guild_id, user_id).await?;
+        Ok(member.map(|member| self.cache.insert_member(&guild, &member)))
+    }
+
     pub async fn get_config(&self, guild_id: GuildId) -> Result<Arc<GuildConfig>, DatabaseError> {
         // Clone the option so we can release the lock much faster
         let config = self.configs.read().await.get(&guild_id).cloned();
@@ -84,9 +116,45 @@ impl BotContext {
         //TODO: validate values? or do we leave that to whoever edited it?
         self.datastore.set_guild_config(guild_id.0, &config).await?;
         self.configs.write().await.insert(guild_id, Arc::new(config));
+        self.announce_config_change(guild_id).await;
         Ok(())
     }
 
+    /// Drops the cached config of a guild so it gets loaded from the database again on next use.
+    ///
+    /// Only affects this cluster, use ``invalidate_config`` after changing the config in the database.
+    pub async fn forget_config(&self, guild_id: GuildId) {
+        self.configs.write().await.remove(&guild_id);
+    }
+
+    /// Drops the cached config of a guild on every cluster, for when it was changed in the database directly.
+    pub async fn invalidate_config(&self, guild_id: GuildId) {
+        self.forget_config(guild_id).await;
+        self.announce_config_change(guild_id).await;
+    }
+
+    /// Drops all cached configs, used when config changes might have been missed.
+    pub async fn forget_all_configs(&self) {
+        self.configs.write().await.clear();
+    }
+
+    /// Lets the other clusters know the config of a guild changed, so they drop the copy they have cached.
+    async fn announce_config_change(&self, guild_id: GuildId) {
+        let invalidation = ConfigInvalidation {
+            guild_id,
+            cluster_id: self.scheme_info.cluster_id,
+        };
+        let result = self
+            .datastore
+            .cache_pool
+            .publish(CONFIG_INVALIDATION_CHANNEL, &invalidation)
+            .await;
+        // the change itself is stored, other clusters would just keep using the old config for now
+        if let Err(e) = result {
+            gearbot_error!("Failed to announce the config change of guild {}: {}", guild_id, e);
+        }
+    }
+
     pub async fn fetch_user_message(
         &self,
         message_id: MessageId,
@@ -97,16 +165,59 @@ impl BotContext {
 
     pub async fn insert_message(&self, message: &Message, guild_id: GuildId) -> Result<(), DatabaseError> {
         // All guilds need to have a config before anything can happen thanks to encryption.
-        let _ = self.get_config(guild_id).await?;
+        let config = self.get_config(guild_id).await?;
 
         let datastore = &self.datastore;
 
-        datastore.insert_message(&message, guild_id).await?;
+        datastore
+            .insert_message(&message, guild_id, config.message_logs.content_storage())
+            .await?;
 
         for attachment in &message.attachments {
             datastore.insert_attachment(message.id, attachment).await?;
+            self.store_attachment_bytes(attachment, guild_id, &config).await?;
         }
 
         Ok(())
     }
+
+    /// Stores a batch of messages at once, see [`DataStorage::insert_messages`].
+    ///
+    /// [`DataStorage::insert_messages`]: crate::database::DataStorage::insert_messages
+    pub async fn insert_messages(&self, messages: &[(Message, GuildId)]) -> Result<BulkInsertReport, DatabaseError> {
+        let mut failed = vec![];
+        let mut configs: HashMap<GuildId, Arc<GuildConfig>> = HashMap::new();
+        let mut batch = Vec::with_capacity(messages.len());
+        for (message, guild_id) in messages {
+            if !configs.contains_key(guild_id) {
+                match self.get_config(*guild_id).await {
+                    Ok(config) => {
+                        configs.insert(*guild_id, config);
+                    }
+                    Err(e) => {
+                        failed.push((message.id, e.to_string()));
+                        continue;
+                    }
+                }
+            }
+            batch.push((message, *guild_id, configs[guild_id].message_logs.content_storage()));
+        }
+
+        let mut report = self.datastore.insert_messages(&batch).await?;
+        report.failed.extend(failed);
+
+        let written: HashSet<MessageId> = report.written.iter().copied().collect();
+        for (message, guild_id, _) in batch {
+            if !written.contains(&message.id) {
+                continue;
+            }
+            for attachment in &message.attachments {
+                self.datastore.insert_attachment(message.id, attachment).await?;
+                self.store_attachment_bytes(attachment, guild_id, &configs[&guild_id])
+                    .await?;
+            }
+        }
+
+        Ok(report)
+    }
 }
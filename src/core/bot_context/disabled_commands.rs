@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use super::BotContext;
+use crate::error::DatabaseError;
+
+/// Redis key holding the commands the bot operators disabled everywhere, shared by all clusters.
+const DISABLED_COMMANDS_KEY: &str = "disabled_commands";
+
+impl BotContext {
+    /// If the bot operators disabled the (root) command in all guilds.
+    pub async fn is_globally_disabled(&self, command: &str) -> bool {
+        self.globally_disabled.read().await.contains(command)
+    }
+
+    /// The commands that are disabled everywhere, sorted by name.
+    pub async fn globally_disabled_commands(&self) -> Vec<String> {
+        let mut commands: Vec<String> = self.globally_disabled.read().await.iter().cloned().collect();
+        commands.sort();
+        commands
+    }
+
+    /// Disables a command in all guilds on all clusters, or enables it again.
+    ///
+    /// Returns false when nothing changed because it already was (or wasn't) disabled.
+    pub async fn set_globally_disabled(&self, command: &str, disabled: bool) -> Result<bool, DatabaseError> {
+        // start from what is stored, another cluster might have changed it since the last sync
+        let mut commands: HashSet<String> = self
+            .datastore
+            .cache_pool
+            .get(DISABLED_COMMANDS_KEY)
+            .await?
+            .unwrap_or_default();
+        let changed = if disabled {
+            commands.insert(command.to_string())
+        } else {
+            commands.remove(command)
+        };
+
+        if changed {
+            self.datastore
+                .cache_pool
+                .set(DISABLED_COMMANDS_KEY, &commands, None)
+                .await?;
+        }
+        *self.globally_disabled.write().await = commands;
+
+        Ok(changed)
+    }
+
+    /// Picks up commands disabled or enabled again by other clusters.
+    pub async fn sync_disabled_commands(&self) -> Result<(), DatabaseError> {
+        let commands = self
+            .datastore
+            .cache_pool
+            .get(DISABLED_COMMANDS_KEY)
+            .await?
+            .unwrap_or_default();
+        *self.globally_disabled.write().await = commands;
+
+        Ok(())
+    }
+}
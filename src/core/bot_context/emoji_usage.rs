@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use twilight_model::id::{EmojiId, GuildId};
+
+use super::BotContext;
+use crate::error::DatabaseError;
+use crate::gearbot_error;
+
+impl BotContext {
+    /// Counts a use of each of the guild's own emoji in the given list, anything else is ignored.
+    ///
+    /// Duplicates are only counted once so repeating the same emoji in a single message doesn't inflate the stats.
+    pub async fn record_emoji_usage(
+        &self,
+        guild_id: GuildId,
+        emoji_ids: impl Iterator<Item = EmojiId>,
+    ) -> Result<(), DatabaseError> {
+        let guild = match self.cache.get_guild(&guild_id) {
+            Some(guild) => guild,
+            None => return Ok(()),
+        };
+
+        let used: HashSet<EmojiId> = emoji_ids
+            .filter(|id| guild.emoji.iter().any(|emoji| emoji.id == *id))
+            .collect();
+
+        if used.is_empty() {
+            return Ok(());
+        }
+
+        let key = emoji_usage_key(guild_id);
+        for emoji_id in used {
+            self.datastore
+                .cache_pool
+                .increment_hash_field(&key, &emoji_id.0.to_string(), 1)
+                .await?;
+        }
+
+        self.emoji_usage_pending.write().await.insert(guild_id);
+
+        Ok(())
+    }
+
+    /// Moves the usage counters collected in Redis into the database.
+    ///
+    /// Counters of guilds that are only pending in Redis from before a restart get picked up again
+    /// as soon as one of their emoji is used. Guilds that fail to flush stay pending for the next round.
+    pub async fn flush_emoji_usage(&self) -> Result<(), DatabaseError> {
+        let pending: Vec<GuildId> = self.emoji_usage_pending.write().await.drain().collect();
+
+        let mut result = Ok(());
+        let mut failed = vec![];
+        for guild_id in pending {
+            if let Err(e) = self.flush_guild_emoji_usage(guild_id).await {
+                failed.push(guild_id);
+                result = Err(e);
+            }
+        }
+
+        if !failed.is_empty() {
+            self.emoji_usage_pending.write().await.extend(failed);
+        }
+
+        result
+    }
+
+    async fn flush_guild_emoji_usage(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        let key = emoji_usage_key(guild_id);
+        let counters = self.datastore.cache_pool.take_hash_counters(&key).await?;
+        let usage: Vec<(u64, i64)> = counters
+            .iter()
+            .filter_map(|(emoji_id, uses)| Some((emoji_id.parse().ok()?, *uses)))
+            .collect();
+
+        if let Err(e) = self.datastore.add_emoji_usage(guild_id, &usage).await {
+            // they're already taken out of Redis, put them back so they go in with the next flush
+            for (emoji_id, uses) in counters {
+                if let Err(e) = self
+                    .datastore
+                    .cache_pool
+                    .increment_hash_field(&key, &emoji_id, uses)
+                    .await
+                {
+                    gearbot_error!("Lost the emoji usage counters of guild {}: {}", guild_id, e);
+                    break;
+                }
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+fn emoji_usage_key(guild_id: GuildId) -> String {
+    format!("emoji_usage:{}", guild_id)
+}
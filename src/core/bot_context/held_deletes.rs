@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use twilight_model::id::{ChannelId, GuildId, UserId};
+
+use super::BotContext;
+use crate::core::logpump::LogData;
+use crate::database::DatabaseError;
+use crate::utils;
+
+/// What is left of a deleted message to recognize a repost by.
+pub enum DeletedContent {
+    /// The content itself, a repost only has to be near-identical.
+    Text(String),
+    /// Only the salted hash privacy mode keeps, this only recognizes exact reposts.
+    Hash(Vec<u8>),
+}
+
+/// A delete log waiting out the grace period, in case the author reposts the message.
+pub struct HeldDelete {
+    channel_id: Option<ChannelId>,
+    author: UserId,
+    content: DeletedContent,
+    release_at: Instant,
+    log: LogData,
+}
+
+impl BotContext {
+    /// Holds back the log of a deleted message, it goes out once the grace period is over
+    /// unless the author reposts the message in the same channel before that.
+    pub async fn hold_delete_log(&self, content: DeletedContent, grace: Duration, log: LogData) {
+        let content = match content {
+            DeletedContent::Text(text) => DeletedContent::Text(normalize(&text)),
+            hash => hash,
+        };
+        let held = HeldDelete {
+            channel_id: log.source_channel,
+            author: log.source_user,
+            content,
+            release_at: Instant::now() + grace,
+            log,
+        };
+        self.held_deletes.lock().await.push(held);
+    }
+
+    /// Drops the held delete logs a new message is a repost of, these were just someone fixing a typo.
+    pub async fn cancel_reposted_deletes(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        author: UserId,
+        content: &str,
+    ) -> Result<(), DatabaseError> {
+        let mut held = self.held_deletes.lock().await;
+        if held.is_empty() {
+            return Ok(());
+        }
+
+        let from_author = |delete: &HeldDelete| delete.channel_id == Some(channel_id) && delete.author == author;
+        // hashing needs the guild key, don't go fetch that for every message
+        let hash = if held
+            .iter()
+            .any(|delete| from_author(delete) && matches!(delete.content, DeletedContent::Hash(_)))
+        {
+            Some(self.datastore.hash_content(guild_id, content).await?)
+        } else {
+            None
+        };
+
+        let normalized = normalize(content);
+        held.retain(|delete| {
+            !from_author(delete)
+                || match &delete.content {
+                    DeletedContent::Text(text) => !nearly_identical(text, &normalized),
+                    DeletedContent::Hash(deleted_hash) => hash.as_ref() != Some(deleted_hash),
+                }
+        });
+
+        Ok(())
+    }
+
+    /// Sends out the held delete logs whose grace period ran out.
+    pub async fn release_held_deletes(&self) {
+        let now = Instant::now();
+        let mut held = self.held_deletes.lock().await;
+
+        if held.is_empty() {
+            return;
+        }
+
+        let (due, waiting): (Vec<HeldDelete>, Vec<HeldDelete>) =
+            held.drain(..).partition(|delete| delete.release_at <= now);
+        *held = waiting;
+        for delete in due {
+            self.log(delete.log);
+        }
+    }
+}
+
+/// Casing and spacing changes alone don't make a different message.
+fn normalize(content: &str) -> String {
+    content.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Close enough to count as the same message: at most one in five characters changed, with some room
+/// for short ones.
+fn nearly_identical(a: &str, b: &str) -> bool {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    let allowed = (a_len.max(b_len) / 5).max(2);
+
+    // the length difference alone already rules out most unrelated messages
+    a_len.max(b_len) - a_len.min(b_len) <= allowed && utils::edit_distance(a, b) <= allowed
+}
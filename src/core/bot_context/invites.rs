@@ -0,0 +1,30 @@
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::error::EventHandlerError;
+
+/// How long to remember which server an invite leads to, spammers tend to post the same invite over and over.
+const INVITE_CACHE_SECONDS: u32 = 60 * 60;
+
+impl BotContext {
+    /// Looks up which server an invite leads to, None for invites that expired or don't lead to a server.
+    pub async fn get_invite_guild(&self, code: &str) -> Result<Option<GuildId>, EventHandlerError> {
+        let key = format!("invite_guild:{}", code);
+        if let Some(guild_id) = self.datastore.cache_pool.get::<Option<u64>>(&key).await? {
+            return Ok(guild_id.map(GuildId));
+        }
+
+        let guild_id = self
+            .http
+            .invite(code)
+            .await?
+            .and_then(|invite| invite.guild)
+            .map(|guild| guild.id);
+        self.datastore
+            .cache_pool
+            .set(&key, &guild_id.map(|id| id.0), Some(INVITE_CACHE_SECONDS))
+            .await?;
+
+        Ok(guild_id)
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use twilight_model::channel::Message;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::error::DatabaseError;
+use crate::{gearbot_info, gearbot_warn};
+
+/// Upper limit on messages held back per degraded shard, the oldest get dropped beyond this.
+const MAX_DEFERRED_MESSAGES: usize = 5_000;
+
+#[derive(Default)]
+pub struct ShardLatency {
+    pub latency: Option<Duration>,
+    pub degraded: bool,
+    deferred_messages: VecDeque<(Message, GuildId)>,
+}
+
+impl BotContext {
+    /// Refreshes the stored heartbeat latency of a shard and moves it in or out of degraded mode.
+    ///
+    /// When a shard recovers, the messages that were held back while it was degraded get stored.
+    pub async fn update_shard_latency(&self, shard_id: u64) {
+        let latency = match self.cluster.shard(shard_id).and_then(|shard| shard.info().ok()) {
+            Some(info) => info.latency().average(),
+            None => return,
+        };
+
+        if let Some(latency) = latency {
+            self.stats
+                .shard_latency
+                .with_label_values(&[&shard_id.to_string()])
+                .set(latency.as_millis() as i64);
+        }
+
+        let degraded = latency.map_or(false, |latency| latency > self.degraded_latency_threshold);
+
+        let deferred = {
+            let mut latencies = self.shard_latencies.write().await;
+            let state = latencies.entry(shard_id).or_insert_with(ShardLatency::default);
+            state.latency = latency;
+
+            if state.degraded == degraded {
+                return;
+            }
+            state.degraded = degraded;
+
+            if degraded {
+                self.stats.degraded_shards.inc();
+                gearbot_warn!(
+                    "Shard {} is degraded with a latency of {}ms, deferring message storage",
+                    shard_id,
+                    latency.unwrap_or_default().as_millis()
+                );
+                return;
+            }
+
+            self.stats.degraded_shards.dec();
+            std::mem::take(&mut state.deferred_messages)
+        };
+
+        gearbot_info!(
+            "Shard {} recovered, storing {} deferred messages",
+            shard_id,
+            deferred.len()
+        );
+        self.stats.deferred_messages.sub(deferred.len() as i64);
+        let deferred: Vec<_> = deferred.into_iter().collect();
+        match self.insert_messages(&deferred).await {
+            Ok(report) => {
+                for (message_id, e) in report.failed {
+                    self.stats.database.dropped_messages.inc();
+                    log::error!("Failed to store deferred message {}: {}", message_id, e);
+                }
+            }
+            Err(e) => {
+                self.stats.database.dropped_messages.inc_by(deferred.len() as i64);
+                log::error!("Failed to store {} deferred messages: {}", deferred.len(), e);
+            }
+        }
+    }
+
+    pub async fn is_shard_degraded(&self, shard_id: u64) -> bool {
+        self.shard_latencies
+            .read()
+            .await
+            .get(&shard_id)
+            .map_or(false, |state| state.degraded)
+    }
+
+    /// Stores a message, or holds it back until later if the shard it came from is degraded.
+    pub async fn store_message(
+        &self,
+        shard_id: u64,
+        message: &Message,
+        guild_id: GuildId,
+    ) -> Result<(), DatabaseError> {
+        {
+            let mut latencies = self.shard_latencies.write().await;
+            if let Some(state) = latencies.get_mut(&shard_id).filter(|state| state.degraded) {
+                if state.deferred_messages.len() >= MAX_DEFERRED_MESSAGES {
+                    state.deferred_messages.pop_front();
+                } else {
+                    self.stats.deferred_messages.inc();
+                }
+                state.deferred_messages.push_back((message.clone(), guild_id));
+                return Ok(());
+            }
+        }
+
+        self.queue_storage(message, guild_id).await
+    }
+
+    /// Heartbeat latency and degraded status of all shards of this cluster that reported in so far.
+    pub async fn get_shard_latencies(&self) -> Vec<(u64, Option<Duration>, bool)> {
+        let mut latencies: Vec<_> = self
+            .shard_latencies
+            .read()
+            .await
+            .iter()
+            .map(|(shard_id, state)| (*shard_id, state.latency, state.degraded))
+            .collect();
+        latencies.sort_by_key(|(shard_id, _, _)| *shard_id);
+        latencies
+    }
+}
@@ -1,6 +1,77 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::mpsc::Sender;
+
 use super::BotContext;
+use crate::core::logpump::{LogData, LogPriority};
+
+/// Percentage of the logpump capacity above which low priority logs are dropped.
+const LOW_PRIORITY_LIMIT: usize = 75;
+
+/// Percentage of the logpump capacity above which only high priority logs still get in.
+const NORMAL_PRIORITY_LIMIT: usize = 90;
+
+/// Bounded entry into the logpump, so a flood of logs can't pile up without limit while it's slow.
+pub struct LogQueue {
+    sender: Sender<LogData>,
+    queued: AtomicUsize,
+    capacity: usize,
+}
+
+impl LogQueue {
+    pub fn new(sender: Sender<LogData>, capacity: usize) -> Self {
+        LogQueue {
+            sender,
+            queued: AtomicUsize::new(0),
+            capacity,
+        }
+    }
+
+    /// How many logs of this priority are allowed to be waiting before it gets dropped instead.
+    fn limit(&self, priority: LogPriority) -> usize {
+        match priority {
+            LogPriority::Low => self.capacity * LOW_PRIORITY_LIMIT / 100,
+            LogPriority::Normal => self.capacity * NORMAL_PRIORITY_LIMIT / 100,
+            LogPriority::High => self.capacity,
+        }
+    }
+}
 
 impl BotContext {
+    /// Hands a log to the logpump, if it's too backed up for a log of this priority it's counted and dropped.
+    pub fn log(&self, data: LogData) {
+        let queue = &self.log_queue;
+        let priority = data.log_type.priority();
+        if queue.queued.fetch_add(1, Ordering::SeqCst) >= queue.limit(priority) {
+            queue.queued.fetch_sub(1, Ordering::SeqCst);
+            self.stats.logpump_stats.overflowed_logs.inc();
+            log::debug!(
+                "Logpump is backed up, dropping {:?} priority log for guild {}",
+                priority,
+                data.guild
+            );
+            return;
+        }
+
+        // a closed channel can only happen if the logpump itself died, nothing to deliver to anymore then
+        if queue.sender.clone().try_send(data).is_err() {
+            queue.queued.fetch_sub(1, Ordering::SeqCst);
+            self.stats.logpump_stats.overflowed_logs.inc();
+            return;
+        }
+        self.stats.logpump_stats.pending_logs.inc();
+    }
+
+    /// The logpump took a log out of the queue, making room for another one.
+    pub fn log_received(&self) {
+        self.log_queue.queued.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// How many logs are waiting on the logpump, and how many fit in the queue.
+    pub fn log_queue_depth(&self) -> (usize, usize) {
+        (self.log_queue.queued.load(Ordering::SeqCst), self.log_queue.capacity)
+    }
+
     // pub fn log(&self, guild_id: GuildId, log: LogType) -> Result<(), Error> {
     //     match self
     //         .log_pumps
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use super::BotContext;
+use crate::error::DatabaseError;
+
+/// Redis key holding the maintenance state, shared by all clusters.
+const MAINTENANCE_KEY: &str = "maintenance";
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct MaintenanceState {
+    /// If messages stop being stored and logged for the duration, to keep the database quiet.
+    pub pause_message_logs: bool,
+}
+
+impl BotContext {
+    pub async fn is_in_maintenance(&self) -> bool {
+        self.maintenance.read().await.is_some()
+    }
+
+    pub async fn message_logs_paused(&self) -> bool {
+        self.maintenance
+            .read()
+            .await
+            .as_ref()
+            .map_or(false, |state| state.pause_message_logs)
+    }
+
+    /// Enters (or with ``None`` leaves) maintenance mode on all clusters.
+    ///
+    /// The state stays in Redis until it is cleared, so restarts don't end maintenance early.
+    pub async fn set_maintenance(&self, state: Option<MaintenanceState>) -> Result<(), DatabaseError> {
+        match &state {
+            Some(state) => self.datastore.cache_pool.set(MAINTENANCE_KEY, state, None).await?,
+            None => self.datastore.cache_pool.delete(MAINTENANCE_KEY).await?,
+        }
+        self.apply_maintenance(state).await;
+
+        Ok(())
+    }
+
+    /// Picks up maintenance changes made by other clusters.
+    pub async fn sync_maintenance(&self) -> Result<(), DatabaseError> {
+        let state = self.datastore.cache_pool.get(MAINTENANCE_KEY).await?;
+        self.apply_maintenance(state).await;
+
+        Ok(())
+    }
+
+    async fn apply_maintenance(&self, state: Option<MaintenanceState>) {
+        {
+            let mut current = self.maintenance.write().await;
+            if *current == state {
+                return;
+            }
+            *current = state;
+        }
+
+        // the status shows if we are in maintenance, so it needs a refresh either way
+        for shard_id in self.scheme_info.cluster_id * self.scheme_info.shards_per_cluster
+            ..self.scheme_info.cluster_id * self.scheme_info.shards_per_cluster + self.scheme_info.shards_per_cluster
+        {
+            if self.cache.shard_cached(shard_id) {
+                if let Err(e) = self.show_current_status(shard_id).await {
+                    log::warn!("Failed to update the status: {}", e);
+                }
+            }
+        }
+    }
+}
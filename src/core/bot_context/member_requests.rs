@@ -0,0 +1,89 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+use twilight_model::gateway::payload::{MemberChunk, RequestGuildMembers};
+use twilight_model::guild::Member;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::error::MemberRequestError;
+
+/// Discord doesn't return more members than this for a query.
+const MAX_QUERY_LIMIT: u64 = 100;
+
+/// How long to wait for all chunks, a shard reconnecting in the meantime means they never arrive.
+const MEMBER_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A member request that is waiting for its chunks to come in.
+pub struct MemberRequest {
+    members: Vec<Member>,
+    received: u32,
+    sender: oneshot::Sender<Vec<Member>>,
+}
+
+impl BotContext {
+    /// Asks the gateway for the members of the guild whose name starts with the query, for when they
+    /// aren't cached. The members come in through the cache as usual, but are handed back here as well.
+    pub async fn request_members(
+        &self,
+        guild_id: GuildId,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<Member>, MemberRequestError> {
+        // the shard hands the nonce back on every chunk, so it has to be unique while the request is open
+        let nonce = format!("members:{}", self.member_request_count.fetch_add(1, Ordering::Relaxed));
+        let (sender, receiver) = oneshot::channel();
+        self.member_requests.lock().await.insert(
+            nonce.clone(),
+            MemberRequest {
+                members: vec![],
+                received: 0,
+                sender,
+            },
+        );
+
+        let shard_id = (guild_id.0 >> 22) % self.scheme_info.total_shards;
+        let data = RequestGuildMembers::builder(guild_id)
+            .nonce(nonce.clone())
+            .query(query, Some(limit.min(MAX_QUERY_LIMIT)));
+        if let Err(e) = self.cluster.command(shard_id, &data).await {
+            self.member_requests.lock().await.remove(&nonce);
+            return Err(e.into());
+        }
+
+        match tokio::time::timeout(MEMBER_REQUEST_TIMEOUT, receiver).await {
+            Ok(Ok(members)) => Ok(members),
+            _ => {
+                // nothing is waiting for the rest anymore
+                self.member_requests.lock().await.remove(&nonce);
+                Err(MemberRequestError::TimedOut)
+            }
+        }
+    }
+
+    /// Collects the members of a chunk for the request that asked for them, if any.
+    pub async fn receive_member_chunk(&self, chunk: &MemberChunk) {
+        let nonce = match &chunk.nonce {
+            Some(nonce) => nonce,
+            None => return,
+        };
+
+        let mut requests = self.member_requests.lock().await;
+        let request = match requests.get_mut(nonce) {
+            Some(request) => request,
+            // chunks requested for the cache itself
+            None => return,
+        };
+        request.members.extend(chunk.members.values().cloned());
+        request.received += 1;
+
+        // chunks don't have to arrive in order, so count them rather than waiting for the last index
+        if request.received >= chunk.chunk_count {
+            if let Some(request) = requests.remove(nonce) {
+                // the requester might have given up already
+                let _ = request.sender.send(request.members);
+            }
+        }
+    }
+}
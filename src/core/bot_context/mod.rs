@@ -3,35 +3,92 @@ use twilight_gateway::Cluster;
 use twilight_http::Client as HttpClient;
 use twilight_model::{
     channel::Message,
-    id::{GuildId, UserId},
+    id::{ChannelId, GuildId, MessageId, UserId},
     user::CurrentUser,
 };
 
+mod anti_raid;
+mod api_circuit;
+mod attachment_scanner;
+mod attachment_storage;
+mod auto_responses;
+mod bot_user;
 mod cold_resume;
+mod command_cleanup;
+mod command_cooldowns;
+mod command_slots;
 mod data_access;
+mod disabled_commands;
+mod emoji_usage;
+mod held_deletes;
+mod invites;
+mod latency;
 mod logpump;
+mod maintenance;
+mod member_requests;
+mod nickname;
 mod permissions;
+mod ratelimits;
+mod reaction_counts;
+mod recent_errors;
+mod role_menus;
+mod send_queue;
+mod shard_watchdog;
+mod spam;
+mod starboard;
 mod stats;
+mod storage_limits;
+mod storage_queue;
+mod timeouts;
+mod welcome_images;
 
 pub mod status;
 
-pub use stats::BotStats;
+pub use attachment_scanner::ScanVerdict;
+pub use bot_user::BotUser;
+pub use data_access::{ConfigInvalidation, CONFIG_INVALIDATION_CHANNEL};
+pub use held_deletes::DeletedContent;
+pub use maintenance::MaintenanceState;
+pub use recent_errors::ERROR_WINDOW;
+pub use spam::TrackedMessage;
+pub use stats::{BotStats, HandledEvent};
+
+use anti_raid::RaidState;
+use api_circuit::ApiCircuit;
+use attachment_scanner::AttachmentScanner;
+use attachment_storage::AttachmentDownloader;
+use held_deletes::HeldDelete;
+use latency::ShardLatency;
+use logpump::LogQueue;
+use member_requests::MemberRequest;
+use ratelimits::RatelimitTracker;
+use recent_errors::RecentErrors;
+use send_queue::SendQueue;
+use spam::SpamTracker;
+use storage_queue::StorageQueue;
 
 use crate::cache::Cache;
 use crate::core::logpump::LogData;
-use crate::core::GuildConfig;
+use crate::core::{BotConfig, GuildConfig, OwnMessageStorage, StatusRotation};
 use crate::database::api_structs::{RawTeamMembers, TeamInfo, TeamMember};
+use crate::database::structures::{AutoResponse, RoleMenu};
 use crate::database::DataStorage;
 use crate::translation::{GearBotString, Translations};
+use crate::utils;
 use crate::SchemeInfo;
 use fluent_bundle::FluentArgs;
-use std::collections::HashMap;
-use std::sync::atomic::AtomicU64;
+use image::RgbaImage;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize};
 use std::sync::Arc;
-use tokio::sync::mpsc::UnboundedSender;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock, Semaphore};
 use unic_langid::LanguageIdentifier;
 
+/// How long to wait before answering a bare mention in the same channel again.
+const MENTION_COOLDOWN: Duration = Duration::from_secs(30);
+
 #[derive(PartialEq, Debug)]
 pub enum ShardState {
     PendingCreation,
@@ -51,16 +108,54 @@ pub struct BotContext {
     pub stats: Arc<BotStats>,
     pub status_type: RwLock<u16>,
     pub status_text: RwLock<String>,
-    pub bot_user: CurrentUser,
+    pub bot_user: BotUser,
     configs: RwLock<HashMap<GuildId, Arc<GuildConfig>>>,
     pub datastore: DataStorage,
-    pub translations: Translations,
+    // std lock, translating happens outside of async code too
+    translations: std::sync::RwLock<Arc<Translations>>,
     pub scheme_info: SchemeInfo,
     pub shard_states: RwLock<HashMap<u64, ShardState>>,
     pub start_time: DateTime<Utc>,
     pub global_admins: Vec<UserId>,
     team_info: RawTeamMembers,
-    logpump_sender: UnboundedSender<LogData>,
+    log_queue: LogQueue,
+    mention_cooldowns: RwLock<HashMap<ChannelId, Instant>>,
+    storage_windows: Mutex<HashMap<GuildId, (Instant, u32)>>,
+    raid_states: RwLock<HashMap<GuildId, RaidState>>,
+    spam_tracker: Mutex<SpamTracker>,
+    maintenance: RwLock<Option<MaintenanceState>>,
+    globally_disabled: RwLock<HashSet<String>>,
+    shard_latencies: RwLock<HashMap<u64, ShardLatency>>,
+    degraded_latency_threshold: Duration,
+    last_shard_events: HashMap<u64, AtomicI64>,
+    silent_shard_threshold: Duration,
+    ratelimits: Mutex<RatelimitTracker>,
+    ratelimit_alert_threshold: usize,
+    api_circuit: ApiCircuit,
+    command_slots: Semaphore,
+    command_queue_timeout: Duration,
+    pub recovery_command_wait: Option<Duration>,
+    send_queue: Option<SendQueue>,
+    storage_queue: Option<StorageQueue>,
+    attachment_scanner: Option<AttachmentScanner>,
+    attachment_downloader: Option<AttachmentDownloader>,
+    pub own_messages: OwnMessageStorage,
+    pub max_transcript_messages: usize,
+    held_deletes: Mutex<Vec<HeldDelete>>,
+    command_delete_warned: Mutex<HashSet<GuildId>>,
+    command_cooldowns: Mutex<HashMap<(UserId, String), Instant>>,
+    emoji_usage_pending: RwLock<HashSet<GuildId>>,
+    reaction_counts_pending: RwLock<HashSet<GuildId>>,
+    starboard_locks: Mutex<HashMap<MessageId, Arc<Mutex<()>>>>,
+    auto_responses: RwLock<HashMap<GuildId, Arc<Vec<AutoResponse>>>>,
+    auto_response_cooldowns: RwLock<HashMap<i32, Instant>>,
+    role_menus: RwLock<HashMap<GuildId, Arc<Vec<RoleMenu>>>>,
+    status_rotation: StatusRotation,
+    status_index: AtomicUsize,
+    recent_errors: Mutex<RecentErrors>,
+    member_requests: Mutex<HashMap<String, MemberRequest>>,
+    member_request_count: AtomicU64,
+    welcome_backgrounds: RwLock<HashMap<GuildId, (String, Arc<RgbaImage>)>>,
 }
 
 impl BotContext {
@@ -69,16 +164,16 @@ impl BotContext {
         http_info: (HttpClient, CurrentUser),
         datastore: DataStorage,
         translations: Translations,
-        global_admins: Vec<u64>,
+        config: &BotConfig,
         stats: Arc<BotStats>,
-        logpump_sender: UnboundedSender<LogData>,
+        logpump_sender: Sender<LogData>,
     ) -> Self {
         let scheme_info = bot_core.2;
         let mut shard_states = HashMap::with_capacity(scheme_info.shards_per_cluster as usize);
-        for i in scheme_info.cluster_id * scheme_info.shards_per_cluster
-            ..scheme_info.cluster_id * scheme_info.shards_per_cluster + scheme_info.shards_per_cluster
-        {
+        let mut last_shard_events = HashMap::with_capacity(scheme_info.shards_per_cluster as usize);
+        for i in scheme_info.shard_range() {
             shard_states.insert(i, ShardState::PendingCreation);
+            last_shard_events.insert(i, AtomicI64::new(Utc::now().timestamp_millis()));
             bot_core
                 .0
                 .missing_per_shard
@@ -87,9 +182,15 @@ impl BotContext {
                 .insert(i, AtomicU64::new(0));
         }
 
-        let global_admins = global_admins.into_iter().map(UserId).collect();
+        let global_admins = config.global_admins.iter().copied().map(UserId).collect();
 
         stats.shard_counts.pending.set(scheme_info.shards_per_cluster as i64);
+        for shard_id in scheme_info.shard_range() {
+            stats
+                .shard_state
+                .with_label_values(&[&shard_id.to_string(), "pending"])
+                .set(1);
+        }
 
         let team_info: RawTeamMembers =
             toml::from_str(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/team.toml"))).unwrap();
@@ -101,16 +202,59 @@ impl BotContext {
             stats,
             status_type: RwLock::new(3),
             status_text: RwLock::new(String::from("the commands turn")),
-            bot_user: http_info.1,
+            bot_user: BotUser::new(http_info.1),
             configs: RwLock::new(HashMap::new()),
             datastore,
-            translations,
+            translations: std::sync::RwLock::new(Arc::new(translations)),
             scheme_info,
             shard_states: RwLock::new(shard_states),
             start_time: Utc::now(),
             global_admins,
             team_info,
-            logpump_sender,
+            log_queue: LogQueue::new(logpump_sender, config.logpump_capacity),
+            mention_cooldowns: RwLock::new(HashMap::new()),
+            storage_windows: Mutex::new(HashMap::new()),
+            raid_states: RwLock::new(HashMap::new()),
+            spam_tracker: Mutex::new(SpamTracker::default()),
+            maintenance: RwLock::new(None),
+            globally_disabled: RwLock::new(HashSet::new()),
+            shard_latencies: RwLock::new(HashMap::new()),
+            degraded_latency_threshold: Duration::from_millis(config.degraded_latency_threshold),
+            last_shard_events,
+            silent_shard_threshold: Duration::from_secs(config.silent_shard_threshold),
+            ratelimits: Mutex::new(RatelimitTracker::default()),
+            ratelimit_alert_threshold: config.ratelimit_alert_threshold,
+            api_circuit: ApiCircuit::default(),
+            command_slots: Semaphore::new(config.max_concurrent_commands),
+            command_queue_timeout: Duration::from_millis(config.command_queue_timeout),
+            recovery_command_wait: config.recovery_command_wait.map(Duration::from_millis),
+            send_queue: config.max_concurrent_sends.map(SendQueue::new),
+            storage_queue: config
+                .max_concurrent_storage_writes
+                .map(|max| StorageQueue::new(max, config.max_queued_storage_writes)),
+            attachment_scanner: config.attachment_scanning.as_ref().map(AttachmentScanner::new),
+            attachment_downloader: config
+                .database
+                .attachment_storage
+                .as_ref()
+                .map(AttachmentDownloader::new),
+            own_messages: config.own_messages,
+            max_transcript_messages: config.max_transcript_messages,
+            held_deletes: Mutex::new(vec![]),
+            command_delete_warned: Mutex::new(HashSet::new()),
+            command_cooldowns: Mutex::new(HashMap::new()),
+            emoji_usage_pending: RwLock::new(HashSet::new()),
+            reaction_counts_pending: RwLock::new(HashSet::new()),
+            starboard_locks: Mutex::new(HashMap::new()),
+            auto_responses: RwLock::new(HashMap::new()),
+            auto_response_cooldowns: RwLock::new(HashMap::new()),
+            role_menus: RwLock::new(HashMap::new()),
+            status_rotation: config.status.clone(),
+            status_index: AtomicUsize::new(0),
+            recent_errors: Mutex::new(RecentErrors::default()),
+            member_requests: Mutex::new(HashMap::new()),
+            member_request_count: AtomicU64::new(0),
+            welcome_backgrounds: RwLock::new(HashMap::new()),
         }
     }
 
@@ -119,8 +263,17 @@ impl BotContext {
         self.bot_user.id == other.author.id
     }
 
+    /// The translations currently in use, these can be swapped out while running.
+    pub fn get_translations(&self) -> Arc<Translations> {
+        Arc::clone(&self.translations.read().expect("The translations lock got poisoned!"))
+    }
+
+    pub fn set_translations(&self, translations: Translations) {
+        *self.translations.write().expect("The translations lock got poisoned!") = Arc::new(translations);
+    }
+
     pub fn translate(&self, language: &LanguageIdentifier, key: GearBotString) -> String {
-        self.translations.get_text_plain(language, key).to_string()
+        self.get_translations().get_text_plain(language, key).to_string()
     }
 
     pub fn translate_with_args(
@@ -129,11 +282,21 @@ impl BotContext {
         string_key: GearBotString,
         args: &FluentArgs<'_>,
     ) -> String {
-        self.translations
+        self.get_translations()
             .get_text_with_args(language, string_key, args)
             .replace("\\n", "\n")
     }
 
+    /// Formats a duration in the given language, see [utils::format_duration].
+    pub fn format_duration(
+        &self,
+        language: &LanguageIdentifier,
+        duration: chrono::Duration,
+        max_parts: usize,
+    ) -> String {
+        utils::format_duration(&self.get_translations(), language, duration, max_parts)
+    }
+
     pub async fn get_team_info(&self) -> TeamInfo {
         let mut members = vec![];
         for m in &self.team_info.members {
@@ -151,9 +314,17 @@ impl BotContext {
         TeamInfo { members }
     }
 
-    pub fn log(&self, data: LogData) {
-        // can only error if the other side is closed, and we never close the main receiver
-        let _ = self.logpump_sender.send(data);
-        self.stats.logpump_stats.pending_logs.inc();
+    /// Returns if we can answer a bare mention in this channel, starting a new cooldown if so.
+    pub async fn check_mention_cooldown(&self, channel_id: ChannelId) -> bool {
+        let now = Instant::now();
+        let mut cooldowns = self.mention_cooldowns.write().await;
+        cooldowns.retain(|_, last| now.duration_since(*last) < MENTION_COOLDOWN);
+
+        if cooldowns.contains_key(&channel_id) {
+            false
+        } else {
+            cooldowns.insert(channel_id, now);
+            true
+        }
     }
 }
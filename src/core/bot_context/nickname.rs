@@ -0,0 +1,40 @@
+use twilight_model::guild::Permissions;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+
+impl BotContext {
+    /// Changes our nickname in the guild to the given one (or removes it), unless it's already set.
+    ///
+    /// Guilds can take away our permission to change it, that only gets a warning as there is nothing we can do.
+    pub async fn apply_bot_nickname(
+        &self,
+        guild_id: GuildId,
+        nickname: Option<&str>,
+    ) -> Result<(), twilight_http::Error> {
+        let current = self
+            .cache
+            .get_member(&guild_id, &self.bot_user.id)
+            .and_then(|member| member.nickname.clone());
+        if current.as_deref() == nickname {
+            return Ok(());
+        }
+
+        if !self
+            .get_guild_permissions_for(&guild_id, &self.bot_user.id)
+            .contains(Permissions::CHANGE_NICKNAME)
+        {
+            log::warn!(
+                "Not allowed to change our nickname in {}, unable to apply the configured one",
+                guild_id
+            );
+            return Ok(());
+        }
+
+        // an empty nickname removes it
+        self.http
+            .update_current_user_nick(guild_id, nickname.unwrap_or_default())
+            .await?;
+        Ok(())
+    }
+}
@@ -1,14 +1,14 @@
 use std::sync::Arc;
 
+use twilight_model::channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType};
 use twilight_model::guild::Permissions;
-use twilight_model::id::{ChannelId, GuildId, UserId};
+use twilight_model::id::{ChannelId, GuildId, RoleId, UserId};
 
 use super::BotContext;
 use crate::cache::{CachedGuild, CachedMember};
-use crate::commands::meta::nodes::{CommandNode, GearBotPermissions};
+use crate::commands::meta::nodes::{CommandGroup, CommandNode, GearBotPermissions};
 use crate::commands::ROOT_NODE;
 use crate::core::guild_config::{GuildConfig, PermissionGroup};
-use twilight_model::channel::permission_overwrite::PermissionOverwriteType;
 
 impl BotContext {
     pub fn get_guild_permissions_for_member(
@@ -16,24 +16,16 @@ impl BotContext {
         member: &Arc<CachedMember>,
         guild: &Arc<CachedGuild>,
     ) -> Permissions {
-        //owners can do whatever they want
-        if guild.owner_id == member.user_id {
-            return Permissions::all();
-        }
-
-        let mut permissions = Permissions::empty();
+        // members don't list the everyone role (it has the id of the guild) but it does apply to them
+        let everyone = RoleId(guild.id.0);
+        let role_permissions = member
+            .roles
+            .iter()
+            .chain(std::iter::once(&everyone))
+            .filter_map(|role_id| guild.get_role(role_id))
+            .map(|role| role.permissions);
 
-        for role_id in &member.roles {
-            if let Some(role) = guild.get_role(role_id) {
-                permissions |= role.permissions;
-            }
-        }
-        if permissions.contains(Permissions::ADMINISTRATOR) {
-            //admins also can do whatever they want
-            Permissions::all()
-        } else {
-            permissions
-        }
+        guild_permissions(guild.owner_id == member.user_id, role_permissions)
     }
 
     pub fn get_guild_permissions_for(&self, guild_id: &GuildId, user_id: &UserId) -> Permissions {
@@ -57,53 +49,48 @@ impl BotContext {
                     | Permissions::READ_MESSAGE_HISTORY;
             }
 
-            let mut permissions = self.get_guild_permissions_for(&channel.get_guild_id().unwrap(), &user_id);
+            let guild_id = channel.get_guild_id().unwrap();
+            let permissions = self.get_guild_permissions_for(&guild_id, &user_id);
             //admins don't give a **** about overrides
             if permissions.contains(Permissions::ADMINISTRATOR) {
                 return Permissions::all();
             }
-            if let Some(member) = &self.cache.get_member(&channel.get_guild_id().unwrap(), &user_id) {
-                let overrides = channel.get_permission_overrides();
-                let mut everyone_allowed = Permissions::empty();
-                let mut everyone_denied = Permissions::empty();
-                let mut user_allowed = Permissions::empty();
-                let mut user_denied = Permissions::empty();
-                let mut role_allowed = Permissions::empty();
-                let mut role_denied = Permissions::empty();
-                for o in overrides {
-                    match o.kind {
-                        PermissionOverwriteType::Member(member_id) => {
-                            if member_id == user_id {
-                                user_allowed |= o.allow;
-                                user_denied |= o.deny;
-                            }
-                        }
-                        PermissionOverwriteType::Role(role_id) => {
-                            if role_id.0 == channel.get_guild_id().unwrap().0 {
-                                everyone_allowed |= o.allow;
-                                everyone_denied |= o.deny
-                            } else if member.roles.contains(&role_id) {
-                                role_allowed |= o.allow;
-                                role_denied |= o.deny;
-                            }
-                        }
-                    }
-                }
-
-                permissions &= !everyone_denied;
-                permissions |= everyone_allowed;
+            match self.cache.get_member(&guild_id, &user_id) {
+                Some(member) => apply_overwrites(
+                    permissions,
+                    channel.get_permission_overrides(),
+                    guild_id,
+                    user_id,
+                    &member.roles,
+                ),
+                None => permissions,
+            }
+        } else {
+            Permissions::empty()
+        }
+    }
 
-                permissions &= !role_denied;
-                permissions |= role_allowed;
+    /// Our own permissions in a channel, these are cached until something changes that could affect them.
+    pub fn get_bot_permissions_for_channel(&self, channel_id: ChannelId) -> Permissions {
+        let guild_id = match self
+            .cache
+            .get_channel(channel_id)
+            .and_then(|channel| channel.get_guild_id())
+        {
+            Some(guild_id) => guild_id,
+            None => return self.get_channel_permissions_for(self.bot_user.id, channel_id),
+        };
 
-                permissions &= !user_denied;
-                permissions |= user_allowed;
-            };
+        if let Some(permissions) = self.cache.get_bot_permissions(guild_id, channel_id) {
+            return permissions;
+        }
 
-            permissions
-        } else {
-            Permissions::empty()
+        let permissions = self.get_channel_permissions_for(self.bot_user.id, channel_id);
+        // without our member we can't know our roles yet, don't hold on to that
+        if self.cache.get_member(&guild_id, &self.bot_user.id).is_some() {
+            self.cache.insert_bot_permissions(guild_id, channel_id, permissions);
         }
+        permissions
     }
 
     pub fn get_permissions_for(
@@ -119,21 +106,7 @@ impl BotContext {
 
         //these are already sorted by priority upon loading
         for group in &config.permission_groups {
-            if let Some(perms) = group.discord_perms {
-                if discord_permissions.contains(perms) {
-                    apply(&mut permissions, &mut not_negated_denies, &group)
-                }
-            }
-
-            if group.needs_all {
-                if group.roles.iter().all(|role_id| member.roles.contains(role_id)) {
-                    apply(&mut permissions, &mut not_negated_denies, &group);
-                }
-            } else if group.roles.iter().any(|role_id| member.roles.contains(role_id)) {
-                apply(&mut permissions, &mut not_negated_denies, &group);
-            }
-
-            if group.users.iter().any(|user_id| member.user_id == *user_id) {
+            if is_in_group(group, member, discord_permissions) {
                 apply(&mut permissions, &mut not_negated_denies, &group);
             }
         }
@@ -144,6 +117,54 @@ impl BotContext {
         permissions
     }
 
+    /// If the member can use the command according to the permission group the guild requires for it.
+    ///
+    /// ``None`` when the command has no (valid) override, or is a bot admin command, those always go by
+    /// the permissions they normally require.
+    pub fn command_override_allows(
+        &self,
+        guild: &Arc<CachedGuild>,
+        member: &Arc<CachedMember>,
+        config: &Arc<GuildConfig>,
+        command: &CommandNode,
+    ) -> Option<bool> {
+        if command.group == CommandGroup::BotAdmin {
+            return None;
+        }
+
+        let group_name = config.command_overrides.get(&command.name)?;
+        let required = config
+            .permission_groups
+            .iter()
+            .find(|group| &group.name == group_name)?
+            .priority;
+
+        let discord_permissions = self.get_guild_permissions_for_member(member, guild);
+        Some(
+            config
+                .permission_groups
+                .iter()
+                .any(|group| group.priority >= required && is_in_group(group, member, discord_permissions)),
+        )
+    }
+
+    /// The priority of the highest permission group the member is in.
+    pub fn get_permission_level(
+        &self,
+        guild: &Arc<CachedGuild>,
+        member: &Arc<CachedMember>,
+        config: &Arc<GuildConfig>,
+    ) -> u8 {
+        let discord_permissions = self.get_guild_permissions_for_member(member, guild);
+        config
+            .permission_groups
+            .iter()
+            .filter(|group| is_in_group(group, member, discord_permissions))
+            .map(|group| group.priority)
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn apply_admin_perms(&self, user_id: &UserId, permissions: &mut GearBotPermissions) {
         if self.global_admins.contains(user_id) {
             permissions.insert(GearBotPermissions::BOT_ADMIN);
@@ -155,6 +176,99 @@ impl BotContext {
     }
 }
 
+/// The guild wide permissions of a member, out of the permissions of all their roles (including the everyone role).
+fn guild_permissions(is_owner: bool, role_permissions: impl Iterator<Item = Permissions>) -> Permissions {
+    //owners can do whatever they want
+    if is_owner {
+        return Permissions::all();
+    }
+
+    let permissions = role_permissions.fold(Permissions::empty(), |permissions, role| permissions | role);
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        //admins also can do whatever they want
+        Permissions::all()
+    } else {
+        permissions
+    }
+}
+
+/// Applies the overwrites of a channel on top of the guild wide permissions, the same way discord does.
+///
+/// The everyone overwrite goes first, then those of all roles together and the one of the member last. Permissions
+/// that build on others are taken away along with them: nothing is possible in a channel that can't be seen, and
+/// without sending messages there is no attaching files or embedding links either.
+fn apply_overwrites(
+    mut permissions: Permissions,
+    overwrites: &[PermissionOverwrite],
+    guild_id: GuildId,
+    user_id: UserId,
+    roles: &[RoleId],
+) -> Permissions {
+    let mut everyone_allowed = Permissions::empty();
+    let mut everyone_denied = Permissions::empty();
+    let mut user_allowed = Permissions::empty();
+    let mut user_denied = Permissions::empty();
+    let mut role_allowed = Permissions::empty();
+    let mut role_denied = Permissions::empty();
+    for o in overwrites {
+        match o.kind {
+            PermissionOverwriteType::Member(member_id) => {
+                if member_id == user_id {
+                    user_allowed |= o.allow;
+                    user_denied |= o.deny;
+                }
+            }
+            PermissionOverwriteType::Role(role_id) => {
+                // the everyone role shares its id with the guild
+                if role_id.0 == guild_id.0 {
+                    everyone_allowed |= o.allow;
+                    everyone_denied |= o.deny
+                } else if roles.contains(&role_id) {
+                    role_allowed |= o.allow;
+                    role_denied |= o.deny;
+                }
+            }
+        }
+    }
+
+    permissions &= !everyone_denied;
+    permissions |= everyone_allowed;
+
+    permissions &= !role_denied;
+    permissions |= role_allowed;
+
+    permissions &= !user_denied;
+    permissions |= user_allowed;
+
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+    if !permissions.contains(Permissions::SEND_MESSAGES) {
+        permissions.remove(
+            Permissions::SEND_TTS_MESSAGES
+                | Permissions::MENTION_EVERYONE
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES,
+        );
+    }
+
+    permissions
+}
+
+fn is_in_group(group: &PermissionGroup, member: &CachedMember, discord_permissions: Permissions) -> bool {
+    let by_discord_perms = group
+        .discord_perms
+        .map_or(false, |perms| discord_permissions.contains(perms));
+
+    let by_roles = if group.needs_all {
+        group.roles.iter().all(|role_id| member.roles.contains(role_id))
+    } else {
+        group.roles.iter().any(|role_id| member.roles.contains(role_id))
+    };
+
+    by_discord_perms || by_roles || group.users.contains(&member.user_id)
+}
+
 fn apply(permissions: &mut GearBotPermissions, not_negated_denies: &mut GearBotPermissions, group: &PermissionGroup) {
     permissions.remove(group.denied_perms);
     permissions.insert(group.granted_perms);
@@ -212,3 +326,145 @@ fn cascade_node(
         permissions.insert(node.command_permission)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_overwrites, guild_permissions};
+    use twilight_model::channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType};
+    use twilight_model::guild::Permissions;
+    use twilight_model::id::{GuildId, RoleId, UserId};
+
+    const GUILD: GuildId = GuildId(1);
+    const USER: UserId = UserId(2);
+    const MEMBER_ROLE: RoleId = RoleId(3);
+    const OTHER_ROLE: RoleId = RoleId(4);
+
+    fn base() -> Permissions {
+        Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::EMBED_LINKS
+            | Permissions::ATTACH_FILES
+            | Permissions::READ_MESSAGE_HISTORY
+    }
+
+    fn overwrite(kind: PermissionOverwriteType, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    fn compute(overwrites: &[PermissionOverwrite]) -> Permissions {
+        apply_overwrites(base(), overwrites, GUILD, USER, &[MEMBER_ROLE])
+    }
+
+    #[test]
+    fn no_overwrites_keeps_guild_permissions() {
+        assert_eq!(compute(&[]), base());
+    }
+
+    #[test]
+    fn everyone_deny_is_undone_by_role_allow() {
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(GUILD.0)),
+                Permissions::empty(),
+                Permissions::EMBED_LINKS,
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(MEMBER_ROLE),
+                Permissions::EMBED_LINKS,
+                Permissions::empty(),
+            ),
+        ];
+        assert!(compute(&overwrites).contains(Permissions::EMBED_LINKS));
+    }
+
+    #[test]
+    fn role_allow_wins_over_role_deny() {
+        // the allows and denies of all roles are combined, an allow on any of them wins
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Role(MEMBER_ROLE),
+                Permissions::MANAGE_MESSAGES,
+                Permissions::empty(),
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(MEMBER_ROLE),
+                Permissions::empty(),
+                Permissions::MANAGE_MESSAGES,
+            ),
+        ];
+        assert!(compute(&overwrites).contains(Permissions::MANAGE_MESSAGES));
+    }
+
+    #[test]
+    fn member_overwrite_goes_last() {
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Member(USER),
+                Permissions::empty(),
+                Permissions::ATTACH_FILES,
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(MEMBER_ROLE),
+                Permissions::ATTACH_FILES,
+                Permissions::empty(),
+            ),
+        ];
+        assert!(!compute(&overwrites).contains(Permissions::ATTACH_FILES));
+    }
+
+    #[test]
+    fn overwrites_for_others_are_ignored() {
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Role(OTHER_ROLE),
+                Permissions::empty(),
+                Permissions::EMBED_LINKS,
+            ),
+            overwrite(
+                PermissionOverwriteType::Member(UserId(5)),
+                Permissions::empty(),
+                Permissions::ATTACH_FILES,
+            ),
+        ];
+        assert_eq!(compute(&overwrites), base());
+    }
+
+    #[test]
+    fn hidden_channel_allows_nothing() {
+        let overwrites = [overwrite(
+            PermissionOverwriteType::Role(RoleId(GUILD.0)),
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        assert_eq!(compute(&overwrites), Permissions::empty());
+    }
+
+    #[test]
+    fn no_sending_means_no_files_or_embeds() {
+        let overwrites = [overwrite(
+            PermissionOverwriteType::Member(USER),
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        )];
+        assert_eq!(
+            compute(&overwrites),
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY
+        );
+    }
+
+    #[test]
+    fn guild_permissions_combine_all_roles() {
+        let roles = vec![Permissions::VIEW_CHANNEL, Permissions::KICK_MEMBERS];
+        assert_eq!(
+            guild_permissions(false, roles.into_iter()),
+            Permissions::VIEW_CHANNEL | Permissions::KICK_MEMBERS
+        );
+    }
+
+    #[test]
+    fn administrator_and_owner_can_do_everything() {
+        let roles = vec![Permissions::ADMINISTRATOR];
+        assert_eq!(guild_permissions(false, roles.into_iter()), Permissions::all());
+        assert_eq!(guild_permissions(true, std::iter::empty()), Permissions::all());
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use hyper::StatusCode;
+use twilight_http::api_error::ApiError;
+use twilight_http::Error;
+
+use super::BotContext;
+use crate::gearbot_warn;
+
+/// The window ratelimited requests are counted in to decide if we need to raise the alarm.
+const RATELIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+pub struct RatelimitTracker {
+    hits: VecDeque<Instant>,
+    alerting: bool,
+}
+
+impl BotContext {
+    /// Counts requests discord refused with a 429, warning once too many of them pile up within a minute.
+    ///
+    /// Errors that aren't about ratelimits are ignored, so any failed request can be passed in.
+    pub async fn track_ratelimit(&self, error: &Error) {
+        let global = match error {
+            Error::Response { status, error, .. } if *status == StatusCode::TOO_MANY_REQUESTS => {
+                matches!(error, ApiError::Ratelimited(ratelimited) if ratelimited.global)
+            }
+            _ => return,
+        };
+
+        if global {
+            self.stats.ratelimits.global.inc();
+        } else {
+            self.stats.ratelimits.bucket.inc();
+        }
+
+        let now = Instant::now();
+        let mut tracker = self.ratelimits.lock().await;
+        while tracker
+            .hits
+            .front()
+            .map_or(false, |hit| now.duration_since(*hit) > RATELIMIT_WINDOW)
+        {
+            tracker.hits.pop_front();
+        }
+        tracker.hits.push_back(now);
+
+        let count = tracker.hits.len();
+        if count >= self.ratelimit_alert_threshold && !tracker.alerting {
+            tracker.alerting = true;
+            gearbot_warn!(
+                "{} requests got ratelimited in the last minute (latest was {}), something might be spamming the api",
+                count,
+                if global { "global" } else { "a bucket" }
+            );
+        } else if count < self.ratelimit_alert_threshold {
+            // calmed down again, warn again the next time it gets out of hand
+            tracker.alerting = false;
+        }
+    }
+}
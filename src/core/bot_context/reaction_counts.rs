@@ -0,0 +1,76 @@
+use twilight_model::channel::ReactionType;
+use twilight_model::id::{GuildId, MessageId};
+
+use super::BotContext;
+use crate::error::DatabaseError;
+
+impl BotContext {
+    /// Adds to (or takes from) the count of a reaction on a message.
+    ///
+    /// Changes are collected in Redis so a burst of reactions doesn't turn into a burst of writes.
+    pub async fn record_reaction(
+        &self,
+        guild_id: GuildId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+        change: i64,
+    ) -> Result<(), DatabaseError> {
+        let field = format!("{}:{}", message_id, reaction_key(emoji));
+        self.datastore
+            .cache_pool
+            .increment_hash_field(&reaction_counts_key(guild_id), &field, change)
+            .await?;
+
+        self.reaction_counts_pending.write().await.insert(guild_id);
+
+        Ok(())
+    }
+
+    /// Moves the reaction counters collected in Redis into the database.
+    pub async fn flush_reaction_counts(&self) -> Result<(), DatabaseError> {
+        let pending: Vec<GuildId> = self.reaction_counts_pending.write().await.drain().collect();
+
+        for guild_id in pending {
+            self.flush_guild_reaction_counts(guild_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the counts of a message that had all its reactions removed.
+    pub async fn clear_reactions(&self, guild_id: GuildId, message_id: MessageId) -> Result<(), DatabaseError> {
+        // anything still pending would otherwise bring back counts for reactions that are gone
+        self.flush_guild_reaction_counts(guild_id).await?;
+        self.datastore.clear_reaction_counts(message_id).await
+    }
+
+    async fn flush_guild_reaction_counts(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        let counts: Vec<(u64, String, i64)> = self
+            .datastore
+            .cache_pool
+            .take_hash_counters(&reaction_counts_key(guild_id))
+            .await?
+            .into_iter()
+            .filter(|(_, change)| *change != 0)
+            .filter_map(|(field, change)| {
+                let mut parts = field.splitn(2, ':');
+                let message_id = parts.next()?.parse().ok()?;
+                Some((message_id, parts.next()?.to_string(), change))
+            })
+            .collect();
+
+        self.datastore.add_reaction_counts(guild_id, &counts).await
+    }
+}
+
+/// Custom emoji are stored by id so renaming them doesn't split their counts.
+fn reaction_key(emoji: &ReactionType) -> String {
+    match emoji {
+        ReactionType::Custom { id, .. } => id.to_string(),
+        ReactionType::Unicode { name } => name.clone(),
+    }
+}
+
+fn reaction_counts_key(guild_id: GuildId) -> String {
+    format!("reaction_counts:{}", guild_id)
+}
@@ -0,0 +1,47 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::BotContext;
+
+/// How far back errors are counted for the health report.
+pub const ERROR_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// When unexpected errors happened within the window, split by where they came from.
+#[derive(Default)]
+pub struct RecentErrors {
+    commands: VecDeque<Instant>,
+    events: VecDeque<Instant>,
+}
+
+impl RecentErrors {
+    fn prune(&mut self, now: Instant) {
+        for errors in [&mut self.commands, &mut self.events].iter_mut() {
+            while errors.front().map_or(false, |at| now - *at > ERROR_WINDOW) {
+                errors.pop_front();
+            }
+        }
+    }
+}
+
+impl BotContext {
+    pub async fn record_command_error(&self) {
+        let now = Instant::now();
+        let mut recent = self.recent_errors.lock().await;
+        recent.prune(now);
+        recent.commands.push_back(now);
+    }
+
+    pub async fn record_event_error(&self) {
+        let now = Instant::now();
+        let mut recent = self.recent_errors.lock().await;
+        recent.prune(now);
+        recent.events.push_back(now);
+    }
+
+    /// How many commands and event handlers failed unexpectedly within the error window.
+    pub async fn recent_error_counts(&self) -> (usize, usize) {
+        let mut recent = self.recent_errors.lock().await;
+        recent.prune(Instant::now());
+        (recent.commands.len(), recent.events.len())
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use hyper::StatusCode;
+use twilight_http::Error;
+use twilight_model::channel::embed::Embed;
+use twilight_model::id::{GuildId, RoleId};
+
+use super::BotContext;
+use crate::core::GuildConfig;
+use crate::database::structures::{RoleMenu, RoleMenuOption};
+use crate::error::{DatabaseError, EventHandlerError, MessageError};
+use crate::translation::GearBotString;
+
+impl BotContext {
+    /// The role menus of a guild, they are only loaded from the database on first use.
+    pub async fn get_role_menus(&self, guild_id: GuildId) -> Result<Arc<Vec<RoleMenu>>, DatabaseError> {
+        // Clone the option so we can release the lock much faster
+        let menus = self.role_menus.read().await.get(&guild_id).cloned();
+        if let Some(menus) = menus {
+            return Ok(menus);
+        }
+
+        let menus = Arc::new(self.datastore.get_role_menus(guild_id).await?);
+        self.role_menus.write().await.insert(guild_id, Arc::clone(&menus));
+
+        Ok(menus)
+    }
+
+    /// Drops the cached role menus of a guild after they changed.
+    pub async fn forget_role_menus(&self, guild_id: GuildId) {
+        self.role_menus.write().await.remove(&guild_id);
+    }
+
+    /// The embed a role menu is shown with, listing all of its options.
+    pub fn role_menu_embed(&self, config: &GuildConfig, options: &[RoleMenuOption]) -> Result<Embed, MessageError> {
+        let mut description = self.translate(&config.language, GearBotString::RoleMenuTitle);
+        for option in options {
+            description += &format!("\n{} {}", option.emoji, option.label);
+        }
+
+        Ok(config.embed_builder()?.description(description)?.build()?)
+    }
+
+    /// Takes a deleted role out of all role menus of the guild, menus left without any options are removed.
+    ///
+    /// Unless the guild wants them left alone, the menu messages are updated to match. Returns how many menus
+    /// had the role in them.
+    pub async fn remove_role_from_menus(&self, guild_id: GuildId, role_id: RoleId) -> Result<usize, EventHandlerError> {
+        let config = self.get_config(guild_id).await?;
+        let menus = self.get_role_menus(guild_id).await?;
+        let mut cleaned = 0;
+        for menu in menus.iter() {
+            let (removed, options): (Vec<_>, Vec<_>) =
+                menu.options.iter().partition(|option| option.role_id == role_id);
+            if removed.is_empty() {
+                continue;
+            }
+            cleaned += 1;
+
+            if options.is_empty() {
+                self.datastore.remove_role_menu(guild_id, menu.message_id).await?;
+                if !config.keep_role_menu_messages {
+                    // it might already be gone, the menu is removed either way
+                    let _ = self.http.delete_message(menu.channel_id, menu.message_id).await;
+                }
+                continue;
+            }
+
+            let options: Vec<RoleMenuOption> = options.into_iter().cloned().collect();
+            self.datastore
+                .update_role_menu_options(guild_id, menu.message_id, &options)
+                .await?;
+            if config.keep_role_menu_messages {
+                continue;
+            }
+
+            let embed = self.role_menu_embed(&config, &options)?;
+            let result = self
+                .http
+                .update_message(menu.channel_id, menu.message_id)
+                .embed(embed)?
+                .await;
+            match result {
+                Ok(_) => {}
+                // the message being gone will also take the menu with it
+                Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => continue,
+                Err(e) => return Err(e.into()),
+            }
+            for option in removed {
+                let _ = self
+                    .http
+                    .delete_all_reaction(menu.channel_id, menu.message_id, option.to_reaction())
+                    .await;
+            }
+        }
+
+        if cleaned > 0 {
+            self.forget_role_menus(guild_id).await;
+        }
+        Ok(cleaned)
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, Semaphore};
+use twilight_http::Error;
+use twilight_model::id::ChannelId;
+
+use super::BotContext;
+use crate::error::ApiRequestError;
+
+/// Smooths out bursts of messages we send on our own, so they don't all hit the ratelimits at once.
+pub struct SendQueue {
+    slots: Semaphore,
+    channels: Mutex<HashMap<ChannelId, Arc<Mutex<()>>>>,
+}
+
+impl SendQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        SendQueue {
+            slots: Semaphore::new(max_concurrent),
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl BotContext {
+    /// Sends through the queue when it's enabled: only a limited amount of sends run at the same time,
+    /// and sends to the same channel go out in the order they were queued in.
+    ///
+    /// These are non-critical requests, skipped while the discord api is failing. Anything that needs
+    /// to go out right away (like command confirmations) should just await the request instead.
+    pub async fn queue_send<T, F>(&self, channel_id: ChannelId, request: F) -> Result<T, ApiRequestError>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let queue = match &self.send_queue {
+            Some(queue) => queue,
+            None => return self.non_critical_request(request).await,
+        };
+
+        self.stats.queued_sends.inc();
+        let channel_lock = queue
+            .channels
+            .lock()
+            .await
+            .entry(channel_id)
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+
+        let result = {
+            // tokio locks are fair, so waiting on the channel first keeps the order intact
+            let _turn = channel_lock.lock().await;
+            let _slot = queue.slots.acquire().await;
+            self.stats.queued_sends.dec();
+            self.non_critical_request(request).await
+        };
+
+        let mut channels = queue.channels.lock().await;
+        // only the map and us hold it, nothing else is waiting for this channel
+        if Arc::strong_count(&channel_lock) == 2 {
+            channels.remove(&channel_id);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use super::{BotContext, ShardState};
+use crate::{gearbot_error, gearbot_warn};
+
+impl BotContext {
+    /// Marks a shard as alive, every event it receives counts, including heartbeat acks.
+    pub fn shard_event_received(&self, shard_id: u64) {
+        if let Some(last_event) = self.last_shard_events.get(&shard_id) {
+            last_event.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+        }
+    }
+
+    /// Ready shards that haven't received a single event for longer than the configured threshold.
+    ///
+    /// Shards in any other state are already (re)connecting, the gateway handles those on its own.
+    pub async fn find_silent_shards(&self) -> Vec<(u64, Duration)> {
+        let now = Utc::now().timestamp_millis();
+        let states = self.shard_states.read().await;
+        let mut silent: Vec<_> = states
+            .iter()
+            .filter(|(_, state)| **state == ShardState::Ready)
+            .filter_map(|(shard_id, _)| {
+                let last_event = self.last_shard_events.get(shard_id)?.load(Ordering::Relaxed);
+                let silent_for = Duration::from_millis(now.saturating_sub(last_event).max(0) as u64);
+                if silent_for > self.silent_shard_threshold {
+                    Some((*shard_id, silent_for))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        silent.sort_by_key(|(shard_id, _)| *shard_id);
+        silent
+    }
+
+    /// Forces a shard that went silent to reconnect from scratch.
+    pub async fn restart_shard(&self, shard_id: u64, silent_for: Duration) {
+        let mut shard = match self.cluster.shard(shard_id) {
+            Some(shard) => shard,
+            None => return,
+        };
+
+        gearbot_warn!(
+            "Shard {} hasn't received any events for {} seconds, restarting it",
+            shard_id,
+            silent_for.as_secs()
+        );
+        self.stats.silent_shard_restarts.inc();
+        // it's no longer ready, keep the watchdog off it until it reports back in
+        self.shard_state_change(shard_id, ShardState::Reconnecting).await;
+        self.shard_event_received(shard_id);
+
+        shard.shutdown();
+        if let Err(e) = shard.start().await {
+            gearbot_error!("Failed to restart silent shard {}: {}", shard_id, e);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use twilight_model::id::{ChannelId, GuildId, MessageId, UserId};
+
+use super::BotContext;
+
+/// The most recent messages remembered per user, thresholds above this can never be reached.
+const MAX_TRACKED_MESSAGES: usize = 50;
+
+/// Messages older than this are forgotten, spam windows can't be any longer.
+const TRACKING_EXPIRY: Duration = Duration::from_secs(10 * 60);
+
+/// How often users that went quiet are cleaned out.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fingerprints of the recent messages of users, to recognize spam by.
+pub struct SpamTracker {
+    users: HashMap<(GuildId, UserId), VecDeque<TrackedMessage>>,
+    last_sweep: Instant,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TrackedMessage {
+    pub sent: Instant,
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    /// Identifies messages with the same content.
+    pub fingerprint: u64,
+    /// Identifies messages with nearly the same content, `None` for messages too short to tell them apart like that.
+    pub similarity: Option<u64>,
+}
+
+impl Default for SpamTracker {
+    fn default() -> Self {
+        SpamTracker {
+            users: HashMap::new(),
+            last_sweep: Instant::now(),
+        }
+    }
+}
+
+impl BotContext {
+    /// Remembers the fingerprint of a new message, returning the ones the user sent recently (this one included).
+    pub async fn track_message(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        message: TrackedMessage,
+    ) -> Vec<TrackedMessage> {
+        let now = Instant::now();
+        let mut tracker = self.spam_tracker.lock().await;
+
+        if now.duration_since(tracker.last_sweep) > SWEEP_INTERVAL {
+            tracker.users.retain(|_, recent| {
+                recent
+                    .back()
+                    .map_or(false, |tracked| now.duration_since(tracked.sent) <= TRACKING_EXPIRY)
+            });
+            tracker.last_sweep = now;
+        }
+
+        let recent = tracker.users.entry((guild_id, user_id)).or_insert_with(VecDeque::new);
+        while recent
+            .front()
+            .map_or(false, |tracked| now.duration_since(tracked.sent) > TRACKING_EXPIRY)
+            || recent.len() >= MAX_TRACKED_MESSAGES
+        {
+            recent.pop_front();
+        }
+        recent.push_back(message);
+
+        recent.iter().copied().collect()
+    }
+
+    /// Forgets the recent messages of a user, so the ones that already got acted on don't count again.
+    pub async fn forget_tracked_messages(&self, guild_id: GuildId, user_id: UserId) {
+        self.spam_tracker.lock().await.users.remove(&(guild_id, user_id));
+    }
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use hyper::StatusCode;
+use tokio::sync::Mutex;
+use twilight_embed_builder::{EmbedAuthorBuilder, ImageSource};
+use twilight_http::Error;
+use twilight_model::channel::embed::Embed;
+use twilight_model::channel::{Message, ReactionType};
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use super::BotContext;
+use crate::cache::CachedUser;
+use crate::core::GuildConfig;
+use crate::error::{EventHandlerError, MessageError};
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::text;
+
+/// Embed descriptions can't be longer than this.
+const MAX_DESCRIPTION_LENGTH: usize = 2048;
+
+impl BotContext {
+    /// Brings the starboard post of a message in line with its current stars and content,
+    /// posting it when it first reaches the threshold of the guild.
+    pub async fn update_starboard(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<(), EventHandlerError> {
+        let config = self.get_config(guild_id).await?;
+        let starboard_channel = match config.starboard.channel {
+            // starring the starboard itself would only repost the same post again
+            Some(channel) if channel != channel_id => channel,
+            _ => return Ok(()),
+        };
+
+        // reactions tend to arrive in bursts, only one of them gets to create the post
+        let message_lock = self
+            .starboard_locks
+            .lock()
+            .await
+            .entry(message_id)
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+
+        let result = {
+            let _guard = message_lock.lock().await;
+            self.update_starboard_post(&config, guild_id, channel_id, starboard_channel, message_id)
+                .await
+        };
+
+        let mut locks = self.starboard_locks.lock().await;
+        // only the map and us hold it, no other reactions are waiting on this message
+        if Arc::strong_count(&message_lock) == 2 {
+            locks.remove(&message_id);
+        }
+
+        result
+    }
+
+    async fn update_starboard_post(
+        &self,
+        config: &GuildConfig,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        starboard_channel: ChannelId,
+        message_id: MessageId,
+    ) -> Result<(), EventHandlerError> {
+        let message = match self.http.message(channel_id, message_id).await? {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+
+        let star = message
+            .reactions
+            .iter()
+            .find(|reaction| config.starboard.is_star(&reaction.emoji));
+        let stars = star.map(|reaction| reaction.count).unwrap_or(0);
+        let emoji = match star {
+            Some(reaction) => emoji_for_chat(&reaction.emoji),
+            None => config.starboard.emoji.clone(),
+        };
+
+        let content = self.translate_with_args(
+            &config.language,
+            GearBotString::StarboardPost,
+            &FluArgs::with_capacity(3)
+                .add("emoji", emoji)
+                .add("count", stars)
+                .add("channel_id", channel_id.to_string())
+                .generate(),
+        );
+        let embed = self.starboard_embed(config, guild_id, &message)?;
+
+        match self.datastore.get_starboard_entry(message_id).await? {
+            Some(entry) => {
+                let result = self
+                    .http
+                    .update_message(
+                        ChannelId(entry.starboard_channel_id as u64),
+                        MessageId(entry.starboard_message_id as u64),
+                    )
+                    .content(content)?
+                    .embed(embed)?
+                    .await;
+
+                match result {
+                    Ok(_) => {}
+                    // the post was removed by hand, it can be posted again once it gets another star
+                    Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => {
+                        self.datastore.remove_starboard_entry(message_id).await?;
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            None if stars >= config.starboard.threshold => {
+                let request = self
+                    .http
+                    .create_message(starboard_channel)
+                    .content(content)?
+                    .embed(embed)?;
+                let post = self.queue_send(starboard_channel, request).await?;
+
+                self.datastore
+                    .insert_starboard_entry(guild_id, message_id, starboard_channel, post.id)
+                    .await?;
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Takes the starboard post of a message that no longer exists down with it.
+    pub async fn remove_from_starboard(&self, message_id: MessageId) -> Result<(), EventHandlerError> {
+        let entry = match self.datastore.remove_starboard_entry(message_id).await? {
+            Some(entry) => entry,
+            None => return Ok(()),
+        };
+
+        let result = self
+            .http
+            .delete_message(
+                ChannelId(entry.starboard_channel_id as u64),
+                MessageId(entry.starboard_message_id as u64),
+            )
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn starboard_embed(
+        &self,
+        config: &GuildConfig,
+        guild_id: GuildId,
+        message: &Message,
+    ) -> Result<Embed, MessageError> {
+        let link = format!(
+            "https://discord.com/channels/{}/{}/{}",
+            guild_id, message.channel_id, message.id
+        );
+        let jump = self.translate_with_args(
+            &config.language,
+            GearBotString::StarboardJumpLink,
+            &FluArgs::with_capacity(1).add("link", link).generate(),
+        );
+
+        // the jump link always has to fit, the content gets cut off instead
+        let room = MAX_DESCRIPTION_LENGTH.saturating_sub(jump.chars().count() + 2);
+        let mut description = text::truncate(&message.content, room).to_string();
+        if !description.is_empty() {
+            description += "\n\n";
+        }
+        description += &jump;
+
+        let author = CachedUser::from_user(&message.author);
+        let mut builder = config.embed_builder()?.description(description)?.author(
+            EmbedAuthorBuilder::new()
+                .name(author.full_name())?
+                .icon_url(ImageSource::url(author.avatar_url())?),
+        );
+
+        if let Some(image) = message.attachments.iter().find(|attachment| attachment.width.is_some()) {
+            builder = builder.image(ImageSource::url(&image.url)?);
+        }
+
+        Ok(builder.timestamp(message.timestamp.clone()).build()?)
+    }
+}
+
+fn emoji_for_chat(emoji: &ReactionType) -> String {
+    match emoji {
+        ReactionType::Custom { animated, id, name } => format!(
+            "<{}:{}:{}>",
+            if *animated { "a" } else { "" },
+            name.as_deref().unwrap_or("_"),
+            id
+        ),
+        ReactionType::Unicode { name } => name.clone(),
+    }
+}
@@ -1,11 +1,15 @@
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use twilight_model::channel::Message;
 
 use super::{BotContext, ShardState};
-use crate::GIT_VERSION;
-use prometheus::{IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry};
+use crate::{SchemeInfo, GIT_VERSION};
+use prometheus::{
+    exponential_buckets, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry,
+};
 
 use crate::core::guild_config::{LogCategory, LogStyle};
 use log::info;
@@ -51,6 +55,82 @@ pub struct EventStats {
     pub webhooks_update: IntCounter,
 }
 
+/// The gateway events we keep per shard handling stats for, everything else is lumped together as ``Other``.
+#[derive(Clone, Copy)]
+pub enum HandledEvent {
+    MessageCreate,
+    MessageUpdate,
+    MessageDelete,
+    MessageDeleteBulk,
+    MemberAdd,
+    MemberRemove,
+    MemberUpdate,
+    GuildCreate,
+    GuildDelete,
+    ReactionAdd,
+    ReactionRemove,
+    Other,
+}
+
+impl HandledEvent {
+    const ALL: [HandledEvent; 12] = [
+        HandledEvent::MessageCreate,
+        HandledEvent::MessageUpdate,
+        HandledEvent::MessageDelete,
+        HandledEvent::MessageDeleteBulk,
+        HandledEvent::MemberAdd,
+        HandledEvent::MemberRemove,
+        HandledEvent::MemberUpdate,
+        HandledEvent::GuildCreate,
+        HandledEvent::GuildDelete,
+        HandledEvent::ReactionAdd,
+        HandledEvent::ReactionRemove,
+        HandledEvent::Other,
+    ];
+
+    pub fn of(event: &Event) -> Self {
+        match event {
+            Event::MessageCreate(_) => HandledEvent::MessageCreate,
+            Event::MessageUpdate(_) => HandledEvent::MessageUpdate,
+            Event::MessageDelete(_) => HandledEvent::MessageDelete,
+            Event::MessageDeleteBulk(_) => HandledEvent::MessageDeleteBulk,
+            Event::MemberAdd(_) => HandledEvent::MemberAdd,
+            Event::MemberRemove(_) => HandledEvent::MemberRemove,
+            Event::MemberUpdate(_) => HandledEvent::MemberUpdate,
+            Event::GuildCreate(_) => HandledEvent::GuildCreate,
+            Event::GuildDelete(_) => HandledEvent::GuildDelete,
+            Event::ReactionAdd(_) => HandledEvent::ReactionAdd,
+            Event::ReactionRemove(_) => HandledEvent::ReactionRemove,
+            _ => HandledEvent::Other,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            HandledEvent::MessageCreate => "MessageCreate",
+            HandledEvent::MessageUpdate => "MessageUpdate",
+            HandledEvent::MessageDelete => "MessageDelete",
+            HandledEvent::MessageDeleteBulk => "MessageDeleteBulk",
+            HandledEvent::MemberAdd => "MemberAdd",
+            HandledEvent::MemberRemove => "MemberRemove",
+            HandledEvent::MemberUpdate => "MemberUpdate",
+            HandledEvent::GuildCreate => "GuildCreate",
+            HandledEvent::GuildDelete => "GuildDelete",
+            HandledEvent::ReactionAdd => "ReactionAdd",
+            HandledEvent::ReactionRemove => "ReactionRemove",
+            HandledEvent::Other => "Other",
+        }
+    }
+}
+
+/// Handling stats of a single shard, indexed by ``HandledEvent``.
+///
+/// The metrics are looked up once at startup so recording them is only an atomic update.
+pub struct ShardEventStats {
+    pub handled: Vec<IntCounter>,
+    pub processing_time: Vec<Histogram>,
+}
+
 pub struct MessageCounters {
     pub user_messages: IntCounter,
     pub other_bot_messages: IntCounter,
@@ -66,6 +146,7 @@ pub struct UserCounters {
 pub struct GuildCounters {
     pub partial: IntGauge,
     pub loaded: IntGauge,
+    pub lazy: IntGauge,
     pub outage: IntGauge,
 }
 
@@ -83,12 +164,56 @@ pub struct ShardStats {
 pub struct LogpumpStats {
     pub active_pumps: IntGauge,
     pub pending_logs: IntGauge,
+    pub dropped_logs: IntCounter,
+    /// Logs that were dropped right away because the logpump was backed up.
+    pub overflowed_logs: IntCounter,
     pub embed: LogTypeCounters,
     pub text: LogTypeCounters,
 }
 
 pub struct LogTypeCounters {
     pub general: IntCounter,
+    pub messages: IntCounter,
+    pub members: IntCounter,
+}
+
+pub struct CryptoStats {
+    pub encrypt_time: Histogram,
+    pub decrypt_time: Histogram,
+    pub decryption_failures: IntCounter,
+}
+
+pub struct DatabaseStats {
+    pub slow_queries: IntCounterVec,
+    pub pool_size: IntGauge,
+    pub pool_idle: IntGauge,
+    pub unavailable: IntGauge,
+    pub dropped_messages: IntCounter,
+    pub rate_limited_messages: IntCounter,
+    pub queued_writes: IntGauge,
+    pub rejected_writes: IntCounter,
+}
+
+pub struct CommandLoadStats {
+    pub in_flight: IntGauge,
+    pub queued: IntGauge,
+    pub rejected: IntCounter,
+    /// Commands that waited for their guild to finish caching and ran after all.
+    pub recovery_deferred: IntCounter,
+    /// Commands that were ignored because their guild wasn't (or didn't get) fully cached.
+    pub recovery_dropped: IntCounter,
+}
+
+pub struct RatelimitCounters {
+    pub global: IntCounter,
+    pub bucket: IntCounter,
+}
+
+pub struct AttachmentCounters {
+    pub images: IntCounter,
+    pub other: IntCounter,
+    /// Size of the attachment copies stored, encrypted as they are.
+    pub stored_bytes: IntCounter,
 }
 
 pub struct BotStats {
@@ -106,26 +231,66 @@ pub struct BotStats {
     pub command_counts: IntCounterVec,
     pub total_command_counts: AtomicU64,
     pub logpump_stats: LogpumpStats,
+    pub crypto: CryptoStats,
+    pub database: DatabaseStats,
+    pub attachments: AttachmentCounters,
+    pub shard_state: IntGaugeVec,
+    pub shard_latency: IntGaugeVec,
+    pub degraded_shards: IntGauge,
+    pub silent_shard_restarts: IntCounter,
+    pub deferred_messages: IntGauge,
+    pub ratelimits: RatelimitCounters,
+    pub command_load: CommandLoadStats,
+    pub queued_sends: IntGauge,
+    pub api_circuit_state: IntGauge,
+    pub shard_events: HashMap<u64, ShardEventStats>,
 }
 
 impl BotStats {
     #[rustfmt::skip]
-    pub fn new(cluster_id: u64) -> Self {
-        let event_counter = IntCounterVec::new(Opts::new("gateway_events", "Events received from the gateway"), &["events"]).unwrap();
-        let message_counter = IntCounterVec::new(Opts::new("messages", "Received messages"), &["sender_type"]).unwrap();
+    pub fn new(scheme_info: &SchemeInfo) -> Self {
+        let event_counter = IntCounterVec::new(Opts::new("gateway_events_total", "Events received from the gateway"), &["events"]).unwrap();
+        let message_counter = IntCounterVec::new(Opts::new("messages_total", "Received messages"), &["sender_type"]).unwrap();
         let channel_count = IntGauge::with_opts(Opts::new("channels", "Channel count")).unwrap();
         let emoji_count = IntGauge::with_opts(Opts::new("emoji", "Emoji count")).unwrap();
         let role_count = IntGauge::with_opts(Opts::new("roles", "Role count")).unwrap();
         let guild_counter = IntGaugeVec::new(Opts::new("guild_counts", "State of the guilds"), &["state"]).unwrap();
         let user_counter = IntGaugeVec::new(Opts::new("user_counts", "User counts"), &["type"]).unwrap();
         let shard_counter = IntGaugeVec::new(Opts::new("shard_counts", "State counts for our shards"), &["state"]).unwrap();
-        let command_counts = IntCounterVec::new(Opts::new("commands", "Executed commands"), &["name"]).unwrap();
+        let command_counts = IntCounterVec::new(Opts::new("commands_total", "Executed commands"), &["name"]).unwrap();
         let active_pumps = IntGauge::with_opts(Opts::new("active_pumps", "Active logpumps")).unwrap();
         let pending_logs = IntGauge::with_opts(Opts::new("pending_logs", "Pending log messages")).unwrap();
-        let pumped_logs = IntCounterVec::new(Opts::new("pumped_logs", "Successfully send logs"), &["type", "category"]).unwrap();
+        let dropped_logs = IntCounter::with_opts(Opts::new("dropped_logs_total", "Logs that could not be delivered and were given up on")).unwrap();
+        let overflowed_logs = IntCounter::with_opts(Opts::new("overflowed_logs_total", "Logs dropped because the logpump was backed up")).unwrap();
+        let pumped_logs = IntCounterVec::new(Opts::new("pumped_logs_total", "Successfully send logs"), &["type", "category"]).unwrap();
+        let crypto_time = HistogramVec::new(HistogramOpts::new("crypto_time", "Time spent encrypting and decrypting messages in seconds").buckets(exponential_buckets(0.000_005, 2.0, 14).unwrap()), &["operation"]).unwrap();
+        let decryption_failures = IntCounter::with_opts(Opts::new("decryption_failures_total", "Messages that failed to decrypt")).unwrap();
+        let slow_queries = IntCounterVec::new(Opts::new("slow_queries_total", "Database queries that exceeded the slow query threshold"), &["query"]).unwrap();
+        let db_pool = IntGaugeVec::new(Opts::new("db_pool_connections", "Postgres pool connections"), &["state"]).unwrap();
+        let db_unavailable = IntGauge::with_opts(Opts::new("db_unavailable", "If queries are currently held back because the database is unreachable")).unwrap();
+        let dropped_messages = IntCounter::with_opts(Opts::new("dropped_messages_total", "Messages that could not be stored")).unwrap();
+        let rate_limited_messages = IntCounter::with_opts(Opts::new("rate_limited_messages_total", "Messages that were not stored because their guild was over its storage limit")).unwrap();
+        let queued_writes = IntGauge::with_opts(Opts::new("queued_storage_writes", "Messages waiting for a free slot to be stored")).unwrap();
+        let rejected_writes = IntCounter::with_opts(Opts::new("rejected_storage_writes_total", "Messages that were not stored because the storage queue was full")).unwrap();
+        let stored_attachments = IntCounterVec::new(Opts::new("stored_attachments_total", "Message attachments stored"), &["type"]).unwrap();
+        let stored_attachment_bytes = IntCounter::with_opts(Opts::new("stored_attachment_bytes_total", "Bytes of attachment copies stored")).unwrap();
+        let shard_state = IntGaugeVec::new(Opts::new("shard_state", "1 for the state each shard is currently in"), &["shard", "state"]).unwrap();
+        let shard_latency = IntGaugeVec::new(Opts::new("shard_latency", "Average gateway heartbeat latency in milliseconds"), &["shard"]).unwrap();
+        let degraded_shards = IntGauge::with_opts(Opts::new("degraded_shards", "Shards with a latency above the degraded threshold")).unwrap();
+        let silent_shard_restarts = IntCounter::with_opts(Opts::new("silent_shard_restarts_total", "Shards that were restarted after receiving no events for too long")).unwrap();
+        let deferred_messages = IntGauge::with_opts(Opts::new("deferred_messages", "Messages held back from storage by degraded shards")).unwrap();
+        let commands_in_flight = IntGauge::with_opts(Opts::new("commands_in_flight", "Commands that are currently running")).unwrap();
+        let commands_queued = IntGauge::with_opts(Opts::new("commands_queued", "Commands waiting for a free slot to run in")).unwrap();
+        let commands_rejected = IntCounter::with_opts(Opts::new("commands_rejected_total", "Commands that gave up waiting for a free slot")).unwrap();
+        let recovery_commands = IntCounterVec::new(Opts::new("recovery_commands_total", "Commands received while their guild was still being cached"), &["outcome"]).unwrap();
+        let queued_sends = IntGauge::with_opts(Opts::new("queued_sends", "Outgoing messages waiting in the send queue")).unwrap();
+        let api_circuit_state = IntGauge::with_opts(Opts::new("api_circuit_state", "If non-critical requests are held back because the discord api is failing (0: no, 1: yes, 2: probing)")).unwrap();
+        let handled_events = IntCounterVec::new(Opts::new("handled_events_total", "Gateway events the handlers finished processing"), &["shard", "event"]).unwrap();
+        let event_processing_time = HistogramVec::new(HistogramOpts::new("event_processing_time", "Time spent handling gateway events in seconds").buckets(exponential_buckets(0.000_1, 2.0, 16).unwrap()), &["shard", "event"]).unwrap();
+        let ratelimits = IntCounterVec::new(Opts::new("http_ratelimits_total", "Requests discord refused with a 429 because a ratelimit was hit"), &["scope"]).unwrap();
 
         let mut static_labels = HashMap::new();
-        static_labels.insert(String::from("cluster"), cluster_id.to_string());
+        static_labels.insert(String::from("cluster"), scheme_info.cluster_id.to_string());
         let registry = Registry::new_custom(Some(String::from("gearbot")), Some(static_labels)).unwrap();
         registry.register(Box::new(event_counter.clone())).unwrap();
         registry.register(Box::new(message_counter.clone())).unwrap();
@@ -138,7 +303,43 @@ impl BotStats {
         registry.register(Box::new(command_counts.clone())).unwrap();
         registry.register(Box::new(active_pumps.clone())).unwrap();
         registry.register(Box::new(pending_logs.clone())).unwrap();
+        registry.register(Box::new(dropped_logs.clone())).unwrap();
+        registry.register(Box::new(overflowed_logs.clone())).unwrap();
         registry.register(Box::new(pumped_logs.clone())).unwrap();
+        registry.register(Box::new(crypto_time.clone())).unwrap();
+        registry.register(Box::new(decryption_failures.clone())).unwrap();
+        registry.register(Box::new(slow_queries.clone())).unwrap();
+        registry.register(Box::new(db_pool.clone())).unwrap();
+        registry.register(Box::new(db_unavailable.clone())).unwrap();
+        registry.register(Box::new(dropped_messages.clone())).unwrap();
+        registry.register(Box::new(rate_limited_messages.clone())).unwrap();
+        registry.register(Box::new(queued_writes.clone())).unwrap();
+        registry.register(Box::new(rejected_writes.clone())).unwrap();
+        registry.register(Box::new(stored_attachments.clone())).unwrap();
+        registry.register(Box::new(stored_attachment_bytes.clone())).unwrap();
+        registry.register(Box::new(shard_state.clone())).unwrap();
+        registry.register(Box::new(shard_latency.clone())).unwrap();
+        registry.register(Box::new(degraded_shards.clone())).unwrap();
+        registry.register(Box::new(silent_shard_restarts.clone())).unwrap();
+        registry.register(Box::new(deferred_messages.clone())).unwrap();
+        registry.register(Box::new(ratelimits.clone())).unwrap();
+        registry.register(Box::new(commands_in_flight.clone())).unwrap();
+        registry.register(Box::new(commands_queued.clone())).unwrap();
+        registry.register(Box::new(commands_rejected.clone())).unwrap();
+        registry.register(Box::new(recovery_commands.clone())).unwrap();
+        registry.register(Box::new(queued_sends.clone())).unwrap();
+        registry.register(Box::new(api_circuit_state.clone())).unwrap();
+        registry.register(Box::new(handled_events.clone())).unwrap();
+        registry.register(Box::new(event_processing_time.clone())).unwrap();
+
+        let shard_events = scheme_info.shard_range().map(|shard| {
+            let shard_label = shard.to_string();
+            let stats = ShardEventStats {
+                handled: HandledEvent::ALL.iter().map(|kind| handled_events.get_metric_with_label_values(&[&shard_label, kind.as_str()]).unwrap()).collect(),
+                processing_time: HandledEvent::ALL.iter().map(|kind| event_processing_time.get_metric_with_label_values(&[&shard_label, kind.as_str()]).unwrap()).collect(),
+            };
+            (shard, stats)
+        }).collect();
 
         BotStats {
             registry,
@@ -195,6 +396,7 @@ impl BotStats {
             guild_counts: GuildCounters {
                 partial: guild_counter.get_metric_with_label_values(&["partial"]).unwrap(),
                 loaded: guild_counter.get_metric_with_label_values(&["loaded"]).unwrap(),
+                lazy: guild_counter.get_metric_with_label_values(&["lazy"]).unwrap(),
                 outage: guild_counter.get_metric_with_label_values(&["outage"]).unwrap(),
             },
             channel_count,
@@ -215,13 +417,65 @@ impl BotStats {
             logpump_stats: LogpumpStats {
                 active_pumps,
                 pending_logs,
+                dropped_logs,
+                overflowed_logs,
                 embed: LogTypeCounters {
-                    general: pumped_logs.get_metric_with_label_values(&["embed", "general"]).unwrap()
+                    general: pumped_logs.get_metric_with_label_values(&["embed", "general"]).unwrap(),
+                    messages: pumped_logs.get_metric_with_label_values(&["embed", "messages"]).unwrap(),
+                    members: pumped_logs.get_metric_with_label_values(&["embed", "members"]).unwrap(),
                 },
                 text: LogTypeCounters {
-                    general: pumped_logs.get_metric_with_label_values(&["text", "general"]).unwrap()
+                    general: pumped_logs.get_metric_with_label_values(&["text", "general"]).unwrap(),
+                    messages: pumped_logs.get_metric_with_label_values(&["text", "messages"]).unwrap(),
+                    members: pumped_logs.get_metric_with_label_values(&["text", "members"]).unwrap(),
                 }
             },
+            crypto: CryptoStats {
+                encrypt_time: crypto_time.get_metric_with_label_values(&["encrypt"]).unwrap(),
+                decrypt_time: crypto_time.get_metric_with_label_values(&["decrypt"]).unwrap(),
+                decryption_failures,
+            },
+            database: DatabaseStats {
+                slow_queries,
+                pool_size: db_pool.get_metric_with_label_values(&["total"]).unwrap(),
+                pool_idle: db_pool.get_metric_with_label_values(&["idle"]).unwrap(),
+                unavailable: db_unavailable,
+                dropped_messages,
+                rate_limited_messages,
+                queued_writes,
+                rejected_writes,
+            },
+            attachments: AttachmentCounters {
+                images: stored_attachments.get_metric_with_label_values(&["image"]).unwrap(),
+                other: stored_attachments.get_metric_with_label_values(&["other"]).unwrap(),
+                stored_bytes: stored_attachment_bytes,
+            },
+            shard_state,
+            shard_latency,
+            degraded_shards,
+            silent_shard_restarts,
+            deferred_messages,
+            ratelimits: RatelimitCounters {
+                global: ratelimits.get_metric_with_label_values(&["global"]).unwrap(),
+                bucket: ratelimits.get_metric_with_label_values(&["bucket"]).unwrap(),
+            },
+            command_load: CommandLoadStats {
+                in_flight: commands_in_flight,
+                queued: commands_queued,
+                rejected: commands_rejected,
+                recovery_deferred: recovery_commands.get_metric_with_label_values(&["deferred"]).unwrap(),
+                recovery_dropped: recovery_commands.get_metric_with_label_values(&["dropped"]).unwrap(),
+            },
+            queued_sends,
+            api_circuit_state,
+            shard_events,
+        }
+    }
+
+    pub fn event_handled(&self, shard_id: u64, kind: HandledEvent, elapsed: Duration) {
+        if let Some(stats) = self.shard_events.get(&shard_id) {
+            stats.handled[kind as usize].inc();
+            stats.processing_time[kind as usize].observe(elapsed.as_secs_f64());
         }
     }
 
@@ -245,12 +499,17 @@ impl BotStats {
         match (log_style, category) {
             (LogStyle::Text, LogCategory::GENERAL) => self.logpump_stats.text.general.inc(),
             (LogStyle::Embed, LogCategory::GENERAL) => self.logpump_stats.embed.general.inc(),
+            (LogStyle::Text, LogCategory::MESSAGES) => self.logpump_stats.text.messages.inc(),
+            (LogStyle::Embed, LogCategory::MESSAGES) => self.logpump_stats.embed.messages.inc(),
+            (LogStyle::Text, LogCategory::MEMBERS) => self.logpump_stats.text.members.inc(),
+            (LogStyle::Embed, LogCategory::MEMBERS) => self.logpump_stats.embed.members.inc(),
         }
     }
 }
 
 impl BotContext {
     pub async fn update_stats(&self, shard_id: u64, event: &Event) {
+        self.shard_event_received(shard_id);
         match event {
             Event::BanAdd(_) => self.stats.event_counts.ban_add.inc(),
             Event::BanRemove(_) => self.stats.event_counts.ban_remove.inc(),
@@ -303,12 +562,21 @@ impl BotContext {
 
     pub async fn shard_state_change(&self, shard: u64, new_state: ShardState) {
         let mut shards = self.shard_states.write().await;
+        let shard_label = shard.to_string();
         if let Some(guard) = shards.get(&shard) {
             self.get_state_metric(guard).dec();
+            self.stats
+                .shard_state
+                .with_label_values(&[&shard_label, state_label(guard)])
+                .set(0);
         }
 
         info!("Shard {} is now {:?}", shard, new_state);
         self.get_state_metric(&new_state).inc();
+        self.stats
+            .shard_state
+            .with_label_values(&[&shard_label, state_label(&new_state)])
+            .set(1);
         shards.insert(shard, new_state);
     }
 
@@ -326,6 +594,20 @@ impl BotContext {
     }
 }
 
+/// The state label of the per shard metrics, the same ones ``shard_counts`` uses.
+fn state_label(state: &ShardState) -> &'static str {
+    match state {
+        ShardState::PendingCreation => "pending",
+        ShardState::Connecting => "connecting",
+        ShardState::Identifying => "identifying",
+        ShardState::Connected => "connected",
+        ShardState::Ready => "ready",
+        ShardState::Resuming => "resuming",
+        ShardState::Reconnecting => "reconnecting",
+        ShardState::Disconnected => "disconnected",
+    }
+}
+
 #[derive(Debug)]
 pub struct LoadingState {
     to_load: u32,
@@ -3,10 +3,67 @@ use twilight_model::gateway::payload::UpdateStatus;
 use twilight_model::gateway::presence::{Activity, ActivityType, Status};
 
 use super::BotContext;
+use crate::core::StatusActivity;
 use crate::error::EventHandlerError;
+use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// What the status shows while in maintenance mode, instead of the rotation.
+const MAINTENANCE_STATUS: &str = "under maintenance";
+
 impl BotContext {
+    /// Moves on to the next status of the rotation, on all shards that are done caching.
+    pub async fn cycle_status(&self) -> Result<(), ClusterCommandError> {
+        if self.status_rotation.messages.len() < 2 {
+            return Ok(());
+        }
+
+        self.status_index.fetch_add(1, Ordering::Relaxed);
+        for shard_id in self.scheme_info.cluster_id * self.scheme_info.shards_per_cluster
+            ..self.scheme_info.cluster_id * self.scheme_info.shards_per_cluster + self.scheme_info.shards_per_cluster
+        {
+            if self.cache.shard_cached(shard_id) {
+                self.show_current_status(shard_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the status the rotation is currently at on a shard, with its placeholders filled in.
+    pub async fn show_current_status(&self, shard_id: u64) -> Result<(), ClusterCommandError> {
+        if self.is_in_maintenance().await {
+            return self
+                .set_shard_activity(
+                    shard_id,
+                    Status::DoNotDisturb,
+                    ActivityType::Playing,
+                    String::from(MAINTENANCE_STATUS),
+                )
+                .await;
+        }
+
+        let messages = &self.status_rotation.messages;
+        let (activity_type, message) = if messages.is_empty() {
+            (ActivityType::Watching, String::from("the gears turn"))
+        } else {
+            let status = &messages[self.status_index.load(Ordering::Relaxed) % messages.len()];
+            let activity_type = match status.activity {
+                StatusActivity::Playing => ActivityType::Playing,
+                StatusActivity::Listening => ActivityType::Listening,
+                StatusActivity::Watching => ActivityType::Watching,
+            };
+            let message = status
+                .text
+                .replace("{guilds}", &self.stats.guild_counts.loaded.get().to_string())
+                .replace("{users}", &self.stats.user_counts.unique.get().to_string())
+                .replace("{version}", self.stats.version);
+            (activity_type, message)
+        };
+
+        self.set_shard_activity(shard_id, Status::Online, activity_type, message)
+            .await
+    }
+
     pub async fn set_cluster_activity(
         &self,
         status: Status,
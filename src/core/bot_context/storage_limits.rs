@@ -0,0 +1,28 @@
+use std::time::{Duration, Instant};
+
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+
+/// The window the storage limit of a guild applies to.
+const STORAGE_WINDOW: Duration = Duration::from_secs(1);
+
+impl BotContext {
+    /// Claims room to store a message in the guild's budget for the current second, false when it's used up.
+    pub async fn take_storage_slot(&self, guild_id: GuildId, per_second: u32) -> bool {
+        let now = Instant::now();
+        let mut windows = self.storage_windows.lock().await;
+        let (started, stored) = windows.entry(guild_id).or_insert((now, 0));
+
+        if now.duration_since(*started) >= STORAGE_WINDOW {
+            *started = now;
+            *stored = 0;
+        }
+
+        if *stored >= per_second {
+            return false;
+        }
+        *stored += 1;
+        true
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::Semaphore;
+use twilight_model::channel::Message;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::error::DatabaseError;
+
+/// Keeps bursts of incoming messages from all hitting the database (and the encryption) at once.
+///
+/// When too many are already waiting, new messages are rejected rather than older ones being dropped:
+/// those are already waiting on their turn and have been around the longest.
+pub struct StorageQueue {
+    slots: Semaphore,
+    waiting: AtomicUsize,
+    max_waiting: usize,
+}
+
+impl StorageQueue {
+    pub fn new(max_concurrent: usize, max_waiting: usize) -> Self {
+        StorageQueue {
+            slots: Semaphore::new(max_concurrent),
+            waiting: AtomicUsize::new(0),
+            max_waiting,
+        }
+    }
+}
+
+impl BotContext {
+    /// Stores a message through the storage queue when it's enabled, directly otherwise.
+    ///
+    /// Messages that don't fit in the queue anymore are counted and skipped, they are not an error.
+    pub async fn queue_storage(&self, message: &Message, guild_id: GuildId) -> Result<(), DatabaseError> {
+        let queue = match &self.storage_queue {
+            Some(queue) => queue,
+            None => return self.insert_message(message, guild_id).await,
+        };
+
+        if queue.waiting.fetch_add(1, Ordering::SeqCst) >= queue.max_waiting {
+            queue.waiting.fetch_sub(1, Ordering::SeqCst);
+            self.stats.database.rejected_writes.inc();
+            log::debug!("Storage queue is full, not storing message {}", message.id);
+            return Ok(());
+        }
+
+        self.stats.database.queued_writes.inc();
+        let _slot = queue.slots.acquire().await;
+        queue.waiting.fetch_sub(1, Ordering::SeqCst);
+        self.stats.database.queued_writes.dec();
+
+        self.insert_message(message, guild_id).await
+    }
+}
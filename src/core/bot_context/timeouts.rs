@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use hyper::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use twilight_http::request::Request;
+use twilight_http::routing::Route;
+use twilight_model::id::{GuildId, UserId};
+
+use super::BotContext;
+use crate::utils;
+
+impl BotContext {
+    /// Sets or clears the timeout of a member.
+    ///
+    /// The http client doesn't know about timeouts yet, so this sends the member update by hand.
+    pub async fn set_member_timeout(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        until: Option<DateTime<Utc>>,
+        audit_reason: &str,
+    ) -> Result<(), twilight_http::Error> {
+        let audit_reason: String = audit_reason.chars().take(utils::MAX_AUDIT_REASON_LENGTH).collect();
+        let mut headers = HeaderMap::new();
+        // percent encoded so it is always a valid header value
+        if let Ok(value) = HeaderValue::from_str(&percent_encode(&audit_reason)) {
+            headers.insert("X-Audit-Log-Reason", value);
+        }
+
+        let body = json!({ "communication_disabled_until": until.map(|until| until.to_rfc3339()) });
+        let request = Request::from((
+            body.to_string().into_bytes(),
+            headers,
+            Route::UpdateMember {
+                guild_id: guild_id.0,
+                user_id: user_id.0,
+            },
+        ));
+
+        self.http.verify(request).await
+    }
+}
+
+fn percent_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
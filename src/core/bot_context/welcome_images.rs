@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use image::{DynamicImage, RgbaImage};
+use log::debug;
+use twilight_model::id::GuildId;
+
+use super::BotContext;
+use crate::cache::CachedUser;
+use crate::core::WelcomeImageConfig;
+use crate::error::ImageError;
+use crate::utils::{self, images};
+
+/// Backgrounds and avatars bigger than this aren't worth downloading, let alone decoding.
+const MAX_IMAGE_SIZE: usize = 8 * 1024 * 1024;
+
+/// Discord serves avatars in the size asked for, this is about what the welcome image shows them at.
+const AVATAR_SIZE: u16 = 256;
+
+impl BotContext {
+    /// Draws the image a member gets welcomed with, as png.
+    ///
+    /// When their avatar can't be fetched the default discord avatar takes its place, a background that
+    /// can't be fetched is replaced by a plain one.
+    pub async fn generate_welcome_image(
+        &self,
+        guild_id: GuildId,
+        config: &WelcomeImageConfig,
+        user: &CachedUser,
+        member_number: Option<String>,
+    ) -> Result<Vec<u8>, ImageError> {
+        let background = match &config.background {
+            Some(url) => self.get_welcome_background(guild_id, url).await.unwrap_or_else(|e| {
+                debug!("Using a plain welcome image background for guild {}: {}", guild_id, e);
+                Arc::new(images::plain_background())
+            }),
+            None => Arc::new(images::plain_background()),
+        };
+
+        let avatar = match fetch_avatar(&format!("{}?size={}", user.avatar_url(), AVATAR_SIZE)).await {
+            Ok(avatar) => Some(avatar),
+            Err(e) => {
+                debug!("Using the default avatar in the welcome image of {}: {}", user.id, e);
+                fetch_avatar(&user.default_avatar_url()).await.ok()
+            }
+        };
+
+        let name = user.username.clone();
+        let text_color = config.text_color;
+        tokio::task::spawn_blocking(move || {
+            images::render_welcome_image(
+                &background,
+                avatar.as_ref(),
+                &name,
+                member_number.as_deref(),
+                text_color,
+            )
+        })
+        .await
+        .expect("Rendering a welcome image panicked")
+    }
+
+    /// The background of the welcome images of a guild, it's only fetched and scaled again when the link changes.
+    pub async fn get_welcome_background(&self, guild_id: GuildId, url: &str) -> Result<Arc<RgbaImage>, ImageError> {
+        if let Some((cached_url, background)) = self.welcome_backgrounds.read().await.get(&guild_id) {
+            if cached_url == url {
+                return Ok(Arc::clone(background));
+            }
+        }
+
+        let data = download_image(url).await?;
+        // backgrounds can be big, reading and scaling them takes a while
+        let background =
+            tokio::task::spawn_blocking(move || images::decode(&data).map(|image| images::welcome_background(&image)))
+                .await
+                .expect("Reading a welcome image background panicked")?;
+        let background = Arc::new(background);
+        self.welcome_backgrounds
+            .write()
+            .await
+            .insert(guild_id, (url.to_string(), Arc::clone(&background)));
+
+        Ok(background)
+    }
+
+    /// Drops the cached welcome image background of a guild once it no longer uses it.
+    pub async fn forget_welcome_background(&self, guild_id: GuildId) {
+        self.welcome_backgrounds.write().await.remove(&guild_id);
+    }
+}
+
+async fn download_image(url: &str) -> Result<Vec<u8>, ImageError> {
+    let data = utils::download_bytes(url).await.map_err(ImageError::Download)?;
+    if data.len() > MAX_IMAGE_SIZE {
+        return Err(ImageError::TooLarge(data.len()));
+    }
+    Ok(data)
+}
+
+/// Avatars are small enough to read right away.
+async fn fetch_avatar(url: &str) -> Result<DynamicImage, ImageError> {
+    images::decode(&download_image(url).await?)
+}
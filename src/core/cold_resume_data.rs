@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+/// Discord only keeps sessions around for a short while after the connection drops, resuming anything older
+/// is bound to get rejected and would just leave us waiting on an invalid session before identifying anyways.
+const MAX_SESSION_AGE: i64 = 120;
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ColdRebootData {
     pub resume_data: HashMap<u64, (String, u64)>,
@@ -9,4 +14,17 @@ pub struct ColdRebootData {
     pub total_shards: u64,
     pub guild_chunks: usize,
     pub user_chunks: usize,
+    /// Unix timestamp (in seconds) of when the sessions were stored, data without one is treated as stale.
+    #[serde(default)]
+    pub stored_at: i64,
+}
+
+impl ColdRebootData {
+    pub fn age(&self) -> i64 {
+        Utc::now().timestamp() - self.stored_at
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.age() > MAX_SESSION_AGE
+    }
 }
@@ -1,14 +1,23 @@
+use std::time::Duration;
+
 use fluent_bundle::FluentArgs;
+use hyper::StatusCode;
+use twilight_http::Error;
 use twilight_model::{
     channel::{embed::Embed, Message},
+    guild::{Permissions, PremiumTier},
     id::{ChannelId, MessageId},
 };
 
-use crate::translation::GearBotString;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, text, Emoji};
 
 use super::CommandContext;
 use crate::error::CommandError;
 
+/// Maximum amount of characters Discord allows in a single message.
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+
 impl CommandContext {
     pub async fn send_message(
         &self,
@@ -43,9 +52,7 @@ impl CommandContext {
     }
 
     pub async fn send_embed(&self, embed: Embed, channel_id: ChannelId) -> Result<Message, CommandError> {
-        let sent_embed_handle = self.bot_context.http.create_message(channel_id).embed(embed)?.await?;
-
-        Ok(sent_embed_handle)
+        self.create_with_embed(channel_id, None, embed).await
     }
 
     pub async fn send_message_with_embed(
@@ -54,15 +61,7 @@ impl CommandContext {
         embed: Embed,
         channel_id: ChannelId,
     ) -> Result<Message, CommandError> {
-        let sent_handle = self
-            .bot_context
-            .http
-            .create_message(channel_id)
-            .content(msg)?
-            .embed(embed)?
-            .await?;
-
-        Ok(sent_handle)
+        self.create_with_embed(channel_id, Some(msg.into()), embed).await
     }
 
     pub async fn update_message(
@@ -93,26 +92,121 @@ impl CommandContext {
         Ok(sent_msg_handle)
     }
 
-    pub async fn reply_raw<T: std::fmt::Display>(&self, message: T) -> Result<Message, CommandError> {
+    /// Same as ``reply`` with a file attached, the caller has to make sure it fits within the upload limit.
+    pub async fn reply_with_file(
+        &self,
+        key: GearBotString,
+        args: FluentArgs<'_>,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<Message, CommandError> {
+        let translated = self.translate_with_args(key, &args);
         let sent_msg_handle = self
             .bot_context
             .http
             .create_message(self.message.channel.get_id())
-            .content(message.to_string())?
+            .content(translated)?
+            .attachment(filename, data)
             .await?;
 
         Ok(sent_msg_handle)
     }
 
-    pub async fn reply_embed(&self, embed: Embed) -> Result<Message, CommandError> {
-        let sent_embed_handle = self
+    /// Same as ``reply`` but the response is cleaned up later on if the guild wants that.
+    pub async fn reply_and_clean_up(&self, key: GearBotString, args: FluentArgs<'_>) -> Result<Message, CommandError> {
+        let sent_msg_handle = self.reply(key, args).await?;
+        self.schedule_cleanup(&sent_msg_handle);
+
+        Ok(sent_msg_handle)
+    }
+
+    /// Confirms a command went through. In quiet mode that's a reaction on the command instead of a reply,
+    /// unless we're not allowed to react in this channel.
+    pub async fn reply_success(&self, key: GearBotString, args: FluentArgs<'_>) -> Result<(), CommandError> {
+        if !self.react_success().await? {
+            self.reply(key, args).await?;
+        }
+        Ok(())
+    }
+
+    /// Same as ``reply_success`` but a reply is cleaned up later on if the guild wants that.
+    pub async fn reply_success_and_clean_up(
+        &self,
+        key: GearBotString,
+        args: FluentArgs<'_>,
+    ) -> Result<(), CommandError> {
+        if !self.react_success().await? {
+            self.reply_and_clean_up(key, args).await?;
+        }
+        Ok(())
+    }
+
+    /// Reacts to the command if the guild is in quiet mode, returns if it did.
+    async fn react_success(&self) -> Result<bool, CommandError> {
+        let quiet = self.config.quiet_mode
+            && self.bot_has_channel_permissions(Permissions::ADD_REACTIONS | Permissions::READ_MESSAGE_HISTORY);
+        if !quiet {
+            return Ok(false);
+        }
+
+        let result = self
+            .bot_context
+            .http
+            .create_reaction(self.message.channel.get_id(), self.message.id, Emoji::Yes.to_reaction())
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            // the cached permissions were out of date, a reply it is
+            Err(Error::Response { status, .. }) if status == StatusCode::FORBIDDEN => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Deletes a response after the delay configured for the guild, and the command that caused
+    /// it as well if the guild asked for that. Does nothing if response cleanup is off.
+    pub fn schedule_cleanup(&self, response: &Message) {
+        let cleanup = match self
+            .get_config()
+            .ok()
+            .and_then(|config| config.response_cleanup.clone())
+        {
+            Some(cleanup) => cleanup,
+            None => return,
+        };
+
+        let channel_id = response.channel_id;
+        let mut to_delete = vec![response.id];
+        if cleanup.include_command && self.bot_has_channel_permissions(Permissions::MANAGE_MESSAGES) {
+            to_delete.push(self.message.id);
+        }
+
+        let http = self.bot_context.http.clone();
+        tokio::spawn(async move {
+            tokio::time::delay_for(Duration::from_secs(cleanup.delay_seconds)).await;
+            for message_id in to_delete {
+                match http.delete_message(channel_id, message_id).await {
+                    Ok(_) => {}
+                    // someone beat us to it, or the channel is gone altogether
+                    Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => {}
+                    Err(e) => log::debug!("Failed to clean up message {} in {}: {}", message_id, channel_id, e),
+                }
+            }
+        });
+    }
+
+    pub async fn reply_raw<T: std::fmt::Display>(&self, message: T) -> Result<Message, CommandError> {
+        let sent_msg_handle = self
             .bot_context
             .http
             .create_message(self.message.channel.get_id())
-            .embed(embed)?
+            .content(message.to_string())?
             .await?;
 
-        Ok(sent_embed_handle)
+        Ok(sent_msg_handle)
+    }
+
+    pub async fn reply_embed(&self, embed: Embed) -> Result<Message, CommandError> {
+        self.create_with_embed(self.message.channel.get_id(), None, embed).await
     }
 
     pub async fn reply_with_embed(
@@ -122,15 +216,8 @@ impl CommandContext {
         embed: Embed,
     ) -> Result<Message, CommandError> {
         let translated = self.translate_with_args(key, &args);
-        let sent_handle = self
-            .bot_context
-            .http
-            .create_message(self.message.channel.get_id())
-            .content(translated)?
-            .embed(embed)?
-            .await?;
-
-        Ok(sent_handle)
+        self.create_with_embed(self.message.channel.get_id(), Some(translated), embed)
+            .await
     }
 
     pub async fn reply_raw_with_embed(
@@ -138,14 +225,85 @@ impl CommandContext {
         message: impl Into<String>,
         embed: Embed,
     ) -> Result<Message, CommandError> {
-        let sent_handle = self
+        self.create_with_embed(self.message.channel.get_id(), Some(message.into()), embed)
+            .await
+    }
+
+    /// Sends a message with an embed, or with the embed written out as text if the guild prefers plaintext.
+    async fn create_with_embed(
+        &self,
+        channel_id: ChannelId,
+        content: Option<String>,
+        embed: Embed,
+    ) -> Result<Message, CommandError> {
+        let request = self.bot_context.http.create_message(channel_id);
+
+        let request = if self.config.plaintext_responses {
+            let rendered = utils::embed_to_plaintext(&embed);
+            let content = match content {
+                Some(content) => format!("{}\n{}", content, rendered),
+                None => rendered,
+            };
+            request.content(truncate_message(content))?
+        } else {
+            match content {
+                Some(content) => request.content(content)?.embed(embed)?,
+                None => request.embed(embed)?,
+            }
+        };
+
+        Ok(request.await?)
+    }
+
+    /// Same as ``reply_raw`` but uploads the output as a text file when it doesn't fit in a message.
+    pub async fn reply_raw_or_file(&self, message: impl Into<String>, filename: &str) -> Result<Message, CommandError> {
+        let message = message.into();
+        if message.chars().count() <= MAX_MESSAGE_LENGTH {
+            return self.reply_raw(message).await;
+        }
+
+        if !self.get_bot_channel_permissions().contains(Permissions::ATTACH_FILES) {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            return self.reply(GearBotString::OutputNoAttachPermission, args).await;
+        }
+
+        if message.len() > self.get_upload_limit() {
+            return self.reply_output_too_large().await;
+        }
+
+        let result = self
             .bot_context
             .http
             .create_message(self.message.channel.get_id())
-            .content(message)?
-            .embed(embed)?
-            .await?;
+            .content(self.translate(GearBotString::OutputAsFile))?
+            .attachment(filename, message.into_bytes())
+            .await;
+
+        match result {
+            Ok(sent_msg_handle) => Ok(sent_msg_handle),
+            // our size estimate can be off (boosts being lost), discord has the final say
+            Err(error) if error.to_string().contains("status: 413") => self.reply_output_too_large().await,
+            Err(error) => Err(error.into()),
+        }
+    }
 
-        Ok(sent_handle)
+    async fn reply_output_too_large(&self) -> Result<Message, CommandError> {
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        self.reply(GearBotString::OutputTooLarge, args).await
     }
+
+    /// The maximum size (in bytes) of files that can be uploaded in this guild.
+    pub fn get_upload_limit(&self) -> usize {
+        let premium_tier = self.get_guild().map_or(PremiumTier::None, |guild| guild.premium_tier);
+        match premium_tier {
+            PremiumTier::Tier2 => 50 * 1024 * 1024,
+            PremiumTier::Tier3 => 100 * 1024 * 1024,
+            _ => 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Cuts text down to what fits in a single message, marking that something was left out.
+fn truncate_message(content: String) -> String {
+    text::truncate_with_ellipsis(&content, MAX_MESSAGE_LENGTH)
 }
@@ -1,24 +1,29 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
 use fluent_bundle::FluentArgs;
+use twilight_embed_builder::EmbedBuilder;
 use twilight_gateway::shard::Information;
 use twilight_model::channel::embed::Embed;
 use twilight_model::channel::message::{MessageFlags, MessageType};
 use twilight_model::channel::Attachment;
-use twilight_model::{id::MessageId, user::CurrentUser};
+use twilight_model::id::MessageId;
 
-use super::bot_context::BotContext;
+use super::bot_context::{BotContext, BotUser};
+use super::guild_config::DEFAULT_EMBED_COLOR;
 use super::logpump::{LogData, LogType};
 use super::GuildConfig;
 use crate::cache::{CachedChannel, CachedGuild, CachedMember, CachedUser};
 use crate::commands::meta::nodes::GearBotPermissions;
 use crate::error::{CommandError, OtherFailure};
 use crate::parser::Parser;
-use crate::translation::GearBotString;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::Emoji;
 use twilight_model::id::{ChannelId, UserId};
 
 mod messaging;
+pub use messaging::MAX_MESSAGE_LENGTH;
 mod object_fetcher;
 mod permissions;
 
@@ -51,7 +56,7 @@ pub struct CommandContext {
     pub bot_context: Arc<BotContext>,
     config: Arc<GuildConfig>,
     pub message: CommandMessage,
-    guild: Arc<CachedGuild>,
+    guild: Option<Arc<CachedGuild>>,
     pub shard: u64,
     pub parser: Parser,
     pub permissions: GearBotPermissions,
@@ -62,7 +67,7 @@ impl CommandContext {
         ctx: Arc<BotContext>,
         config: Arc<GuildConfig>,
         message: CommandMessage,
-        guild: Arc<CachedGuild>,
+        guild: Option<Arc<CachedGuild>>,
         shard: u64,
         parser: Parser,
         permissions: GearBotPermissions,
@@ -82,30 +87,29 @@ impl CommandContext {
         self.bot_context.cluster.info()
     }
 
-    pub fn get_bot_user(&self) -> &CurrentUser {
+    pub fn get_bot_user(&self) -> &BotUser {
         &self.bot_context.bot_user
     }
 
     pub fn translate(&self, key: GearBotString) -> String {
-        self.bot_context
-            .translations
-            .get_text_plain(&self.config.language, key)
-            .to_string()
+        self.bot_context.translate(&self.config.language, key)
     }
 
     pub fn translate_with_args(&self, string_key: GearBotString, args: &FluentArgs<'_>) -> String {
-        let guild_lang = &self.config.language;
+        self.bot_context
+            .translate_with_args(&self.config.language, string_key, args)
+    }
 
+    /// Formats a duration in the language of the guild.
+    pub fn format_duration(&self, duration: Duration, max_parts: usize) -> String {
         self.bot_context
-            .translations
-            .get_text_with_args(guild_lang, string_key, args)
-            .replace("\\n", "\n")
+            .format_duration(&self.config.language, duration, max_parts)
     }
 
     pub async fn set_config(&self, new_config: GuildConfig) -> Result<(), CommandError> {
         // This updates it both in the DB and handles our element guard
         self.bot_context
-            .set_config(self.guild.id, new_config)
+            .set_config(self.get_guild()?.id, new_config)
             .await
             .map_err(|e| CommandError::OtherFailure(OtherFailure::DatabaseError(e)))
     }
@@ -118,17 +122,67 @@ impl CommandContext {
         }
     }
 
-    pub fn get_guild(&self) -> &Arc<CachedGuild> {
-        &self.guild
+    /// Lets the user know when the guild turned off content storage, for commands that have nothing to show then.
+    ///
+    /// Returns if the command can go ahead.
+    pub async fn check_content_storage(&self) -> Result<bool, CommandError> {
+        if !self.get_config()?.message_logs.disable_content_storage {
+            return Ok(true);
+        }
+
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        self.reply(GearBotString::ContentStorageDisabled, args).await?;
+        Ok(false)
+    }
+
+    /// Formats a point in time for display, in the timezone of the guild (UTC in DMs).
+    pub fn format_time(&self, time: DateTime<Utc>) -> String {
+        self.config.format_time(time)
+    }
+
+    /// Checks if a reason was given for a moderation action when the guild requires one,
+    /// telling the user so if it is missing.
+    pub async fn require_reason(&self, reason: &str) -> Result<bool, CommandError> {
+        if !self.config.require_mod_reasons || !reason.trim().is_empty() {
+            return Ok(true);
+        }
+
+        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+        self.reply(GearBotString::ModReasonRequired, args).await?;
+        Ok(false)
+    }
+
+    /// The color the guild picked for embeds, or the default one when it didn't.
+    pub fn embed_color(&self) -> u32 {
+        self.config.embed_color.unwrap_or(DEFAULT_EMBED_COLOR)
+    }
+
+    /// Creates an embed builder that already has the guild's embed color and footer applied.
+    ///
+    /// Embeds that need the footer for something else can simply set their own.
+    pub fn embed_builder(&self) -> Result<EmbedBuilder, CommandError> {
+        Ok(self.config.embed_builder()?)
+    }
+
+    /// The guild the command was used in, commands used in DMs don't have one.
+    pub fn get_guild(&self) -> Result<&Arc<CachedGuild>, CommandError> {
+        self.guild.as_ref().ok_or(CommandError::NoDM)
     }
 
     pub fn log(&self, log_type: LogType, source_channel: Option<ChannelId>, source_user: UserId) {
+        // there are no logs to send things to outside of guilds
+        let guild = match &self.guild {
+            Some(guild) => guild,
+            None => return,
+        };
+
         log::debug!("Logging {:?}", log_type);
         self.bot_context.log(LogData {
             log_type,
-            guild: self.guild.id,
+            guild: guild.id,
             source_channel,
             source_user,
+            failed_attempts: 0,
         });
     }
 }
@@ -18,8 +18,13 @@ impl CommandContext {
             .map_err(CommandError::ParseError)
     }
 
-    pub fn get_member(&self, user_id: &UserId) -> Option<Arc<CachedMember>> {
-        self.bot_context.cache.get_member(&self.guild.id, user_id)
+    /// Members of lazily cached guilds that aren't cached yet are fetched from the api.
+    pub async fn get_member(&self, user_id: &UserId) -> Result<Option<Arc<CachedMember>>, CommandError> {
+        let guild_id = match self.guild.as_ref() {
+            Some(guild) => guild.id,
+            None => return Ok(None),
+        };
+        Ok(self.bot_context.fetch_member(guild_id, *user_id).await?)
     }
 
     pub fn get_channel(&self, channel_id: ChannelId) -> Option<Arc<CachedChannel>> {
@@ -27,14 +32,11 @@ impl CommandContext {
     }
 
     pub fn get_role(&self, role_id: &RoleId) -> Option<Arc<CachedRole>> {
-        match self.guild.get_role(role_id) {
-            Some(guard) => Some(guard),
-            None => None,
-        }
+        self.guild.as_ref()?.get_role(role_id)
     }
 
     pub async fn get_ban(&self, user_id: UserId) -> Result<Option<Ban>, CommandError> {
-        Ok(self.bot_context.http.ban(self.guild.id, user_id).await?)
+        Ok(self.bot_context.http.ban(self.get_guild()?.id, user_id).await?)
     }
 
     pub async fn get_dm_for_author(&self) -> Result<Arc<CachedChannel>, twilight_http::Error> {
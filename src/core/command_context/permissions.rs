@@ -4,6 +4,9 @@ use twilight_model::{
 };
 
 use super::CommandContext;
+use crate::cache::CachedMember;
+use crate::commands::meta::nodes::GearBotPermissions;
+use crate::error::{CommandError, ModerationError};
 
 impl CommandContext {
     pub fn bot_has_guild_permissions(&self, permissions: Permissions) -> bool {
@@ -16,7 +19,10 @@ impl CommandContext {
     }
 
     pub fn get_guild_permissions_for(&self, user_id: &UserId) -> Permissions {
-        self.bot_context.get_guild_permissions_for(&self.guild.id, user_id)
+        match &self.guild {
+            Some(guild) => self.bot_context.get_guild_permissions_for(&guild.id, user_id),
+            None => Permissions::empty(),
+        }
     }
 
     pub fn get_bot_channel_permissions(&self) -> Permissions {
@@ -24,8 +30,7 @@ impl CommandContext {
     }
 
     pub fn get_bot_permissions_for_channel(&self, channel_id: ChannelId) -> Permissions {
-        self.bot_context
-            .get_channel_permissions_for(self.get_bot_user().id, channel_id)
+        self.bot_context.get_bot_permissions_for_channel(channel_id)
     }
 
     pub fn has_channel_permissions(&self, user_id: UserId, channel_id: ChannelId, permissions: Permissions) -> bool {
@@ -58,4 +63,34 @@ impl CommandContext {
     pub fn author_has_guild_permissions(&self, permissions: Permissions) -> bool {
         self.get_author_guild_permissions().contains(permissions)
     }
+
+    /// Makes sure both the author and the bot are above the member in the role hierarchy.
+    /// Bot admins and members with one of the guild's bypass roles don't have to wait out command cooldowns.
+    pub fn bypasses_cooldowns(&self) -> bool {
+        if self.permissions.contains(GearBotPermissions::BOT_ADMIN) {
+            return true;
+        }
+
+        match (&self.guild, &self.message.author_as_member) {
+            (Some(_), Some(member)) => member
+                .roles
+                .iter()
+                .any(|role_id| self.config.cooldown_bypass_roles.contains(role_id)),
+            _ => false,
+        }
+    }
+
+    pub fn check_hierarchy(&self, target: &CachedMember) -> Result<(), CommandError> {
+        let guild = self.get_guild()?;
+        let author = self.message.get_author_as_member()?;
+        let bot = self.bot_context.cache.get_member(&guild.id, &self.get_bot_user().id);
+
+        guild.can_act_on(&author, target, bot.as_deref()).map_err(|reason| {
+            ModerationError::Hierarchy {
+                target: target.user_id,
+                reason,
+            }
+            .into()
+        })
+    }
 }
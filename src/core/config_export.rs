@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use twilight_model::id::{ChannelId, GuildId, RoleId};
+
+use crate::cache::CachedGuild;
+use crate::core::guild_config::{AccountAgeAction, StarboardConfig};
+use crate::core::GuildConfig;
+
+/// A guild config as it gets exported, along with the names of the roles and channels it points to.
+///
+/// Ids are only valid in the guild they came from, the names are used to find their counterparts
+/// when importing the config into a different guild.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ConfigExport {
+    pub guild_id: GuildId,
+    pub config: GuildConfig,
+    #[serde(default)]
+    pub roles: HashMap<RoleId, String>,
+    #[serde(default)]
+    pub channels: HashMap<ChannelId, String>,
+}
+
+impl ConfigExport {
+    pub fn new(guild: &CachedGuild, config: GuildConfig) -> Self {
+        let roles = referenced_roles(&config)
+            .into_iter()
+            .filter_map(|role_id| guild.get_role(&role_id))
+            .map(|role| (role.id, role.name.clone()))
+            .collect();
+
+        let guild_channels = guild.channels.read().expect("Guild inner channels cache got poisoned!");
+        let channels = referenced_channels(&config)
+            .into_iter()
+            .filter_map(|channel_id| guild_channels.get(&channel_id))
+            .map(|channel| (channel.get_id(), channel.get_name().to_string()))
+            .collect();
+
+        ConfigExport {
+            guild_id: guild.id,
+            config,
+            roles,
+            channels,
+        }
+    }
+
+    /// Points the config at the roles and channels of the guild it is being imported into.
+    ///
+    /// Anything that can't be found by id or (unambiguous) name is left out, those are returned
+    /// along with the config so they can be reported.
+    pub fn into_config_for(self, guild: &CachedGuild) -> (GuildConfig, Vec<String>) {
+        let ConfigExport {
+            mut config,
+            roles,
+            channels,
+            ..
+        } = self;
+        let mut skipped = vec![];
+
+        {
+            let guild_roles = guild.roles.read().expect("Global role cache got poisoned!");
+            let find_role = |role_id: RoleId| {
+                if guild_roles.contains_key(&role_id) {
+                    return Some(role_id);
+                }
+                let name = roles.get(&role_id)?;
+                let mut matches = guild_roles.values().filter(|role| &role.name == name);
+                match (matches.next(), matches.next()) {
+                    (Some(role), None) => Some(role.id),
+                    _ => None,
+                }
+            };
+
+            for group in &mut config.permission_groups {
+                let mut remapped = vec![];
+                for role_id in &group.roles {
+                    match find_role(*role_id) {
+                        Some(role_id) => remapped.push(role_id),
+                        None => skipped.push(format!("@{}", describe(roles.get(role_id), role_id.0))),
+                    }
+                }
+                group.roles = remapped;
+            }
+
+            let mut remapped = vec![];
+            for role_id in &config.cooldown_bypass_roles {
+                match find_role(*role_id) {
+                    Some(role_id) => remapped.push(role_id),
+                    None => skipped.push(format!("@{}", describe(roles.get(role_id), role_id.0))),
+                }
+            }
+            config.cooldown_bypass_roles = remapped;
+
+            if let AccountAgeAction::AssignRole(role_id) = config.account_age_gate.action {
+                match find_role(role_id) {
+                    Some(role_id) => config.account_age_gate.action = AccountAgeAction::AssignRole(role_id),
+                    None => {
+                        skipped.push(format!("@{}", describe(roles.get(&role_id), role_id.0)));
+                        // without the role there is nothing left to restrict them with
+                        config.account_age_gate.action = AccountAgeAction::Kick;
+                        config.account_age_gate.min_age_days = None;
+                    }
+                }
+            }
+        }
+
+        {
+            let guild_channels = guild.channels.read().expect("Guild inner channels cache got poisoned!");
+            let find_channel = |channel_id: ChannelId, skipped: &mut Vec<String>| {
+                if guild_channels.contains_key(&channel_id) {
+                    return Some(channel_id);
+                }
+                let found = channels.get(&channel_id).and_then(|name| {
+                    let mut matches = guild_channels.values().filter(|channel| channel.get_name() == name);
+                    match (matches.next(), matches.next()) {
+                        (Some(channel), None) => Some(channel.get_id()),
+                        _ => None,
+                    }
+                });
+                if found.is_none() {
+                    skipped.push(format!("#{}", describe(channels.get(&channel_id), channel_id.0)));
+                }
+                found
+            };
+
+            config.log_channels = std::mem::take(&mut config.log_channels)
+                .into_iter()
+                .filter_map(|(channel_id, log_config)| {
+                    find_channel(channel_id, &mut skipped).map(|channel_id| (channel_id, log_config))
+                })
+                .collect();
+
+            config.message_logs.ignored_channels = std::mem::take(&mut config.message_logs.ignored_channels)
+                .into_iter()
+                .filter_map(|channel_id| {
+                    find_channel(ChannelId(channel_id), &mut skipped).map(|channel_id| channel_id.0)
+                })
+                .collect();
+
+            config.message_logs.excluded_channels = std::mem::take(&mut config.message_logs.excluded_channels)
+                .into_iter()
+                .filter_map(|channel_id| find_channel(channel_id, &mut skipped))
+                .collect();
+
+            config.auto_pin_channels = std::mem::take(&mut config.auto_pin_channels)
+                .into_iter()
+                .filter_map(|channel_id| find_channel(channel_id, &mut skipped))
+                .collect();
+
+            let command_channels = &mut config.command_channels;
+            command_channels.allowed = std::mem::take(&mut command_channels.allowed)
+                .into_iter()
+                .filter_map(|channel_id| find_channel(channel_id, &mut skipped))
+                .collect();
+            command_channels.ignored = std::mem::take(&mut command_channels.ignored)
+                .into_iter()
+                .filter_map(|channel_id| find_channel(channel_id, &mut skipped))
+                .collect();
+
+            config.starboard.channel = config
+                .starboard
+                .channel
+                .and_then(|channel_id| find_channel(channel_id, &mut skipped));
+
+            config.account_age_gate.notify_channel = config
+                .account_age_gate
+                .notify_channel
+                .and_then(|channel_id| find_channel(channel_id, &mut skipped));
+
+            config.welcome_image.channel = config
+                .welcome_image
+                .channel
+                .and_then(|channel_id| find_channel(channel_id, &mut skipped));
+        }
+
+        // custom emoji are stored by id, those only carry over if the guild has access to them
+        let emoji = &config.starboard.emoji;
+        if emoji
+            .parse::<u64>()
+            .map_or(false, |id| !guild.emoji.iter().any(|e| e.id.0 == id))
+        {
+            skipped.push(format!(":{}:", emoji));
+            config.starboard.emoji = StarboardConfig::default().emoji;
+        }
+
+        config.permission_groups.sort_by(|a, b| a.priority.cmp(&b.priority));
+
+        (config, skipped)
+    }
+}
+
+fn referenced_roles(config: &GuildConfig) -> Vec<RoleId> {
+    let mut roles: Vec<RoleId> = config
+        .permission_groups
+        .iter()
+        .flat_map(|group| group.roles.iter().copied())
+        .collect();
+    roles.extend(config.cooldown_bypass_roles.iter().copied());
+    if let AccountAgeAction::AssignRole(role_id) = config.account_age_gate.action {
+        roles.push(role_id);
+    }
+    roles
+}
+
+fn referenced_channels(config: &GuildConfig) -> Vec<ChannelId> {
+    let mut channels: Vec<ChannelId> = config.log_channels.keys().copied().collect();
+    channels.extend(config.message_logs.ignored_channels.iter().map(|id| ChannelId(*id)));
+    channels.extend(config.starboard.channel);
+    channels.extend(config.message_logs.excluded_channels.iter().copied());
+    channels.extend(config.auto_pin_channels.iter().copied());
+    channels.extend(config.command_channels.allowed.iter().copied());
+    channels.extend(config.command_channels.ignored.iter().copied());
+    channels.extend(config.account_age_gate.notify_channel);
+    channels.extend(config.welcome_image.channel);
+    channels
+}
+
+fn describe(name: Option<&String>, id: u64) -> String {
+    match name {
+        Some(name) => format!("{} ({})", name, id),
+        None => id.to_string(),
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+
+use crate::core::{BotContext, ConfigInvalidation, CONFIG_INVALIDATION_CHANNEL};
+use crate::gearbot_error;
+
+/// Wait before subscribing again after the subscription was lost.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Drops cached configs whenever another cluster announces it changed them, they get loaded again on next use.
+pub async fn run(ctx: Arc<BotContext>) {
+    let ctx = &ctx;
+    let mut resubscribing = false;
+    loop {
+        match ctx.datastore.cache_pool.subscribe(CONFIG_INVALIDATION_CHANNEL).await {
+            Ok(invalidations) => {
+                // anything announced while we weren't listening was missed, start over from the database
+                if resubscribing {
+                    ctx.forget_all_configs().await;
+                }
+
+                invalidations
+                    .for_each(|message| async move {
+                        match serde_json::from_slice::<ConfigInvalidation>(&message) {
+                            // guilds this cluster never cached have nothing to drop
+                            Ok(invalidation) if invalidation.cluster_id != ctx.scheme_info.cluster_id => {
+                                ctx.forget_config(invalidation.guild_id).await
+                            }
+                            Ok(_) => {}
+                            Err(e) => log::warn!("Received an invalid config invalidation: {}", e),
+                        }
+                    })
+                    .await;
+                log::warn!("Lost the config invalidation subscription, resubscribing");
+            }
+            Err(e) => gearbot_error!("Failed to subscribe to config invalidations: {}", e),
+        }
+
+        resubscribing = true;
+        tokio::time::delay_for(RESUBSCRIBE_DELAY).await;
+    }
+}
@@ -1,14 +1,34 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use twilight_embed_builder::{EmbedBuilder, EmbedColorError, EmbedFooterBuilder, ImageSource};
+use twilight_model::channel::ReactionType;
 use twilight_model::guild::Permissions;
-use twilight_model::id::{ChannelId, RoleId, UserId};
+use twilight_model::id::{ChannelId, GuildId, RoleId, UserId};
 use unic_langid::LanguageIdentifier;
 
 use crate::commands::meta::nodes::GearBotPermissions;
 use crate::core::logpump::{DataLessLogType, LogFilter};
+use crate::database::structures::ContentStorage;
 use crate::translation::DEFAULT_LANG;
 use std::collections::HashMap;
 
-#[derive(Deserialize, Serialize, Debug)]
+/// The color used for embeds when a guild didn't pick one itself.
+pub const DEFAULT_EMBED_COLOR: u32 = 0x00_cea2;
+
+/// Discord doesn't accept embed footers with more characters than this.
+pub const MAX_EMBED_FOOTER_LENGTH: usize = 2048;
+
+/// The longest we're willing to wait before cleaning up command responses.
+pub const MAX_RESPONSE_CLEANUP_DELAY: u64 = 300;
+
+/// The longest delete logs can be held back for, any longer and the logs fall too far behind.
+pub const MAX_DELETE_GRACE: u64 = 60;
+
+/// Discord doesn't accept nicknames with more characters than this.
+pub const MAX_NICKNAME_LENGTH: usize = 32;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GuildConfig {
     pub prefix: String,
     pub log_style: LogStyle,
@@ -16,9 +36,197 @@ pub struct GuildConfig {
     pub language: LanguageIdentifier,
     pub permission_groups: Vec<PermissionGroup>,
     pub log_channels: HashMap<ChannelId, LogChannelConfig>,
+    #[serde(default)]
+    pub automod: AutomodConfig,
+    #[serde(default)]
+    pub embed_color: Option<u32>,
+    #[serde(default)]
+    pub anti_raid: AntiRaidConfig,
+    /// Names of the top level commands that can not be used in this guild.
+    #[serde(default)]
+    pub disabled_commands: Vec<String>,
+    /// Names of the top level commands that get logged every time they are used successfully.
+    #[serde(default)]
+    pub logged_commands: Vec<String>,
+    /// Permission group needed for top level commands (and their subcommands) instead of the permissions
+    /// they normally require. Members of that group, or any group with a higher priority, can use them.
+    #[serde(default)]
+    pub command_overrides: HashMap<String, String>,
+    /// If the prefix and command names should be matched regardless of their casing.
+    #[serde(default)]
+    pub case_insensitive_commands: bool,
+    #[serde(default)]
+    pub starboard: StarboardConfig,
+    /// If moderation actions are refused when no reason is given for them.
+    #[serde(default)]
+    pub require_mod_reasons: bool,
+    /// IANA name of the timezone times are shown in, UTC when not set.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Branding put in the footer of the embeds we send, unless they need the footer for something else.
+    #[serde(default)]
+    pub embed_footer: Option<EmbedFooterConfig>,
+    /// Channels where the latest message always gets pinned, taking the place of the previous one.
+    #[serde(default)]
+    pub auto_pin_channels: Vec<ChannelId>,
+    #[serde(default)]
+    pub account_age_gate: AccountAgeGate,
+    /// Keep count of the reactions on messages, off by default as reactions come in at a high rate.
+    #[serde(default)]
+    pub track_reactions: bool,
+    /// Delete confirmations of commands after a while, so they don't clutter the channel.
+    #[serde(default)]
+    pub response_cleanup: Option<ResponseCleanup>,
+    /// Nickname the bot goes by in this guild, put back whenever it gets changed or reset.
+    #[serde(default)]
+    pub bot_nickname: Option<String>,
+    /// Welcomes users the very first time they talk in the guild.
+    #[serde(default)]
+    pub first_message_greeting: Option<FirstMessageGreeting>,
+    /// Send command responses as plain text instead of embeds.
+    #[serde(default)]
+    pub plaintext_responses: bool,
+    /// React to successful commands instead of replying to them, only errors still get a response.
+    #[serde(default)]
+    pub quiet_mode: bool,
+    /// How much users get to see when a command fails unexpectedly.
+    #[serde(default)]
+    pub error_verbosity: ErrorVerbosity,
+    /// Delete command messages shortly after they were handled, keeping the channels tidy.
+    #[serde(default)]
+    pub delete_command_messages: bool,
+    #[serde(default)]
+    pub welcome_image: WelcomeImageConfig,
+    /// Members with any of these roles don't have to wait out command cooldowns.
+    #[serde(default)]
+    pub cooldown_bypass_roles: Vec<RoleId>,
+    /// Channels commands are (not) answered in, anyone who can change the config can use commands anywhere.
+    #[serde(default)]
+    pub command_channels: CommandChannels,
+    /// Leave role menu messages as they are when one of their roles gets deleted, only the stored menu is updated.
+    #[serde(default)]
+    pub keep_role_menu_messages: bool,
+}
+
+impl GuildConfig {
+    /// The timezone of the guild, names that aren't (or no longer) known fall back to UTC.
+    pub fn get_timezone(&self) -> Tz {
+        self.timezone
+            .as_deref()
+            .and_then(|name| name.parse().ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Formats a point in time for display, in the timezone of the guild.
+    pub fn format_time(&self, time: DateTime<Utc>) -> String {
+        time.with_timezone(&self.get_timezone()).format("%F %T %Z").to_string()
+    }
+
+    /// An embed builder with the color and footer the guild picked for the embeds we send.
+    pub fn embed_builder(&self) -> Result<EmbedBuilder, EmbedColorError> {
+        let color = self.embed_color.unwrap_or(DEFAULT_EMBED_COLOR);
+        let mut builder = EmbedBuilder::new().color(color)?;
+
+        // a footer that somehow got past validation shouldn't break every embed
+        if let Some(footer) = &self.embed_footer {
+            if let Ok(mut footer_builder) = EmbedFooterBuilder::new(footer.text.clone()) {
+                if let Some(icon) = footer.icon_url.as_ref().and_then(|url| ImageSource::url(url).ok()) {
+                    footer_builder = footer_builder.icon_url(icon);
+                }
+                builder = builder.footer(footer_builder);
+            }
+        }
+
+        Ok(builder)
+    }
+
+    /// The channels that receive logs of the given category.
+    pub fn get_log_channels(&self, category: &LogCategory) -> Vec<ChannelId> {
+        self.log_channels
+            .iter()
+            .filter(|(_, config)| config.categories.contains(category))
+            .map(|(channel_id, _)| *channel_id)
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct CommandChannels {
+    /// Only answer commands in these channels, every channel is allowed when empty.
+    #[serde(default)]
+    pub allowed: Vec<ChannelId>,
+    /// Never answer commands in these channels.
+    #[serde(default)]
+    pub ignored: Vec<ChannelId>,
+}
+
+impl CommandChannels {
+    pub fn allows(&self, channel_id: ChannelId) -> bool {
+        (self.allowed.is_empty() || self.allowed.contains(&channel_id)) && !self.ignored.contains(&channel_id)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EmbedFooterConfig {
+    pub text: String,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+}
+
+/// The full error always ends up in the bot logs, along with the error id users are shown.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ErrorVerbosity {
+    /// Only a generic message and the error id, nothing about what went wrong internally.
+    Generic,
+    /// The error itself is included in the reply.
+    Detailed,
+}
+
+impl Default for ErrorVerbosity {
+    fn default() -> Self {
+        ErrorVerbosity::Generic
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ResponseCleanup {
+    pub delay_seconds: u64,
+    /// Also delete the message that invoked the command.
+    #[serde(default)]
+    pub include_command: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FirstMessageGreeting {
+    /// Sent in the channel the user talked in, ``{user}`` gets replaced with a mention of them.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// The unicode emoji, or custom emoji in chat format, to react to their message with.
+    #[serde(default)]
+    pub reaction: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WelcomeImageConfig {
+    /// Where members get welcomed with an image as they join, no images are made without one.
+    pub channel: Option<ChannelId>,
+    /// Link to the image that goes behind the avatar and text, a plain background is used when not set.
+    pub background: Option<String>,
+    /// Color of the name and member number, as 0xRRGGBB.
+    pub text_color: u32,
+}
+
+impl Default for WelcomeImageConfig {
+    fn default() -> Self {
+        WelcomeImageConfig {
+            channel: None,
+            background: None,
+            text_color: 0xFF_FFFF,
+        }
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PermissionGroup {
     pub priority: u8,
     pub name: String,
@@ -30,12 +238,307 @@ pub struct PermissionGroup {
     pub users: Vec<UserId>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct MessageLogs {
     pub enabled: bool,
     pub ignored_users: Vec<u64>,
     pub ignored_channels: Vec<u64>,
     pub ignore_bots: bool,
+    /// Only keep a salted hash of message content instead of the content itself.
+    ///
+    /// Duplicate content can still be recognized, but the content is lost for quotes and logs.
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// Don't store messages that invoke commands, those are rarely worth keeping as chat.
+    #[serde(default)]
+    pub ignore_commands: bool,
+    /// Store at most this many messages per second, anything over it is counted but not stored.
+    ///
+    /// Relieves the database on extremely busy guilds, at the cost of gaps in the message logs.
+    #[serde(default)]
+    pub max_stored_per_second: Option<u32>,
+    /// Seconds to hold back the log of a deleted message, it's dropped if the author posts
+    /// a near-identical message in the meantime (like when fixing a typo by reposting).
+    /// With privacy mode only the hash is around, so only exact reposts are recognized.
+    #[serde(default)]
+    pub delete_grace: Option<u64>,
+    /// What to do with system messages (joins, boosts, pins, ...) that don't have any content.
+    #[serde(default)]
+    pub empty_system_messages: SystemMessageStorage,
+    /// Never store message content at all, not even as a hash. Only who sent what when is kept.
+    #[serde(default)]
+    pub disable_content_storage: bool,
+    /// Channels messages never get stored for, not even without content.
+    #[serde(default)]
+    pub excluded_channels: Vec<ChannelId>,
+    /// Don't store any messages sent in channels marked as NSFW.
+    #[serde(default)]
+    pub exclude_nsfw_channels: bool,
+    /// Keep copies of attachments up to this many bytes, only when content is stored encrypted and the
+    /// bot is set up to store attachments. None are kept when not set.
+    #[serde(default)]
+    pub max_attachment_size: Option<u64>,
+}
+
+impl MessageLogs {
+    /// If messages sent in this channel are kept out of storage entirely.
+    pub fn excludes_channel(&self, channel_id: ChannelId, nsfw: bool) -> bool {
+        self.excluded_channels.contains(&channel_id) || (self.exclude_nsfw_channels && nsfw)
+    }
+
+    /// What part of the message content gets stored.
+    pub fn content_storage(&self) -> ContentStorage {
+        if self.disable_content_storage {
+            ContentStorage::Nothing
+        } else if self.privacy_mode {
+            ContentStorage::Hash
+        } else {
+            ContentStorage::Encrypted
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SystemMessageStorage {
+    /// Stored like any other message, but without content so there is nothing to encrypt.
+    Marker,
+    /// Not stored at all.
+    Skip,
+}
+
+impl Default for SystemMessageStorage {
+    fn default() -> Self {
+        SystemMessageStorage::Marker
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct AutomodConfig {
+    /// File extensions (without the leading dot) that are not allowed to be uploaded.
+    pub blocked_extensions: Vec<String>,
+    #[serde(default)]
+    pub spam: SpamConfig,
+    /// Sends uploaded attachments to the scanning service the bot is set up with, off when not set.
+    #[serde(default)]
+    pub attachment_scan: Option<AttachmentScanRule>,
+    /// Which action wins when a single message breaks several rules, heaviest first.
+    /// Actions that are left out keep their default order, after the ones listed here.
+    #[serde(default)]
+    pub action_precedence: Vec<AutomodActionKind>,
+    #[serde(default)]
+    pub links: LinkFilterConfig,
+}
+
+/// The default order actions win in, heaviest first.
+pub const DEFAULT_ACTION_PRECEDENCE: [AutomodActionKind; 5] = [
+    AutomodActionKind::Ban,
+    AutomodActionKind::Mute,
+    AutomodActionKind::Warn,
+    AutomodActionKind::Delete,
+    AutomodActionKind::Alert,
+];
+
+impl AutomodConfig {
+    /// The order actions win in for this guild, heaviest first.
+    pub fn action_order(&self) -> Vec<AutomodActionKind> {
+        let mut order: Vec<AutomodActionKind> = vec![];
+        for kind in self.action_precedence.iter().chain(DEFAULT_ACTION_PRECEDENCE.iter()) {
+            if !order.contains(kind) {
+                order.push(*kind);
+            }
+        }
+        order
+    }
+
+    /// Where the action ranks in the order of this guild, lower ranks win.
+    pub fn action_rank(&self, kind: AutomodActionKind) -> usize {
+        self.action_order()
+            .iter()
+            .position(|ranked| *ranked == kind)
+            .unwrap_or(DEFAULT_ACTION_PRECEDENCE.len())
+    }
+}
+
+/// The kinds of actions automod rules can take, regardless of their details.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+pub enum AutomodActionKind {
+    Ban,
+    Mute,
+    /// Delete and add a warning to their history.
+    Warn,
+    Delete,
+    /// Leave the message up, only log it.
+    Alert,
+}
+
+impl AutomodActionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AutomodActionKind::Ban => "ban",
+            AutomodActionKind::Mute => "mute",
+            AutomodActionKind::Warn => "warn",
+            AutomodActionKind::Delete => "delete",
+            AutomodActionKind::Alert => "alert",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        DEFAULT_ACTION_PRECEDENCE
+            .iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Spam checks, each of them is off unless it has a rule configured.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct SpamConfig {
+    /// The same message being sent this many times within the window.
+    pub duplicates: Option<SpamRule>,
+    /// Single messages mentioning this many users and roles, the window is not used for this one.
+    pub mentions: Option<SpamRule>,
+    /// This many messages being sent within the window.
+    pub rate: Option<SpamRule>,
+    /// Nearly the same message being sent in this many different channels within the window, all copies are removed.
+    pub cross_channel: Option<SpamRule>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SpamRule {
+    pub threshold: usize,
+    pub window_seconds: u64,
+    pub action: SpamAction,
+}
+
+/// What happens to spammers, the message that crossed the threshold is removed for all of them.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub enum SpamAction {
+    Delete,
+    /// Delete and add a warning to their history.
+    Warn,
+    /// Delete and time the spammer out for this many minutes.
+    Mute(i64),
+}
+
+/// Invite and link filters, each of them is off unless it has an action configured.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct LinkFilterConfig {
+    /// Invites to other servers, invites to this server are always allowed.
+    pub invites: Option<SpamAction>,
+    /// Servers that invites can be posted to besides this one.
+    #[serde(default)]
+    pub allowed_invite_guilds: Vec<GuildId>,
+    /// Links to any domain that isn't allowed, invites are left to the invite filter.
+    pub links: Option<SpamAction>,
+    /// Domains that can be linked to, their subdomains included.
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AttachmentScanRule {
+    pub action: ScanAction,
+    /// Remove messages that could not be scanned (service down, too slow, ...) instead of letting them through.
+    /// These are only removed, the action is reserved for attachments that were actually flagged.
+    #[serde(default)]
+    pub fail_closed: bool,
+}
+
+/// What happens to messages with attachments the scanning service flagged.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub enum ScanAction {
+    /// Remove the message.
+    Delete,
+    /// Leave the message up but log it for the moderators to look at.
+    Alert,
+    /// Remove the message and ban the author.
+    Ban,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AntiRaidConfig {
+    /// If join rates should be monitored to automatically enable raid mode.
+    pub detection_enabled: bool,
+    /// Amount of joins within the window that is considered a raid.
+    pub join_threshold: usize,
+    pub window_seconds: u64,
+    /// What to do while raid mode is active.
+    pub actions: Vec<AntiRaidAction>,
+    /// Accounts younger than this are kicked by ``AntiRaidAction::KickNewAccounts``.
+    pub min_account_age_days: i64,
+}
+
+impl Default for AntiRaidConfig {
+    fn default() -> Self {
+        AntiRaidConfig {
+            detection_enabled: false,
+            join_threshold: 10,
+            window_seconds: 10,
+            actions: vec![AntiRaidAction::KickNewAccounts],
+            min_account_age_days: 7,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct AccountAgeGate {
+    /// Accounts younger than this are handled as they join, the gate is off without it.
+    pub min_age_days: Option<i64>,
+    pub action: AccountAgeAction,
+    /// Channel moderators get told about gated members in, on top of the regular logs.
+    pub notify_channel: Option<ChannelId>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub enum AccountAgeAction {
+    Kick,
+    /// Give the member a restricted role instead, so they can still be let in by hand.
+    AssignRole(RoleId),
+}
+
+impl Default for AccountAgeAction {
+    fn default() -> Self {
+        AccountAgeAction::Kick
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StarboardConfig {
+    /// Where starred messages are reposted, the starboard is disabled without one.
+    pub channel: Option<ChannelId>,
+    /// The unicode emoji, or the id of a custom emoji, that counts as a star.
+    pub emoji: String,
+    /// How many stars a message needs before it is reposted.
+    pub threshold: u64,
+}
+
+impl StarboardConfig {
+    pub fn is_star(&self, emoji: &ReactionType) -> bool {
+        match emoji {
+            ReactionType::Custom { id, .. } => self.emoji == id.to_string(),
+            ReactionType::Unicode { name } => self.emoji == *name,
+        }
+    }
+}
+
+impl Default for StarboardConfig {
+    fn default() -> Self {
+        StarboardConfig {
+            channel: None,
+            emoji: String::from("⭐"),
+            threshold: 3,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub enum AntiRaidAction {
+    /// Kick accounts younger than the configured minimum age as they join.
+    KickNewAccounts,
+    /// Kick everyone who joins, a full lockdown.
+    KickAll,
+    /// Raise the server verification level to the highest one, restoring it afterwards.
+    RaiseVerification,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -44,12 +547,14 @@ pub enum LogStyle {
     Embed,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 pub enum LogCategory {
     GENERAL,
+    MESSAGES,
+    MEMBERS,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LogChannelConfig {
     pub categories: Vec<LogCategory>,
     pub disabled_keys: Vec<DataLessLogType>,
@@ -68,6 +573,15 @@ impl Default for GuildConfig {
                 ignored_users: vec![],
                 ignored_channels: vec![],
                 ignore_bots: true,
+                privacy_mode: false,
+                ignore_commands: false,
+                max_stored_per_second: None,
+                delete_grace: None,
+                empty_system_messages: SystemMessageStorage::Marker,
+                disable_content_storage: false,
+                excluded_channels: vec![],
+                exclude_nsfw_channels: false,
+                max_attachment_size: None,
             },
             language: DEFAULT_LANG,
             permission_groups: vec![
@@ -88,7 +602,15 @@ impl Default for GuildConfig {
                         | GearBotPermissions::EMOJI_LIST_COMMAND
                         | GearBotPermissions::MODERATION_GROUP
                         | GearBotPermissions::READ_CONFIG,
-                    denied_perms: GearBotPermissions::empty(),
+                    // removing warnings, mass bans, pruning, alt lookups, staff stats and history imports
+                    // are for administrators only
+                    denied_perms: GearBotPermissions::DELWARN_COMMAND
+                        | GearBotPermissions::CLEARWARNINGS_COMMAND
+                        | GearBotPermissions::MASSBAN_COMMAND
+                        | GearBotPermissions::PRUNE_COMMAND
+                        | GearBotPermissions::ALTS_COMMAND
+                        | GearBotPermissions::MODSTATS_COMMAND
+                        | GearBotPermissions::IMPORTHISTORY_COMMAND,
                     discord_perms: Some(Permissions::BAN_MEMBERS),
                     roles: vec![],
                     needs_all: false,
@@ -100,7 +622,14 @@ impl Default for GuildConfig {
                     granted_perms: GearBotPermissions::BASIC_GROUP
                         | GearBotPermissions::MODERATION_GROUP
                         | GearBotPermissions::MISC_GROUP
-                        | GearBotPermissions::GUILD_ADMIN_GROUP,
+                        | GearBotPermissions::GUILD_ADMIN_GROUP
+                        | GearBotPermissions::DELWARN_COMMAND
+                        | GearBotPermissions::CLEARWARNINGS_COMMAND
+                        | GearBotPermissions::MASSBAN_COMMAND
+                        | GearBotPermissions::PRUNE_COMMAND
+                        | GearBotPermissions::ALTS_COMMAND
+                        | GearBotPermissions::MODSTATS_COMMAND
+                        | GearBotPermissions::IMPORTHISTORY_COMMAND,
                     denied_perms: GearBotPermissions::empty(),
                     discord_perms: Some(Permissions::ADMINISTRATOR),
                     roles: vec![],
@@ -109,6 +638,31 @@ impl Default for GuildConfig {
                 },
             ],
             log_channels: HashMap::new(),
+            automod: AutomodConfig::default(),
+            embed_color: None,
+            anti_raid: AntiRaidConfig::default(),
+            disabled_commands: vec![],
+            logged_commands: vec![],
+            command_overrides: HashMap::new(),
+            case_insensitive_commands: false,
+            starboard: StarboardConfig::default(),
+            require_mod_reasons: false,
+            timezone: None,
+            embed_footer: None,
+            auto_pin_channels: vec![],
+            account_age_gate: AccountAgeGate::default(),
+            track_reactions: false,
+            response_cleanup: None,
+            bot_nickname: None,
+            first_message_greeting: None,
+            plaintext_responses: false,
+            quiet_mode: false,
+            error_verbosity: ErrorVerbosity::Generic,
+            delete_command_messages: false,
+            welcome_image: WelcomeImageConfig::default(),
+            cooldown_bypass_roles: vec![],
+            command_channels: CommandChannels::default(),
+            keep_role_menu_messages: false,
         }
     }
 }
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::BotContext;
+
+/// How often held back delete logs are checked for being due.
+const RELEASE_INTERVAL: Duration = Duration::from_secs(1);
+
+pub async fn run(ctx: Arc<BotContext>) {
+    let mut interval = tokio::time::interval(RELEASE_INTERVAL);
+    loop {
+        interval.tick().await;
+        ctx.release_held_deletes().await;
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use twilight_model::id::UserId;
+
+/// The kinds of moderation actions that can be imported, same as the ``historyType`` enum in the database.
+pub const HISTORY_TYPES: [&str; 10] = [
+    "note",
+    "warning",
+    "censor",
+    "mute",
+    "kick",
+    "cleankick",
+    "tempban",
+    "ban",
+    "forceban",
+    "unban",
+];
+
+/// Longest reason that gets imported, longer ones get the entry rejected rather than cut off.
+const MAX_IMPORTED_REASON_LENGTH: usize = 1024;
+
+/// Moderation history exported from another bot, in the format the ``importhistory`` command accepts:
+///
+/// ```json
+/// {
+///     "entries": [
+///         {
+///             "type": "warning",
+///             "user_id": "106354106196570112",
+///             "moderator_id": "140130139605434369",
+///             "timestamp": "2020-06-01T12:00:00Z",
+///             "reason": "Spamming in general",
+///             "end": null
+///         }
+///     ]
+/// }
+/// ```
+///
+/// ``type`` is one of note, warning, censor, mute, kick, cleankick, tempban, ban, forceban or unban.
+/// Ids are strings (numbers that big don't survive every json library), times are RFC 3339.
+/// ``reason`` is optional, as is ``end`` which is only meant for mutes and tempbans.
+#[derive(Deserialize, Debug)]
+pub struct HistoryImport {
+    // kept raw so one broken entry only gets itself rejected instead of the whole file
+    pub entries: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawImportedAction {
+    #[serde(rename = "type")]
+    kind: String,
+    user_id: String,
+    moderator_id: String,
+    timestamp: String,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+}
+
+/// An entry that passed validation, ready to be stored.
+#[derive(Debug)]
+pub struct ImportedAction {
+    pub kind: &'static str,
+    pub user_id: UserId,
+    pub mod_id: UserId,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+/// An entry that won't be imported, along with its position in the file (starting at 1) and why.
+#[derive(Debug)]
+pub struct RejectedEntry {
+    pub position: usize,
+    pub problem: String,
+}
+
+impl HistoryImport {
+    /// Splits the entries up in the ones that can be imported and the ones that can't.
+    pub fn validate(self) -> (Vec<ImportedAction>, Vec<RejectedEntry>) {
+        let now = Utc::now();
+        let mut accepted = vec![];
+        let mut rejected = vec![];
+        for (index, entry) in self.entries.into_iter().enumerate() {
+            match validate_entry(entry, now) {
+                Ok(action) => accepted.push(action),
+                Err(problem) => rejected.push(RejectedEntry {
+                    position: index + 1,
+                    problem,
+                }),
+            }
+        }
+
+        (accepted, rejected)
+    }
+}
+
+fn validate_entry(entry: serde_json::Value, now: DateTime<Utc>) -> Result<ImportedAction, String> {
+    let raw: RawImportedAction = serde_json::from_value(entry).map_err(|e| e.to_string())?;
+
+    let kind = HISTORY_TYPES
+        .iter()
+        .find(|kind| kind.eq_ignore_ascii_case(raw.kind.trim()))
+        .ok_or_else(|| format!("unknown type \"{}\"", raw.kind))?;
+    let user_id = parse_id(&raw.user_id, "user_id")?;
+    let mod_id = parse_id(&raw.moderator_id, "moderator_id")?;
+
+    let start = parse_time(&raw.timestamp, "timestamp")?;
+    if start > now {
+        return Err(String::from("timestamp is in the future"));
+    }
+
+    let end = match &raw.end {
+        Some(end) => {
+            let end = parse_time(end, "end")?;
+            if end < start {
+                return Err(String::from("end is before the timestamp"));
+            }
+            Some(end)
+        }
+        None => None,
+    };
+
+    let reason = raw
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if let Some(reason) = &reason {
+        if reason.chars().count() > MAX_IMPORTED_REASON_LENGTH {
+            return Err(format!(
+                "reason is longer than {} characters",
+                MAX_IMPORTED_REASON_LENGTH
+            ));
+        }
+    }
+
+    Ok(ImportedAction {
+        kind,
+        user_id,
+        mod_id,
+        start,
+        end,
+        reason,
+    })
+}
+
+fn parse_id(input: &str, field: &str) -> Result<UserId, String> {
+    match input.trim().parse::<u64>() {
+        Ok(id) if id > 0 => Ok(UserId(id)),
+        _ => Err(format!("{} \"{}\" is not a valid id", field, input)),
+    }
+}
+
+fn parse_time(input: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(input.trim())
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| format!("{} \"{}\" is not an RFC 3339 time", field, input))
+}
@@ -4,10 +4,12 @@ use std::sync::Arc;
 
 use flexi_logger::writers::LogWriter;
 use flexi_logger::{
-    colored_opt_format, Age, Cleanup, Criterion, DeferredNow, Duplicate, Logger, Naming, ReconfigurationHandle,
+    colored_opt_format, Age, Cleanup, Criterion, DeferredNow, Duplicate, FlexiLoggerError, LogSpecification, Logger,
+    Naming, ReconfigurationHandle,
 };
 use log::{Level, LevelFilter, Record};
 use once_cell::sync::OnceCell;
+use twilight_http::request::channel::webhook::ExecuteWebhook;
 use twilight_http::Client as HttpClient;
 use twilight_model::user::CurrentUser;
 
@@ -15,10 +17,10 @@ use crate::core::BotConfig;
 use crate::error::StartupError;
 use crate::gearbot_error;
 use crate::utils::Emoji;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 
-static LOGGER_HANDLE: OnceCell<ReconfigurationHandle> = OnceCell::new();
+static LOGGER_HANDLE: OnceCell<Mutex<ReconfigurationHandle>> = OnceCell::new();
 type LogQueue = Arc<RwLock<VecDeque<String>>>;
 
 struct WebhookLogger {
@@ -66,23 +68,28 @@ pub fn initialize(http: HttpClient, config: &BotConfig, user: CurrentUser) -> Re
         queue: info_queue.clone(),
     });
 
-    let log_init_status = LOGGER_HANDLE.set(
-        Logger::with_env_or_str("info")
-            .duplicate_to_stderr(Duplicate::Debug)
-            .log_to_file()
-            .directory("logs")
-            .format(colored_opt_format)
-            .o_timestamp(true)
-            .rotate(
-                Criterion::Age(Age::Day),
-                Naming::Timestamps,
-                Cleanup::KeepLogAndCompressedFiles(10, 30),
-            )
-            .add_writer("gearbot_important", gearbot_important)
-            .add_writer("gearbot_info", gearbot_info)
-            .start_with_specfile("logconfig.toml")
-            .map_err(|_| StartupError::NoLoggingSpec)?,
-    );
+    let logger = Logger::with_env_or_str(config.logging.filter.as_deref().unwrap_or("info"))
+        .duplicate_to_stderr(Duplicate::Debug)
+        .log_to_file()
+        .directory("logs")
+        .format(colored_opt_format)
+        .o_timestamp(true)
+        .rotate(
+            Criterion::Age(Age::Day),
+            Naming::Timestamps,
+            Cleanup::KeepLogAndCompressedFiles(10, 30),
+        )
+        .add_writer("gearbot_important", gearbot_important)
+        .add_writer("gearbot_info", gearbot_info);
+
+    // a filter in the config takes over, otherwise the spec file would overwrite it as soon as it gets touched
+    let handle = match config.logging.filter {
+        Some(_) => logger.start(),
+        None => logger.start_with_specfile("logconfig.toml"),
+    }
+    .map_err(|_| StartupError::NoLoggingSpec)?;
+
+    let log_init_status = LOGGER_HANDLE.set(Mutex::new(handle));
 
     if log_init_status.is_err() {
         gearbot_error!("The logging system was attempted to be initalized a second time!");
@@ -100,6 +107,17 @@ pub fn initialize(http: HttpClient, config: &BotConfig, user: CurrentUser) -> Re
     Ok(())
 }
 
+/// Replaces the active log levels, for example ``info, gearbot::database=debug``.
+pub fn set_log_filter(filter: &str) -> Result<(), FlexiLoggerError> {
+    let spec = LogSpecification::parse(filter)?;
+
+    if let Some(handle) = LOGGER_HANDLE.get() {
+        handle.lock().unwrap().set_new_spec(spec);
+    }
+
+    Ok(())
+}
+
 pub fn run_logging_queue(http: HttpClient, queue: LogQueue, url: String, user: Arc<CurrentUser>) {
     //TODO: when we get too far behind group into a file
     tokio::spawn(async move {
@@ -137,23 +155,30 @@ pub fn run_logging_queue(http: HttpClient, queue: LogQueue, url: String, user: A
     });
 }
 
+/// Sends a test message through a logging webhook, unlike the queue this reports every failure back.
+///
+/// An invalid url fails with [`WebhookTestFailure::InvalidUrl`] before anything is sent to discord.
+pub async fn test_webhook(http: &HttpClient, url: &str, user: &CurrentUser) -> Result<(), WebhookTestFailure> {
+    let message = format!("{} Webhook test, logs sent here will arrive", Emoji::Yes.for_chat());
+    build_webhook(http, url, user, &message)
+        .map_err(WebhookTestFailure::InvalidUrl)?
+        .await
+        .map_err(WebhookTestFailure::Execution)?;
+    Ok(())
+}
+
+pub enum WebhookTestFailure {
+    InvalidUrl(twilight_http::Error),
+    Execution(twilight_http::Error),
+}
+
 async fn send_webhook(
     http: &HttpClient,
     url: &str,
     user: &CurrentUser,
     message: &str,
 ) -> Result<(), twilight_http::Error> {
-    let executor = {
-        let raw = http
-            .execute_webhook_from_url(url)?
-            .content(message)
-            .username(&user.name);
-
-        match &user.avatar {
-            Some(avatar) => raw.avatar_url(format!("{}{}/{}.png", DISCORD_AVATAR_URL, &user.id, avatar)),
-            None => raw,
-        }
-    };
+    let executor = build_webhook(http, url, user, message)?;
 
     if let Err(e) = executor.await {
         log::error!("Log failure: {}", e);
@@ -161,6 +186,23 @@ async fn send_webhook(
     Ok(())
 }
 
+fn build_webhook<'a>(
+    http: &'a HttpClient,
+    url: &str,
+    user: &CurrentUser,
+    message: &str,
+) -> Result<ExecuteWebhook<'a>, twilight_http::Error> {
+    let raw = http
+        .execute_webhook_from_url(url)?
+        .content(message)
+        .username(&user.name);
+
+    Ok(match &user.avatar {
+        Some(avatar) => raw.avatar_url(format!("{}{}/{}.png", DISCORD_AVATAR_URL, &user.id, avatar)),
+        None => raw,
+    })
+}
+
 fn get_emoji(level: Level) -> Emoji {
     match level {
         Level::Error => Emoji::No,
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::unbounded_channel;
+use twilight_model::id::{ChannelId, GuildId};
+
+use super::{pump, LogData};
+use crate::core::BotContext;
+use crate::gearbot_error;
+
+/// Logs that failed to be delivered this many times are given up on.
+const MAX_DELIVERY_ATTEMPTS: u8 = 5;
+
+/// The most undelivered logs kept per cluster, once full the oldest ones are dropped to make room.
+const MAX_DEAD_LETTERS: usize = 1000;
+
+/// How long undelivered logs wait before they are tried again.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize, Serialize)]
+struct DeadLetter {
+    channel_id: ChannelId,
+    data: LogData,
+}
+
+fn dead_letter_key(ctx: &BotContext) -> String {
+    format!("logpump_dead_letters:{}", ctx.scheme_info.cluster_id)
+}
+
+/// Stores logs that could not be delivered to a channel in redis, so they can be tried again later.
+///
+/// Logs that failed too often already, or that got pushed out of the full buffer, are dropped.
+pub async fn dead_letter(ctx: &Arc<BotContext>, channel_id: ChannelId, batch: Vec<Arc<LogData>>) {
+    let total = batch.len();
+    let letters: Vec<DeadLetter> = batch
+        .into_iter()
+        .map(|data| {
+            let mut data = (*data).clone();
+            data.failed_attempts += 1;
+            data
+        })
+        .filter(|data| data.failed_attempts < MAX_DELIVERY_ATTEMPTS)
+        .map(|data| DeadLetter { channel_id, data })
+        .collect();
+
+    let mut dropped = total - letters.len();
+    if !letters.is_empty() {
+        match ctx
+            .datastore
+            .cache_pool
+            .push_bounded(&dead_letter_key(ctx), &letters, MAX_DEAD_LETTERS)
+            .await
+        {
+            Ok(trimmed) => dropped += trimmed,
+            Err(e) => {
+                gearbot_error!(
+                    "Failed to store {} undelivered logs for {}: {}",
+                    letters.len(),
+                    channel_id,
+                    e
+                );
+                dropped += letters.len();
+            }
+        }
+    }
+
+    if dropped > 0 {
+        log::warn!("Gave up on delivering {} logs", dropped);
+        ctx.stats.logpump_stats.dropped_logs.inc_by(dropped as i64);
+    }
+}
+
+/// Periodically hands the undelivered logs back to pumps for the channels they were meant for.
+pub async fn retry_dead_letters(ctx: Arc<BotContext>) {
+    let mut interval = tokio::time::interval(RETRY_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let letters: Vec<DeadLetter> = match ctx.datastore.cache_pool.take_list(&dead_letter_key(&ctx)).await {
+            Ok(letters) => letters,
+            Err(e) => {
+                gearbot_error!("Failed to load the undelivered logs: {}", e);
+                continue;
+            }
+        };
+        if letters.is_empty() {
+            continue;
+        }
+        log::info!("Retrying delivery of {} logs", letters.len());
+
+        let mut by_channel: HashMap<ChannelId, (GuildId, Vec<LogData>)> = HashMap::new();
+        for letter in letters {
+            let guild_id = letter.data.guild;
+            by_channel
+                .entry(letter.channel_id)
+                .or_insert_with(|| (guild_id, vec![]))
+                .1
+                .push(letter.data);
+        }
+
+        for (channel_id, (guild_id, logs)) in by_channel {
+            let (sender, receiver) = unbounded_channel();
+            ctx.stats.logpump_stats.pending_logs.add(logs.len() as i64);
+            for log in logs {
+                let _ = sender.send(Arc::new(log));
+            }
+            tokio::spawn(pump(ctx.clone(), receiver, guild_id, channel_id));
+        }
+    }
+}
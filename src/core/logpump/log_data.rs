@@ -1,10 +1,14 @@
 use crate::core::logpump::log_type::LogType;
+use serde::{Deserialize, Serialize};
 use twilight_model::id::{ChannelId, GuildId, UserId};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LogData {
     pub log_type: LogType,
     pub guild: GuildId,
     pub source_channel: Option<ChannelId>,
     pub source_user: UserId,
+    /// How many times delivering this log has failed so far.
+    #[serde(default)]
+    pub failed_attempts: u8,
 }
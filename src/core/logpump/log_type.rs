@@ -3,7 +3,7 @@ use crate::core::guild_config::{LogCategory, LogStyle};
 use crate::core::BotContext;
 use crate::error::MessageError;
 use crate::translation::{FluArgs, GearBotString};
-use crate::utils::Emoji;
+use crate::utils::{text, Emoji};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use twilight_embed_builder::{EmbedAuthorBuilder, EmbedBuilder, EmbedFooterBuilder, ImageSource};
@@ -11,20 +11,150 @@ use twilight_model::channel::embed::Embed;
 use twilight_model::id::ChannelId;
 use unic_langid::LanguageIdentifier;
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
+/// Members can have a lot of roles, past this many they no longer fit in a log.
+const MAX_LISTED_ROLES: usize = 40;
+/// Bulk delete archives longer than this are cut off in the log itself and attached in full.
+const MAX_INLINE_ARCHIVE: usize = 1800;
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 pub enum LogType {
-    CommandUsed { command: String },
+    CommandUsed {
+        command: String,
+    },
+    MessagesBulkDeleted {
+        count: usize,
+        archive: String,
+    },
+    RaidModeEnabled,
+    RaidModeDisabled,
+    MassBan {
+        count: usize,
+    },
+    MassUnban {
+        count: usize,
+    },
+    MemberTimedOut {
+        target: u64,
+        until: String,
+        reason: String,
+    },
+    MemberTimeoutRemoved {
+        target: u64,
+        reason: String,
+    },
+    MessageDeleted {
+        content: String,
+        #[serde(default)]
+        attachments: Vec<DeletedAttachment>,
+    },
+    MessageEdited {
+        diff: String,
+    },
+    NewAccountKicked {
+        target: u64,
+        age: String,
+    },
+    NewAccountRestricted {
+        target: u64,
+        age: String,
+        role: u64,
+    },
+    MembersPruned {
+        count: u64,
+        days: u64,
+    },
+    SpamDetected {
+        target: u64,
+        rule: String,
+        action: String,
+    },
+    AttachmentScanned {
+        target: u64,
+        files: String,
+        action: String,
+    },
+    MemberJoined {
+        age: String,
+    },
+    MemberLeft {
+        member_for: String,
+        roles: Vec<u64>,
+    },
+    RoleMenusCleanedUp {
+        role: u64,
+        count: usize,
+    },
+}
+
+/// An attachment of a deleted message.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct DeletedAttachment {
+    pub filename: String,
+    /// The copy that was kept of it, if the guild keeps those and it wasn't too big.
+    pub copy: Option<Vec<u8>>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 pub enum DataLessLogType {
     CommandUsed,
+    MessagesBulkDeleted,
+    RaidModeEnabled,
+    RaidModeDisabled,
+    MassBan,
+    MassUnban,
+    MemberTimedOut,
+    MemberTimeoutRemoved,
+    MessageDeleted,
+    MessageEdited,
+    NewAccountKicked,
+    NewAccountRestricted,
+    MembersPruned,
+    SpamDetected,
+    AttachmentScanned,
+    MemberJoined,
+    MemberLeft,
+    RoleMenusCleanedUp,
+}
+
+/// How much a log matters, when the logpump is backing up the least important ones are dropped first.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+pub enum LogPriority {
+    Low,
+    Normal,
+    High,
 }
 
 impl LogType {
+    pub fn priority(&self) -> LogPriority {
+        match self {
+            // these come in by the bucketload on busy guilds
+            LogType::CommandUsed { .. } => LogPriority::Low,
+            LogType::MessageDeleted { .. } | LogType::MessageEdited { .. } => LogPriority::Low,
+            LogType::MemberJoined { .. } | LogType::MemberLeft { .. } => LogPriority::Low,
+            LogType::MessagesBulkDeleted { .. } => LogPriority::Normal,
+            LogType::MemberTimedOut { .. } | LogType::MemberTimeoutRemoved { .. } => LogPriority::Normal,
+            LogType::NewAccountKicked { .. } | LogType::NewAccountRestricted { .. } => LogPriority::Normal,
+            LogType::SpamDetected { .. } | LogType::AttachmentScanned { .. } => LogPriority::Normal,
+            LogType::RaidModeEnabled | LogType::RaidModeDisabled => LogPriority::High,
+            LogType::MassBan { .. } | LogType::MassUnban { .. } => LogPriority::High,
+            LogType::MembersPruned { .. } => LogPriority::High,
+            LogType::RoleMenusCleanedUp { .. } => LogPriority::Normal,
+        }
+    }
+
     pub fn get_category(&self) -> LogCategory {
         match self {
             LogType::CommandUsed { .. } => LogCategory::GENERAL,
+            LogType::MessagesBulkDeleted { .. } => LogCategory::MESSAGES,
+            LogType::RaidModeEnabled | LogType::RaidModeDisabled => LogCategory::GENERAL,
+            LogType::MassBan { .. } | LogType::MassUnban { .. } => LogCategory::GENERAL,
+            LogType::MemberTimedOut { .. } | LogType::MemberTimeoutRemoved { .. } => LogCategory::GENERAL,
+            LogType::MessageDeleted { .. } | LogType::MessageEdited { .. } => LogCategory::MESSAGES,
+            LogType::NewAccountKicked { .. } | LogType::NewAccountRestricted { .. } => LogCategory::GENERAL,
+            LogType::MembersPruned { .. } => LogCategory::GENERAL,
+            LogType::SpamDetected { .. } | LogType::AttachmentScanned { .. } => LogCategory::GENERAL,
+            LogType::MemberJoined { .. } | LogType::MemberLeft { .. } => LogCategory::MEMBERS,
+            LogType::RoleMenusCleanedUp { .. } => LogCategory::GENERAL,
         }
     }
 
@@ -37,8 +167,7 @@ impl LogType {
     ) -> Result<Embed, MessageError> {
         Ok(match self {
             LogType::CommandUsed { command } => {
-                let mut command = command.to_string();
-                command.truncate(1800);
+                let command = text::truncate(command, 1800);
                 EmbedBuilder::new()
                     .description(
                         ctx.translate_with_args(
@@ -61,6 +190,321 @@ impl LogType {
                             .icon_url(ImageSource::url(self.emoji().url())?),
                     )
             }
+            LogType::MessagesBulkDeleted { count, archive } => {
+                let archive = archive.replace("`", "ˋ");
+                let archive = text::truncate(&archive, MAX_INLINE_ARCHIVE);
+                EmbedBuilder::new()
+                    .description(
+                        ctx.translate_with_args(
+                            lang,
+                            GearBotString::MessagesBulkDeletedEmbed,
+                            &FluArgs::with_capacity(3)
+                                .add("count", *count)
+                                .add("channel_id", channel.unwrap().to_string())
+                                .add("archive", archive)
+                                .generate(),
+                        ),
+                    )?
+                    .footer(
+                        EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MessagesBulkDeletedFooter))?
+                            .icon_url(ImageSource::url(self.emoji().url())?),
+                    )
+            }
+            LogType::RaidModeEnabled | LogType::RaidModeDisabled => {
+                let (description, footer) = match self {
+                    LogType::RaidModeEnabled => (
+                        GearBotString::RaidModeEnabledEmbed,
+                        GearBotString::RaidModeEnabledFooter,
+                    ),
+                    _ => (
+                        GearBotString::RaidModeDisabledEmbed,
+                        GearBotString::RaidModeDisabledFooter,
+                    ),
+                };
+                EmbedBuilder::new()
+                    .description(ctx.translate(lang, description))?
+                    .author(
+                        EmbedAuthorBuilder::new()
+                            .name(user.full_name_with_id())?
+                            .url(user.profile_link()),
+                    )
+                    .footer(
+                        EmbedFooterBuilder::new(ctx.translate(lang, footer))?
+                            .icon_url(ImageSource::url(self.emoji().url())?),
+                    )
+            }
+            LogType::MassBan { count } | LogType::MassUnban { count } => {
+                let (description, footer) = match self {
+                    LogType::MassBan { .. } => (GearBotString::MassBanEmbed, GearBotString::MassBanFooter),
+                    _ => (GearBotString::MassUnbanEmbed, GearBotString::MassUnbanFooter),
+                };
+                EmbedBuilder::new()
+                    .description(ctx.translate_with_args(
+                        lang,
+                        description,
+                        &FluArgs::with_capacity(1).add("count", *count).generate(),
+                    ))?
+                    .author(
+                        EmbedAuthorBuilder::new()
+                            .name(user.full_name_with_id())?
+                            .url(user.profile_link()),
+                    )
+                    .footer(
+                        EmbedFooterBuilder::new(ctx.translate(lang, footer))?
+                            .icon_url(ImageSource::url(self.emoji().url())?),
+                    )
+            }
+            LogType::MemberTimedOut { target, until, reason } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::MemberTimedOutEmbed,
+                        &FluArgs::with_capacity(3)
+                            .add("target_id", target.to_string())
+                            .add("until", until.as_str())
+                            .add("reason", reason.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MemberTimedOutFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::MemberTimeoutRemoved { target, reason } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::MemberTimeoutRemovedEmbed,
+                        &FluArgs::with_capacity(2)
+                            .add("target_id", target.to_string())
+                            .add("reason", reason.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MemberTimeoutRemovedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::MessageDeleted { content, .. } | LogType::MessageEdited { diff: content } => {
+                let (description, footer, value) = match self {
+                    LogType::MessageDeleted { .. } => (
+                        GearBotString::MessageDeletedEmbed,
+                        GearBotString::MessageDeletedFooter,
+                        "content",
+                    ),
+                    _ => (
+                        GearBotString::MessageEditedEmbed,
+                        GearBotString::MessageEditedFooter,
+                        "diff",
+                    ),
+                };
+                let content = content.replace("`", "ˋ");
+                let content = text::truncate(&content, 1800);
+                let attachments = match self {
+                    LogType::MessageDeleted { attachments, .. } => list_attachments(ctx, lang, attachments),
+                    _ => String::new(),
+                };
+                EmbedBuilder::new()
+                    .description(
+                        ctx.translate_with_args(
+                            lang,
+                            description,
+                            &FluArgs::with_capacity(4)
+                                .add("user_id", user.id.to_string())
+                                .add("channel_id", channel.unwrap().to_string())
+                                .add(value, content)
+                                .add("attachments", attachments)
+                                .generate(),
+                        ),
+                    )?
+                    .author(
+                        EmbedAuthorBuilder::new()
+                            .name(user.full_name_with_id())?
+                            .url(user.profile_link()),
+                    )
+                    .thumbnail(ImageSource::url(user.avatar_url())?)
+                    .footer(
+                        EmbedFooterBuilder::new(ctx.translate(lang, footer))?
+                            .icon_url(ImageSource::url(self.emoji().url())?),
+                    )
+            }
+            LogType::NewAccountKicked { target, age } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::NewAccountKickedEmbed,
+                        &FluArgs::with_capacity(2)
+                            .add("target_id", target.to_string())
+                            .add("age", age.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::NewAccountGatedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::NewAccountRestricted { target, age, role } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::NewAccountRestrictedEmbed,
+                        &FluArgs::with_capacity(3)
+                            .add("target_id", target.to_string())
+                            .add("age", age.as_str())
+                            .add("role_id", role.to_string())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::NewAccountGatedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::MembersPruned { count, days } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::MembersPrunedEmbed,
+                        &FluArgs::with_capacity(2)
+                            .add("count", *count)
+                            .add("days", *days)
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MembersPrunedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::SpamDetected { target, rule, action } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::SpamDetectedEmbed,
+                        &FluArgs::with_capacity(3)
+                            .add("target_id", target.to_string())
+                            .add("rule", rule.as_str())
+                            .add("action", action.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::SpamDetectedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::AttachmentScanned { target, files, action } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::AttachmentScannedEmbed,
+                        &FluArgs::with_capacity(4)
+                            .add("target_id", target.to_string())
+                            .add("channel_id", channel.unwrap().to_string())
+                            .add("files", files.replace("`", "ˋ"))
+                            .add("action", action.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::AttachmentScannedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::MemberJoined { age } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::MemberJoinedEmbed,
+                        &FluArgs::with_capacity(2)
+                            .add("user_id", user.id.to_string())
+                            .add("age", age.as_str())
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .thumbnail(ImageSource::url(user.avatar_url())?)
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MemberJoinedFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::MemberLeft { member_for, roles } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::MemberLeftEmbed,
+                        &FluArgs::with_capacity(3)
+                            .add("user_id", user.id.to_string())
+                            .add("member_for", member_for.as_str())
+                            .add("roles", role_list(ctx, lang, roles))
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .thumbnail(ImageSource::url(user.avatar_url())?)
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::MemberLeftFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
+            LogType::RoleMenusCleanedUp { role, count } => EmbedBuilder::new()
+                .description(
+                    ctx.translate_with_args(
+                        lang,
+                        GearBotString::RoleMenusCleanedUpEmbed,
+                        &FluArgs::with_capacity(2)
+                            .add("role_id", role.to_string())
+                            .add("count", *count)
+                            .generate(),
+                    ),
+                )?
+                .author(
+                    EmbedAuthorBuilder::new()
+                        .name(user.full_name_with_id())?
+                        .url(user.profile_link()),
+                )
+                .footer(
+                    EmbedFooterBuilder::new(ctx.translate(lang, GearBotString::RoleMenusCleanedUpFooter))?
+                        .icon_url(ImageSource::url(self.emoji().url())?),
+                ),
         }
         .timestamp(chrono::Utc::now().format("%+").to_string())
         .build()?)
@@ -75,34 +519,225 @@ impl LogType {
     ) -> String {
         match self {
             LogType::CommandUsed { command } => {
-                let mut command = command.clone();
-                command.truncate(1800);
+                let command = text::truncate(command, 1800);
                 let args = add_user_args(FluArgs::with_capacity(4), user)
                     .add("command", command.replace("`", "ˋ"))
                     .add("channel_id", channel.unwrap().to_string()); // we always have a channel for command executions
 
                 ctx.translate_with_args(lang, GearBotString::CommandUsedText, &args.generate())
             }
+            LogType::MessagesBulkDeleted { count, archive } => {
+                let archive = archive.replace("`", "ˋ");
+                let archive = text::truncate(&archive, MAX_INLINE_ARCHIVE);
+                let args = FluArgs::with_capacity(3)
+                    .add("count", *count)
+                    .add("channel_id", channel.unwrap().to_string()) // bulk deletes always happen in a channel
+                    .add("archive", archive);
+
+                ctx.translate_with_args(lang, GearBotString::MessagesBulkDeletedText, &args.generate())
+            }
+            LogType::RaidModeEnabled => {
+                let args = add_user_args(FluArgs::with_capacity(2), user);
+                ctx.translate_with_args(lang, GearBotString::RaidModeEnabledText, &args.generate())
+            }
+            LogType::RaidModeDisabled => {
+                let args = add_user_args(FluArgs::with_capacity(2), user);
+                ctx.translate_with_args(lang, GearBotString::RaidModeDisabledText, &args.generate())
+            }
+            LogType::MassBan { count } => {
+                let args = add_user_args(FluArgs::with_capacity(3), user).add("count", *count);
+                ctx.translate_with_args(lang, GearBotString::MassBanText, &args.generate())
+            }
+            LogType::MassUnban { count } => {
+                let args = add_user_args(FluArgs::with_capacity(3), user).add("count", *count);
+                ctx.translate_with_args(lang, GearBotString::MassUnbanText, &args.generate())
+            }
+            LogType::MemberTimedOut { target, until, reason } => {
+                let args = add_user_args(FluArgs::with_capacity(5), user)
+                    .add("target_id", target.to_string())
+                    .add("until", until.as_str())
+                    .add("reason", reason.replace("`", "ˋ"));
+                ctx.translate_with_args(lang, GearBotString::MemberTimedOutText, &args.generate())
+            }
+            LogType::MemberTimeoutRemoved { target, reason } => {
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("target_id", target.to_string())
+                    .add("reason", reason.replace("`", "ˋ"));
+                ctx.translate_with_args(lang, GearBotString::MemberTimeoutRemovedText, &args.generate())
+            }
+            LogType::MessageDeleted { content, attachments } => {
+                let content = content.replace("`", "ˋ");
+                let content = text::truncate(&content, 1800);
+                let args = add_user_args(FluArgs::with_capacity(5), user)
+                    .add("channel_id", channel.unwrap().to_string()) // messages always live in a channel
+                    .add("content", content)
+                    .add("attachments", list_attachments(ctx, lang, attachments));
+                ctx.translate_with_args(lang, GearBotString::MessageDeletedText, &args.generate())
+            }
+            LogType::MessageEdited { diff } => {
+                let diff = diff.replace("`", "ˋ");
+                let diff = text::truncate(&diff, 1800);
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("channel_id", channel.unwrap().to_string())
+                    .add("diff", diff);
+                ctx.translate_with_args(lang, GearBotString::MessageEditedText, &args.generate())
+            }
+            LogType::NewAccountKicked { target, age } => {
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("target_id", target.to_string())
+                    .add("age", age.as_str());
+                ctx.translate_with_args(lang, GearBotString::NewAccountKickedText, &args.generate())
+            }
+            LogType::NewAccountRestricted { target, age, role } => {
+                let args = add_user_args(FluArgs::with_capacity(5), user)
+                    .add("target_id", target.to_string())
+                    .add("age", age.as_str())
+                    .add("role_id", role.to_string());
+                ctx.translate_with_args(lang, GearBotString::NewAccountRestrictedText, &args.generate())
+            }
+            LogType::MembersPruned { count, days } => {
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("count", *count)
+                    .add("days", *days);
+                ctx.translate_with_args(lang, GearBotString::MembersPrunedText, &args.generate())
+            }
+            LogType::SpamDetected { target, rule, action } => {
+                let args = add_user_args(FluArgs::with_capacity(5), user)
+                    .add("target_id", target.to_string())
+                    .add("rule", rule.as_str())
+                    .add("action", action.as_str());
+                ctx.translate_with_args(lang, GearBotString::SpamDetectedText, &args.generate())
+            }
+            LogType::AttachmentScanned { target, files, action } => {
+                let args = add_user_args(FluArgs::with_capacity(6), user)
+                    .add("target_id", target.to_string())
+                    .add("channel_id", channel.unwrap().to_string()) // attachments are always uploaded in a channel
+                    .add("files", files.replace("`", "ˋ"))
+                    .add("action", action.as_str());
+                ctx.translate_with_args(lang, GearBotString::AttachmentScannedText, &args.generate())
+            }
+            LogType::MemberJoined { age } => {
+                let args = add_user_args(FluArgs::with_capacity(3), user).add("age", age.as_str());
+                ctx.translate_with_args(lang, GearBotString::MemberJoinedText, &args.generate())
+            }
+            LogType::MemberLeft { member_for, roles } => {
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("member_for", member_for.as_str())
+                    .add("roles", role_list(ctx, lang, roles));
+                ctx.translate_with_args(lang, GearBotString::MemberLeftText, &args.generate())
+            }
+            LogType::RoleMenusCleanedUp { role, count } => {
+                let args = add_user_args(FluArgs::with_capacity(4), user)
+                    .add("role_id", role.to_string())
+                    .add("count", *count);
+                ctx.translate_with_args(lang, GearBotString::RoleMenusCleanedUpText, &args.generate())
+            }
         }
     }
 
     pub fn emoji(&self) -> Emoji {
         match self {
             LogType::CommandUsed { .. } => Emoji::Online,
+            LogType::MessagesBulkDeleted { .. } => Emoji::Trash,
+            LogType::RaidModeEnabled => Emoji::Warn,
+            LogType::RaidModeDisabled => Emoji::Yes,
+            LogType::MassBan { .. } => Emoji::Warn,
+            LogType::MassUnban { .. } => Emoji::Yes,
+            LogType::MemberTimedOut { .. } => Emoji::Warn,
+            LogType::MemberTimeoutRemoved { .. } => Emoji::Yes,
+            LogType::MessageDeleted { .. } => Emoji::Trash,
+            LogType::MessageEdited { .. } => Emoji::Info,
+            LogType::NewAccountKicked { .. } | LogType::NewAccountRestricted { .. } => Emoji::Warn,
+            LogType::MembersPruned { .. } => Emoji::Trash,
+            LogType::SpamDetected { .. } => Emoji::Warn,
+            LogType::AttachmentScanned { .. } => Emoji::Bad,
+            LogType::MemberJoined { .. } => Emoji::Online,
+            LogType::MemberLeft { .. } => Emoji::Info,
+            LogType::RoleMenusCleanedUp { .. } => Emoji::Trash,
+        }
+    }
+
+    /// Files to send along with the log, for content that doesn't fit in the log itself.
+    pub fn attachments(&self, channel: &Option<ChannelId>) -> Vec<(String, Vec<u8>)> {
+        match self {
+            LogType::MessagesBulkDeleted { archive, .. } if archive.chars().count() > MAX_INLINE_ARCHIVE => {
+                let filename = match channel {
+                    Some(channel_id) => format!("bulk_delete_{}.txt", channel_id),
+                    None => String::from("bulk_delete.txt"),
+                };
+                vec![(filename, archive.clone().into_bytes())]
+            }
+            LogType::MessageDeleted { attachments, .. } => attachments
+                .iter()
+                .filter_map(|attachment| Some((attachment.filename.clone(), attachment.copy.clone()?)))
+                .collect(),
+            _ => vec![],
         }
     }
 
     pub fn dataless(&self) -> DataLessLogType {
         match self {
             Self::CommandUsed { .. } => DataLessLogType::CommandUsed,
+            Self::MessagesBulkDeleted { .. } => DataLessLogType::MessagesBulkDeleted,
+            Self::RaidModeEnabled => DataLessLogType::RaidModeEnabled,
+            Self::RaidModeDisabled => DataLessLogType::RaidModeDisabled,
+            Self::MassBan { .. } => DataLessLogType::MassBan,
+            Self::MassUnban { .. } => DataLessLogType::MassUnban,
+            Self::MemberTimedOut { .. } => DataLessLogType::MemberTimedOut,
+            Self::MemberTimeoutRemoved { .. } => DataLessLogType::MemberTimeoutRemoved,
+            Self::MessageDeleted { .. } => DataLessLogType::MessageDeleted,
+            Self::MessageEdited { .. } => DataLessLogType::MessageEdited,
+            Self::NewAccountKicked { .. } => DataLessLogType::NewAccountKicked,
+            Self::NewAccountRestricted { .. } => DataLessLogType::NewAccountRestricted,
+            Self::MembersPruned { .. } => DataLessLogType::MembersPruned,
+            Self::SpamDetected { .. } => DataLessLogType::SpamDetected,
+            Self::AttachmentScanned { .. } => DataLessLogType::AttachmentScanned,
+            Self::MemberJoined { .. } => DataLessLogType::MemberJoined,
+            Self::MemberLeft { .. } => DataLessLogType::MemberLeft,
+            Self::RoleMenusCleanedUp { .. } => DataLessLogType::RoleMenusCleanedUp,
         }
     }
 }
 
+/// One line per attachment of a deleted message, telling if its copy is sent along.
+fn list_attachments(ctx: &Arc<BotContext>, lang: &LanguageIdentifier, attachments: &[DeletedAttachment]) -> String {
+    attachments
+        .iter()
+        .map(|attachment| {
+            let key = match attachment.copy {
+                Some(_) => GearBotString::DeletedAttachmentKept,
+                None => GearBotString::DeletedAttachmentNotKept,
+            };
+            let args = FluArgs::with_capacity(1)
+                .add("filename", attachment.filename.replace("`", "ˋ"))
+                .generate();
+            format!("\n{}", ctx.translate_with_args(lang, key, &args))
+        })
+        .collect()
+}
+
 fn add_user_args<'a>(args: FluArgs<'a>, user: &Arc<CachedUser>) -> FluArgs<'a> {
     args.add("name", user.full_name()).add("user_id", user.id.to_string())
 }
 
+/// Mentions the roles, these don't ping in logs but do render with their name and color.
+fn role_list(ctx: &Arc<BotContext>, lang: &LanguageIdentifier, roles: &[u64]) -> String {
+    if roles.is_empty() {
+        return ctx.translate(lang, GearBotString::MemberLeftNoRoles);
+    }
+
+    let mentions: Vec<String> = roles
+        .iter()
+        .take(MAX_LISTED_ROLES)
+        .map(|role_id| format!("<@&{}>", role_id))
+        .collect();
+    let mut list = mentions.join(", ");
+    if roles.len() > MAX_LISTED_ROLES {
+        list += &format!(" (+{})", roles.len() - MAX_LISTED_ROLES);
+    }
+    list
+}
+
 impl LogStyle {
     pub fn get_fallback(&self) -> Option<Self> {
         match self {
@@ -1,34 +1,43 @@
+mod dead_letters;
 mod log_data;
 mod log_filter;
 mod log_type;
 
 const GEARBOT_LOGO: &str = include_str!("../../../assets/logo");
 
+pub use dead_letters::retry_dead_letters;
 pub use log_data::LogData;
 pub use log_filter::LogFilter;
 pub use log_type::DataLessLogType;
+pub use log_type::DeletedAttachment;
+pub use log_type::LogPriority;
 pub use log_type::LogType;
 
 use crate::core::bot_context::BotContext;
 use crate::core::guild_config::LogStyle;
-use crate::error::OtherFailure;
+use crate::core::MAX_MESSAGE_LENGTH;
+use crate::error::{ApiRequestError, OtherFailure};
 use crate::gearbot_error;
+use crate::utils::text;
+use chrono_tz::Tz;
+use dead_letters::dead_letter;
 use hyper::StatusCode;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::mpsc::{unbounded_channel, Receiver, UnboundedReceiver, UnboundedSender};
 use twilight_http::Error;
 use twilight_model::guild::Permissions;
 use twilight_model::id::{ChannelId, GuildId, WebhookId};
 use unic_langid::LanguageIdentifier;
 
-pub async fn run(ctx: Arc<BotContext>, mut receiver: UnboundedReceiver<LogData>) {
+pub async fn run(ctx: Arc<BotContext>, mut receiver: Receiver<LogData>) {
     log::info!("Logpump started!");
     let mut outputs: HashMap<ChannelId, UnboundedSender<Arc<LogData>>> = HashMap::new();
     loop {
         //it's impossible to drop the sender at this time
         let log = Arc::new(receiver.recv().await.unwrap());
+        ctx.log_received();
         log::debug!("log data received: {:?}", log);
         let guild_id = log.guild;
         match ctx.get_config(guild_id).await {
@@ -136,14 +145,21 @@ async fn pump(
                             &config.language,
                             channel_id,
                             &mut webhook_info,
-                            channel_config.timestamps,
+                            if channel_config.timestamps {
+                                Some(config.get_timezone())
+                            } else {
+                                None
+                            },
                         )
                         .await
                         {
-                            gearbot_error!("Logpump failure: {}", e)
+                            if e.http_error().is_some() {
+                                gearbot_error!("Logpump failure: {}", e)
+                            }
                         }
                     } else {
                         //we can't log anything, hit the self-destruct
+                        ctx.stats.logpump_stats.dropped_logs.inc_by(todo.len() as i64);
                         break 'outer;
                     }
                 } else {
@@ -151,7 +167,10 @@ async fn pump(
                         "Channel {} (in guild {}) was removed as log channel but something still tried to log to it!",
                         channel_id,
                         guild_id
-                    )
+                    );
+                    // these will never go anywhere
+                    ctx.stats.logpump_stats.dropped_logs.inc_by(todo.len() as i64);
+                    break 'outer;
                 }
             }
             Err(e) => gearbot_error!("Failed to retrieve guild config {}: {}", guild_id, e),
@@ -174,7 +193,7 @@ async fn can_send(
 ) -> Result<bool, OtherFailure> {
     let ok = match style {
         LogStyle::Text => ctx
-            .get_channel_permissions_for(ctx.bot_user.id, *channel_id)
+            .get_bot_permissions_for_channel(*channel_id)
             .contains(Permissions::SEND_MESSAGES),
         LogStyle::Embed => {
             if webhook_info.is_none() {
@@ -187,7 +206,48 @@ async fn can_send(
     Ok(ok)
 }
 
-async fn get_webhook(
+/// Removes stored webhooks that were deleted while we weren't looking, so the table doesn't keep growing.
+///
+/// This goes over the webhooks of all clusters so it should only run on one of them.
+pub async fn prune_webhooks(ctx: Arc<BotContext>) {
+    let webhooks = match ctx.datastore.get_all_webhooks().await {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            gearbot_error!("Failed to load the webhooks to prune: {}", e);
+            return;
+        }
+    };
+
+    let mut removed = 0;
+    for webhook in webhooks {
+        let missing = match ctx
+            .http
+            .webhook(WebhookId(webhook.id as u64))
+            .token(webhook.token)
+            .await
+        {
+            Ok(found) => found.is_none(),
+            Err(Error::Response { status, .. }) => status == StatusCode::NOT_FOUND,
+            Err(e) => {
+                log::warn!("Unable to verify webhook {}: {}", webhook.id, e);
+                false
+            }
+        };
+
+        if missing {
+            let channel_id = ChannelId(webhook.channel_id as u64);
+            match ctx.datastore.remove_webhook(channel_id).await {
+                Ok(()) => removed += 1,
+                Err(e) => gearbot_error!("Failed to remove webhook {} from the database: {}", channel_id, e),
+            }
+        }
+    }
+
+    log::info!("Webhook pruning done, removed {} dangling webhooks", removed);
+}
+
+/// The webhook stored for a channel, one is created (and stored) when there is none yet and we are allowed to.
+pub async fn get_webhook(
     ctx: &Arc<BotContext>,
     channel_id: &ChannelId,
 ) -> Result<Option<(WebhookId, String)>, OtherFailure> {
@@ -195,7 +255,7 @@ async fn get_webhook(
     if webhook_info.is_none() {
         //nope, can we make one?
         if ctx
-            .get_channel_permissions_for(ctx.bot_user.id, *channel_id)
+            .get_bot_permissions_for_channel(*channel_id)
             .contains(Permissions::MANAGE_WEBHOOKS)
         {
             let webhook = ctx
@@ -221,11 +281,12 @@ async fn send(
     language: &LanguageIdentifier,
     channel_id: ChannelId,
     webhook_info: &mut Option<(WebhookId, String)>,
-    timestamp: bool,
-) -> Result<(), twilight_http::Error> {
+    timestamp: Option<Tz>,
+) -> Result<(), ApiRequestError> {
     match log_style {
         LogStyle::Text => {
             let mut output = String::from("");
+            let mut batch = vec![];
             //grab leftovers from last iteration
             while let Some(item) = left_over.first() {
                 if output.len() + item.len() < 2000 {
@@ -250,34 +311,43 @@ async fn send(
                     }
                 };
 
-                let timestamp = if timestamp {
-                    format!("`[{}]`", chrono::Utc::now().format("%T").to_string())
-                } else {
-                    String::from("")
+                let timestamp = match timestamp {
+                    Some(timezone) => format!("`[{}]`", chrono::Utc::now().with_timezone(&timezone).format("%T")),
+                    None => String::from(""),
                 };
 
-                let mut extra = format!(
+                let extra = format!(
                     "{} {} {}",
                     timestamp,
                     item.log_type.emoji().for_chat(),
                     item.log_type.to_text(&ctx, language, &user, &item.source_channel)
                 );
-                extra.truncate(2000);
+                // leave room for the newline, so even a single huge log still goes out on its own
+                let extra = text::truncate(&extra, MAX_MESSAGE_LENGTH - 1);
                 //only add to the output and remove from todo if it actually fits
-                if output.len() + extra.len() < 2000 {
-                    output += &extra;
+                if output.chars().count() + extra.chars().count() < MAX_MESSAGE_LENGTH {
+                    output += extra;
                     output += "\n";
-                    todo.remove(0);
+                    batch.push(todo.remove(0));
                 } else {
                     //didn't fit, we're done here
                     break;
                 }
             }
             // assembly done, pack it into the future
-            ctx.http.create_message(channel_id).content(output).unwrap().await?;
+            let request = ctx.http.create_message(channel_id).content(output).unwrap();
+            if let Err(e) = ctx.queue_send(channel_id, request).await {
+                if let Some(http_error) = e.http_error() {
+                    ctx.track_ratelimit(http_error).await;
+                }
+                dead_letter(ctx, channel_id, batch).await;
+                return Err(e);
+            }
+            send_attachments(ctx, channel_id, &batch).await;
         }
         LogStyle::Embed => {
             let mut out = vec![];
+            let mut batch = vec![];
             while let Some(_) = todo.first() {
                 let data = todo.remove(0);
                 let user = match ctx.get_user(data.source_user).await {
@@ -292,6 +362,7 @@ async fn send(
                 match data.log_type.to_embed(&ctx, language, &user, &data.source_channel) {
                     Ok(embed) => {
                         out.push(embed);
+                        batch.push(data.clone());
                         if out.len() == 10 {
                             break;
                         }
@@ -302,9 +373,17 @@ async fn send(
                 }
             }
             let (webhook_id, token) = webhook_info.as_ref().unwrap();
-            if let Err(e) = ctx.http.execute_webhook(webhook_id.clone(), token).embeds(out).await {
+            let request = ctx.http.execute_webhook(webhook_id.clone(), token).embeds(out);
+            if let Err(e) = ctx.queue_send(channel_id, request).await {
+                if let Some(http_error) = e.http_error() {
+                    ctx.track_ratelimit(http_error).await;
+                }
+                // a new webhook gets made on the retry if this one is gone
+                dead_letter(ctx, channel_id, batch).await;
                 match e {
-                    Error::Response { status, .. } => {
+                    // dead lettered for later, no need to make noise about it
+                    ApiRequestError::CircuitOpen => {}
+                    ApiRequestError::Http(Error::Response { status, .. }) => {
                         if status == StatusCode::NOT_FOUND {
                             //webhook is gone, remove it
                             *webhook_info = None;
@@ -315,26 +394,64 @@ async fn send(
                     }
                     _ => gearbot_error!("Logpump failure: {}", e),
                 }
+            } else {
+                send_attachments(ctx, channel_id, &batch).await;
             }
         }
     }
     Ok(())
 }
 
+/// Sends the files of logs that didn't fit in the log itself, as separate messages after the logs.
+///
+/// Webhooks can't carry files, so these always go out as regular messages. Without the permissions for that
+/// the logs stay as they were sent, cut off.
+async fn send_attachments(ctx: &Arc<BotContext>, channel_id: ChannelId, batch: &[Arc<LogData>]) {
+    let attachments: Vec<(String, Vec<u8>)> = batch
+        .iter()
+        .flat_map(|data| data.log_type.attachments(&data.source_channel))
+        .collect();
+    if attachments.is_empty() {
+        return;
+    }
+
+    if !ctx
+        .get_bot_permissions_for_channel(channel_id)
+        .contains(Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES)
+    {
+        return;
+    }
+
+    for (filename, data) in attachments {
+        let request = ctx.http.create_message(channel_id).attachment(filename, data);
+        if let Err(e) = ctx.queue_send(channel_id, request).await {
+            // skipped while discord is struggling, those only get logged cut off
+            if let Some(http_error) = e.http_error() {
+                ctx.track_ratelimit(http_error).await;
+                gearbot_error!("Failed to attach a log file in {}: {}", channel_id, e)
+            }
+        }
+    }
+}
+
 async fn receive_up_to(count: usize, receiver: &mut UnboundedReceiver<Arc<LogData>>) -> Vec<Arc<LogData>> {
     let mut out = vec![];
-    if let Ok(log_data) = tokio::time::timeout(Duration::from_secs(6), receiver.recv()).await {
-        //since we never drop the sender, we can never get a none value
-        let log_data = log_data.unwrap();
-        out.push(log_data);
-        if count > 1 {
-            while let Ok(data) = receiver.try_recv() {
-                out.push(data);
-                if out.len() >= count {
-                    break;
+    match tokio::time::timeout(Duration::from_secs(6), receiver.recv()).await {
+        Ok(Some(log_data)) => {
+            out.push(log_data);
+            if count > 1 {
+                while let Ok(data) = receiver.try_recv() {
+                    out.push(data);
+                    if out.len() >= count {
+                        break;
+                    }
                 }
             }
         }
+        // the senders for retried dead letters are dropped once everything is queued up,
+        // still wait so a pump that can't deliver yet doesn't spin
+        Ok(None) => tokio::time::delay_for(Duration::from_secs(6)).await,
+        Err(_) => {}
     }
     out
 }
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::BotContext;
+use crate::gearbot_error;
+
+/// How often the maintenance state and globally disabled commands are checked for changes made by other clusters.
+const SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+pub async fn run(ctx: Arc<BotContext>) {
+    let mut interval = tokio::time::interval(SYNC_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = ctx.sync_maintenance().await {
+            gearbot_error!("Failed to sync the maintenance state: {}", e);
+        }
+        if let Err(e) = ctx.sync_disabled_commands().await {
+            gearbot_error!("Failed to sync the globally disabled commands: {}", e);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use twilight_model::gateway::payload::RequestGuildMembers;
+use twilight_model::id::GuildId;
+
+use crate::cache::LAZY_CHUNK_NONCE;
+use crate::core::BotContext;
+
+/// Requests that didn't complete by now probably got lost with a reconnect, so they are tried again.
+const RETRY_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// Slowly fills in the members of lazily cached guilds, one guild at a time.
+pub async fn run(ctx: Arc<BotContext>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    let mut requested: HashMap<GuildId, Instant> = HashMap::new();
+    loop {
+        interval.tick().await;
+        requested.retain(|_, requested_at| requested_at.elapsed() < RETRY_AFTER);
+
+        let next = ctx
+            .cache
+            .guilds
+            .read()
+            .expect("Global guild cache got poisoned!")
+            .values()
+            .find(|guild| guild.is_lazily_cached() && !requested.contains_key(&guild.id))
+            .map(|guild| guild.id);
+
+        if let Some(guild_id) = next {
+            requested.insert(guild_id, Instant::now());
+            let shard_id = (guild_id.0 >> 22) % ctx.scheme_info.total_shards;
+            let data = RequestGuildMembers::builder(guild_id)
+                .nonce(LAZY_CHUNK_NONCE)
+                .query("", None);
+            log::debug!(
+                "Requesting the members of lazily cached guild {} in the background",
+                guild_id
+            );
+            if let Err(e) = ctx.cluster.command(shard_id, &data).await {
+                log::warn!("Failed to request the members of {}: {}", guild_id, e);
+            }
+        }
+    }
+}
@@ -1,20 +1,45 @@
-pub use bot_config::BotConfig;
+pub use bot_config::{
+    AttachmentScanning, AttachmentStorage, BotConfig, OwnMessageStorage, StatusActivity, StatusRotation,
+};
 pub use cold_resume_data::ColdRebootData;
-pub use guild_config::GuildConfig;
+pub use guild_config::{
+    AccountAgeAction, AccountAgeGate, AntiRaidAction, AntiRaidConfig, AttachmentScanRule, AutomodActionKind,
+    EmbedFooterConfig, ErrorVerbosity, GuildConfig, LinkFilterConfig, LogCategory, LogChannelConfig, LogStyle,
+    ResponseCleanup, ScanAction, SpamAction, SystemMessageStorage, WelcomeImageConfig, MAX_DELETE_GRACE,
+    MAX_EMBED_FOOTER_LENGTH, MAX_NICKNAME_LENGTH, MAX_RESPONSE_CLEANUP_DELAY,
+};
 pub use reactors::Reactor;
 
 mod bot_config;
 mod cold_resume_data;
 
 mod bot_context;
-pub use bot_context::{status, BotContext, BotStats, ShardState};
+pub use bot_context::{
+    status, BotContext, BotStats, BotUser, ConfigInvalidation, DeletedContent, HandledEvent, MaintenanceState,
+    ScanVerdict, ShardState, TrackedMessage, CONFIG_INVALIDATION_CHANNEL, ERROR_WINDOW,
+};
 
 mod command_context;
-pub use command_context::{CommandContext, CommandMessage};
+pub use command_context::{CommandContext, CommandMessage, MAX_MESSAGE_LENGTH};
 
 mod guild_config;
 
+mod config_export;
+pub use config_export::ConfigExport;
+
+mod history_import;
+pub use history_import::{HistoryImport, ImportedAction, HISTORY_TYPES};
+
+pub mod announcements;
+pub mod config_sync;
+pub mod emoji_usage;
+pub mod held_deletes;
 pub mod logging;
 pub mod logpump;
+pub mod maintenance;
+pub mod member_chunking;
+pub mod reaction_counts;
+pub mod shard_watchdog;
+pub mod status_rotation;
 
 pub mod reactors;
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::BotContext;
+use crate::gearbot_error;
+
+/// How often the reaction counters are moved from Redis to the database.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+pub async fn run(ctx: Arc<BotContext>) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = ctx.flush_reaction_counts().await {
+            gearbot_error!("Failed to flush reaction counts: {}", e);
+        }
+    }
+}
@@ -0,0 +1,444 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use twilight_embed_builder::{EmbedFieldBuilder, EmbedFooterBuilder};
+use twilight_model::channel::embed::Embed;
+use twilight_model::channel::Reaction;
+use twilight_model::id::ChannelId;
+
+use crate::cache::CachedMember;
+use crate::core::bot_context::BotContext;
+use crate::core::guild_config::DEFAULT_EMBED_COLOR;
+use crate::core::reactors::{get_emoji, scroll_page};
+use crate::core::{AccountAgeAction, GuildConfig, LogCategory, SystemMessageStorage};
+use crate::error::{MessageError, ReactorError};
+use crate::utils::{self, text, Emoji};
+
+/// Embed fields can't hold more than this.
+const MAX_FIELD_LENGTH: usize = 1024;
+
+/// The overview is split in general, logging, moderation and community settings.
+const CONFIG_PAGES: u8 = 4;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ConfigReactor {
+    pub page: u8,
+}
+
+impl ConfigReactor {
+    pub fn processes(&self, reaction: &Reaction) -> Option<Emoji> {
+        get_emoji(vec![Emoji::Left, Emoji::Right], reaction)
+    }
+
+    pub async fn do_the_thing(
+        &mut self,
+        emoji: &Emoji,
+        ctx: &Arc<BotContext>,
+        member: Option<Arc<CachedMember>>,
+        reaction: &Reaction,
+    ) -> Result<(), ReactorError> {
+        if member.is_some() {
+            // If we have a cached member, we have a guild id
+            let config = ctx.get_config(reaction.guild_id.unwrap()).await?;
+            self.page = scroll_page(CONFIG_PAGES, self.page, emoji);
+            let embed = gen_config_page(self.page, &config)?;
+            let update = ctx.http.update_message(reaction.channel_id, reaction.message_id);
+            if config.plaintext_responses {
+                update.content(utils::embed_to_plaintext(&embed))?.await?;
+            } else {
+                update.embed(embed)?.await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders one page of the overview of a guild's settings, with how to change each of them.
+///
+/// Only settings from the guild config are shown, the guild's encryption key is not part of it.
+pub fn gen_config_page(page: u8, config: &GuildConfig) -> Result<Embed, MessageError> {
+    let prefix = &config.prefix;
+    let (title, settings) = match page {
+        0 => ("General", general_settings(config)),
+        1 => ("Logging", logging_settings(config)),
+        2 => ("Moderation", moderation_settings(config)),
+        _ => ("Community", community_settings(config)),
+    };
+
+    let mut builder = config
+        .embed_builder()?
+        .description(format!("**{} settings**", title))?
+        .footer(EmbedFooterBuilder::new(format!(
+            "Page {}/{}, settings without their own command can be changed with {}config export and {}config import",
+            page + 1,
+            CONFIG_PAGES,
+            prefix,
+            prefix
+        ))?);
+
+    for (name, value, command) in settings {
+        let value = match command {
+            Some(command) => format!("{}\n*Change with* ``{}config {}``", value, prefix, command),
+            None => value,
+        };
+        let value = text::truncate_with_ellipsis(&value, MAX_FIELD_LENGTH);
+        builder = builder.field(EmbedFieldBuilder::new(name, value)?.build());
+    }
+
+    Ok(builder.build()?)
+}
+
+type Setting = (&'static str, String, Option<&'static str>);
+
+fn general_settings(config: &GuildConfig) -> Vec<Setting> {
+    vec![
+        ("Prefix", format!("``{}``", config.prefix), None),
+        ("Language", config.language.to_string(), None),
+        (
+            "Timezone",
+            config.get_timezone().name().to_string(),
+            Some("timezone <name>"),
+        ),
+        (
+            "Case insensitive commands",
+            on_off(config.case_insensitive_commands),
+            None,
+        ),
+        (
+            "Disabled commands",
+            list_or_none(config.disabled_commands.iter().map(|name| format!("``{}``", name))),
+            None,
+        ),
+        (
+            "Plain text responses",
+            on_off(config.plaintext_responses),
+            Some("plaintext <on/off>"),
+        ),
+        ("Quiet mode", on_off(config.quiet_mode), Some("quiet <on/off>")),
+        (
+            "Delete commands",
+            on_off(config.delete_command_messages),
+            Some("deletecommands <on/off>"),
+        ),
+        (
+            "Command errors",
+            format!("{:?}", config.error_verbosity),
+            Some("errors <generic/detailed>"),
+        ),
+        (
+            "Response cleanup",
+            match &config.response_cleanup {
+                Some(cleanup) if cleanup.include_command => {
+                    format!("After {} seconds, commands included", cleanup.delay_seconds)
+                }
+                Some(cleanup) => format!("After {} seconds", cleanup.delay_seconds),
+                None => String::from("Off"),
+            },
+            Some("cleanup <seconds/off> [command]"),
+        ),
+        (
+            "Bot nickname",
+            config.bot_nickname.clone().unwrap_or_else(|| String::from("None")),
+            Some("nickname <nickname/off>"),
+        ),
+    ]
+}
+
+fn logging_settings(config: &GuildConfig) -> Vec<Setting> {
+    let message_logs = &config.message_logs;
+    let mut storage = vec![on_off(message_logs.enabled)];
+    if message_logs.disable_content_storage {
+        storage.push(String::from("no content"));
+    } else if message_logs.privacy_mode {
+        storage.push(String::from("privacy mode"));
+    }
+    if message_logs.ignore_bots {
+        storage.push(String::from("ignoring bots"));
+    }
+    if message_logs.ignore_commands {
+        storage.push(String::from("ignoring commands"));
+    }
+    if message_logs.empty_system_messages == SystemMessageStorage::Skip {
+        storage.push(String::from("skipping empty system messages"));
+    }
+    if let Some(limit) = message_logs.max_stored_per_second {
+        storage.push(format!("at most {} per second", limit));
+    }
+    if let Some(size) = message_logs.max_attachment_size {
+        storage.push(format!("attachments up to {} KB", size / 1024));
+    }
+    let mut excluded = message_logs
+        .excluded_channels
+        .iter()
+        .map(|channel_id| format!("<#{}>", channel_id))
+        .collect::<Vec<_>>();
+    if message_logs.exclude_nsfw_channels {
+        excluded.push(String::from("NSFW channels"));
+    }
+
+    vec![
+        ("Log style", format!("{:?}", config.log_style), None),
+        ("Message storage", storage.join(", "), None),
+        (
+            "Excluded from storage",
+            list_or_none(excluded.into_iter()),
+            Some("storageexclude <channel/nsfw> <on/off>"),
+        ),
+        (
+            "Message log channels",
+            channel_list(config.get_log_channels(&LogCategory::MESSAGES)),
+            Some("messagelog <channel/off>"),
+        ),
+        (
+            "Delete log grace period",
+            match message_logs.delete_grace {
+                Some(grace) => format!("{} seconds", grace),
+                None => String::from("Off"),
+            },
+            Some("deletegrace <seconds/off>"),
+        ),
+        (
+            "Join log channels",
+            channel_list(config.get_log_channels(&LogCategory::MEMBERS)),
+            Some("joinlog <channel/off>"),
+        ),
+        (
+            "General log channels",
+            channel_list(config.get_log_channels(&LogCategory::GENERAL)),
+            None,
+        ),
+        (
+            "Logged commands",
+            list_or_none(config.logged_commands.iter().map(|name| format!("``{}``", name))),
+            Some("logcommand <command> <on/off>"),
+        ),
+        (
+            "Embed color",
+            format!("#{:06x}", config.embed_color.unwrap_or(DEFAULT_EMBED_COLOR)),
+            Some("color <hex>"),
+        ),
+        (
+            "Embed footer",
+            config
+                .embed_footer
+                .as_ref()
+                .map_or_else(|| String::from("None"), |footer| footer.text.clone()),
+            Some("footer <text/off>"),
+        ),
+    ]
+}
+
+fn moderation_settings(config: &GuildConfig) -> Vec<Setting> {
+    let spam = &config.automod.spam;
+    let spam_rules: Vec<_> = vec![
+        ("duplicates", &spam.duplicates),
+        ("mentions", &spam.mentions),
+        ("rate", &spam.rate),
+        ("cross channel", &spam.cross_channel),
+    ]
+    .into_iter()
+    .filter_map(|(name, rule)| {
+        rule.as_ref().map(|rule| {
+            format!(
+                "{} ({} in {}s, {:?})",
+                name, rule.threshold, rule.window_seconds, rule.action
+            )
+        })
+    })
+    .collect();
+
+    let links = &config.automod.links;
+    let anti_raid = &config.anti_raid;
+    let account_age_gate = &config.account_age_gate;
+
+    vec![
+        ("Require reasons", on_off(config.require_mod_reasons), None),
+        ("Spam rules", list_or_none(spam_rules.into_iter()), None),
+        (
+            "Automod action order",
+            config
+                .automod
+                .action_order()
+                .iter()
+                .map(|kind| kind.name())
+                .collect::<Vec<_>>()
+                .join(" > "),
+            Some("automodorder <actions/default>"),
+        ),
+        (
+            "Blocked extensions",
+            list_or_none(
+                config
+                    .automod
+                    .blocked_extensions
+                    .iter()
+                    .map(|ext| format!("``.{}``", ext)),
+            ),
+            None,
+        ),
+        (
+            "Attachment scanning",
+            config.automod.attachment_scan.as_ref().map_or_else(
+                || String::from("Off"),
+                |rule| {
+                    let failure = if rule.fail_closed { "remove" } else { "allow" };
+                    format!("{:?}, {} when unscanned", rule.action, failure)
+                },
+            ),
+            None,
+        ),
+        (
+            "Invite filter",
+            links.invites.as_ref().map_or_else(
+                || String::from("Off"),
+                |action| {
+                    format!(
+                        "{:?}, {} other servers allowed",
+                        action,
+                        links.allowed_invite_guilds.len()
+                    )
+                },
+            ),
+            None,
+        ),
+        (
+            "Link filter",
+            links.links.as_ref().map_or_else(
+                || String::from("Off"),
+                |action| {
+                    let allowed = list_or_none(links.allowed_domains.iter().map(|domain| format!("``{}``", domain)));
+                    format!("{:?}, allowed: {}", action, allowed)
+                },
+            ),
+            None,
+        ),
+        (
+            "Raid detection",
+            if anti_raid.detection_enabled {
+                format!(
+                    "On, {} joins in {} seconds",
+                    anti_raid.join_threshold, anti_raid.window_seconds
+                )
+            } else {
+                String::from("Off")
+            },
+            None,
+        ),
+        (
+            "Account age gate",
+            match account_age_gate.min_age_days {
+                Some(days) => match &account_age_gate.action {
+                    AccountAgeAction::Kick => format!("Kick accounts younger than {} days", days),
+                    AccountAgeAction::AssignRole(role_id) => {
+                        format!("Give <@&{}> to accounts younger than {} days", role_id, days)
+                    }
+                },
+                None => String::from("Off"),
+            },
+            None,
+        ),
+        (
+            "Permission groups",
+            list_or_none(config.permission_groups.iter().map(|group| group.name.clone())),
+            None,
+        ),
+        (
+            "Command permission overrides",
+            list_or_none(
+                config
+                    .command_overrides
+                    .iter()
+                    .map(|(command, group)| format!("``{}``: {} and up", command, group)),
+            ),
+            Some("commandlevel <command> <group/default>"),
+        ),
+        (
+            "Cooldown bypass roles",
+            list_or_none(
+                config
+                    .cooldown_bypass_roles
+                    .iter()
+                    .map(|role_id| format!("<@&{}>", role_id)),
+            ),
+            Some("cooldownbypass <role> <on/off>"),
+        ),
+        (
+            "Command channels",
+            if config.command_channels.allowed.is_empty() {
+                String::from("Every channel")
+            } else {
+                channel_list(config.command_channels.allowed.clone())
+            },
+            Some("commandchannels allow <channel> <on/off>"),
+        ),
+        (
+            "Ignored command channels",
+            channel_list(config.command_channels.ignored.clone()),
+            Some("commandchannels ignore <channel> <on/off>"),
+        ),
+    ]
+}
+
+fn community_settings(config: &GuildConfig) -> Vec<Setting> {
+    let starboard = &config.starboard;
+    let greeting = &config.first_message_greeting;
+
+    vec![
+        (
+            "Starboard",
+            match starboard.channel {
+                Some(channel_id) => format!("<#{}>, {} {} needed", channel_id, starboard.threshold, starboard.emoji),
+                None => String::from("Off"),
+            },
+            None,
+        ),
+        (
+            "Auto pin channels",
+            channel_list(config.auto_pin_channels.clone()),
+            None,
+        ),
+        (
+            "First message greeting",
+            on_off(greeting.as_ref().map_or(false, |greeting| {
+                greeting.message.is_some() || greeting.reaction.is_some()
+            })),
+            None,
+        ),
+        (
+            "Welcome images",
+            match config.welcome_image.channel {
+                Some(channel_id) => format!("<#{}>, text color #{:06x}", channel_id, config.welcome_image.text_color),
+                None => String::from("Off"),
+            },
+            Some("welcomeimage <channel/off>"),
+        ),
+        ("Reaction tracking", on_off(config.track_reactions), None),
+        (
+            "Role menus on role deletion",
+            String::from(if config.keep_role_menu_messages {
+                "Keep message"
+            } else {
+                "Update message"
+            }),
+            Some("rolemenumessages <update/keep>"),
+        ),
+    ]
+}
+
+fn on_off(enabled: bool) -> String {
+    String::from(if enabled { "On" } else { "Off" })
+}
+
+fn channel_list(channels: Vec<ChannelId>) -> String {
+    list_or_none(channels.into_iter().map(|channel_id| format!("<#{}>", channel_id)))
+}
+
+fn list_or_none(items: impl Iterator<Item = String>) -> String {
+    let items: Vec<_> = items.collect();
+    if items.is_empty() {
+        String::from("None")
+    } else {
+        items.join(", ")
+    }
+}
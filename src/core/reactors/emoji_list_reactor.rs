@@ -11,7 +11,7 @@ use crate::core::GuildConfig;
 use crate::error::{MessageError, ReactorError};
 use crate::translation::{FluArgs, GearBotString};
 use crate::utils::Emoji;
-use twilight_embed_builder::{EmbedAuthorBuilder, EmbedBuilder, ImageSource};
+use twilight_embed_builder::{EmbedAuthorBuilder, ImageSource};
 use twilight_model::channel::embed::Embed;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -68,7 +68,8 @@ pub async fn gen_emoji_page(
             //can not panic since server names are only 100 chars long
             .name(ctx.translate_with_args(lang, GearBotString::EmojiOverviewHeader, &header_args))
             .unwrap();
-        EmbedBuilder::new()
+        guild_config
+            .embed_builder()?
             .author(author_builder.build())
             .description("TODO: add jumbo image!")
             .unwrap()
@@ -113,7 +114,8 @@ pub async fn gen_emoji_page(
             .add("role_requirement", role_info)
             .generate();
 
-        EmbedBuilder::new()
+        guild_config
+            .embed_builder()?
             .author(author_builder.build())
             .description(ctx.translate_with_args(lang, GearBotString::EmojiInfo, &info_arguments))?
             .image(ImageSource::url(emoji.get_url()).unwrap())
@@ -5,20 +5,24 @@ use twilight_model::channel::Reaction;
 use twilight_model::id::MessageId;
 
 use crate::core::bot_context::BotContext;
+use crate::core::reactors::config_reactor::ConfigReactor;
 use crate::core::reactors::emoji_list_reactor::EmojiListReactor;
 use crate::error::{DatabaseError, ReactorError};
 use crate::utils::Emoji;
 
+mod config_reactor;
 mod emoji_list_reactor;
 mod help_reactor;
 pub mod reactor_controller;
 
+pub use config_reactor::gen_config_page;
 pub use emoji_list_reactor::gen_emoji_page;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub enum Reactor {
     Help,
     EmojiList(EmojiListReactor),
+    Config(ConfigReactor),
 }
 
 impl Reactor {
@@ -28,10 +32,17 @@ impl Reactor {
         }
     }
 
+    pub fn new_config() -> Self {
+        Reactor::Config {
+            0: ConfigReactor { page: 0 },
+        }
+    }
+
     pub fn processes(&self, reaction: &Reaction) -> Option<Emoji> {
         match self {
             Reactor::Help => None,
             Reactor::EmojiList(inner) => inner.processes(reaction),
+            Reactor::Config(inner) => inner.processes(reaction),
         }
     }
 
@@ -51,6 +62,10 @@ impl Reactor {
                 inner.do_the_thing(emoji, ctx, member, reaction).await?;
                 Reactor::EmojiList { 0: inner }
             }
+            Reactor::Config(mut inner) => {
+                inner.do_the_thing(emoji, ctx, member, reaction).await?;
+                Reactor::Config { 0: inner }
+            }
         };
 
         new.save(ctx, reaction.message_id).await?;
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::BotContext;
+
+/// How often shards are checked for having gone silent.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub async fn run(ctx: Arc<BotContext>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        for (shard_id, silent_for) in ctx.find_silent_shards().await {
+            ctx.restart_shard(shard_id, silent_for).await;
+        }
+    }
+}
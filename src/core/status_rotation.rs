@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::BotContext;
+
+/// Discord only accepts a handful of presence updates per minute, so don't let the config go below this.
+const MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+pub async fn run(ctx: Arc<BotContext>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval.max(MIN_INTERVAL));
+    // the first tick completes right away, shards show the first status once they are ready
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = ctx.cycle_status().await {
+            log::warn!("Failed to update the status: {}", e);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::Duration;
+use twilight_model::id::{GuildId, UserId};
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Finds users whose first message in the guild came within the window around the first message of
+    /// the given user, in channels that user talked in as well.
+    ///
+    /// Returns (user id, first message id, shared channels) for each of them, those sharing the most
+    /// channels first. Only metadata is used, nothing has to be decrypted for this.
+    pub async fn find_alt_candidates(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        window: Duration,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64, i64)>, DatabaseError> {
+        // snowflakes carry their timestamp in milliseconds above the lower 22 bits
+        let window = window.num_milliseconds() << 22;
+        let candidates = self
+            .timed(
+                "find_alt_candidates",
+                sqlx::query_as(
+                    "WITH target AS (SELECT min(id) AS first_id FROM message WHERE guild_id=$1 AND author_id=$2)
+                    SELECT m.author_id, min(m.id) AS first_id, count(DISTINCT m.channel_id) AS shared_channels
+                    FROM message m, target
+                    WHERE m.guild_id=$1 AND m.author_id<>$2
+                        AND m.id BETWEEN target.first_id - $3 AND target.first_id + $3
+                        AND m.channel_id IN (SELECT DISTINCT channel_id FROM message WHERE guild_id=$1 AND author_id=$2)
+                        AND NOT EXISTS (
+                            SELECT 1 FROM message older
+                            WHERE older.guild_id=$1 AND older.author_id=m.author_id AND older.id < target.first_id - $3
+                        )
+                    GROUP BY m.author_id ORDER BY shared_channels DESC, first_id LIMIT $4",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .bind(window)
+                .bind(limit)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(candidates)
+    }
+}
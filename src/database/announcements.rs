@@ -0,0 +1,92 @@
+use twilight_model::id::{ChannelId, GuildId, UserId};
+
+use super::structures::ScheduledAnnouncement;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Stores a new scheduled announcement, returning its id.
+    pub async fn insert_announcement(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        creator_id: UserId,
+        cron: &str,
+        message: &str,
+        next_run: i64,
+    ) -> Result<i32, DatabaseError> {
+        let id: (i32,) = self
+            .timed(
+                "insert_announcement",
+                sqlx::query_as(
+                    "INSERT INTO scheduledAnnouncement (guild_id, channel_id, creator_id, cron, message, next_run)
+                    VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(channel_id.0 as i64)
+                .bind(creator_id.0 as i64)
+                .bind(cron)
+                .bind(message)
+                .bind(next_run)
+                .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(id.0)
+    }
+
+    pub async fn get_announcements(&self, guild_id: GuildId) -> Result<Vec<ScheduledAnnouncement>, DatabaseError> {
+        let announcements = self
+            .timed(
+                "get_announcements",
+                sqlx::query_as("SELECT * FROM scheduledAnnouncement WHERE guild_id=$1 ORDER BY id")
+                    .bind(guild_id.0 as i64)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(announcements)
+    }
+
+    /// Retrieves all announcements (of all guilds) that should have been posted by the given time.
+    pub async fn get_due_announcements(&self, now: i64) -> Result<Vec<ScheduledAnnouncement>, DatabaseError> {
+        let announcements = self
+            .timed(
+                "get_due_announcements",
+                sqlx::query_as("SELECT * FROM scheduledAnnouncement WHERE next_run <= $1")
+                    .bind(now)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(announcements)
+    }
+
+    pub async fn set_announcement_next_run(&self, id: i32, next_run: i64) -> Result<(), DatabaseError> {
+        self.timed(
+            "set_announcement_next_run",
+            sqlx::query("UPDATE scheduledAnnouncement SET next_run=$1 WHERE id=$2")
+                .bind(next_run)
+                .bind(id)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes an announcement, returns false if the guild had no announcement with this id.
+    pub async fn remove_announcement(&self, guild_id: GuildId, id: i32) -> Result<bool, DatabaseError> {
+        let result = self
+            .timed(
+                "remove_announcement",
+                sqlx::query("DELETE FROM scheduledAnnouncement WHERE id=$1 AND guild_id=$2")
+                    .bind(id)
+                    .bind(guild_id.0 as i64)
+                    .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
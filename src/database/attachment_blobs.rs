@@ -0,0 +1,78 @@
+use twilight_model::id::{AttachmentId, GuildId};
+
+use super::crypto;
+use super::structures::AttachmentBytes;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Keeps a copy of the attachment, encrypted with the guild key.
+    pub async fn insert_attachment_blob(
+        &self,
+        attachment_id: AttachmentId,
+        guild_id: GuildId,
+        bytes: &[u8],
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self
+            .timed("insert_attachment_blob", self.persistent_pool.begin())
+            .await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        let ciphertext = crypto::encrypt_attachment(bytes, &guild_key, attachment_id.0, self.message_encryption);
+        let size = ciphertext.len();
+
+        self.timed(
+            "insert_attachment_blob",
+            sqlx::query(
+                "INSERT INTO attachment_blob (attachment_id, encrypted_content, encryption_algorithm)
+                VALUES ($1, $2, $3)",
+            )
+            .bind(attachment_id.0 as i64)
+            .bind(ciphertext)
+            .bind(self.message_encryption.id())
+            .execute(&mut *tx),
+        )
+        .await?;
+        self.timed("insert_attachment_blob", tx.commit()).await?;
+
+        self.stats.attachments.stored_bytes.inc_by(size as i64);
+
+        Ok(())
+    }
+
+    /// Retrieves the copy of an attachment that was sent in the guild.
+    pub async fn get_attachment_bytes(
+        &self,
+        attachment_id: AttachmentId,
+        guild_id: GuildId,
+    ) -> Result<AttachmentBytes, DatabaseError> {
+        let stored: Option<(Option<Vec<u8>>, Option<i16>)> = self
+            .timed(
+                "get_attachment_bytes",
+                sqlx::query_as(
+                    "SELECT blob.encrypted_content, blob.encryption_algorithm
+                    FROM attachment INNER JOIN message ON message.id = attachment.message_id
+                    LEFT JOIN attachment_blob blob ON blob.attachment_id = attachment.id
+                    WHERE attachment.id=$1 AND message.guild_id=$2",
+                )
+                .bind(attachment_id.0 as i64)
+                .bind(guild_id.0 as i64)
+                .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        let (ciphertext, algorithm) = match stored {
+            Some((Some(ciphertext), Some(algorithm))) => (ciphertext, algorithm),
+            Some(_) => return Ok(AttachmentBytes::NotStored),
+            None => return Ok(AttachmentBytes::NotFound),
+        };
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        match crypto::decrypt_attachment(&ciphertext, &guild_key, attachment_id.0, algorithm) {
+            Some(bytes) => Ok(AttachmentBytes::Stored(bytes)),
+            None => {
+                log::warn!("Stored copy of attachment {} could not be decrypted", attachment_id);
+                Ok(AttachmentBytes::NotStored)
+            }
+        }
+    }
+}
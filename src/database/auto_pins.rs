@@ -0,0 +1,42 @@
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// The message we currently have pinned in an auto pin channel, if any.
+    pub async fn get_auto_pin(&self, channel_id: ChannelId) -> Result<Option<MessageId>, DatabaseError> {
+        let message_id: Option<(i64,)> = self
+            .timed(
+                "get_auto_pin",
+                sqlx::query_as("SELECT message_id FROM auto_pin WHERE channel_id=$1")
+                    .bind(channel_id.0 as i64)
+                    .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(message_id.map(|(id,)| MessageId(id as u64)))
+    }
+
+    pub async fn set_auto_pin(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "set_auto_pin",
+            sqlx::query(
+                "INSERT INTO auto_pin (channel_id, guild_id, message_id) VALUES ($1, $2, $3)
+                ON CONFLICT (channel_id) DO UPDATE SET message_id = EXCLUDED.message_id",
+            )
+            .bind(channel_id.0 as i64)
+            .bind(guild_id.0 as i64)
+            .bind(message_id.0 as i64)
+            .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use twilight_model::id::GuildId;
+
+use super::structures::AutoResponse;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Stores a new auto response rule, returning its id.
+    pub async fn insert_auto_response(
+        &self,
+        guild_id: GuildId,
+        trigger: &str,
+        response: &str,
+        exact: bool,
+        cooldown_seconds: i64,
+    ) -> Result<i32, DatabaseError> {
+        let id: (i32,) = self
+            .timed(
+                "insert_auto_response",
+                sqlx::query_as(
+                    "INSERT INTO autoResponse (guild_id, trigger, response, exact, cooldown_seconds)
+                    VALUES ($1, $2, $3, $4, $5) RETURNING id",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(trigger)
+                .bind(response)
+                .bind(exact)
+                .bind(cooldown_seconds)
+                .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(id.0)
+    }
+
+    pub async fn get_auto_responses(&self, guild_id: GuildId) -> Result<Vec<AutoResponse>, DatabaseError> {
+        let responses = self
+            .timed(
+                "get_auto_responses",
+                sqlx::query_as("SELECT * FROM autoResponse WHERE guild_id=$1 ORDER BY id")
+                    .bind(guild_id.0 as i64)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(responses)
+    }
+
+    /// Removes an auto response, returns false if the guild had no auto response with this id.
+    pub async fn remove_auto_response(&self, guild_id: GuildId, id: i32) -> Result<bool, DatabaseError> {
+        let result = self
+            .timed(
+                "remove_auto_response",
+                sqlx::query("DELETE FROM autoResponse WHERE id=$1 AND guild_id=$2")
+                    .bind(id)
+                    .bind(guild_id.0 as i64)
+                    .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
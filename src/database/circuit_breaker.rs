@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use prometheus::IntGauge;
+
+use crate::{gearbot_error, gearbot_important};
+
+/// Keeps track of queries failing to reach the database, so we stop hammering it while it's down.
+///
+/// Once enough queries in a row failed to connect, all queries are refused for a while. After that
+/// they are let through again, the first one to succeed closes the breaker again.
+pub struct CircuitBreaker {
+    failures: AtomicU32,
+    open_until: Mutex<Option<Instant>>,
+    failure_threshold: u32,
+    retry_after: Duration,
+    unavailable: IntGauge,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, retry_after: Duration, unavailable: IntGauge) -> Self {
+        CircuitBreaker {
+            failures: AtomicU32::new(0),
+            open_until: Mutex::new(None),
+            failure_threshold: failure_threshold.max(1),
+            retry_after,
+            unavailable,
+        }
+    }
+
+    /// If queries should be sent to the database right now.
+    pub fn allows_queries(&self) -> bool {
+        match *self.open_until.lock().expect("The circuit breaker got poisoned!") {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    pub fn record_success(&self) {
+        if self.failures.swap(0, Ordering::Relaxed) < self.failure_threshold {
+            return;
+        }
+
+        *self.open_until.lock().expect("The circuit breaker got poisoned!") = None;
+        self.unavailable.set(0);
+        gearbot_important!("The database is reachable again, resuming queries");
+    }
+
+    pub fn record_connection_failure(&self) {
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures < self.failure_threshold {
+            return;
+        }
+
+        *self.open_until.lock().expect("The circuit breaker got poisoned!") = Some(Instant::now() + self.retry_after);
+        if failures == self.failure_threshold {
+            self.unavailable.set(1);
+            gearbot_error!(
+                "The database failed to connect {} times in a row, holding back queries for {} seconds at a time until it recovers",
+                failures,
+                self.retry_after.as_secs()
+            );
+        } else {
+            log::warn!(
+                "The database is still unreachable, trying again in {} seconds",
+                self.retry_after.as_secs()
+            );
+        }
+    }
+}
@@ -9,9 +9,13 @@ impl DataStorage {
     ///
     /// The permissions inside the config are guaranteed to be in the correct order.
     pub async fn get_guild_config(&self, guild_id: u64) -> Result<Option<GuildConfig>, DatabaseError> {
-        let row: Option<(serde_json::Value,)> = sqlx::query_as("SELECT config from guildconfig where id=$1")
-            .bind(guild_id as i64)
-            .fetch_optional(&self.persistent_pool)
+        let row: Option<(serde_json::Value,)> = self
+            .timed(
+                "get_guild_config",
+                sqlx::query_as("SELECT config from guildconfig where id=$1")
+                    .bind(guild_id as i64)
+                    .fetch_optional(&self.persistent_pool),
+            )
             .await?;
 
         let config = if let Some(c_val) = row {
@@ -34,25 +38,58 @@ impl DataStorage {
         let master_ek = &self.primary_encryption_key;
         let guild_encryption_key = crypto::generate_guild_encryption_key(master_ek, guild_id);
 
-        sqlx::query("INSERT INTO guildconfig (id, config, encryption_key) VALUES ($1, $2, $3)")
+        self.timed(
+            "create_new_guild_config",
+            sqlx::query("INSERT INTO guildconfig (id, config, encryption_key) VALUES ($1, $2, $3)")
+                .bind(guild_id as i64)
+                .bind(serde_json::to_value(&new_config).map_err(DatabaseError::Serializing)?)
+                .bind(guild_encryption_key)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(new_config)
+    }
+
+    /// The config of a guild as it can be handed out, the encryption key of the guild is never part of it.
+    pub async fn export_guild_config(&self, guild_id: u64) -> Result<Option<GuildConfig>, DatabaseError> {
+        self.get_guild_config(guild_id).await
+    }
+
+    /// Replaces the config of a guild with an imported one, the encryption key of the guild is kept as is.
+    ///
+    /// Unlike ``set_guild_config`` this also works for guilds that don't have a config yet.
+    pub async fn import_guild_config(&self, guild_id: u64, config: &GuildConfig) -> Result<(), DatabaseError> {
+        let guild_encryption_key = crypto::generate_guild_encryption_key(&self.primary_encryption_key, guild_id);
+
+        self.timed(
+            "import_guild_config",
+            sqlx::query(
+                "INSERT INTO guildconfig (id, config, encryption_key) VALUES ($1, $2, $3)
+                ON CONFLICT (id) DO UPDATE SET config = EXCLUDED.config",
+            )
             .bind(guild_id as i64)
-            .bind(serde_json::to_value(&new_config).map_err(DatabaseError::Serializing)?)
+            .bind(serde_json::to_value(config).map_err(DatabaseError::Serializing)?)
             .bind(guild_encryption_key)
-            .execute(&self.persistent_pool)
-            .await?;
+            .execute(&self.persistent_pool),
+        )
+        .await?;
 
-        Ok(new_config)
+        Ok(())
     }
 
     /// Updates a guild config for the specified guild with the provided new value.
     ///
     /// Errors if the guild doesn't exist already.
     pub async fn set_guild_config(&self, guild_id: u64, config: &GuildConfig) -> Result<(), DatabaseError> {
-        sqlx::query("UPDATE guildconfig set config=$1 WHERE id=$2")
-            .bind(serde_json::to_value(config).map_err(DatabaseError::Serializing)?)
-            .bind(guild_id as i64)
-            .execute(&self.persistent_pool)
-            .await?;
+        self.timed(
+            "set_guild_config",
+            sqlx::query("UPDATE guildconfig set config=$1 WHERE id=$2")
+                .bind(serde_json::to_value(config).map_err(DatabaseError::Serializing)?)
+                .bind(guild_id as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
 
         Ok(())
     }
@@ -6,7 +6,10 @@ use aes_gcm::{
     aead::{Aead, NewAead},
     Aes256Gcm,
 };
+use chacha20poly1305::ChaCha20Poly1305;
 use rand::{thread_rng, RngCore};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 /// An encryption key that is used to secure guild data.
 pub struct EncryptionKey<'a>(Cow<'a, GenericArray<u8, U32>>);
@@ -32,38 +35,211 @@ impl fmt::Debug for EncryptionKey<'_> {
 }
 
 pub fn generate_guild_encryption_key(main_encryption_key: &EncryptionKey, guild_id: u64) -> Vec<u8> {
+    encrypt_bytes(&generate_key_bytes(), main_encryption_key, guild_id)
+}
+
+/// A fresh random key, it still has to be encrypted with the main key before it can be stored.
+pub fn generate_key_bytes() -> [u8; 32] {
     let mut csprng = thread_rng();
-    let mut guild_encryption_key = [0u8; 32];
-    csprng.fill_bytes(&mut guild_encryption_key);
+    let mut key = [0u8; 32];
+    csprng.fill_bytes(&mut key);
+    key
+}
+
+/// Nonce domain for message content, these use the message id as nonce base.
+pub const MESSAGE_NONCE_DOMAIN: u32 = 0;
 
-    encrypt_bytes(&guild_encryption_key, main_encryption_key, guild_id)
+/// Nonce domain for moderator notes. Notes use the id of the message that created them as nonce
+/// base, the domain keeps them from ever sharing a nonce with that message under the same guild key.
+pub const NOTE_NONCE_DOMAIN: u32 = 1;
+
+/// Nonce domain for stored attachments, these use the attachment id as nonce base.
+pub const ATTACHMENT_NONCE_DOMAIN: u32 = 2;
+
+/// Nonce domains for the content of edited messages start here, the revision is added to it so every version
+/// of a message gets its own nonce. The content a message was sent with keeps using ``MESSAGE_NONCE_DOMAIN``.
+pub const MESSAGE_REVISION_NONCE_DOMAIN: u32 = 0x1000_0000;
+
+/// The AEADs message content can be encrypted with. The id of the one that was used is stored with
+/// every message, so switching doesn't leave the older messages unreadable.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EncryptionAlgorithm {
+    #[serde(rename = "aes-256-gcm")]
+    Aes256Gcm,
+    #[serde(rename = "chacha20-poly1305")]
+    ChaCha20Poly1305,
+}
+
+impl EncryptionAlgorithm {
+    /// The id stored alongside the content, these can never change.
+    pub fn id(self) -> i16 {
+        match self {
+            EncryptionAlgorithm::Aes256Gcm => 0,
+            EncryptionAlgorithm::ChaCha20Poly1305 => 1,
+        }
+    }
+
+    pub fn from_id(id: i16) -> Option<Self> {
+        match id {
+            0 => Some(EncryptionAlgorithm::Aes256Gcm),
+            1 => Some(EncryptionAlgorithm::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
+    fn encrypt(self, plaintext: &[u8], key: &EncryptionKey, nonce_bytes: &[u8; 12]) -> Vec<u8> {
+        let nonce = GenericArray::from_slice(nonce_bytes);
+        let encrypted = match self {
+            EncryptionAlgorithm::Aes256Gcm => Aes256Gcm::new(&key.0).encrypt(nonce, plaintext),
+            EncryptionAlgorithm::ChaCha20Poly1305 => ChaCha20Poly1305::new(&key.0).encrypt(nonce, plaintext),
+        };
+
+        encrypted.expect("Failed to encrypt an object!")
+    }
+
+    fn decrypt(self, ciphertext: &[u8], key: &EncryptionKey, nonce_bytes: &[u8; 12]) -> Option<Vec<u8>> {
+        let nonce = GenericArray::from_slice(nonce_bytes);
+        let decrypted = match self {
+            EncryptionAlgorithm::Aes256Gcm => Aes256Gcm::new(&key.0).decrypt(nonce, ciphertext),
+            EncryptionAlgorithm::ChaCha20Poly1305 => ChaCha20Poly1305::new(&key.0).decrypt(nonce, ciphertext),
+        };
+
+        decrypted.ok()
+    }
+}
+
+impl Default for EncryptionAlgorithm {
+    fn default() -> Self {
+        EncryptionAlgorithm::Aes256Gcm
+    }
 }
 
 pub fn encrypt_bytes(plaintext: &[u8], key: &EncryptionKey, msg_id: u64) -> Vec<u8> {
+    encrypt_bytes_in_domain(plaintext, key, msg_id, MESSAGE_NONCE_DOMAIN)
+}
+
+/// Encrypts (a revision of) message content with the given algorithm, store its id next to the ciphertext.
+pub fn encrypt_message(
+    plaintext: &[u8],
+    key: &EncryptionKey,
+    msg_id: u64,
+    revision: i32,
+    algorithm: EncryptionAlgorithm,
+) -> Vec<u8> {
+    algorithm.encrypt(plaintext, key, &build_nonce(msg_id, message_nonce_domain(revision)))
+}
+
+/// Decrypts (a revision of) message content with the algorithm it was stored with.
+///
+/// ``None`` when the key doesn't match, the content is damaged or the algorithm id is unknown.
+pub fn decrypt_message(
+    ciphertext: &[u8],
+    key: &EncryptionKey,
+    msg_id: u64,
+    revision: i32,
+    algorithm_id: i16,
+) -> Option<Vec<u8>> {
+    let nonce = build_nonce(msg_id, message_nonce_domain(revision));
+    EncryptionAlgorithm::from_id(algorithm_id)?.decrypt(ciphertext, key, &nonce)
+}
+
+/// Encrypts the bytes of an attachment with the given algorithm, store its id next to the ciphertext.
+pub fn encrypt_attachment(
+    plaintext: &[u8],
+    key: &EncryptionKey,
+    attachment_id: u64,
+    algorithm: EncryptionAlgorithm,
+) -> Vec<u8> {
+    algorithm.encrypt(plaintext, key, &build_nonce(attachment_id, ATTACHMENT_NONCE_DOMAIN))
+}
+
+/// Decrypts the bytes of an attachment, ``None`` under the same conditions as ``decrypt_message``.
+pub fn decrypt_attachment(
+    ciphertext: &[u8],
+    key: &EncryptionKey,
+    attachment_id: u64,
+    algorithm_id: i16,
+) -> Option<Vec<u8>> {
+    let nonce = build_nonce(attachment_id, ATTACHMENT_NONCE_DOMAIN);
+    EncryptionAlgorithm::from_id(algorithm_id)?.decrypt(ciphertext, key, &nonce)
+}
+
+fn message_nonce_domain(revision: i32) -> u32 {
+    if revision == 0 {
+        MESSAGE_NONCE_DOMAIN
+    } else {
+        MESSAGE_REVISION_NONCE_DOMAIN + revision as u32
+    }
+}
+
+/// Decrypts data from ``encrypt_bytes``, ``None`` when the key doesn't match.
+pub fn try_decrypt_bytes(ciphertext: &[u8], key: &EncryptionKey, msg_id: u64) -> Option<Vec<u8>> {
+    try_decrypt_bytes_in_domain(ciphertext, key, msg_id, MESSAGE_NONCE_DOMAIN)
+}
+
+/// Decrypts data from ``encrypt_bytes_in_domain``, ``None`` when the key doesn't match.
+pub fn try_decrypt_bytes_in_domain(ciphertext: &[u8], key: &EncryptionKey, id: u64, domain: u32) -> Option<Vec<u8>> {
     let aead = Aes256Gcm::new(&key.0);
 
-    // Since nonce's only never need to be reused, and Discor's snowflakes for messages
-    // are unique, we can use the messasge id to construct the nonce with its 64 bits, and then
-    // pad the rest with zeros.
-    let mut nonce_bytes = [0u8; 12];
-    let msg_id_bytes = msg_id.to_le_bytes();
-    nonce_bytes[..8].copy_from_slice(&msg_id_bytes);
-    nonce_bytes[8..].copy_from_slice(&[0u8; 4]);
+    let nonce_bytes = build_nonce(id, domain);
+    let nonce = GenericArray::from_slice(&nonce_bytes);
+
+    aead.decrypt(&nonce, ciphertext).ok()
+}
 
+pub fn encrypt_bytes_in_domain(plaintext: &[u8], key: &EncryptionKey, id: u64, domain: u32) -> Vec<u8> {
+    let aead = Aes256Gcm::new(&key.0);
+
+    let nonce_bytes = build_nonce(id, domain);
     let nonce = GenericArray::from_slice(&nonce_bytes);
 
     aead.encrypt(&nonce, plaintext).expect("Failed to encrypt an object!")
 }
 
-pub fn decrypt_bytes(ciphertext: &[u8], key: &EncryptionKey, msg_id: u64) -> Vec<u8> {
-    let aead = Aes256Gcm::new(&key.0);
+/// Length of the random nonce in front of data encrypted with ``encrypt_with_random_nonce``.
+const RANDOM_NONCE_LENGTH: usize = 12;
 
-    let mut nonce_bytes = [0u8; 12];
-    let msg_id_bytes = msg_id.to_le_bytes();
-    nonce_bytes[..8].copy_from_slice(&msg_id_bytes);
-    nonce_bytes[8..].copy_from_slice(&[0u8; 4]);
+/// Encrypts data that has no id to build a nonce from, a random nonce is generated and put in front of the ciphertext.
+pub fn encrypt_with_random_nonce(plaintext: &[u8], key: &EncryptionKey) -> Vec<u8> {
+    let aead = Aes256Gcm::new(&key.0);
 
+    let mut nonce_bytes = [0u8; RANDOM_NONCE_LENGTH];
+    thread_rng().fill_bytes(&mut nonce_bytes);
     let nonce = GenericArray::from_slice(&nonce_bytes);
 
-    aead.decrypt(&nonce, ciphertext).expect("Failed to decrypt an object!")
+    let mut encrypted = nonce_bytes.to_vec();
+    encrypted.extend(aead.encrypt(&nonce, plaintext).expect("Failed to encrypt an object!"));
+    encrypted
+}
+
+/// Decrypts data from ``encrypt_with_random_nonce``, ``None`` when the key doesn't match or the data is damaged.
+pub fn decrypt_with_random_nonce(data: &[u8], key: &EncryptionKey) -> Option<Vec<u8>> {
+    if data.len() < RANDOM_NONCE_LENGTH {
+        return None;
+    }
+
+    let aead = Aes256Gcm::new(&key.0);
+    let (nonce_bytes, ciphertext) = data.split_at(RANDOM_NONCE_LENGTH);
+    let nonce = GenericArray::from_slice(nonce_bytes);
+
+    aead.decrypt(&nonce, ciphertext).ok()
+}
+
+/// Hashes content salted with the guild key, so equal content can be recognized within a guild
+/// without it being recoverable, or comparable across guilds.
+pub fn salted_hash(content: &[u8], key: &EncryptionKey) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(key.0.as_slice());
+    hasher.update(content);
+    hasher.finalize().to_vec()
+}
+
+fn build_nonce(id: u64, domain: u32) -> [u8; 12] {
+    // Since nonce's only never need to be reused, and Discor's snowflakes for messages
+    // are unique, we can use the messasge id to construct the nonce with its 64 bits, and then
+    // pad the rest with the domain (zeros for messages).
+    let mut nonce_bytes = [0u8; 12];
+    nonce_bytes[..8].copy_from_slice(&id.to_le_bytes());
+    nonce_bytes[8..].copy_from_slice(&domain.to_le_bytes());
+    nonce_bytes
 }
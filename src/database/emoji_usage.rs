@@ -0,0 +1,40 @@
+use twilight_model::id::GuildId;
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Adds the given amounts to the stored usage counts of the emoji of a guild.
+    pub async fn add_emoji_usage(&self, guild_id: GuildId, usage: &[(u64, i64)]) -> Result<(), DatabaseError> {
+        for (emoji_id, uses) in usage {
+            self.timed(
+                "add_emoji_usage",
+                sqlx::query(
+                    "INSERT INTO emojiUsage (guild_id, emoji_id, uses) VALUES ($1, $2, $3)
+                    ON CONFLICT (guild_id, emoji_id) DO UPDATE SET uses = emojiUsage.uses + EXCLUDED.uses",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(*emoji_id as i64)
+                .bind(*uses)
+                .execute(&self.persistent_pool),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves how often each emoji of a guild has been used, as (emoji id, uses) pairs.
+    pub async fn get_emoji_usage(&self, guild_id: GuildId) -> Result<Vec<(i64, i64)>, DatabaseError> {
+        let usage = self
+            .timed(
+                "get_emoji_usage",
+                sqlx::query_as("SELECT emoji_id, uses FROM emojiUsage WHERE guild_id=$1")
+                    .bind(guild_id.0 as i64)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(usage)
+    }
+}
@@ -0,0 +1,34 @@
+use twilight_model::id::{GuildId, MessageId, UserId};
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Records that the user talked in the guild, returns if this is the first message we know of from them.
+    ///
+    /// Only the message that gets the user recorded can return true, so concurrent messages or restarts
+    /// can't make this fire twice. Users with messages stored from before they got recorded don't count as new.
+    pub async fn record_first_message(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        message_id: MessageId,
+    ) -> Result<bool, DatabaseError> {
+        let recorded: Option<(bool,)> = self
+            .timed(
+                "record_first_message",
+                sqlx::query_as(
+                    "INSERT INTO first_message (guild_id, user_id, greeted)
+                    VALUES ($1, $2, NOT EXISTS (SELECT 1 FROM message WHERE guild_id=$1 AND author_id=$2 AND id<$3))
+                    ON CONFLICT DO NOTHING RETURNING greeted",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .bind(message_id.0 as i64)
+                .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(recorded.map_or(false, |(greeted,)| greeted))
+    }
+}
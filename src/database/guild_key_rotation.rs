@@ -0,0 +1,459 @@
+use sqlx::PgConnection;
+use twilight_model::id::GuildId;
+
+use super::crypto::{self, EncryptionKey, NOTE_NONCE_DOMAIN};
+use super::structures::GuildKeyRotationReport;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+/// How many rows are re-encrypted per query.
+const ROTATION_CHUNK_SIZE: i64 = 500;
+
+/// Stored attachments are a lot larger than anything else, so far fewer of those are loaded at once.
+const ATTACHMENT_CHUNK_SIZE: i64 = 20;
+
+/// The key everything of the guild was encrypted with, and the one replacing it.
+struct KeyPair<'a> {
+    old: EncryptionKey<'a>,
+    new: EncryptionKey<'a>,
+}
+
+impl DataStorage {
+    /// Replaces the encryption key of a guild with a freshly generated one, re-encrypting everything stored with it.
+    ///
+    /// It all happens in a single transaction, so anything going wrong halfway leaves both the old key and the
+    /// content as they were. Rows are handled in chunks, large guilds never have to fit in memory all at once.
+    /// Content that was only kept as a hash can't be rehashed, those hashes were salted with the old key and
+    /// get cleared. Content that doesn't decrypt with the old key either would be lost for good once the old key
+    /// is gone, so running into any of that calls the whole rotation off.
+    ///
+    /// Storing new content locks the key until it's stored (see ``lock_guild_encryption_key``), the rotation
+    /// waits for those writes to finish and new ones wait for the rotation.
+    pub async fn rotate_guild_encryption_key(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<GuildKeyRotationReport, DatabaseError> {
+        let mut tx = self
+            .timed("rotate_guild_encryption_key", self.persistent_pool.begin())
+            .await?;
+
+        // locking the config also keeps a second rotation for the same guild from running alongside this one
+        let (encrypted_key, generation): (Vec<u8>, i32) = self
+            .timed(
+                "rotate_guild_encryption_key",
+                sqlx::query_as("SELECT encryption_key, key_generation FROM guildconfig WHERE id=$1 FOR UPDATE")
+                    .bind(guild_id.0 as i64)
+                    .fetch_one(&mut *tx),
+            )
+            .await?;
+
+        let (old_key, _) = self.unwrap_guild_key(guild_id.0, generation, &encrypted_key)?;
+        let new_key = crypto::generate_key_bytes();
+        let keys = KeyPair {
+            old: EncryptionKey::construct_owned(&old_key),
+            new: EncryptionKey::construct_owned(&new_key),
+        };
+
+        let mut report = GuildKeyRotationReport::default();
+        self.rotate_messages(&mut tx, guild_id, &keys, &mut report).await?;
+        self.rotate_message_edits(&mut tx, guild_id, &keys, &mut report).await?;
+        self.rotate_attachments(&mut tx, guild_id, &keys, &mut report).await?;
+        self.rotate_notes(&mut tx, guild_id, &keys, &mut report).await?;
+        self.rotate_reasons(&mut tx, guild_id, &keys, &mut report).await?;
+        self.rotate_nicknames(&mut tx, guild_id, &keys, &mut report).await?;
+
+        // every generation gets its own nonce, the main key never encrypts two guild keys with the same one
+        let generation = generation + 1;
+        let wrapped =
+            crypto::encrypt_bytes_in_domain(&new_key, &self.primary_encryption_key, guild_id.0, generation as u32);
+        self.timed(
+            "rotate_guild_encryption_key",
+            sqlx::query("UPDATE guildconfig SET encryption_key=$2, key_generation=$3 WHERE id=$1")
+                .bind(guild_id.0 as i64)
+                .bind(wrapped)
+                .bind(generation)
+                .execute(&mut *tx),
+        )
+        .await?;
+
+        self.timed("rotate_guild_encryption_key", tx.commit()).await?;
+
+        Ok(report)
+    }
+
+    async fn rotate_messages(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = 0;
+        loop {
+            let chunk: Vec<(i64, i32, Option<Vec<u8>>, i16, bool)> = self
+                .timed(
+                    "rotate_guild_messages",
+                    sqlx::query_as(
+                        "SELECT id, revision, encrypted_content, encryption_algorithm, content_hash IS NOT NULL
+                        FROM message WHERE guild_id=$1 AND id > $2
+                        AND (encrypted_content IS NOT NULL OR content_hash IS NOT NULL)
+                        ORDER BY id LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after)
+                    .bind(ROTATION_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((id, ..)) => *id,
+                None => return Ok(()),
+            };
+
+            for (id, revision, encrypted_content, algorithm, hashed) in chunk {
+                let (ciphertext, content_hash) = match encrypted_content {
+                    Some(encrypted_content) => {
+                        match self.reencrypt_content(id, revision, &encrypted_content, algorithm, hashed, keys) {
+                            Some((ciphertext, content_hash)) => {
+                                report.messages += 1;
+                                (Some(ciphertext), content_hash)
+                            }
+                            None => return Err(unreadable(guild_id, "message", id)),
+                        }
+                    }
+                    None => {
+                        report.hashes_cleared += 1;
+                        (None, None)
+                    }
+                };
+
+                self.timed(
+                    "rotate_guild_messages",
+                    sqlx::query(
+                        "UPDATE message SET encrypted_content=$2, encryption_algorithm=$3, content_hash=$4 WHERE id=$1",
+                    )
+                    .bind(id)
+                    .bind(ciphertext)
+                    .bind(self.message_encryption.id())
+                    .bind(content_hash)
+                    .execute(&mut *conn),
+                )
+                .await?;
+            }
+        }
+    }
+
+    async fn rotate_message_edits(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = (0, 0);
+        loop {
+            let chunk: Vec<(i64, i32, Option<Vec<u8>>, i16, bool)> = self
+                .timed(
+                    "rotate_guild_message_edits",
+                    sqlx::query_as(
+                        "SELECT edit.message_id, edit.revision, edit.encrypted_content, edit.encryption_algorithm,
+                        edit.content_hash IS NOT NULL
+                        FROM message_edit edit INNER JOIN message ON message.id = edit.message_id
+                        WHERE message.guild_id=$1 AND (edit.message_id, edit.revision) > ($2, $3)
+                        AND (edit.encrypted_content IS NOT NULL OR edit.content_hash IS NOT NULL)
+                        ORDER BY edit.message_id, edit.revision LIMIT $4",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after.0)
+                    .bind(after.1)
+                    .bind(ROTATION_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((message_id, revision, ..)) => (*message_id, *revision),
+                None => return Ok(()),
+            };
+
+            for (message_id, revision, encrypted_content, algorithm, hashed) in chunk {
+                let (ciphertext, content_hash) = match encrypted_content {
+                    Some(encrypted_content) => {
+                        match self.reencrypt_content(message_id, revision, &encrypted_content, algorithm, hashed, keys)
+                        {
+                            Some((ciphertext, content_hash)) => {
+                                report.edits += 1;
+                                (Some(ciphertext), content_hash)
+                            }
+                            None => return Err(unreadable(guild_id, "earlier version of message", message_id)),
+                        }
+                    }
+                    None => {
+                        report.hashes_cleared += 1;
+                        (None, None)
+                    }
+                };
+
+                self.timed(
+                    "rotate_guild_message_edits",
+                    sqlx::query(
+                        "UPDATE message_edit SET encrypted_content=$3, encryption_algorithm=$4, content_hash=$5
+                        WHERE message_id=$1 AND revision=$2",
+                    )
+                    .bind(message_id)
+                    .bind(revision)
+                    .bind(ciphertext)
+                    .bind(self.message_encryption.id())
+                    .bind(content_hash)
+                    .execute(&mut *conn),
+                )
+                .await?;
+            }
+        }
+    }
+
+    async fn rotate_attachments(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = 0;
+        loop {
+            let chunk: Vec<(i64, Vec<u8>, i16)> = self
+                .timed(
+                    "rotate_guild_attachments",
+                    sqlx::query_as(
+                        "SELECT blob.attachment_id, blob.encrypted_content, blob.encryption_algorithm
+                        FROM attachment_blob blob INNER JOIN attachment ON attachment.id = blob.attachment_id
+                        INNER JOIN message ON message.id = attachment.message_id
+                        WHERE message.guild_id=$1 AND blob.attachment_id > $2
+                        ORDER BY blob.attachment_id LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after)
+                    .bind(ATTACHMENT_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((id, ..)) => *id,
+                None => return Ok(()),
+            };
+
+            for (attachment_id, encrypted_content, algorithm) in chunk {
+                let bytes =
+                    match crypto::decrypt_attachment(&encrypted_content, &keys.old, attachment_id as u64, algorithm) {
+                        Some(bytes) => bytes,
+                        None => return Err(unreadable(guild_id, "attachment", attachment_id)),
+                    };
+                let ciphertext =
+                    crypto::encrypt_attachment(&bytes, &keys.new, attachment_id as u64, self.message_encryption);
+
+                self.timed(
+                    "rotate_guild_attachments",
+                    sqlx::query(
+                        "UPDATE attachment_blob SET encrypted_content=$2, encryption_algorithm=$3
+                        WHERE attachment_id=$1",
+                    )
+                    .bind(attachment_id)
+                    .bind(ciphertext)
+                    .bind(self.message_encryption.id())
+                    .execute(&mut *conn),
+                )
+                .await?;
+                report.attachments += 1;
+            }
+        }
+    }
+
+    async fn rotate_notes(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = 0;
+        loop {
+            let chunk: Vec<(i32, i64, Vec<u8>)> = self
+                .timed(
+                    "rotate_guild_notes",
+                    sqlx::query_as(
+                        "SELECT id, nonce_id, encrypted_content FROM note WHERE guild_id=$1 AND id > $2
+                        ORDER BY id LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after)
+                    .bind(ROTATION_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((id, ..)) => *id,
+                None => return Ok(()),
+            };
+
+            for (id, nonce_id, encrypted_content) in chunk {
+                let nonce_id = nonce_id as u64;
+                let content = match crypto::try_decrypt_bytes_in_domain(
+                    &encrypted_content,
+                    &keys.old,
+                    nonce_id,
+                    NOTE_NONCE_DOMAIN,
+                ) {
+                    Some(content) => content,
+                    None => return Err(unreadable(guild_id, "note", id.into())),
+                };
+                let ciphertext = crypto::encrypt_bytes_in_domain(&content, &keys.new, nonce_id, NOTE_NONCE_DOMAIN);
+
+                self.timed(
+                    "rotate_guild_notes",
+                    sqlx::query("UPDATE note SET encrypted_content=$2 WHERE id=$1")
+                        .bind(id)
+                        .bind(ciphertext)
+                        .execute(&mut *conn),
+                )
+                .await?;
+                report.other += 1;
+            }
+        }
+    }
+
+    async fn rotate_reasons(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = 0;
+        loop {
+            let chunk: Vec<(i32, Vec<u8>)> = self
+                .timed(
+                    "rotate_guild_reasons",
+                    sqlx::query_as(
+                        "SELECT id, encrypted_reason FROM history
+                        WHERE guild_id=$1 AND id > $2 AND encrypted_reason IS NOT NULL
+                        ORDER BY id LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after)
+                    .bind(ROTATION_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((id, _)) => *id,
+                None => return Ok(()),
+            };
+
+            for (id, encrypted_reason) in chunk {
+                let reason = match crypto::decrypt_with_random_nonce(&encrypted_reason, &keys.old) {
+                    Some(reason) => reason,
+                    None => return Err(unreadable(guild_id, "reason of case", id.into())),
+                };
+
+                self.timed(
+                    "rotate_guild_reasons",
+                    sqlx::query("UPDATE history SET encrypted_reason=$2 WHERE id=$1")
+                        .bind(id)
+                        .bind(crypto::encrypt_with_random_nonce(&reason, &keys.new))
+                        .execute(&mut *conn),
+                )
+                .await?;
+                report.other += 1;
+            }
+        }
+    }
+
+    async fn rotate_nicknames(
+        &self,
+        conn: &mut PgConnection,
+        guild_id: GuildId,
+        keys: &KeyPair<'_>,
+        report: &mut GuildKeyRotationReport,
+    ) -> Result<(), DatabaseError> {
+        let mut after = 0;
+        loop {
+            let chunk: Vec<(i32, Option<Vec<u8>>, Option<Vec<u8>>)> = self
+                .timed(
+                    "rotate_guild_nicknames",
+                    sqlx::query_as(
+                        "SELECT id, encrypted_old_name, encrypted_new_name FROM nickname_change
+                        WHERE guild_id=$1 AND id > $2 ORDER BY id LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(after)
+                    .bind(ROTATION_CHUNK_SIZE)
+                    .fetch_all(&mut *conn),
+                )
+                .await?;
+
+            after = match chunk.last() {
+                Some((id, ..)) => *id,
+                None => return Ok(()),
+            };
+
+            for (id, old_name, new_name) in chunk {
+                let reencrypt = |name: Option<Vec<u8>>| match name {
+                    Some(name) => crypto::decrypt_with_random_nonce(&name, &keys.old)
+                        .map(|name| Some(crypto::encrypt_with_random_nonce(&name, &keys.new))),
+                    None => Some(None),
+                };
+                let (old_name, new_name) = match (reencrypt(old_name), reencrypt(new_name)) {
+                    (Some(old_name), Some(new_name)) => (old_name, new_name),
+                    _ => return Err(unreadable(guild_id, "nickname change", id.into())),
+                };
+
+                self.timed(
+                    "rotate_guild_nicknames",
+                    sqlx::query("UPDATE nickname_change SET encrypted_old_name=$2, encrypted_new_name=$3 WHERE id=$1")
+                        .bind(id)
+                        .bind(old_name)
+                        .bind(new_name)
+                        .execute(&mut *conn),
+                )
+                .await?;
+                report.other += 1;
+            }
+        }
+    }
+
+    /// Message content (of a revision) encrypted with the new key, and rehashed if it had a hash as well.
+    ///
+    /// The nonce stays tied to the message id and revision, ``None`` if the old key can't decrypt it.
+    fn reencrypt_content(
+        &self,
+        message_id: i64,
+        revision: i32,
+        ciphertext: &[u8],
+        algorithm: i16,
+        hashed: bool,
+        keys: &KeyPair<'_>,
+    ) -> Option<(Vec<u8>, Option<Vec<u8>>)> {
+        let message_id = message_id as u64;
+        let plaintext = crypto::decrypt_message(ciphertext, &keys.old, message_id, revision, algorithm)?;
+        let ciphertext = crypto::encrypt_message(&plaintext, &keys.new, message_id, revision, self.message_encryption);
+        let content_hash = if hashed {
+            Some(crypto::salted_hash(&plaintext, &keys.new))
+        } else {
+            None
+        };
+
+        Some((ciphertext, content_hash))
+    }
+}
+
+fn unreadable(guild_id: GuildId, kind: &'static str, id: i64) -> DatabaseError {
+    DatabaseError::UnreadableDuringRotation {
+        guild_id: guild_id.0,
+        kind,
+        id,
+    }
+}
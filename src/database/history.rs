@@ -0,0 +1,298 @@
+use chrono::{DateTime, TimeZone, Utc};
+use twilight_model::id::{GuildId, UserId};
+
+use super::crypto;
+use super::structures::{ExportedHistoryEntry, HistoryEntry, HistoryFilter, StoredHistoryEntry};
+use super::DataStorage;
+use crate::core::ImportedAction;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Records a moderation action against a user, returning the case number it got.
+    pub async fn insert_history_entry(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        mod_id: UserId,
+        kind: &str,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<i32, DatabaseError> {
+        let id: (i32,) = self
+            .timed(
+                "insert_history_entry",
+                sqlx::query_as(
+                    "INSERT INTO history (guild_id, user_id, mod_id, type, \"end\")
+                    VALUES ($1, $2, $3, $4::historyType, to_timestamp($5)) RETURNING id",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .bind(mod_id.0 as i64)
+                .bind(kind)
+                .bind(end.map(|end| end.timestamp()))
+                .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(id.0)
+    }
+
+    /// Stores moderation actions brought over from another bot, flagged as imported and with the time they
+    /// originally happened. Reasons get encrypted with the guild key, returns how many entries were stored.
+    pub async fn import_history_entries(
+        &self,
+        guild_id: GuildId,
+        actions: &[ImportedAction],
+    ) -> Result<usize, DatabaseError> {
+        let mut tx = self.timed("import_history_entry", self.persistent_pool.begin()).await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        for action in actions {
+            // there is no id to base a nonce on before the entry is stored
+            let reason = action
+                .reason
+                .as_ref()
+                .map(|reason| crypto::encrypt_with_random_nonce(reason.as_bytes(), &guild_key));
+
+            self.timed(
+                "import_history_entry",
+                sqlx::query(
+                    "INSERT INTO history (guild_id, user_id, mod_id, type, start, \"end\", imported, encrypted_reason)
+                    VALUES ($1, $2, $3, $4::historyType, to_timestamp($5), to_timestamp($6), true, $7)",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(action.user_id.0 as i64)
+                .bind(action.mod_id.0 as i64)
+                .bind(action.kind)
+                .bind(action.start.timestamp())
+                .bind(action.end.map(|end| end.timestamp()))
+                .bind(reason)
+                .execute(&mut *tx),
+            )
+            .await?;
+        }
+        self.timed("import_history_entry", tx.commit()).await?;
+
+        Ok(actions.len())
+    }
+
+    /// Ends the mutes of a user that are still running, returning how many there were.
+    pub async fn end_active_mutes(&self, guild_id: GuildId, user_id: UserId) -> Result<u64, DatabaseError> {
+        let result = self
+            .timed(
+                "end_active_mutes",
+                sqlx::query(
+                    "UPDATE history SET \"end\"=now()
+                    WHERE guild_id=$1 AND user_id=$2 AND type='mute' AND \"end\" > now()",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Retrieves a single moderation history entry (case) for a guild.
+    pub async fn get_history_entry(
+        &self,
+        guild_id: GuildId,
+        case_id: i32,
+    ) -> Result<Option<HistoryEntry>, DatabaseError> {
+        let entry = self
+            .timed(
+                "get_history_entry",
+                sqlx::query_as(
+                    "SELECT h.id, h.guild_id, h.user_id, h.mod_id, h.type::text AS kind, r.id IS NOT NULL AS removed
+                    FROM history h LEFT JOIN historyRemoval r ON r.history_id = h.id
+                    WHERE h.id=$1 AND h.guild_id=$2",
+                )
+                .bind(case_id)
+                .bind(guild_id.0 as i64)
+                .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(entry)
+    }
+
+    /// Replaces the reason of a case, encrypted with the guild key, and records who changed it.
+    pub async fn update_history_reason(
+        &self,
+        guild_id: GuildId,
+        case_id: i32,
+        reason: &str,
+        edited_by: UserId,
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self
+            .timed("update_history_reason", self.persistent_pool.begin())
+            .await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        let reason = crypto::encrypt_with_random_nonce(reason.as_bytes(), &guild_key);
+
+        self.timed(
+            "update_history_reason",
+            sqlx::query(
+                "UPDATE history SET encrypted_reason=$3, reason_edited_by=$4, reason_edited_at=now()
+                WHERE id=$1 AND guild_id=$2",
+            )
+            .bind(case_id)
+            .bind(guild_id.0 as i64)
+            .bind(reason)
+            .bind(edited_by.0 as i64)
+            .execute(&mut *tx),
+        )
+        .await?;
+        self.timed("update_history_reason", tx.commit()).await?;
+
+        Ok(())
+    }
+
+    /// Counts the moderation actions taken since the given time, per moderator and type of action.
+    pub async fn get_moderator_activity(
+        &self,
+        guild_id: GuildId,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(i64, String, i64)>, DatabaseError> {
+        let activity = self
+            .timed(
+                "get_moderator_activity",
+                sqlx::query_as(
+                    "SELECT mod_id, type::text AS kind, count(*) FROM history
+                    WHERE guild_id=$1 AND start >= to_timestamp($2)
+                    GROUP BY mod_id, type",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(since.timestamp())
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(activity)
+    }
+
+    /// Counts the warnings of a user that haven't been removed.
+    pub async fn count_active_warnings(&self, guild_id: GuildId, user_id: UserId) -> Result<i64, DatabaseError> {
+        let count: (i64,) = self
+            .timed(
+                "count_active_warnings",
+                sqlx::query_as(
+                    "SELECT count(*) FROM history h
+                    WHERE h.guild_id=$1 AND h.user_id=$2 AND h.type='warning'
+                    AND NOT EXISTS (SELECT 1 FROM historyRemoval r WHERE r.history_id = h.id)",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(count.0)
+    }
+
+    /// Marks a single history entry as removed by the given moderator.
+    pub async fn remove_history_entry(
+        &self,
+        guild_id: GuildId,
+        case_id: i32,
+        removed_by: UserId,
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "remove_history_entry",
+            sqlx::query("INSERT INTO historyRemoval (history_id, guild_id, removed_by) VALUES ($1, $2, $3)")
+                .bind(case_id)
+                .bind(guild_id.0 as i64)
+                .bind(removed_by.0 as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marks all active warnings of a user as removed, returning how many there were.
+    pub async fn clear_warnings(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        removed_by: UserId,
+    ) -> Result<u64, DatabaseError> {
+        let result = self
+            .timed(
+                "clear_warnings",
+                sqlx::query(
+                    "INSERT INTO historyRemoval (history_id, guild_id, removed_by)
+                    SELECT h.id, h.guild_id, $3 FROM history h
+                    WHERE h.guild_id=$1 AND h.user_id=$2 AND h.type='warning'
+                    AND NOT EXISTS (SELECT 1 FROM historyRemoval r WHERE r.history_id = h.id)",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .bind(removed_by.0 as i64)
+                .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// A page of the moderation history of a guild matching the filter, oldest first. Pages continue after the
+    /// entry with the given id, start at 0 for the first page.
+    pub async fn get_history_page(
+        &self,
+        guild_id: GuildId,
+        filter: &HistoryFilter,
+        after: i32,
+        limit: i64,
+    ) -> Result<Vec<ExportedHistoryEntry>, DatabaseError> {
+        let stored: Vec<StoredHistoryEntry> = self
+            .timed(
+                "get_history_page",
+                sqlx::query_as(
+                    "SELECT h.id, h.user_id, h.mod_id, h.type::text AS kind,
+                    extract(epoch FROM h.start)::bigint AS start, extract(epoch FROM h.\"end\")::bigint AS \"end\",
+                    h.imported, r.id IS NOT NULL AS removed, h.encrypted_reason
+                    FROM history h LEFT JOIN historyRemoval r ON r.history_id = h.id
+                    WHERE h.guild_id=$1 AND h.id > $2
+                    AND ($3::bigint IS NULL OR h.start >= to_timestamp($3))
+                    AND ($4::bigint IS NULL OR h.start < to_timestamp($4))
+                    AND ($5::bigint IS NULL OR h.mod_id = $5)
+                    AND ($6::text IS NULL OR h.type::text = $6)
+                    ORDER BY h.id LIMIT $7",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(after)
+                .bind(filter.since.map(|since| since.timestamp()))
+                .bind(filter.until.map(|until| until.timestamp()))
+                .bind(filter.moderator.map(|moderator| moderator.0 as i64))
+                .bind(filter.kind)
+                .bind(limit)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        if stored.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let entries = stored
+            .into_iter()
+            .map(|entry| ExportedHistoryEntry {
+                id: entry.id,
+                user_id: UserId(entry.user_id as u64),
+                mod_id: UserId(entry.mod_id as u64),
+                kind: entry.kind,
+                start: Utc.timestamp(entry.start, 0),
+                end: entry.end.map(|end| Utc.timestamp(end, 0)),
+                imported: entry.imported,
+                removed: entry.removed,
+                reason: entry
+                    .encrypted_reason
+                    .and_then(|reason| crypto::decrypt_with_random_nonce(&reason, &guild_key))
+                    .and_then(|reason| String::from_utf8(reason).ok()),
+            })
+            .collect();
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use sqlx::migrate::MigrateError;
+use sqlx::PgPool;
+
+use crate::error::{self, StartupError};
+use crate::{gearbot_error, gearbot_info};
+
+/// How many times running the migrations is tried when the database can't be reached, before giving up.
+const MIGRATION_ATTEMPTS: u32 = 5;
+
+/// Wait before the first retry, it doubles after every failed attempt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Brings the database schema up to date, retrying if the database drops away halfway through.
+///
+/// A migration that failed partway during an earlier run is refused, that needs someone to look at the
+/// schema and fix it by hand rather than piling more migrations on top.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), StartupError> {
+    let migrator = sqlx::migrate!("./migrations");
+
+    let before = applied_migrations(pool).await?;
+    if let Some((version, _)) = before.iter().find(|(_, success)| !success) {
+        gearbot_error!(
+            "Migration {} was only partially applied during an earlier run, the schema has to be checked and fixed by hand before the bot can start",
+            version
+        );
+        return Err(StartupError::PartialMigration(*version));
+    }
+
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match migrator.run(pool).await {
+            Ok(()) => break,
+            Err(MigrateError::Execute(e)) if error::is_connection_error(&e) && attempt < MIGRATION_ATTEMPTS => {
+                log::warn!(
+                    "Lost the database connection while migrating (attempt {} of {}), retrying in {} seconds: {}",
+                    attempt,
+                    MIGRATION_ATTEMPTS,
+                    delay.as_secs(),
+                    e
+                );
+                tokio::time::delay_for(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => {
+                gearbot_error!("Failed to run SQL migrations: {}", e);
+                return Err(StartupError::Migration(e));
+            }
+        }
+    }
+
+    let after = applied_migrations(pool).await?;
+    if let Some((version, _)) = after.iter().find(|(_, success)| !success) {
+        gearbot_error!(
+            "Migration {} did not complete, the schema has to be checked and fixed by hand before the bot can start",
+            version
+        );
+        return Err(StartupError::PartialMigration(*version));
+    }
+
+    let mut applied = 0;
+    for migration in migrator.iter() {
+        let was_applied = before.iter().any(|(version, _)| *version == migration.version);
+        let is_applied = after.iter().any(|(version, _)| *version == migration.version);
+        if is_applied && !was_applied {
+            log::info!("Applied migration {}: {}", migration.version, migration.description);
+            applied += 1;
+        }
+    }
+
+    if applied > 0 {
+        gearbot_info!("Applied {} database migrations", applied);
+    } else {
+        log::info!("Database schema is up to date, no migrations needed");
+    }
+
+    Ok(())
+}
+
+/// The migrations the database knows about and if they completed, empty before the very first migration.
+async fn applied_migrations(pool: &PgPool) -> Result<Vec<(i64, bool)>, StartupError> {
+    let (exists,): (bool,) = sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Ok(vec![]);
+    }
+
+    let migrations = sqlx::query_as("SELECT version, success FROM _sqlx_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?;
+    Ok(migrations)
+}
@@ -1,29 +1,83 @@
+mod alt_accounts;
+mod announcements;
+mod attachment_blobs;
+mod auto_pins;
+mod auto_responses;
 pub mod configs;
+mod emoji_usage;
+mod first_messages;
+mod guild_key_rotation;
+mod history;
+mod name_history;
+mod notes;
+mod raid_mode;
+mod reaction_counts;
+mod role_menus;
+mod starboard;
 
-mod crypto;
-use crypto::EncryptionKey;
+mod circuit_breaker;
+use circuit_breaker::CircuitBreaker;
+
+mod migrations;
+
+pub mod crypto;
+use crypto::{EncryptionAlgorithm, EncryptionKey};
 
 mod redis;
 pub use redis::{api_structs, Redis};
 
 pub mod structures;
-use structures::{StoredUserMessage, UserMessage};
+use structures::{
+    BulkInsertReport, ContentStorage, GuildAttachmentStats, KeyRotationReport, MessageEdit, MessageVerificationReport,
+    StoredAttachment, StoredMessageEdit, StoredUserMessage, UserMessage,
+};
 
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use twilight_model::channel::message::MessageType;
 use twilight_model::channel::{Attachment, Message};
 use twilight_model::id::{ChannelId, GuildId, MessageId, UserId, WebhookId};
 
+use crate::core::BotStats;
 use crate::database::structures::WebhookInfo;
-use crate::error::{DatabaseError, StartupError};
+use crate::error::{self, DatabaseError, StartupError};
+use crate::utils;
 use crate::BotConfig;
 use crate::{gearbot_error, gearbot_info};
 
 /// An abstraction over the persistent backing storage of the Bot (SQL) and the Redis cache that lives inbetween.
 ///
 /// All database access should go through here.
+/// How many messages are decrypted at once before giving other tasks a turn.
+const DECRYPT_BATCH_SIZE: usize = 100;
+
+/// How many messages are fetched per query when verifying the messages of a guild.
+const VERIFY_FETCH_SIZE: i64 = 1_000;
+
+/// How many ids of messages that failed verification are kept for the report.
+const VERIFY_FAILURE_SAMPLES: usize = 10;
+
+/// How many messages go into a single insert when storing a batch, each one takes up a bind parameter per column.
+const BULK_INSERT_SIZE: usize = 1_000;
+
+/// The columns set when inserting a message.
+const MESSAGE_COLUMNS: usize = 10;
+
 pub struct DataStorage {
     persistent_pool: sqlx::PgPool,
     pub cache_pool: Redis,
     primary_encryption_key: EncryptionKey<'static>,
+    previous_encryption_key: Option<EncryptionKey<'static>>,
+    stats: Arc<BotStats>,
+    slow_query_threshold: Duration,
+    breaker: CircuitBreaker,
+    message_encryption: EncryptionAlgorithm,
+    /// Guilds we already warned about for still having a key encrypted with the previous main key.
+    outdated_key_warned: Mutex<HashSet<GuildId>>,
 }
 
 impl DataStorage {
@@ -34,7 +88,7 @@ impl DataStorage {
     ///
     /// While connecting to the SQL server, any required migrations will be ran
     /// before returning.
-    pub async fn initalize(config: &BotConfig) -> Result<Self, StartupError> {
+    pub async fn initalize(config: &BotConfig, stats: Arc<BotStats>) -> Result<Self, StartupError> {
         let postgres_pool = match sqlx::Pool::connect(&config.database.postgres).await {
             Ok(pool) => pool,
             Err(e) => {
@@ -46,11 +100,7 @@ impl DataStorage {
         log::info!("Connected to Postgres!");
 
         log::info!("Handling database migrations...");
-        if let Err(e) = sqlx::migrate!("./migrations").run(&postgres_pool).await {
-            gearbot_error!("Failed to run SQL migrations: {}", e);
-            return Err(StartupError::Sqlx(e.into()));
-        }
-
+        migrations::run_migrations(&postgres_pool).await?;
         log::info!("Finished migrations!");
 
         let redis_pool = match Redis::new(&config.database.redis).await {
@@ -69,58 +119,463 @@ impl DataStorage {
             persistent_pool: postgres_pool,
             cache_pool: redis_pool,
             primary_encryption_key: EncryptionKey::construct_owned(&config.main_encryption_key),
+            previous_encryption_key: config
+                .previous_encryption_key
+                .as_deref()
+                .map(EncryptionKey::construct_owned),
+            breaker: CircuitBreaker::new(
+                config.database.failure_threshold,
+                Duration::from_secs(config.database.retry_after),
+                stats.database.unavailable.clone(),
+            ),
+            stats,
+            slow_query_threshold: Duration::from_millis(config.database.slow_query_threshold),
+            message_encryption: config.database.message_encryption,
+            outdated_key_warned: Mutex::new(HashSet::new()),
         })
     }
 
+    /// Awaits a query, warning about it and counting it in the metrics if it was slower than the configured threshold.
+    ///
+    /// All queries should go through this so they are instrumented the same way.
+    /// While the database is unreachable queries are refused right away instead.
+    async fn timed<T>(
+        &self,
+        label: &'static str,
+        query: impl Future<Output = Result<T, sqlx::Error>>,
+    ) -> Result<T, DatabaseError> {
+        if !self.breaker.allows_queries() {
+            return Err(DatabaseError::Unavailable);
+        }
+
+        let start = Instant::now();
+        let result = query.await;
+        let elapsed = start.elapsed();
+
+        if elapsed > self.slow_query_threshold {
+            log::warn!("Slow query: {} took {}ms", label, elapsed.as_millis());
+            self.stats.database.slow_queries.with_label_values(&[label]).inc();
+        }
+
+        self.stats.database.pool_size.set(self.persistent_pool.size() as i64);
+        self.stats
+            .database
+            .pool_idle
+            .set(self.persistent_pool.num_idle() as i64);
+
+        match &result {
+            Err(e) if error::is_connection_error(e) => self.breaker.record_connection_failure(),
+            // the database answered, even if it didn't like the query
+            _ => self.breaker.record_success(),
+        }
+
+        Ok(result?)
+    }
+
+    /// Runs the simplest possible query, returning how long the round trip took.
+    pub async fn ping(&self) -> Result<Duration, DatabaseError> {
+        let start = Instant::now();
+        self.timed("ping", sqlx::query("SELECT 1").execute(&self.persistent_pool))
+            .await?;
+        Ok(start.elapsed())
+    }
+
+    /// The size of the postgres connection pool, and how many of those connections are idle.
+    pub fn pool_usage(&self) -> (u32, usize) {
+        (self.persistent_pool.size(), self.persistent_pool.num_idle())
+    }
+
     /// Inserts a message into the database.
     ///
     /// The guild ID provided *must* be the same guild that the message was recieved in, otherwise
     /// it will fail to decrypt upon retrieval.
-    pub async fn insert_message(&self, message: &Message, guild_id: GuildId) -> Result<(), DatabaseError> {
-        let start = std::time::Instant::now();
-
-        let ciphertext = {
-            let plaintext = message.content.as_bytes();
-
-            let guild_key = self.get_guild_encryption_key(guild_id).await?;
-            crypto::encrypt_bytes(plaintext, &guild_key, message.id.0)
+    ///
+    /// In privacy mode only a salted hash of the content is kept, the content itself can't be retrieved later.
+    pub async fn insert_message(
+        &self,
+        message: &Message,
+        guild_id: GuildId,
+        storage: ContentStorage,
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.timed("insert_message", self.persistent_pool.begin()).await?;
+        let (ciphertext, content_hash) = match stored_content_kind(message, storage) {
+            ContentStorage::Nothing => (None, None),
+            storage => {
+                let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+                self.protect_content(message, storage, &guild_key)
+            }
         };
 
-        log::debug!("It took {}us to encrypt the user message!", start.elapsed().as_micros());
-
-        sqlx::query(
-            "INSERT INTO message (id, encrypted_content, author_id, channel_id, guild_id, kind, pinned)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        self.timed(
+            "insert_message",
+            sqlx::query(
+                "INSERT INTO message (id, encrypted_content, encryption_algorithm, content_hash, author_id, channel_id,
+                    guild_id, kind, pinned, referenced_message_id)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+            )
+            .bind(message.id.0 as i64)
+            .bind(ciphertext)
+            .bind(self.message_encryption.id())
+            .bind(content_hash)
+            .bind(message.author.id.0 as i64)
+            .bind(message.channel_id.0 as i64)
+            .bind(message.guild_id.unwrap().0 as i64)
+            .bind(message.kind as i16)
+            .bind(message.pinned)
+            .bind(
+                message
+                    .reference
+                    .as_ref()
+                    .and_then(|reference| reference.message_id)
+                    .map(|id| id.0 as i64),
+            )
+            .execute(&mut *tx),
         )
-        .bind(message.id.0 as i64)
-        .bind(ciphertext)
-        .bind(message.author.id.0 as i64)
-        .bind(message.channel_id.0 as i64)
-        .bind(message.guild_id.unwrap().0 as i64)
-        .bind(message.kind as i16)
-        .bind(message.pinned)
-        .execute(&self.persistent_pool)
         .await?;
+        self.timed("insert_message", tx.commit()).await?;
 
         Ok(())
     }
 
+    /// Inserts a batch of messages, like after recovering from an outage, in a handful of queries.
+    ///
+    /// Each guild key is only fetched once and dropped again when the batch is done, the whole batch is stored
+    /// in one transaction so the keys stay locked until it is. Messages of a guild whose key can't be used are
+    /// reported as failed without holding back the rest of the batch, messages that were already stored are
+    /// left as they are.
+    pub async fn insert_messages(
+        &self,
+        messages: &[(&Message, GuildId, ContentStorage)],
+    ) -> Result<BulkInsertReport, DatabaseError> {
+        let mut report = BulkInsertReport::default();
+        let mut guild_keys: HashMap<GuildId, Result<EncryptionKey<'_>, String>> = HashMap::new();
+        let mut rows = Vec::with_capacity(messages.len());
+        let mut tx = self.timed("insert_messages", self.persistent_pool.begin()).await?;
+
+        for (message, guild_id, storage) in messages {
+            let (ciphertext, content_hash) = match stored_content_kind(message, *storage) {
+                ContentStorage::Nothing => (None, None),
+                storage => {
+                    if !guild_keys.contains_key(guild_id) {
+                        let guild_key = match self.lock_guild_encryption_key(&mut tx, *guild_id).await {
+                            Ok(guild_key) => Ok(guild_key),
+                            // nothing else is going to get through either
+                            Err(DatabaseError::Unavailable) => return Err(DatabaseError::Unavailable),
+                            Err(e) => Err(e.to_string()),
+                        };
+                        guild_keys.insert(*guild_id, guild_key);
+                    }
+
+                    match &guild_keys[guild_id] {
+                        Ok(guild_key) => self.protect_content(message, storage, guild_key),
+                        Err(e) => {
+                            report.failed.push((message.id, e.clone()));
+                            continue;
+                        }
+                    }
+                }
+            };
+            rows.push((*message, *guild_id, ciphertext, content_hash));
+        }
+        drop(guild_keys);
+
+        for chunk in rows.chunks(BULK_INSERT_SIZE) {
+            let values = (0..chunk.len())
+                .map(|row| {
+                    let placeholders = (1..=MESSAGE_COLUMNS)
+                        .map(|column| format!("${}", row * MESSAGE_COLUMNS + column))
+                        .collect::<Vec<_>>();
+                    format!("({})", placeholders.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let statement = format!(
+                "INSERT INTO message (id, encrypted_content, encryption_algorithm, content_hash, author_id, channel_id,
+                    guild_id, kind, pinned, referenced_message_id)
+                    VALUES {} ON CONFLICT (id) DO NOTHING RETURNING id",
+                values
+            );
+
+            let mut query = sqlx::query_as::<_, (i64,)>(&statement);
+            for (message, guild_id, ciphertext, content_hash) in chunk {
+                query = query
+                    .bind(message.id.0 as i64)
+                    .bind(ciphertext.as_deref())
+                    .bind(self.message_encryption.id())
+                    .bind(content_hash.as_deref())
+                    .bind(message.author.id.0 as i64)
+                    .bind(message.channel_id.0 as i64)
+                    .bind(guild_id.0 as i64)
+                    .bind(message.kind as i16)
+                    .bind(message.pinned)
+                    .bind(
+                        message
+                            .reference
+                            .as_ref()
+                            .and_then(|reference| reference.message_id)
+                            .map(|id| id.0 as i64),
+                    );
+            }
+
+            let written = self.timed("insert_messages", query.fetch_all(&mut *tx)).await?;
+            report
+                .written
+                .extend(written.into_iter().map(|(id,)| MessageId(id as u64)));
+        }
+        self.timed("insert_messages", tx.commit()).await?;
+
+        Ok(report)
+    }
+
+    /// Encrypts or hashes the content of a message for storage, depending on what the guild wants kept.
+    fn protect_content(
+        &self,
+        message: &Message,
+        storage: ContentStorage,
+        guild_key: &EncryptionKey<'_>,
+    ) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+        let plaintext = message.content.as_bytes();
+        match storage {
+            ContentStorage::Nothing => (None, None),
+            ContentStorage::Hash => (None, Some(crypto::salted_hash(plaintext, guild_key))),
+            ContentStorage::Encrypted => {
+                let start = Instant::now();
+                let ciphertext =
+                    crypto::encrypt_message(plaintext, guild_key, message.id.0, 0, self.message_encryption);
+
+                let elapsed = start.elapsed();
+                self.stats.crypto.encrypt_time.observe(elapsed.as_secs_f64());
+                log::debug!("It took {}us to encrypt the user message!", elapsed.as_micros());
+
+                (Some(ciphertext), None)
+            }
+        }
+    }
+
+    /// Hashes content the same way privacy mode stores it, to compare new messages against stored ones.
+    pub async fn hash_content(&self, guild_id: GuildId, content: &str) -> Result<Vec<u8>, DatabaseError> {
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        Ok(crypto::salted_hash(content.as_bytes(), &guild_key))
+    }
+
+    /// Stores the new content of an edited message, the version it replaces is kept as an edit.
+    ///
+    /// Edits to messages that were never stored (sent before we got here) are ignored.
+    pub async fn update_message(
+        &self,
+        message_id: MessageId,
+        guild_id: GuildId,
+        content: &str,
+        storage: ContentStorage,
+    ) -> Result<(), DatabaseError> {
+        if storage == ContentStorage::Nothing {
+            return Ok(());
+        }
+
+        let mut tx = self.timed("update_message", self.persistent_pool.begin()).await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        let plaintext = content.as_bytes();
+
+        loop {
+            let current: Option<(i32,)> = self
+                .timed(
+                    "get_message_revision",
+                    sqlx::query_as("SELECT revision FROM message WHERE id=$1")
+                        .bind(message_id.0 as i64)
+                        .fetch_optional(&mut *tx),
+                )
+                .await?;
+            let revision = match current {
+                Some((revision,)) => revision,
+                None => return Ok(()),
+            };
+
+            // the new content is encrypted for the next revision, reusing the nonce of the old one would leak both
+            let (ciphertext, content_hash) = if storage == ContentStorage::Hash {
+                (None, Some(crypto::salted_hash(plaintext, &guild_key)))
+            } else {
+                let ciphertext = crypto::encrypt_message(
+                    plaintext,
+                    &guild_key,
+                    message_id.0,
+                    revision + 1,
+                    self.message_encryption,
+                );
+                (Some(ciphertext), None)
+            };
+
+            let result = self
+                .timed(
+                    "update_message",
+                    sqlx::query(
+                        "WITH previous AS (
+                            SELECT id, revision, encrypted_content, encryption_algorithm, content_hash FROM message
+                            WHERE id=$1 AND revision=$2 FOR UPDATE
+                        ), archived AS (
+                            INSERT INTO message_edit (message_id, revision, encrypted_content, encryption_algorithm,
+                                content_hash)
+                            SELECT id, revision, encrypted_content, encryption_algorithm, content_hash FROM previous
+                        )
+                        UPDATE message SET encrypted_content=$3, encryption_algorithm=$4, content_hash=$5,
+                            revision=previous.revision + 1
+                        FROM previous WHERE message.id = previous.id",
+                    )
+                    .bind(message_id.0 as i64)
+                    .bind(revision)
+                    .bind(ciphertext)
+                    .bind(self.message_encryption.id())
+                    .bind(content_hash)
+                    .execute(&mut *tx),
+                )
+                .await?;
+
+            if result.rows_affected() > 0 {
+                self.timed("update_message", tx.commit()).await?;
+                return Ok(());
+            }
+            // another edit got stored in the meantime, go again on top of that one
+        }
+    }
+
+    /// Retrieves the earlier versions of an edited message, oldest first.
+    ///
+    /// The guild ID provided *must* be the same guild that the message was recieved in, otherwise
+    /// it will fail to decrypt upon retrieval.
+    pub async fn get_message_edits(
+        &self,
+        message_id: MessageId,
+        guild_id: GuildId,
+    ) -> Result<Vec<MessageEdit>, DatabaseError> {
+        let stored: Vec<StoredMessageEdit> = self
+            .timed(
+                "get_message_edits",
+                sqlx::query_as(
+                    "SELECT e.revision, e.encrypted_content, e.encryption_algorithm, e.edited_at
+                    FROM message_edit e INNER JOIN message m ON m.id = e.message_id
+                    WHERE e.message_id=$1 AND m.guild_id=$2 ORDER BY e.revision",
+                )
+                .bind(message_id.0 as i64)
+                .bind(guild_id.0 as i64)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        if stored.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let edits = stored
+            .into_iter()
+            .map(|edit| MessageEdit {
+                revision: edit.revision,
+                edited_at: edit.edited_at,
+                content: edit.encrypted_content.and_then(|encrypted_content| {
+                    self.decrypt_content(
+                        message_id,
+                        edit.revision,
+                        &encrypted_content,
+                        edit.encryption_algorithm,
+                        &guild_key,
+                    )
+                }),
+            })
+            .collect();
+
+        Ok(edits)
+    }
+
     /// Inserts a message attachment into the database.
     pub async fn insert_attachment(&self, message_id: MessageId, attachment: &Attachment) -> Result<(), DatabaseError> {
-        sqlx::query(
-            "INSERT INTO attachment (id, name, image, message_id)
-            VALUES ($1, $2, $3, $4)",
+        self.timed(
+            "insert_attachment",
+            sqlx::query(
+                "INSERT INTO attachment (id, name, image, message_id)
+                    VALUES ($1, $2, $3, $4)",
+            )
+            .bind(attachment.id.0 as i64)
+            .bind(&attachment.filename)
+            .bind(attachment.width.is_some())
+            .bind(message_id.0 as i64)
+            .execute(&self.persistent_pool),
         )
-        .bind(attachment.id.0 as i64)
-        .bind(&attachment.filename)
-        .bind(attachment.width.is_some())
-        .bind(message_id.0 as i64)
-        .execute(&self.persistent_pool)
         .await?;
 
+        if attachment.width.is_some() {
+            self.stats.attachments.images.inc();
+        } else {
+            self.stats.attachments.other.inc();
+        }
+
         Ok(())
     }
 
+    /// The attachments that were stored along with these messages.
+    pub async fn get_attachments(&self, message_ids: &[MessageId]) -> Result<Vec<StoredAttachment>, DatabaseError> {
+        let ids: Vec<i64> = message_ids.iter().map(|id| id.0 as i64).collect();
+
+        let attachments = self
+            .timed(
+                "get_attachments",
+                sqlx::query_as(
+                    "SELECT id, name AS filename, image AS is_image, message_id FROM attachment
+                    WHERE message_id = ANY($1)",
+                )
+                .bind(ids)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(attachments)
+    }
+
+    /// Counts the attachments stored for a guild, split into images and other files, along with how much
+    /// space the copies kept of them take up.
+    pub async fn get_attachment_stats(&self, guild_id: GuildId) -> Result<GuildAttachmentStats, DatabaseError> {
+        let stats = self
+            .timed(
+                "get_attachment_stats",
+                sqlx::query_as(
+                    "SELECT count(*) AS total, count(*) FILTER (WHERE attachment.image) AS images,
+                    COALESCE(SUM(octet_length(blob.encrypted_content)), 0)::bigint AS stored_bytes
+                    FROM attachment INNER JOIN message ON message.id = attachment.message_id
+                    LEFT JOIN attachment_blob blob ON blob.attachment_id = attachment.id
+                    WHERE message.guild_id=$1",
+                )
+                .bind(guild_id.0 as i64)
+                .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(stats)
+    }
+
+    /// Counts the stored messages per channel of a guild since the given time, busiest channels first.
+    ///
+    /// Only metadata is used, nothing has to be decrypted for this.
+    pub async fn get_channel_activity(
+        &self,
+        guild_id: GuildId,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64)>, DatabaseError> {
+        let activity = self
+            .timed(
+                "get_channel_activity",
+                sqlx::query_as(
+                    "SELECT channel_id, count(*) AS messages FROM message WHERE guild_id=$1 AND id >= $2
+                    GROUP BY channel_id ORDER BY messages DESC LIMIT $3",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(utils::timestamp_snowflake(since) as i64)
+                .bind(limit)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(activity)
+    }
+
     /// Retrieves a user's message from the database, if it existed.
     ///
     /// The guild ID provided *must* be the same guild that the message was recieved in, otherwise
@@ -130,39 +585,194 @@ impl DataStorage {
         message_id: MessageId,
         guild_id: GuildId,
     ) -> Result<Option<UserMessage>, DatabaseError> {
-        let stored_message: Option<StoredUserMessage> = sqlx::query_as("SELECT * from message where id=$1")
-            .bind(message_id.0 as i64)
-            .fetch_optional(&self.persistent_pool)
+        let stored_message: Option<StoredUserMessage> = self
+            .timed(
+                "get_full_message",
+                sqlx::query_as("SELECT * from message where id=$1")
+                    .bind(message_id.0 as i64)
+                    .fetch_optional(&self.persistent_pool),
+            )
             .await?;
 
         let user_msg = match stored_message {
             Some(sm) => {
-                let start = std::time::Instant::now();
-
                 let guild_key = self.get_guild_encryption_key(guild_id).await?;
-                let decrypted_content = crypto::decrypt_bytes(&sm.encrypted_content, &guild_key, message_id.0);
+                Some(self.decrypt_message(sm, &guild_key))
+            }
+            None => None,
+        };
 
-                log::debug!("It took {}us to decrypt a user message!", start.elapsed().as_micros());
+        Ok(user_msg)
+    }
 
-                Some(UserMessage {
-                    content: String::from_utf8(decrypted_content).unwrap(),
-                    author: UserId(sm.author_id as u64),
-                    channel: ChannelId(sm.channel_id as u64),
-                    guild: GuildId(sm.guild_id as u64),
-                    kind: sm.kind(),
-                    pinned: sm.pinned,
-                })
+    /// Retrieves the next page of stored channel messages with an id between ``after`` and ``before``, oldest first.
+    ///
+    /// Paging by id instead of an offset keeps every page equally fast, no matter how deep into the channel it is.
+    pub async fn get_channel_messages_after(
+        &self,
+        channel_id: ChannelId,
+        guild_id: GuildId,
+        after: u64,
+        before: u64,
+        limit: i64,
+    ) -> Result<Vec<UserMessage>, DatabaseError> {
+        let stored: Vec<StoredUserMessage> = self
+            .timed(
+                "get_channel_messages_after",
+                sqlx::query_as(
+                    "SELECT * from message WHERE channel_id=$1 AND guild_id=$2 AND id > $3 AND id < $4
+                    ORDER BY id LIMIT $5",
+                )
+                .bind(channel_id.0 as i64)
+                .bind(guild_id.0 as i64)
+                .bind(after as i64)
+                .bind(before as i64)
+                .bind(limit)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        self.decrypt_messages(stored, guild_id).await
+    }
+
+    /// Retrieves the last ``limit`` stored messages sent in a channel, newest first.
+    pub async fn get_recent_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        guild_id: GuildId,
+        limit: i64,
+    ) -> Result<Vec<UserMessage>, DatabaseError> {
+        let stored: Vec<StoredUserMessage> = self
+            .timed(
+                "get_recent_channel_messages",
+                sqlx::query_as("SELECT * from message WHERE channel_id=$1 AND guild_id=$2 ORDER BY id DESC LIMIT $3")
+                    .bind(channel_id.0 as i64)
+                    .bind(guild_id.0 as i64)
+                    .bind(limit)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        self.decrypt_messages(stored, guild_id).await
+    }
+
+    /// Decrypts the messages in batches, yielding in between so large amounts don't hold up everything else.
+    async fn decrypt_messages(
+        &self,
+        stored: Vec<StoredUserMessage>,
+        guild_id: GuildId,
+    ) -> Result<Vec<UserMessage>, DatabaseError> {
+        if stored.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let mut messages = Vec::with_capacity(stored.len());
+        let mut stored = stored.into_iter().peekable();
+        while stored.peek().is_some() {
+            for sm in stored.by_ref().take(DECRYPT_BATCH_SIZE) {
+                messages.push(self.decrypt_message(sm, &guild_key));
             }
+
+            tokio::task::yield_now().await;
+        }
+
+        Ok(messages)
+    }
+
+    fn decrypt_message(&self, sm: StoredUserMessage, guild_key: &EncryptionKey<'_>) -> UserMessage {
+        let message_id = MessageId(sm.id as u64);
+        let content = match &sm.encrypted_content {
+            Some(encrypted_content) => self.decrypt_content(
+                message_id,
+                sm.revision,
+                encrypted_content,
+                sm.encryption_algorithm,
+                guild_key,
+            ),
+            // stored in privacy mode, or a system message stored without content
             None => None,
         };
 
-        Ok(user_msg)
+        UserMessage {
+            id: message_id,
+            content,
+            author: UserId(sm.author_id as u64),
+            channel: ChannelId(sm.channel_id as u64),
+            guild: GuildId(sm.guild_id as u64),
+            kind: sm.kind(),
+            pinned: sm.pinned,
+            referenced_message: sm.referenced_message_id.map(|id| MessageId(id as u64)),
+            content_hash: sm.content_hash,
+        }
+    }
+
+    fn decrypt_content(
+        &self,
+        message_id: MessageId,
+        revision: i32,
+        encrypted_content: &[u8],
+        algorithm: i16,
+        guild_key: &EncryptionKey<'_>,
+    ) -> Option<String> {
+        let start = Instant::now();
+
+        let decrypted_content =
+            crypto::decrypt_message(encrypted_content, guild_key, message_id.0, revision, algorithm)
+                .expect("Failed to decrypt a message!");
+
+        let elapsed = start.elapsed();
+        self.stats.crypto.decrypt_time.observe(elapsed.as_secs_f64());
+        log::debug!("It took {}us to decrypt a user message!", elapsed.as_micros());
+
+        match String::from_utf8(decrypted_content) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                log::warn!("Stored content of message {} is not valid utf-8: {}", message_id, e);
+                None
+            }
+        }
+    }
+
+    /// Flags the stored copies of these messages as deleted.
+    pub async fn mark_messages_deleted(&self, message_ids: &[MessageId]) -> Result<(), DatabaseError> {
+        let ids: Vec<i64> = message_ids.iter().map(|id| id.0 as i64).collect();
+
+        self.timed(
+            "mark_messages_deleted",
+            sqlx::query("UPDATE message SET deleted=true WHERE id = ANY($1)")
+                .bind(ids)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns which of these messages already have a stored copy.
+    pub async fn get_stored_message_ids(&self, message_ids: &[MessageId]) -> Result<Vec<MessageId>, DatabaseError> {
+        let ids: Vec<i64> = message_ids.iter().map(|id| id.0 as i64).collect();
+
+        let stored: Vec<(i64,)> = self
+            .timed(
+                "get_stored_message_ids",
+                sqlx::query_as("SELECT id FROM message WHERE id = ANY($1)")
+                    .bind(ids)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(stored.into_iter().map(|(id,)| MessageId(id as u64)).collect())
     }
 
     pub async fn get_webhook_parts(&self, channel_id: ChannelId) -> Result<Option<(WebhookId, String)>, DatabaseError> {
-        let data: Option<WebhookInfo> = sqlx::query_as("SELECT * from webhook where channel_id=$1")
-            .bind(channel_id.0 as i64)
-            .fetch_optional(&self.persistent_pool)
+        let data: Option<WebhookInfo> = self
+            .timed(
+                "get_webhook_parts",
+                sqlx::query_as("SELECT * from webhook where channel_id=$1")
+                    .bind(channel_id.0 as i64)
+                    .fetch_optional(&self.persistent_pool),
+            )
             .await?;
         match data {
             Some(data) => Ok(Some((WebhookId(data.id as u64), data.token))),
@@ -176,38 +786,241 @@ impl DataStorage {
         id: WebhookId,
         token: String,
     ) -> Result<(), DatabaseError> {
-        sqlx::query("INSERT INTO webhook (channel_id, id, token) VALUES ($1, $2, $3)")
-            .bind(channel_id.0 as i64)
-            .bind(id.0 as i64)
-            .bind(token)
-            .execute(&self.persistent_pool)
-            .await?;
+        self.timed(
+            "insert_webhook",
+            sqlx::query("INSERT INTO webhook (channel_id, id, token) VALUES ($1, $2, $3)")
+                .bind(channel_id.0 as i64)
+                .bind(id.0 as i64)
+                .bind(token)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
 
         Ok(())
     }
 
-    pub async fn remove_webhook(&self, channel_id: ChannelId) -> Result<(), DatabaseError> {
-        sqlx::query("DELETE FROM webhook where channel_id = $1")
-            .bind(channel_id.0 as i64)
-            .execute(&self.persistent_pool)
+    /// Retrieves all stored webhooks, of all clusters.
+    pub async fn get_all_webhooks(&self) -> Result<Vec<WebhookInfo>, DatabaseError> {
+        let webhooks = self
+            .timed(
+                "get_all_webhooks",
+                sqlx::query_as("SELECT * from webhook").fetch_all(&self.persistent_pool),
+            )
             .await?;
 
+        Ok(webhooks)
+    }
+
+    pub async fn remove_webhook(&self, channel_id: ChannelId) -> Result<(), DatabaseError> {
+        self.timed(
+            "remove_webhook",
+            sqlx::query("DELETE FROM webhook where channel_id = $1")
+                .bind(channel_id.0 as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
         Ok(())
     }
 
     /// Fetches the encryption key for a guild out of its config.
     async fn get_guild_encryption_key(&self, guild_id: GuildId) -> Result<EncryptionKey<'_>, DatabaseError> {
-        let ek_bytes: (Vec<u8>,) = sqlx::query_as("SELECT encryption_key from guildconfig where id=$1")
-            .bind(guild_id.0 as i64)
-            .fetch_one(&self.persistent_pool)
+        let (encrypted_key, generation): (Vec<u8>, i32) = self
+            .timed(
+                "get_guild_encryption_key",
+                sqlx::query_as("SELECT encryption_key, key_generation from guildconfig where id=$1")
+                    .bind(guild_id.0 as i64)
+                    .fetch_one(&self.persistent_pool),
+            )
             .await?;
 
-        let guild_key = {
-            let main_ek = &self.primary_encryption_key;
-            let decrypted_gk_bytes = crypto::decrypt_bytes(&ek_bytes.0, main_ek, guild_id.0);
-            EncryptionKey::construct_owned(&decrypted_gk_bytes)
-        };
+        self.open_guild_key(guild_id, generation, &encrypted_key)
+    }
+
+    /// Fetches the guild key to encrypt new content with, as part of the transaction that stores that content.
+    ///
+    /// Holding the lock until the content is stored makes a key rotation wait for it, so it gets re-encrypted
+    /// along with everything else. Content stored while a rotation is running waits for it and gets the new
+    /// key instead. A key share lock is enough for this, and doesn't hold back config updates.
+    async fn lock_guild_encryption_key(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        guild_id: GuildId,
+    ) -> Result<EncryptionKey<'_>, DatabaseError> {
+        let (encrypted_key, generation): (Vec<u8>, i32) = self
+            .timed(
+                "lock_guild_encryption_key",
+                sqlx::query_as("SELECT encryption_key, key_generation from guildconfig where id=$1 FOR KEY SHARE")
+                    .bind(guild_id.0 as i64)
+                    .fetch_one(&mut *conn),
+            )
+            .await?;
+
+        self.open_guild_key(guild_id, generation, &encrypted_key)
+    }
+
+    fn open_guild_key(
+        &self,
+        guild_id: GuildId,
+        generation: i32,
+        encrypted_key: &[u8],
+    ) -> Result<EncryptionKey<'_>, DatabaseError> {
+        let (key_bytes, outdated) = self.unwrap_guild_key(guild_id.0, generation, encrypted_key)?;
+        // the key is fetched for every message, once per guild is plenty
+        if outdated
+            && self
+                .outdated_key_warned
+                .lock()
+                .expect("Outdated key warnings got poisoned!")
+                .insert(guild_id)
+        {
+            log::warn!(
+                "The encryption key of guild {} is still encrypted with the previous main key, run rotate_keys",
+                guild_id
+            );
+        }
+
+        Ok(EncryptionKey::construct_owned(&key_bytes))
+    }
+
+    /// Decrypts a stored guild key, falling back to the previous main key if there is one.
+    ///
+    /// Also returns if the fallback was needed, meaning the key should be re-encrypted.
+    fn unwrap_guild_key(
+        &self,
+        guild_id: u64,
+        generation: i32,
+        encrypted_key: &[u8],
+    ) -> Result<(Vec<u8>, bool), DatabaseError> {
+        let domain = generation as u32;
+        if let Some(key) =
+            crypto::try_decrypt_bytes_in_domain(encrypted_key, &self.primary_encryption_key, guild_id, domain)
+        {
+            return Ok((key, false));
+        }
+
+        if let Some(previous) = &self.previous_encryption_key {
+            if let Some(key) = crypto::try_decrypt_bytes_in_domain(encrypted_key, previous, guild_id, domain) {
+                return Ok((key, true));
+            }
+        }
+
+        self.stats.crypto.decryption_failures.inc();
+        Err(DatabaseError::GuildKeyDecryption(guild_id))
+    }
+
+    /// Re-encrypts all guild keys that are still encrypted with the previous main key with the current one.
+    pub async fn rotate_guild_keys(&self) -> Result<KeyRotationReport, DatabaseError> {
+        let keys: Vec<(i64, Vec<u8>, i32)> = self
+            .timed(
+                "rotate_guild_keys",
+                sqlx::query_as("SELECT id, encryption_key, key_generation from guildconfig")
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        let mut report = KeyRotationReport::default();
+        for (guild_id, encrypted_key, generation) in keys {
+            let guild_id = guild_id as u64;
+            match self.unwrap_guild_key(guild_id, generation, &encrypted_key) {
+                Ok((_, false)) => report.current += 1,
+                Ok((key, true)) => {
+                    let rewrapped = crypto::encrypt_bytes_in_domain(
+                        &key,
+                        &self.primary_encryption_key,
+                        guild_id,
+                        generation as u32,
+                    );
+                    self.timed(
+                        "rotate_guild_keys",
+                        // a guild that got a new key in the meantime already has it encrypted with the current one
+                        sqlx::query("UPDATE guildconfig set encryption_key=$1 WHERE id=$2 AND key_generation=$3")
+                            .bind(rewrapped)
+                            .bind(guild_id as i64)
+                            .bind(generation)
+                            .execute(&self.persistent_pool),
+                    )
+                    .await?;
+                    report.rotated += 1;
+                }
+                Err(_) => report.failed.push(guild_id),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Checks that the stored messages of a guild decrypt with its current key, without changing anything.
+    ///
+    /// Goes from the newest message back, stopping after ``limit`` messages if given.
+    pub async fn verify_guild_messages(
+        &self,
+        guild_id: GuildId,
+        limit: Option<i64>,
+    ) -> Result<MessageVerificationReport, DatabaseError> {
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let mut report = MessageVerificationReport::default();
+        let mut remaining = limit.unwrap_or(i64::MAX);
+        let mut before = i64::MAX;
+
+        while remaining > 0 {
+            let batch: Vec<(i64, Option<Vec<u8>>, i16, i32)> = self
+                .timed(
+                    "verify_guild_messages",
+                    sqlx::query_as(
+                        "SELECT id, encrypted_content, encryption_algorithm, revision from message
+                        WHERE guild_id=$1 AND id < $2 ORDER BY id DESC LIMIT $3",
+                    )
+                    .bind(guild_id.0 as i64)
+                    .bind(before)
+                    .bind(remaining.min(VERIFY_FETCH_SIZE))
+                    .fetch_all(&self.persistent_pool),
+                )
+                .await?;
+
+            let last = match batch.last() {
+                Some((id, ..)) => *id,
+                None => break,
+            };
+            remaining -= batch.len() as i64;
+            before = last;
+
+            for chunk in batch.chunks(DECRYPT_BATCH_SIZE) {
+                for (id, encrypted_content, algorithm, revision) in chunk {
+                    let encrypted_content = match encrypted_content {
+                        Some(encrypted_content) => encrypted_content,
+                        None => {
+                            report.skipped += 1;
+                            continue;
+                        }
+                    };
+
+                    let valid =
+                        crypto::decrypt_message(encrypted_content, &guild_key, *id as u64, *revision, *algorithm)
+                            .map_or(false, |decrypted| String::from_utf8(decrypted).is_ok());
+                    if valid {
+                        report.verified += 1;
+                    } else {
+                        report.failed += 1;
+                        if report.failed_samples.len() < VERIFY_FAILURE_SAMPLES {
+                            report.failed_samples.push(MessageId(*id as u64));
+                        }
+                    }
+                }
+
+                tokio::task::yield_now().await;
+            }
+        }
+
+        Ok(report)
+    }
+}
 
-        Ok(guild_key)
+/// System messages without content only need the marker (the kind) stored.
+fn stored_content_kind(message: &Message, storage: ContentStorage) -> ContentStorage {
+    if message.kind != MessageType::Regular && message.content.is_empty() {
+        ContentStorage::Nothing
+    } else {
+        storage
     }
 }
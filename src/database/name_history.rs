@@ -0,0 +1,153 @@
+use chrono::{TimeZone, Utc};
+use twilight_model::id::{GuildId, UserId};
+
+use super::crypto;
+use super::structures::NameChange;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+/// How many name changes are kept per user (per guild for nicknames), older ones make room for new ones.
+const MAX_NAME_HISTORY: i64 = 25;
+
+impl DataStorage {
+    /// Records a username change, these are public and the same in every guild so they aren't encrypted.
+    pub async fn record_username_change(
+        &self,
+        user_id: UserId,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "record_username_change",
+            sqlx::query("INSERT INTO username_change (user_id, old_name, new_name) VALUES ($1, $2, $3)")
+                .bind(user_id.0 as i64)
+                .bind(old_name)
+                .bind(new_name)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        self.timed(
+            "trim_username_changes",
+            sqlx::query(
+                "DELETE FROM username_change WHERE id IN
+                (SELECT id FROM username_change WHERE user_id=$1 ORDER BY id DESC OFFSET $2)",
+            )
+            .bind(user_id.0 as i64)
+            .bind(MAX_NAME_HISTORY)
+            .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a nickname change, encrypted with the guild key. ``None`` when there was or is no nickname.
+    pub async fn record_nickname_change(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        old_name: Option<&str>,
+        new_name: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self
+            .timed("record_nickname_change", self.persistent_pool.begin())
+            .await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        let encrypt = |name: &str| crypto::encrypt_with_random_nonce(name.as_bytes(), &guild_key);
+
+        self.timed(
+            "record_nickname_change",
+            sqlx::query(
+                "INSERT INTO nickname_change (guild_id, user_id, encrypted_old_name, encrypted_new_name)
+                VALUES ($1, $2, $3, $4)",
+            )
+            .bind(guild_id.0 as i64)
+            .bind(user_id.0 as i64)
+            .bind(old_name.map(encrypt))
+            .bind(new_name.map(encrypt))
+            .execute(&mut *tx),
+        )
+        .await?;
+        self.timed("record_nickname_change", tx.commit()).await?;
+
+        self.timed(
+            "trim_nickname_changes",
+            sqlx::query(
+                "DELETE FROM nickname_change WHERE id IN
+                (SELECT id FROM nickname_change WHERE guild_id=$1 AND user_id=$2 ORDER BY id DESC OFFSET $3)",
+            )
+            .bind(guild_id.0 as i64)
+            .bind(user_id.0 as i64)
+            .bind(MAX_NAME_HISTORY)
+            .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// The username changes of a user, newest first.
+    pub async fn get_username_changes(&self, user_id: UserId) -> Result<Vec<NameChange>, DatabaseError> {
+        let stored: Vec<(String, String, i64)> = self
+            .timed(
+                "get_username_changes",
+                sqlx::query_as(
+                    "SELECT old_name, new_name, extract(epoch FROM changed_at)::bigint FROM username_change
+                    WHERE user_id=$1 ORDER BY id DESC",
+                )
+                .bind(user_id.0 as i64)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(stored
+            .into_iter()
+            .map(|(old_name, new_name, changed_at)| NameChange {
+                old_name: Some(old_name),
+                new_name: Some(new_name),
+                changed_at: Utc.timestamp(changed_at, 0),
+            })
+            .collect())
+    }
+
+    /// The nickname changes of a user in a guild, decrypted and newest first.
+    pub async fn get_nickname_changes(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Vec<NameChange>, DatabaseError> {
+        let stored: Vec<(Option<Vec<u8>>, Option<Vec<u8>>, i64)> = self
+            .timed(
+                "get_nickname_changes",
+                sqlx::query_as(
+                    "SELECT encrypted_old_name, encrypted_new_name, extract(epoch FROM changed_at)::bigint
+                    FROM nickname_change WHERE guild_id=$1 AND user_id=$2 ORDER BY id DESC",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        if stored.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let decrypt = |encrypted: Vec<u8>| {
+            let decrypted =
+                crypto::decrypt_with_random_nonce(&encrypted, &guild_key).expect("Failed to decrypt a nickname!");
+            String::from_utf8(decrypted).unwrap()
+        };
+
+        Ok(stored
+            .into_iter()
+            .map(|(old_name, new_name, changed_at)| NameChange {
+                old_name: old_name.map(decrypt),
+                new_name: new_name.map(decrypt),
+                changed_at: Utc.timestamp(changed_at, 0),
+            })
+            .collect())
+    }
+}
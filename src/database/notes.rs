@@ -0,0 +1,124 @@
+use twilight_model::id::{GuildId, MessageId, UserId};
+
+use super::crypto::{self, NOTE_NONCE_DOMAIN};
+use super::structures::{StoredNote, UserNote};
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Stores a new moderator note about a user, encrypted with the guild key.
+    ///
+    /// The id of the message that created the note is used to construct the nonce, and must be unique.
+    pub async fn insert_note(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        mod_id: UserId,
+        source_message: MessageId,
+        content: &str,
+    ) -> Result<i32, DatabaseError> {
+        let mut tx = self.timed("insert_note", self.persistent_pool.begin()).await?;
+        let guild_key = self.lock_guild_encryption_key(&mut tx, guild_id).await?;
+        let ciphertext =
+            crypto::encrypt_bytes_in_domain(content.as_bytes(), &guild_key, source_message.0, NOTE_NONCE_DOMAIN);
+
+        let id: (i32,) = self
+            .timed(
+                "insert_note",
+                sqlx::query_as(
+                    "INSERT INTO note (guild_id, user_id, mod_id, nonce_id, encrypted_content)
+                    VALUES ($1, $2, $3, $4, $5) RETURNING id",
+                )
+                .bind(guild_id.0 as i64)
+                .bind(user_id.0 as i64)
+                .bind(mod_id.0 as i64)
+                .bind(source_message.0 as i64)
+                .bind(ciphertext)
+                .fetch_one(&mut *tx),
+            )
+            .await?;
+        self.timed("insert_note", tx.commit()).await?;
+
+        Ok(id.0)
+    }
+
+    /// Retrieves and decrypts all notes about a user in a guild, oldest first.
+    pub async fn get_notes(&self, guild_id: GuildId, user_id: UserId) -> Result<Vec<UserNote>, DatabaseError> {
+        let stored: Vec<StoredNote> = self
+            .timed(
+                "get_notes",
+                sqlx::query_as("SELECT id, mod_id, nonce_id, encrypted_content FROM note WHERE guild_id=$1 AND user_id=$2 ORDER BY id")
+                    .bind(guild_id.0 as i64)
+                    .bind(user_id.0 as i64)
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        if stored.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let guild_key = self.get_guild_encryption_key(guild_id).await?;
+        let notes = stored
+            .into_iter()
+            .filter_map(|note| {
+                let content = crypto::try_decrypt_bytes_in_domain(
+                    &note.encrypted_content,
+                    &guild_key,
+                    note.nonce_id as u64,
+                    NOTE_NONCE_DOMAIN,
+                )
+                .and_then(|decrypted| String::from_utf8(decrypted).ok());
+
+                match content {
+                    Some(content) => Some(UserNote {
+                        id: note.id,
+                        moderator: UserId(note.mod_id as u64),
+                        content,
+                    }),
+                    // a single broken note shouldn't hide all the others
+                    None => {
+                        log::warn!(
+                            "Note {} in guild {} could not be decrypted, leaving it out",
+                            note.id,
+                            guild_id
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Ok(notes)
+    }
+
+    /// Counts the notes about a user without decrypting them.
+    pub async fn count_notes(&self, guild_id: GuildId, user_id: UserId) -> Result<i64, DatabaseError> {
+        let count: (i64,) = self
+            .timed(
+                "count_notes",
+                sqlx::query_as("SELECT count(*) FROM note WHERE guild_id=$1 AND user_id=$2")
+                    .bind(guild_id.0 as i64)
+                    .bind(user_id.0 as i64)
+                    .fetch_one(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(count.0)
+    }
+
+    /// Removes a note, returns false if the guild had no note with this id.
+    pub async fn remove_note(&self, guild_id: GuildId, note_id: i32) -> Result<bool, DatabaseError> {
+        let result = self
+            .timed(
+                "remove_note",
+                sqlx::query("DELETE FROM note WHERE id=$1 AND guild_id=$2")
+                    .bind(note_id)
+                    .bind(guild_id.0 as i64)
+                    .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
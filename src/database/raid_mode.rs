@@ -0,0 +1,66 @@
+use twilight_model::guild::VerificationLevel;
+use twilight_model::id::GuildId;
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// All guilds that are in raid mode, with the verification level they had before.
+    pub async fn get_raid_modes(&self) -> Result<Vec<(GuildId, Option<VerificationLevel>)>, DatabaseError> {
+        let rows: Vec<(i64, Option<i16>)> = self
+            .timed(
+                "get_raid_modes",
+                sqlx::query_as("SELECT guild_id, previous_verification FROM raid_mode")
+                    .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(guild_id, level)| (GuildId(guild_id as u64), level.and_then(level_from_i16)))
+            .collect())
+    }
+
+    pub async fn set_raid_mode(
+        &self,
+        guild_id: GuildId,
+        previous_verification: Option<VerificationLevel>,
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "set_raid_mode",
+            sqlx::query(
+                "INSERT INTO raid_mode (guild_id, previous_verification) VALUES ($1, $2)
+                ON CONFLICT (guild_id) DO UPDATE SET previous_verification = EXCLUDED.previous_verification",
+            )
+            .bind(guild_id.0 as i64)
+            .bind(previous_verification.map(|level| level as i16))
+            .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_raid_mode(&self, guild_id: GuildId) -> Result<(), DatabaseError> {
+        self.timed(
+            "remove_raid_mode",
+            sqlx::query("DELETE FROM raid_mode WHERE guild_id=$1")
+                .bind(guild_id.0 as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn level_from_i16(level: i16) -> Option<VerificationLevel> {
+    match level {
+        0 => Some(VerificationLevel::None),
+        1 => Some(VerificationLevel::Low),
+        2 => Some(VerificationLevel::Medium),
+        3 => Some(VerificationLevel::High),
+        4 => Some(VerificationLevel::VeryHigh),
+        _ => None,
+    }
+}
@@ -0,0 +1,61 @@
+use twilight_model::id::{GuildId, MessageId};
+
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Applies the given (message id, emoji, change) counts to the stored reactions of messages.
+    pub async fn add_reaction_counts(
+        &self,
+        guild_id: GuildId,
+        counts: &[(u64, String, i64)],
+    ) -> Result<(), DatabaseError> {
+        for (message_id, emoji, change) in counts {
+            self.timed(
+                "add_reaction_counts",
+                sqlx::query(
+                    "INSERT INTO messageReactions (message_id, guild_id, emoji, count) VALUES ($1, $2, $3, GREATEST($4, 0))
+                    ON CONFLICT (message_id, emoji) DO UPDATE SET count = GREATEST(messageReactions.count + $4, 0)",
+                )
+                .bind(*message_id as i64)
+                .bind(guild_id.0 as i64)
+                .bind(emoji)
+                .bind(*change)
+                .execute(&self.persistent_pool),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn clear_reaction_counts(&self, message_id: MessageId) -> Result<(), DatabaseError> {
+        self.timed(
+            "clear_reaction_counts",
+            sqlx::query("DELETE FROM messageReactions WHERE message_id=$1")
+                .bind(message_id.0 as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the reactions a message received as (emoji, count) pairs, most used first.
+    ///
+    /// Custom emoji are given by id, unicode ones as the emoji itself.
+    pub async fn get_reaction_summary(&self, message_id: MessageId) -> Result<Vec<(String, i64)>, DatabaseError> {
+        let summary = self
+            .timed(
+                "get_reaction_summary",
+                sqlx::query_as(
+                    "SELECT emoji, count FROM messageReactions WHERE message_id=$1 AND count > 0 ORDER BY count DESC",
+                )
+                .bind(message_id.0 as i64)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(summary)
+    }
+}
@@ -1,12 +1,13 @@
-use darkredis::ConnectionPool;
+use darkredis::{Command, ConnectionPool, Value};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::core::BotContext;
 use crate::database::redis::api_structs::{ApiRequest, Reply, Request};
 use crate::error::{ApiCommunicaionError, DatabaseError};
 use crate::gearbot_error;
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use team_info::get_team_info;
 
 pub mod api_structs;
@@ -67,6 +68,141 @@ impl Redis {
         Ok(())
     }
 
+    /// Pings Redis, returning how long the round trip took.
+    pub async fn ping(&self) -> Result<Duration, DatabaseError> {
+        let start = Instant::now();
+        let mut conn = self.pool.get().await;
+
+        conn.run_command(Command::new("PING")).await?;
+
+        Ok(start.elapsed())
+    }
+
+    /// Increments a counter stored in a hash, both get created if they don't exist yet.
+    pub async fn increment_hash_field(&self, key: &str, field: &str, amount: i64) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.get().await;
+
+        let amount = amount.to_string();
+        conn.run_command(Command::new("HINCRBY").arg(&key).arg(&field).arg(&amount))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reads and deletes all counters in a hash in one transaction, so no increments get lost in between.
+    pub async fn take_hash_counters(&self, key: &str) -> Result<Vec<(String, i64)>, DatabaseError> {
+        let mut conn = self.pool.get().await;
+
+        conn.run_command(Command::new("MULTI")).await?;
+        conn.run_command(Command::new("HGETALL").arg(&key)).await?;
+        conn.run_command(Command::new("DEL").arg(&key)).await?;
+        let results = conn.run_command(Command::new("EXEC")).await?;
+
+        // EXEC replies with the results of the queued commands, the first one is the HGETALL
+        let fields = match results {
+            Value::Array(results) => match results.into_iter().next() {
+                Some(Value::Array(fields)) => fields,
+                _ => return Ok(vec![]),
+            },
+            _ => return Ok(vec![]),
+        };
+
+        let counters = fields
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [Value::String(field), Value::String(count)] => {
+                    let field = String::from_utf8_lossy(field).to_string();
+                    let count = String::from_utf8_lossy(count).parse().ok()?;
+                    Some((field, count))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(counters)
+    }
+
+    /// Appends values to a list, trimming the oldest entries off when it grows past the max length.
+    ///
+    /// Returns how many entries were trimmed off.
+    pub async fn push_bounded<T: Serialize>(
+        &self,
+        key: &str,
+        values: &[T],
+        max_length: usize,
+    ) -> Result<usize, DatabaseError> {
+        let mut command = Command::new("RPUSH").arg(&key);
+        let mut serialized = Vec::with_capacity(values.len());
+        for value in values {
+            serialized.push(serde_json::to_string(value).map_err(DatabaseError::Serializing)?);
+        }
+        for value in &serialized {
+            command = command.arg(value);
+        }
+
+        let mut conn = self.pool.get().await;
+        let length = match conn.run_command(command).await? {
+            Value::Integer(length) => length as usize,
+            _ => 0,
+        };
+
+        if length <= max_length {
+            return Ok(0);
+        }
+
+        let start = format!("-{}", max_length);
+        conn.run_command(Command::new("LTRIM").arg(&key).arg(&start).arg(&"-1"))
+            .await?;
+
+        Ok(length - max_length)
+    }
+
+    /// Reads and deletes a list in one transaction, entries that fail to deserialize are skipped.
+    pub async fn take_list<D: DeserializeOwned>(&self, key: &str) -> Result<Vec<D>, DatabaseError> {
+        let mut conn = self.pool.get().await;
+
+        conn.run_command(Command::new("MULTI")).await?;
+        conn.run_command(Command::new("LRANGE").arg(&key).arg(&"0").arg(&"-1"))
+            .await?;
+        conn.run_command(Command::new("DEL").arg(&key)).await?;
+        let results = conn.run_command(Command::new("EXEC")).await?;
+
+        // EXEC replies with the results of the queued commands, the first one is the LRANGE
+        let entries = match results {
+            Value::Array(results) => match results.into_iter().next() {
+                Some(Value::Array(entries)) => entries,
+                _ => return Ok(vec![]),
+            },
+            _ => return Ok(vec![]),
+        };
+
+        let values = entries
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Value::String(data) => serde_json::from_slice(&data).ok(),
+                _ => None,
+            })
+            .collect();
+
+        Ok(values)
+    }
+
+    /// Publishes a value to everyone subscribed to the channel.
+    pub async fn publish<T: Serialize>(&self, channel: &str, value: &T) -> Result<(), DatabaseError> {
+        let data = serde_json::to_string(value).map_err(DatabaseError::Serializing)?;
+        self.pool.get().await.publish(channel, data).await?;
+
+        Ok(())
+    }
+
+    /// Subscribes to a channel on a connection of its own, the stream ends when that connection is lost.
+    pub async fn subscribe(&self, channel: &str) -> Result<impl Stream<Item = Vec<u8>>, DatabaseError> {
+        let con = self.pool.spawn(channel).await?;
+        let messages = con.subscribe(&[channel]).await?;
+
+        Ok(messages.map(|message| message.message))
+    }
+
     pub async fn establish_api_link(&self, ctx: Arc<BotContext>) {
         let con = match self.pool.spawn("api_connection").await {
             Ok(con) => con,
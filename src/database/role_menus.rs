@@ -0,0 +1,87 @@
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use super::structures::{RoleMenu, RoleMenuOption};
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    pub async fn insert_role_menu(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        options: &[RoleMenuOption],
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "insert_role_menu",
+            sqlx::query("INSERT INTO roleMenu (message_id, guild_id, channel_id, options) VALUES ($1, $2, $3, $4)")
+                .bind(message_id.0 as i64)
+                .bind(guild_id.0 as i64)
+                .bind(channel_id.0 as i64)
+                .bind(serde_json::to_value(options).map_err(DatabaseError::Serializing)?)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_role_menus(&self, guild_id: GuildId) -> Result<Vec<RoleMenu>, DatabaseError> {
+        let rows: Vec<(i64, i64, serde_json::Value)> = self
+            .timed(
+                "get_role_menus",
+                sqlx::query_as(
+                    "SELECT message_id, channel_id, options FROM roleMenu WHERE guild_id=$1 ORDER BY message_id",
+                )
+                .bind(guild_id.0 as i64)
+                .fetch_all(&self.persistent_pool),
+            )
+            .await?;
+
+        let mut menus = Vec::with_capacity(rows.len());
+        for (message_id, channel_id, options) in rows {
+            menus.push(RoleMenu {
+                message_id: MessageId(message_id as u64),
+                guild_id,
+                channel_id: ChannelId(channel_id as u64),
+                options: serde_json::from_value(options).map_err(DatabaseError::Deserializing)?,
+            });
+        }
+
+        Ok(menus)
+    }
+
+    pub async fn update_role_menu_options(
+        &self,
+        guild_id: GuildId,
+        message_id: MessageId,
+        options: &[RoleMenuOption],
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "update_role_menu_options",
+            sqlx::query("UPDATE roleMenu SET options=$1 WHERE message_id=$2 AND guild_id=$3")
+                .bind(serde_json::to_value(options).map_err(DatabaseError::Serializing)?)
+                .bind(message_id.0 as i64)
+                .bind(guild_id.0 as i64)
+                .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes a role menu, returns false if the guild had no menu on that message.
+    pub async fn remove_role_menu(&self, guild_id: GuildId, message_id: MessageId) -> Result<bool, DatabaseError> {
+        let result = self
+            .timed(
+                "remove_role_menu",
+                sqlx::query("DELETE FROM roleMenu WHERE message_id=$1 AND guild_id=$2")
+                    .bind(message_id.0 as i64)
+                    .bind(guild_id.0 as i64)
+                    .execute(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
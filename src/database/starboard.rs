@@ -0,0 +1,68 @@
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use super::structures::StarboardEntry;
+use super::DataStorage;
+use crate::error::DatabaseError;
+
+impl DataStorage {
+    /// Remembers which starboard post belongs to a message so it can be updated later on.
+    pub async fn insert_starboard_entry(
+        &self,
+        guild_id: GuildId,
+        original_id: MessageId,
+        starboard_channel_id: ChannelId,
+        starboard_message_id: MessageId,
+    ) -> Result<(), DatabaseError> {
+        self.timed(
+            "insert_starboard_entry",
+            sqlx::query(
+                "INSERT INTO starboard (original_id, guild_id, starboard_channel_id, starboard_message_id)
+                VALUES ($1, $2, $3, $4) ON CONFLICT (original_id) DO UPDATE
+                SET starboard_channel_id = EXCLUDED.starboard_channel_id, starboard_message_id = EXCLUDED.starboard_message_id",
+            )
+            .bind(original_id.0 as i64)
+            .bind(guild_id.0 as i64)
+            .bind(starboard_channel_id.0 as i64)
+            .bind(starboard_message_id.0 as i64)
+            .execute(&self.persistent_pool),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_starboard_entry(&self, original_id: MessageId) -> Result<Option<StarboardEntry>, DatabaseError> {
+        let entry = self
+            .timed(
+                "get_starboard_entry",
+                sqlx::query_as(
+                    "SELECT original_id, guild_id, starboard_channel_id, starboard_message_id FROM starboard WHERE original_id=$1",
+                )
+                .bind(original_id.0 as i64)
+                .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(entry)
+    }
+
+    /// Forgets the starboard post of a message, returning it if there was one.
+    pub async fn remove_starboard_entry(
+        &self,
+        original_id: MessageId,
+    ) -> Result<Option<StarboardEntry>, DatabaseError> {
+        let entry = self
+            .timed(
+                "remove_starboard_entry",
+                sqlx::query_as(
+                    "DELETE FROM starboard WHERE original_id=$1
+                    RETURNING original_id, guild_id, starboard_channel_id, starboard_message_id",
+                )
+                .bind(original_id.0 as i64)
+                .fetch_optional(&self.persistent_pool),
+            )
+            .await?;
+
+        Ok(entry)
+    }
+}
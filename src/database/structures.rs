@@ -1,33 +1,298 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use twilight_http::request::channel::reaction::RequestReactionType;
 use twilight_model::channel::message::MessageType;
-use twilight_model::id::{ChannelId, GuildId, UserId};
+use twilight_model::channel::ReactionType;
+use twilight_model::id::{ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId};
+
+use crate::utils::matchers;
+
+/// How the content of a message is kept when it's stored.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ContentStorage {
+    Encrypted,
+    /// Only a salted hash, to recognize duplicates without being able to read them.
+    Hash,
+    Nothing,
+}
 
 #[derive(Debug)]
 pub struct UserMessage {
-    pub content: String,
+    pub id: MessageId,
+    /// Not available for messages stored while the guild had privacy mode or no content storage enabled,
+    /// or system messages without content.
+    pub content: Option<String>,
     pub author: UserId,
     pub channel: ChannelId,
     pub guild: GuildId,
     pub kind: MessageType,
     pub pinned: bool,
+    /// The message this one was a reply to, if any.
+    pub referenced_message: Option<MessageId>,
+    /// Salted hash of the content, only kept for messages stored while the guild had privacy mode enabled.
+    pub content_hash: Option<Vec<u8>>,
 }
 
 #[derive(Debug, sqlx::FromRow)]
 pub(super) struct StoredUserMessage {
     pub id: i64,
-    pub encrypted_content: Vec<u8>,
+    pub encrypted_content: Option<Vec<u8>>,
+    /// Id of the ``EncryptionAlgorithm`` the content was encrypted with.
+    pub encryption_algorithm: i16,
     pub author_id: i64,
     pub channel_id: i64,
     pub guild_id: i64,
     pub kind: i16,
     pub pinned: bool,
+    pub referenced_message_id: Option<i64>,
+    /// How many times the content was edited since it was stored.
+    pub revision: i32,
+    pub content_hash: Option<Vec<u8>>,
+}
+
+/// A version an edited message had before.
+#[derive(Debug)]
+pub struct MessageEdit {
+    /// 0 for the content the message was sent with, counting up with every edit after that.
+    pub revision: i32,
+    /// When this version got replaced.
+    pub edited_at: DateTime<Utc>,
+    /// Not available when the guild had privacy mode or no content storage enabled at the time.
+    pub content: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StoredMessageEdit {
+    pub revision: i32,
+    pub encrypted_content: Option<Vec<u8>>,
+    pub encryption_algorithm: i16,
+    pub edited_at: DateTime<Utc>,
 }
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct StoredAttachment {
-    id: i64,
-    filename: String,
-    is_image: bool,
-    message_id: i64,
+    pub id: i64,
+    pub filename: String,
+    pub is_image: bool,
+    pub message_id: i64,
+}
+
+/// What is known about the bytes of an attachment.
+#[derive(Debug)]
+pub enum AttachmentBytes {
+    Stored(Vec<u8>),
+    /// The attachment is known, but no copy was kept. It was too large, the guild didn't opt in, the
+    /// download failed or the copy can't be decrypted anymore.
+    NotStored,
+    NotFound,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct GuildAttachmentStats {
+    pub total: i64,
+    pub images: i64,
+    /// Size of the stored copies, encrypted as they are.
+    pub stored_bytes: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct HistoryEntry {
+    pub id: i32,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub mod_id: i64,
+    pub kind: String,
+    pub removed: bool,
+}
+
+/// Narrows down which history entries get exported, entries have to match all filters that are set.
+#[derive(Debug, Default)]
+pub struct HistoryFilter {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub moderator: Option<UserId>,
+    pub kind: Option<&'static str>,
+}
+
+/// A moderation history entry with everything there is to know about it, reason decrypted.
+#[derive(Debug)]
+pub struct ExportedHistoryEntry {
+    pub id: i32,
+    pub user_id: UserId,
+    pub mod_id: UserId,
+    pub kind: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub imported: bool,
+    pub removed: bool,
+    /// ``None`` when no reason was given, or it can no longer be decrypted.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StoredHistoryEntry {
+    pub id: i32,
+    pub user_id: i64,
+    pub mod_id: i64,
+    pub kind: String,
+    pub start: i64,
+    pub end: Option<i64>,
+    pub imported: bool,
+    pub removed: bool,
+    pub encrypted_reason: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+pub struct KeyRotationReport {
+    /// Guilds whose key was re-encrypted with the current main key.
+    pub rotated: usize,
+    /// Guilds whose key was already encrypted with the current main key.
+    pub current: usize,
+    /// Guilds whose key couldn't be decrypted with either main key.
+    pub failed: Vec<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct BulkInsertReport {
+    /// Messages that were written, ones that were already stored are left out.
+    pub written: Vec<MessageId>,
+    /// Messages that couldn't be stored because of their guild key, with the reason.
+    pub failed: Vec<(MessageId, String)>,
+}
+
+#[derive(Debug, Default)]
+pub struct GuildKeyRotationReport {
+    /// Messages whose content was re-encrypted with the new key.
+    pub messages: usize,
+    /// Earlier versions of edited messages that were re-encrypted.
+    pub edits: usize,
+    /// Stored copies of attachments that were re-encrypted.
+    pub attachments: usize,
+    /// Notes, case reasons and nicknames that were re-encrypted.
+    pub other: usize,
+    /// Content only kept as a hash salted with the old key, these hashes were cleared.
+    pub hashes_cleared: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct MessageVerificationReport {
+    /// Messages whose content decrypted to valid text.
+    pub verified: usize,
+    /// Messages stored in privacy mode, these have no content to decrypt.
+    pub skipped: usize,
+    /// Total messages that failed to decrypt.
+    pub failed: usize,
+    /// The first few messages that failed, to look into further.
+    pub failed_samples: Vec<MessageId>,
+}
+
+#[derive(Debug)]
+pub struct UserNote {
+    pub id: i32,
+    pub moderator: UserId,
+    pub content: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StoredNote {
+    pub id: i32,
+    pub mod_id: i64,
+    pub nonce_id: i64,
+    pub encrypted_content: Vec<u8>,
+}
+
+/// A name change of a user, ``None`` for nicknames means they had none.
+#[derive(Debug)]
+pub struct NameChange {
+    pub old_name: Option<String>,
+    pub new_name: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ScheduledAnnouncement {
+    pub id: i32,
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub creator_id: i64,
+    pub cron: String,
+    pub message: String,
+    pub next_run: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AutoResponse {
+    pub id: i32,
+    pub guild_id: i64,
+    pub trigger: String,
+    pub response: String,
+    pub exact: bool,
+    pub cooldown_seconds: i64,
+}
+
+impl AutoResponse {
+    /// Checks if a message triggers this response, casing is ignored.
+    pub fn matches(&self, content: &str) -> bool {
+        let content = content.trim().to_lowercase();
+        let trigger = self.trigger.to_lowercase();
+        if self.exact {
+            content == trigger
+        } else {
+            content.contains(&trigger)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RoleMenu {
+    pub message_id: MessageId,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub options: Vec<RoleMenuOption>,
+}
+
+impl RoleMenu {
+    pub fn get_option(&self, emoji: &ReactionType) -> Option<&RoleMenuOption> {
+        self.options.iter().find(|option| option.matches(emoji))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoleMenuOption {
+    /// Either a unicode emoji or a custom emoji as it is written in chat
+    pub emoji: String,
+    pub role_id: RoleId,
+    pub label: String,
+}
+
+impl RoleMenuOption {
+    pub fn matches(&self, emoji: &ReactionType) -> bool {
+        match (emoji, matchers::get_emoji_parts(&self.emoji).first()) {
+            (ReactionType::Custom { id, .. }, Some(custom)) => id.0 == custom.id,
+            (ReactionType::Unicode { name }, None) => *name == self.emoji,
+            _ => false,
+        }
+    }
+
+    pub fn to_reaction(&self) -> RequestReactionType {
+        match matchers::get_emoji_parts(&self.emoji).into_iter().next() {
+            Some(custom) => RequestReactionType::Custom {
+                id: EmojiId(custom.id),
+                name: Some(custom.name),
+            },
+            None => RequestReactionType::Unicode {
+                name: self.emoji.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct StarboardEntry {
+    pub original_id: i64,
+    pub guild_id: i64,
+    pub starboard_channel_id: i64,
+    pub starboard_message_id: i64,
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -1,5 +1,6 @@
 use std::{error, fmt, io};
 
+use fluent_bundle::FluentArgs;
 use serde::export::Formatter;
 use twilight_embed_builder::{
     EmbedAuthorNameError, EmbedBuildError, EmbedColorError, EmbedDescriptionError, EmbedFieldError,
@@ -9,17 +10,26 @@ use twilight_gateway::cluster::{ClusterCommandError, ClusterStartError};
 use twilight_gateway::{cluster, shard};
 use twilight_http::request::channel::message::create_message::CreateMessageError;
 use twilight_http::request::channel::message::update_message::UpdateMessageError;
+use twilight_model::guild::Permissions;
 use twilight_model::id::{ChannelId, GuildId, UserId};
 
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, text, Emoji};
+
 pub type CommandResult = Result<(), CommandError>;
 
 #[derive(Debug)]
 pub enum StartupError {
     NoConfig,
     InvalidConfig,
+    NoSecretsKey,
+    InvalidSecrets,
     NoLoggingSpec,
     Twilight(twilight_http::Error),
     Sqlx(sqlx::Error),
+    Migration(sqlx::migrate::MigrateError),
+    /// A migration was left half applied, with the version of it.
+    PartialMigration(i64),
     DarkRedis(darkredis::Error),
     ClusterStart(ClusterStartError),
     Io(io::Error),
@@ -63,7 +73,6 @@ impl fmt::Display for ApiCommunicaionError {
 
 #[derive(Debug)]
 pub enum EventHandlerError {
-    InvalidSession(u64),
     Gateway(shard::CommandError),
     TwilightCluster(cluster::ClusterCommandError),
     UnknownGuild(GuildId),
@@ -72,13 +81,15 @@ pub enum EventHandlerError {
     Reactor(ReactorError),
     Database(DatabaseError),
     Twilight(twilight_http::Error),
+    Message(MessageError),
+    Image(ImageError),
+    ApiUnavailable,
 }
 impl error::Error for EventHandlerError {}
 
 impl fmt::Display for EventHandlerError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            EventHandlerError::InvalidSession(e) => write!(f, "Our gateway session died: {}", e),
             EventHandlerError::Gateway(e) => write!(f, "Gateway error: {}", e),
             EventHandlerError::TwilightCluster(e) => write!(f, "Gateway command error: {}", e),
             EventHandlerError::UnknownGuild(e) => write!(f, "Event recieved for unknown guild {}", e),
@@ -87,6 +98,75 @@ impl fmt::Display for EventHandlerError {
             EventHandlerError::Reactor(e) => write!(f, "Message reactor failure: {}", e),
             EventHandlerError::Database(e) => write!(f, "Database interaction failed: {}", e),
             EventHandlerError::Twilight(e) => write!(f, "Failed to interact with the discord api: {}", e),
+            EventHandlerError::Message(e) => write!(f, "Failed to construct a message: {}", e),
+            EventHandlerError::Image(e) => write!(f, "Failed to generate an image: {}", e),
+            EventHandlerError::ApiUnavailable => write!(f, "Skipped a request, the discord api is failing"),
+        }
+    }
+}
+
+impl EventHandlerError {
+    pub fn is_database_unavailable(&self) -> bool {
+        matches!(self, EventHandlerError::Database(e) if e.is_unavailable())
+    }
+
+    pub fn is_api_unavailable(&self) -> bool {
+        matches!(self, EventHandlerError::ApiUnavailable)
+    }
+
+    /// The error discord gave us, if this failed on a request to the api.
+    pub fn http_error(&self) -> Option<&twilight_http::Error> {
+        match self {
+            EventHandlerError::Twilight(e) | EventHandlerError::Reactor(ReactorError::TwilightHttp(e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A request to the discord api that failed, or wasn't even sent.
+#[derive(Debug)]
+pub enum ApiRequestError {
+    /// The api has been failing, this request was skipped so it doesn't pile on.
+    CircuitOpen,
+    Http(twilight_http::Error),
+}
+
+impl error::Error for ApiRequestError {}
+
+impl fmt::Display for ApiRequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiRequestError::CircuitOpen => write!(f, "Skipped the request, the discord api is failing"),
+            ApiRequestError::Http(e) => write!(f, "Failed to interact with the discord api: {}", e),
+        }
+    }
+}
+
+impl ApiRequestError {
+    /// The error discord gave us, if the request was sent.
+    pub fn http_error(&self) -> Option<&twilight_http::Error> {
+        match self {
+            ApiRequestError::Http(e) => Some(e),
+            ApiRequestError::CircuitOpen => None,
+        }
+    }
+}
+
+/// Members were asked for through the gateway, but didn't all arrive.
+#[derive(Debug)]
+pub enum MemberRequestError {
+    Cluster(ClusterCommandError),
+    /// The shard might have reconnected in the meantime and lost the request.
+    TimedOut,
+}
+
+impl error::Error for MemberRequestError {}
+
+impl fmt::Display for MemberRequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MemberRequestError::Cluster(e) => write!(f, "Failed to send the member request: {}", e),
+            MemberRequestError::TimedOut => write!(f, "Timed out waiting for the requested members"),
         }
     }
 }
@@ -147,6 +227,14 @@ pub enum DatabaseError {
     Deserializing(serde_json::Error),
     Serializing(serde_json::Error),
     Darkredis(darkredis::Error),
+    GuildKeyDecryption(u64),
+    /// Something of the guild didn't decrypt with its key during a key rotation, so the rotation was called off.
+    UnreadableDuringRotation {
+        guild_id: u64,
+        kind: &'static str,
+        id: i64,
+    },
+    Unavailable,
 }
 
 impl error::Error for DatabaseError {}
@@ -158,6 +246,56 @@ impl fmt::Display for DatabaseError {
             DatabaseError::Deserializing(e) => write!(f, "Failed to deserialize: {}", e),
             DatabaseError::Serializing(e) => write!(f, "Failed to seralize: {}", e),
             DatabaseError::Darkredis(e) => write!(f, "Redis failure: {}", e),
+            DatabaseError::GuildKeyDecryption(guild_id) => write!(
+                f,
+                "Unable to decrypt the encryption key of guild {} with the main encryption key. If the main key was changed, set previous_encryption_key to the old key and run the rotate_keys command",
+                guild_id
+            ),
+            DatabaseError::UnreadableDuringRotation { guild_id, kind, id } => write!(
+                f,
+                "The encryption key of guild {} was not replaced, {} {} doesn't decrypt with the current key and would be lost",
+                guild_id, kind, id
+            ),
+            DatabaseError::Unavailable => write!(f, "The database is unreachable, queries are held back for now"),
+        }
+    }
+}
+
+impl DatabaseError {
+    /// If this failed because the database couldn't be reached, rather than because of the query itself.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            DatabaseError::Unavailable => true,
+            DatabaseError::Sqlx(e) => is_connection_error(e),
+            _ => false,
+        }
+    }
+}
+
+pub fn is_connection_error(e: &sqlx::Error) -> bool {
+    matches!(
+        e,
+        sqlx::Error::Io(_) | sqlx::Error::Tls(_) | sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed
+    )
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    Download(reqwest::Error),
+    TooLarge(usize),
+    Decode(image::ImageError),
+    Encode(image::ImageError),
+}
+
+impl error::Error for ImageError {}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Download(e) => write!(f, "Failed to download the image: {}", e),
+            ImageError::TooLarge(size) => write!(f, "The image is too large ({} bytes)", size),
+            ImageError::Decode(e) => write!(f, "Failed to read the image: {}", e),
+            ImageError::Encode(e) => write!(f, "Failed to encode the image: {}", e),
         }
     }
 }
@@ -193,6 +331,7 @@ pub enum CommandError {
     NoDM,
     InvalidPermissions,
     ParseError(ParseError),
+    Moderation(ModerationError),
     OtherFailure(OtherFailure),
 }
 
@@ -204,11 +343,107 @@ impl fmt::Display for CommandError {
             CommandError::NoDM => write!(f, "You can not use this command in DMs"),
             CommandError::InvalidPermissions => write!(f, "You don't have the permissions to run this command!"),
             CommandError::ParseError(e) => write!(f, "Failed to parse the command arguments!\n``{}``", e),
+            CommandError::Moderation(e) => write!(f, "Unable to carry out the moderation action: {}", e),
             CommandError::OtherFailure(_) => write!(f, "Unexpected error while executing the command, please report this on the support server if it keeps happening"),
         }
     }
 }
 
+impl CommandError {
+    pub fn is_database_unavailable(&self) -> bool {
+        match self {
+            CommandError::OtherFailure(OtherFailure::DatabaseError(e))
+            | CommandError::ParseError(ParseError::Other(OtherFailure::DatabaseError(e))) => e.is_unavailable(),
+            _ => false,
+        }
+    }
+
+    /// The error discord gave us, if this failed on a request to the api.
+    pub fn http_error(&self) -> Option<&twilight_http::Error> {
+        match self {
+            CommandError::OtherFailure(OtherFailure::TwilightHttp(e))
+            | CommandError::ParseError(ParseError::Other(OtherFailure::TwilightHttp(e))) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Why the role hierarchy doesn't allow a moderation action on a member.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PermissionError {
+    TargetIsOwner,
+    AuthorTooLow,
+    BotTooLow,
+}
+
+impl error::Error for PermissionError {}
+
+impl fmt::Display for PermissionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionError::TargetIsOwner => write!(f, "The target owns the server"),
+            PermissionError::AuthorTooLow => write!(f, "The target's top role is not below the author's"),
+            PermissionError::BotTooLow => write!(f, "The target's top role is not below the bot's"),
+        }
+    }
+}
+
+/// Why a moderation action could not be carried out, the command dispatcher tells the user about it.
+#[derive(Debug)]
+pub enum ModerationError {
+    /// We are missing these guild permissions for the action.
+    BotMissingPermissions(Permissions),
+    /// The role hierarchy doesn't allow acting on the target.
+    Hierarchy { target: UserId, reason: PermissionError },
+    /// The target is not (or no longer) a member of the guild.
+    TargetNotFound(UserId),
+    /// Discord refused the action on the target.
+    Refused(UserId),
+}
+
+impl ModerationError {
+    /// The translation explaining this error to the user, with the arguments it needs.
+    pub fn reply(&self) -> (GearBotString, FluentArgs<'static>) {
+        let args = FluArgs::with_capacity(2).add("gearno", Emoji::No.for_chat());
+        match self {
+            ModerationError::BotMissingPermissions(permissions) => (
+                GearBotString::ModerationBotMissingPermissions,
+                args.add("permissions", utils::format_permissions(*permissions))
+                    .generate(),
+            ),
+            ModerationError::Hierarchy { target, reason } => {
+                let key = match reason {
+                    PermissionError::TargetIsOwner => GearBotString::HierarchyTargetIsOwner,
+                    PermissionError::AuthorTooLow => GearBotString::HierarchyAuthorTooLow,
+                    PermissionError::BotTooLow => GearBotString::HierarchyBotTooLow,
+                };
+                (key, args.add("user_id", target.to_string()).generate())
+            }
+            ModerationError::TargetNotFound(target) => (
+                GearBotString::ModerationTargetNotFound,
+                args.add("user_id", target.to_string()).generate(),
+            ),
+            ModerationError::Refused(target) => (
+                GearBotString::ModerationRefused,
+                args.add("user_id", target.to_string()).generate(),
+            ),
+        }
+    }
+}
+
+impl error::Error for ModerationError {}
+
+impl fmt::Display for ModerationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ModerationError::BotMissingPermissions(permissions) => write!(f, "Missing permissions: {:?}", permissions),
+            ModerationError::Hierarchy { target, reason } => write!(f, "Not allowed to act on {}: {}", target, reason),
+            ModerationError::TargetNotFound(target) => write!(f, "{} is not a member", target),
+            ModerationError::Refused(target) => write!(f, "Discord refused the action on {}", target),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum OtherFailure {
     ShardOrCluster(String),
@@ -216,6 +451,7 @@ pub enum OtherFailure {
     DatabaseError(DatabaseError),
     CorruptCache,
     Message(MessageError),
+    Io(io::Error),
 }
 
 impl error::Error for OtherFailure {}
@@ -228,6 +464,29 @@ impl fmt::Display for OtherFailure {
             OtherFailure::ShardOrCluster(e) => write!(f, "Shard command failed: {}", e),
             OtherFailure::TwilightHttp(e) => write!(f, "Something when wrong interacting with the discord api: {}", e),
             OtherFailure::Message(e) => write!(f, "Failed to construct a message: {}", e),
+            OtherFailure::Io(e) => write!(f, "Failed to read or write a file: {}", e),
+        }
+    }
+}
+
+/// The kinds of arguments the parser can read and resolve by itself.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ArgumentType {
+    Number,
+    Duration,
+    User,
+    Channel,
+    Role,
+}
+
+impl fmt::Display for ArgumentType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgumentType::Number => write!(f, "number"),
+            ArgumentType::Duration => write!(f, "duration"),
+            ArgumentType::User => write!(f, "user"),
+            ArgumentType::Channel => write!(f, "channel"),
+            ArgumentType::Role => write!(f, "role"),
         }
     }
 }
@@ -235,12 +494,15 @@ impl fmt::Display for OtherFailure {
 #[derive(Debug)]
 pub enum ParseError {
     MissingArgument,
+    /// The argument isn't what was expected, holds what was given instead.
+    InvalidArgument(ArgumentType, String),
     MemberNotFoundById(u64),
     MemberNotFoundByName(String),
     MultipleMembersByName(String),
     WrongArgumentType(String),
     InvalidUserID(u64),
     UnknownChannel(u64),
+    UnknownRole(String),
     NoChannelAccessBot(String),
     NoChannelAccessUser(String),
     UnknownMessage,
@@ -250,6 +512,38 @@ pub enum ParseError {
     Other(OtherFailure),
 }
 
+impl ParseError {
+    /// The translation explaining this error to the user with the arguments it needs, if there is one.
+    pub fn reply(&self) -> Option<(GearBotString, FluentArgs<'static>)> {
+        let args = FluArgs::with_capacity(2).add("gearno", Emoji::No.for_chat());
+        match self {
+            ParseError::MissingArgument => Some((GearBotString::ParseMissingArgument, args.generate())),
+            ParseError::InvalidArgument(expected, input) => Some(Self::expected_reply(*expected, input, args)),
+            // nothing by that name or id is the same as not getting one at all as far as the user is concerned
+            ParseError::UnknownChannel(id) => Some(Self::expected_reply(ArgumentType::Channel, &id.to_string(), args)),
+            ParseError::UnknownRole(input) => Some(Self::expected_reply(ArgumentType::Role, input, args)),
+            _ => None,
+        }
+    }
+
+    fn expected_reply(
+        expected: ArgumentType,
+        input: &str,
+        args: FluArgs<'static>,
+    ) -> (GearBotString, FluentArgs<'static>) {
+        let key = match expected {
+            ArgumentType::Number => GearBotString::ParseExpectedNumber,
+            ArgumentType::Duration => GearBotString::ParseExpectedDuration,
+            ArgumentType::User => GearBotString::ParseExpectedUser,
+            ArgumentType::Channel => GearBotString::ParseExpectedChannel,
+            ArgumentType::Role => GearBotString::ParseExpectedRole,
+        };
+        // it's shown in inline code, so it can't be allowed to break out of that
+        let input = text::truncate_with_ellipsis(input, 100).replace('`', "ˋ");
+        (key, args.add("input", input).generate())
+    }
+}
+
 impl error::Error for ParseError {}
 
 impl fmt::Display for ParseError {
@@ -257,6 +551,7 @@ impl fmt::Display for ParseError {
         match self {
             ParseError::MemberNotFoundById(id) => write!(f, "no member with userid ``{}`` found on this server", id),
             ParseError::MissingArgument => write!(f, "You are missing one or more required arguments"),
+            ParseError::InvalidArgument(expected, input) => write!(f, "Expected a {}, got ``{}``", expected, input),
             ParseError::MemberNotFoundByName(name) => write!(f, "There is nobody named ``{}`` on this server", name),
             ParseError::MultipleMembersByName(name) => write!(
                 f,
@@ -270,6 +565,7 @@ impl fmt::Display for ParseError {
             ),
             ParseError::InvalidUserID(id) => write!(f, "``{}`` is not a valid discord userid", id),
             ParseError::UnknownChannel(id) => write!(f, "Unable to find any channel with id ``{}``", id),
+            ParseError::UnknownRole(input) => write!(f, "Unable to find any role named or with id ``{}``", input),
             ParseError::NoChannelAccessBot(_) => write!(f, "I do not have access to that channel!"),
             ParseError::NoChannelAccessUser(_) => write!(f, "You do not have access to that channel!"),
             ParseError::UnknownMessage => write!(f, "Unable to find that message"),
@@ -288,9 +584,21 @@ impl fmt::Display for StartupError {
         match self {
             StartupError::NoConfig => write!(f, "Unable to locate the config file"),
             StartupError::InvalidConfig => write!(f, "Unable to load the config file"),
+            StartupError::NoSecretsKey => write!(
+                f,
+                "The SECRETS_KEY environment variable needs to hold the 64 character hex key of the secrets file"
+            ),
+            StartupError::InvalidSecrets => write!(
+                f,
+                "Unable to decrypt the secrets file, either SECRETS_KEY is not the key it was encrypted with or the file is damaged"
+            ),
             StartupError::NoLoggingSpec => write!(f, "Problem with the log spec file"),
             StartupError::Twilight(e) => write!(f, "Twilight error during startup, unable to continue: {}", e),
             StartupError::Sqlx(e) => write!(f, "Unable to create database pool: {:?}", e),
+            StartupError::Migration(e) => write!(f, "Unable to migrate the database: {}", e),
+            StartupError::PartialMigration(version) => {
+                write!(f, "Database migration {} was only partially applied", version)
+            }
             StartupError::DarkRedis(e) => write!(f, "Unable to create redis database pool: {}", e),
             StartupError::ClusterStart(e) => write!(f, "The cluster failed to start: {}", e),
             StartupError::Io(e) => write!(f, "IO error: {}", e),
@@ -368,6 +676,12 @@ impl From<CreateMessageError> for CommandError {
     }
 }
 
+impl From<ModerationError> for CommandError {
+    fn from(e: ModerationError) -> Self {
+        CommandError::Moderation(e)
+    }
+}
+
 impl From<twilight_http::Error> for CommandError {
     fn from(e: twilight_http::Error) -> Self {
         CommandError::OtherFailure(OtherFailure::TwilightHttp(e))
@@ -409,6 +723,12 @@ impl From<ClusterCommandError> for EventHandlerError {
     }
 }
 
+impl From<ClusterCommandError> for MemberRequestError {
+    fn from(e: ClusterCommandError) -> Self {
+        MemberRequestError::Cluster(e)
+    }
+}
+
 impl From<ReactorError> for EventHandlerError {
     fn from(e: ReactorError) -> Self {
         EventHandlerError::Reactor(e)
@@ -449,12 +769,51 @@ impl From<DatabaseError> for ReactorError {
         ReactorError::Database(e)
     }
 }
+impl From<twilight_http::Error> for ApiRequestError {
+    fn from(e: twilight_http::Error) -> Self {
+        ApiRequestError::Http(e)
+    }
+}
+
 impl From<twilight_http::Error> for EventHandlerError {
     fn from(e: twilight_http::Error) -> Self {
         EventHandlerError::Twilight(e)
     }
 }
 
+impl From<ApiRequestError> for EventHandlerError {
+    fn from(e: ApiRequestError) -> Self {
+        match e {
+            ApiRequestError::CircuitOpen => EventHandlerError::ApiUnavailable,
+            ApiRequestError::Http(e) => EventHandlerError::Twilight(e),
+        }
+    }
+}
+
+impl From<MessageError> for EventHandlerError {
+    fn from(e: MessageError) -> Self {
+        EventHandlerError::Message(e)
+    }
+}
+
+impl From<CreateMessageError> for EventHandlerError {
+    fn from(e: CreateMessageError) -> Self {
+        EventHandlerError::Message(MessageError::Create(e))
+    }
+}
+
+impl From<UpdateMessageError> for EventHandlerError {
+    fn from(e: UpdateMessageError) -> Self {
+        EventHandlerError::Message(MessageError::Update(e))
+    }
+}
+
+impl From<ImageError> for EventHandlerError {
+    fn from(e: ImageError) -> Self {
+        EventHandlerError::Image(e)
+    }
+}
+
 impl From<twilight_http::Error> for ReactorError {
     fn from(e: twilight_http::Error) -> Self {
         ReactorError::TwilightHttp(e)
@@ -558,6 +917,12 @@ impl From<io::Error> for StartupError {
     }
 }
 
+impl From<io::Error> for CommandError {
+    fn from(e: io::Error) -> Self {
+        CommandError::OtherFailure(OtherFailure::Io(e))
+    }
+}
+
 impl From<EmbedBuildError> for MessageError {
     fn from(e: EmbedBuildError) -> Self {
         MessageError::EmbedBuild(e)
@@ -570,6 +935,18 @@ impl From<EmbedFooterTextError> for MessageError {
     }
 }
 
+impl From<EmbedFieldError> for MessageError {
+    fn from(e: EmbedFieldError) -> Self {
+        MessageError::EmbedField(e)
+    }
+}
+
+impl From<EmbedColorError> for MessageError {
+    fn from(e: EmbedColorError) -> Self {
+        MessageError::EmbedColor(e)
+    }
+}
+
 impl From<DatabaseError> for OtherFailure {
     fn from(e: DatabaseError) -> Self {
         OtherFailure::DatabaseError(e)
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use log::{debug, info};
+use twilight_gateway::Event;
+use twilight_model::gateway::payload::MemberAdd;
+use twilight_model::guild::Permissions;
+use twilight_model::id::UserId;
+
+use crate::core::logpump::{LogData, LogType};
+use crate::core::{AccountAgeAction, AccountAgeGate, AntiRaidAction, AntiRaidConfig, BotContext, GuildConfig};
+use crate::error::EventHandlerError;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::{self, Emoji};
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    if let Event::MemberAdd(member) = event {
+        // bots can only be added by people with manage server, not a raid
+        if member.user.bot {
+            return Ok(());
+        }
+
+        let config = ctx.get_config(member.guild_id).await?;
+        if gate_new_account(member, &config, &ctx).await? {
+            return Ok(());
+        }
+
+        let anti_raid = &config.anti_raid;
+
+        if anti_raid.detection_enabled && ctx.register_join(member.guild_id, anti_raid).await {
+            info!("Raid detected in guild {}, enabling raid mode", member.guild_id);
+            ctx.enable_raid_mode(member.guild_id, anti_raid, ctx.bot_user.id)
+                .await?;
+        }
+
+        if ctx.is_raid_mode_active(member.guild_id).await {
+            handle_raid_join(member, anti_raid, &ctx).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_raid_join(
+    member: &MemberAdd,
+    config: &AntiRaidConfig,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    if !kicked_by_raid_mode(member.user.id, config) {
+        return Ok(());
+    }
+
+    if !ctx
+        .get_guild_permissions_for(&member.guild_id, &ctx.bot_user.id)
+        .contains(Permissions::KICK_MEMBERS)
+    {
+        debug!(
+            "Raid mode is active in {} but i'm unable to kick {}",
+            member.guild_id, member.user.id
+        );
+        return Ok(());
+    }
+
+    ctx.http.remove_guild_member(member.guild_id, member.user.id).await?;
+
+    Ok(())
+}
+
+/// Kicks or restricts members whose account is younger than the configured minimum age.
+///
+/// Returns true if the member was kicked, in which case there is nothing left to handle for them.
+async fn gate_new_account(
+    member: &MemberAdd,
+    config: &GuildConfig,
+    ctx: &BotContext,
+) -> Result<bool, EventHandlerError> {
+    let gate = &config.account_age_gate;
+    if !fails_account_age_gate(member.user.id, gate) {
+        return Ok(false);
+    }
+
+    if !ctx
+        .get_guild_permissions_for(&member.guild_id, &ctx.bot_user.id)
+        .contains(gate_permission(&gate.action))
+    {
+        debug!(
+            "Account of {} is too new to join {} but i'm unable to act on it",
+            member.user.id, member.guild_id
+        );
+        return Ok(false);
+    }
+
+    let role = match gate.action {
+        AccountAgeAction::Kick => {
+            ctx.http.remove_guild_member(member.guild_id, member.user.id).await?;
+            None
+        }
+        AccountAgeAction::AssignRole(role_id) => {
+            ctx.http
+                .add_guild_member_role(member.guild_id, member.user.id, role_id)
+                .await?;
+            Some(role_id)
+        }
+    };
+
+    let created_at = utils::snowflake_timestamp(member.user.id.0);
+    let age = ctx.format_duration(&config.language, Utc::now() - created_at, 2);
+    let target = member.user.id.0;
+    let log_type = match role {
+        Some(role_id) => LogType::NewAccountRestricted {
+            target,
+            age: age.clone(),
+            role: role_id.0,
+        },
+        None => LogType::NewAccountKicked {
+            target,
+            age: age.clone(),
+        },
+    };
+    ctx.log(LogData {
+        log_type,
+        guild: member.guild_id,
+        source_channel: None,
+        source_user: ctx.bot_user.id,
+        failed_attempts: 0,
+    });
+
+    if let Some(channel_id) = gate.notify_channel {
+        if ctx
+            .get_bot_permissions_for_channel(channel_id)
+            .contains(Permissions::SEND_MESSAGES)
+        {
+            let args = FluArgs::with_capacity(4)
+                .add("gearwarn", Emoji::Warn.for_chat())
+                .add("user_id", member.user.id.to_string())
+                .add("age", age);
+            let notice = match role {
+                Some(role_id) => ctx.translate_with_args(
+                    &config.language,
+                    GearBotString::NewAccountRestrictedNotice,
+                    &args.add("role_id", role_id.to_string()).generate(),
+                ),
+                None => ctx.translate_with_args(
+                    &config.language,
+                    GearBotString::NewAccountKickedNotice,
+                    &args.generate(),
+                ),
+            };
+            ctx.http.create_message(channel_id).content(notice).unwrap().await?;
+        }
+    }
+
+    Ok(role.is_none())
+}
+
+/// If the account is too young to get past the account age gate, the gate is off without a minimum age.
+pub fn fails_account_age_gate(user_id: UserId, gate: &AccountAgeGate) -> bool {
+    match gate.min_age_days {
+        Some(days) => Utc::now() - utils::snowflake_timestamp(user_id.0) < Duration::days(days),
+        None => false,
+    }
+}
+
+/// The permission needed to act on members stopped by the account age gate.
+pub fn gate_permission(action: &AccountAgeAction) -> Permissions {
+    match action {
+        AccountAgeAction::Kick => Permissions::KICK_MEMBERS,
+        AccountAgeAction::AssignRole(_) => Permissions::MANAGE_ROLES,
+    }
+}
+
+/// If raid mode kicks this user as they join.
+pub fn kicked_by_raid_mode(user_id: UserId, config: &AntiRaidConfig) -> bool {
+    let account_age = Utc::now() - utils::snowflake_timestamp(user_id.0);
+    config.actions.contains(&AntiRaidAction::KickAll)
+        || (config.actions.contains(&AntiRaidAction::KickNewAccounts)
+            && account_age < Duration::days(config.min_account_age_days))
+}
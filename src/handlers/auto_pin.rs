@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use hyper::StatusCode;
+use twilight_gateway::Event;
+use twilight_http::Error;
+use twilight_model::channel::message::MessageType;
+use twilight_model::channel::Message;
+use twilight_model::guild::Permissions;
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    if let Event::MessageCreate(msg) = event {
+        // pinning posts a system message in the channel as well, that one shouldn't replace the pin
+        if msg.kind != MessageType::Regular {
+            return Ok(());
+        }
+
+        if let Some(guild_id) = msg.guild_id {
+            let config = ctx.get_config(guild_id).await?;
+            if config.auto_pin_channels.contains(&msg.channel_id) {
+                pin_latest(&msg.0, guild_id, &ctx).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pins the new message and unpins the one we pinned before it.
+async fn pin_latest(message: &Message, guild_id: GuildId, ctx: &BotContext) -> Result<(), EventHandlerError> {
+    let channel_id = message.channel_id;
+    if !ctx
+        .get_bot_permissions_for_channel(channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        return Ok(());
+    }
+
+    let previous = ctx.datastore.get_auto_pin(channel_id).await?;
+
+    if let Err(e) = ctx.http.create_pin(channel_id, message.id).await {
+        let at_pin_limit = matches!(&e, Error::Response { status, .. } if *status == StatusCode::BAD_REQUEST);
+        match previous {
+            // channels can only have 50 pins, make room by taking our old one down first
+            Some(previous) if at_pin_limit => {
+                unpin(channel_id, previous, ctx).await?;
+                ctx.http.create_pin(channel_id, message.id).await?;
+                ctx.datastore.set_auto_pin(guild_id, channel_id, message.id).await?;
+                return Ok(());
+            }
+            _ if at_pin_limit => {
+                log::debug!(
+                    "Channel {} is full of pins that aren't ours, not auto pinning",
+                    channel_id
+                );
+                return Ok(());
+            }
+            _ => return Err(e.into()),
+        }
+    }
+
+    ctx.datastore.set_auto_pin(guild_id, channel_id, message.id).await?;
+    if let Some(previous) = previous {
+        unpin(channel_id, previous, ctx).await?;
+    }
+
+    Ok(())
+}
+
+async fn unpin(channel_id: ChannelId, message_id: MessageId, ctx: &BotContext) -> Result<(), EventHandlerError> {
+    match ctx.http.delete_pin(channel_id, message_id).await {
+        Ok(_) => Ok(()),
+        // already unpinned by someone else or deleted, either way it's no longer pinned
+        Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_model::guild::Permissions;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let msg = match event {
+        Event::MessageCreate(msg) if !msg.author.bot => msg,
+        _ => return Ok(()),
+    };
+
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    let responses = ctx.get_auto_responses(guild_id).await?;
+    if responses.is_empty() {
+        return Ok(());
+    }
+
+    // commands are answered by the command handler instead
+    if msg.content.starts_with(&ctx.get_config(guild_id).await?.prefix) {
+        return Ok(());
+    }
+
+    let response = match responses.iter().find(|response| response.matches(&msg.content)) {
+        Some(response) => response,
+        None => return Ok(()),
+    };
+
+    let can_send = ctx
+        .get_bot_permissions_for_channel(msg.channel_id)
+        .contains(Permissions::SEND_MESSAGES);
+    if !can_send || !ctx.check_auto_response_cooldown(response).await {
+        return Ok(());
+    }
+
+    let request = ctx
+        .http
+        .create_message(msg.channel_id)
+        .content(response.response.clone())?;
+    ctx.queue_send(msg.channel_id, request).await?;
+
+    Ok(())
+}
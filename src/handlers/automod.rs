@@ -0,0 +1,588 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use hyper::StatusCode;
+use log::debug;
+use twilight_gateway::Event;
+use twilight_http::Error;
+use twilight_model::gateway::payload::MessageCreate;
+use twilight_model::guild::Permissions;
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+use crate::core::logpump::{LogData, LogType};
+use crate::core::{
+    AutomodActionKind, BotContext, GuildConfig, LinkFilterConfig, ScanAction, ScanVerdict, SpamAction, TrackedMessage,
+    MAX_MESSAGE_LENGTH,
+};
+use crate::error::EventHandlerError;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::matchers::{self, InviteLink};
+use crate::utils::{text, Emoji};
+
+/// Short messages like greetings are posted in many channels innocently, those aren't checked for similarity.
+const MIN_SIMILARITY_LETTERS: usize = 12;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    if let Event::MessageCreate(msg) = event {
+        let guild_id = match msg.guild_id {
+            Some(guild_id) => guild_id,
+            None => return Ok(()),
+        };
+
+        if msg.author.bot {
+            return Ok(());
+        }
+
+        let config = ctx.get_config(guild_id).await?;
+        let mut hits = vec![];
+        match check_attachments(msg, &config, &ctx) {
+            Some(hit) => hits.push(hit),
+            None => hits.extend(scan_attachments(msg, &config, &ctx).await),
+        }
+        hits.extend(check_spam(msg, guild_id, &config, &ctx).await);
+        hits.extend(check_links(msg, guild_id, &config, &ctx).await);
+
+        act_on_hits(msg, guild_id, hits, &config, &ctx).await?;
+    }
+
+    Ok(())
+}
+
+/// What kind of rule a message broke.
+enum AutomodRule {
+    BlockedAttachment,
+    AttachmentScan { files: String, scanned: bool },
+    Spam,
+    Link,
+}
+
+/// A rule the message broke and what that rule wants done about it.
+struct AutomodHit {
+    rule: AutomodRule,
+    punishment: Punishment,
+    /// Why the rule was triggered, as shown in the logs.
+    reason: String,
+    /// Lets the author know what they did wrong, only the notice of the hit that gets acted on is posted.
+    notice: Option<String>,
+    /// Copies of the message elsewhere that go down along with it.
+    copies: Vec<(ChannelId, MessageId)>,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy)]
+enum Punishment {
+    Alert,
+    Delete,
+    Warn,
+    Mute(i64),
+    Ban,
+}
+
+impl Punishment {
+    fn kind(&self) -> AutomodActionKind {
+        match self {
+            Punishment::Alert => AutomodActionKind::Alert,
+            Punishment::Delete => AutomodActionKind::Delete,
+            Punishment::Warn => AutomodActionKind::Warn,
+            Punishment::Mute(_) => AutomodActionKind::Mute,
+            Punishment::Ban => AutomodActionKind::Ban,
+        }
+    }
+}
+
+impl From<&SpamAction> for Punishment {
+    fn from(action: &SpamAction) -> Self {
+        match action {
+            SpamAction::Delete => Punishment::Delete,
+            SpamAction::Warn => Punishment::Warn,
+            SpamAction::Mute(minutes) => Punishment::Mute(*minutes),
+        }
+    }
+}
+
+impl From<&ScanAction> for Punishment {
+    fn from(action: &ScanAction) -> Self {
+        match action {
+            ScanAction::Delete => Punishment::Delete,
+            ScanAction::Alert => Punishment::Alert,
+            ScanAction::Ban => Punishment::Ban,
+        }
+    }
+}
+
+/// Catches messages with blocked attachments.
+fn check_attachments(msg: &MessageCreate, config: &GuildConfig, ctx: &BotContext) -> Option<AutomodHit> {
+    let blocked = &config.automod.blocked_extensions;
+    if blocked.is_empty() {
+        return None;
+    }
+
+    // people who can clean up messages themselves are trusted to know what they are uploading
+    if ctx
+        .get_channel_permissions_for(msg.author.id, msg.channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        return None;
+    }
+
+    let extension = msg
+        .attachments
+        .iter()
+        .find_map(|attachment| matchers::get_blocked_extension(&attachment.filename, blocked))?
+        .to_lowercase();
+
+    if !ctx
+        .get_bot_permissions_for_channel(msg.channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        debug!(
+            "Message {} contains a blocked .{} attachment but i'm unable to remove it",
+            msg.id, extension
+        );
+        return None;
+    }
+
+    let args = FluArgs::with_capacity(1).add("extension", extension.clone()).generate();
+    let reason = ctx.translate_with_args(&config.language, GearBotString::SpamReasonBlockedAttachment, &args);
+
+    let args = FluArgs::with_capacity(3)
+        .add("gearno", Emoji::No.for_chat())
+        .add("user_id", msg.author.id.0)
+        .add("extension", extension)
+        .generate();
+    let notice = ctx.translate_with_args(&config.language, GearBotString::AutomodBlockedAttachment, &args);
+
+    Some(AutomodHit {
+        rule: AutomodRule::BlockedAttachment,
+        punishment: Punishment::Delete,
+        reason,
+        notice: Some(notice),
+        copies: vec![],
+    })
+}
+
+/// Runs the attachments by the scanning service, when the guild opted in to that.
+async fn scan_attachments(msg: &MessageCreate, config: &GuildConfig, ctx: &BotContext) -> Option<AutomodHit> {
+    let rule = match &config.automod.attachment_scan {
+        Some(rule) if !msg.attachments.is_empty() => rule,
+        _ => return None,
+    };
+
+    let (punishment, scanned, reason) = match ctx.scan_attachments(msg).await {
+        ScanVerdict::Clean => return None,
+        ScanVerdict::Failed if !rule.fail_closed => return None,
+        // only removed, the action is for attachments that were actually flagged
+        ScanVerdict::Failed => (Punishment::Delete, false, GearBotString::SpamReasonScanFailed),
+        ScanVerdict::Flagged => (
+            Punishment::from(&rule.action),
+            true,
+            GearBotString::SpamReasonScanFlagged,
+        ),
+    };
+
+    let files = msg
+        .attachments
+        .iter()
+        .map(|attachment| attachment.filename.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let args = FluArgs::with_capacity(1)
+        .add("files", files.replace("`", "ˋ"))
+        .generate();
+    let reason = ctx.translate_with_args(&config.language, reason, &args);
+
+    Some(AutomodHit {
+        rule: AutomodRule::AttachmentScan { files, scanned },
+        punishment,
+        reason,
+        notice: None,
+        copies: vec![],
+    })
+}
+
+/// Runs the spam checks the guild has rules for, every rule that trips is a hit.
+async fn check_spam(msg: &MessageCreate, guild_id: GuildId, config: &GuildConfig, ctx: &BotContext) -> Vec<AutomodHit> {
+    let spam = &config.automod.spam;
+    if spam.duplicates.is_none() && spam.mentions.is_none() && spam.rate.is_none() && spam.cross_channel.is_none() {
+        return vec![];
+    }
+
+    if ctx
+        .get_channel_permissions_for(msg.author.id, msg.channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        return vec![];
+    }
+
+    let now = Instant::now();
+    let tracked = TrackedMessage {
+        sent: now,
+        channel_id: msg.channel_id,
+        message_id: msg.id,
+        fingerprint: fingerprint(&msg.content),
+        similarity: similarity_fingerprint(&msg.content),
+    };
+    let recent = ctx.track_message(guild_id, msg.author.id, tracked).await;
+    let sent_within = |seconds: u64| {
+        recent
+            .iter()
+            .filter(move |other| now.duration_since(other.sent) <= Duration::from_secs(seconds))
+    };
+
+    let mut tripped = vec![];
+    if let Some(rule) = &spam.duplicates {
+        // attachments without text all look the same, those aren't duplicates
+        let count = sent_within(rule.window_seconds)
+            .filter(|other| other.fingerprint == tracked.fingerprint)
+            .count();
+        if !msg.content.trim().is_empty() && count >= rule.threshold {
+            tripped.push((rule, GearBotString::SpamReasonDuplicates, count, vec![]));
+        }
+    }
+    if let Some(rule) = &spam.mentions {
+        let count = msg.mentions.len() + msg.mention_roles.len();
+        if count >= rule.threshold {
+            tripped.push((rule, GearBotString::SpamReasonMentions, count, vec![]));
+        }
+    }
+    if let Some(rule) = &spam.rate {
+        let count = sent_within(rule.window_seconds).count();
+        if count >= rule.threshold {
+            tripped.push((rule, GearBotString::SpamReasonRate, count, vec![]));
+        }
+    }
+    if let (Some(rule), Some(similarity)) = (&spam.cross_channel, tracked.similarity) {
+        let similar: Vec<_> = sent_within(rule.window_seconds)
+            .filter(|other| other.similarity == Some(similarity))
+            .collect();
+        let channels = similar
+            .iter()
+            .map(|other| other.channel_id)
+            .collect::<HashSet<_>>()
+            .len();
+        if channels >= rule.threshold {
+            // copies of the message posted elsewhere, these get cleaned up along with it
+            let copies = similar
+                .into_iter()
+                .filter(|other| other.message_id != msg.id)
+                .map(|other| (other.channel_id, other.message_id))
+                .collect();
+            tripped.push((rule, GearBotString::SpamReasonCrossChannel, channels, copies));
+        }
+    }
+
+    if tripped.is_empty() {
+        return vec![];
+    }
+
+    // start over, the messages that got them caught shouldn't trip the checks again
+    ctx.forget_tracked_messages(guild_id, msg.author.id).await;
+
+    tripped
+        .into_iter()
+        .map(|(rule, reason, count, copies)| {
+            let args = FluArgs::with_capacity(2)
+                .add("count", count)
+                .add("seconds", rule.window_seconds)
+                .generate();
+            let reason = ctx.translate_with_args(&config.language, reason, &args);
+            let punishment = Punishment::from(&rule.action);
+            let notice = if punishment == Punishment::Delete {
+                None
+            } else {
+                let args = FluArgs::with_capacity(3)
+                    .add("gearwarn", Emoji::Warn.for_chat())
+                    .add("user_id", msg.author.id.to_string())
+                    .add("rule", reason.clone())
+                    .generate();
+                Some(ctx.translate_with_args(&config.language, GearBotString::AutomodSpamWarning, &args))
+            };
+            AutomodHit {
+                rule: AutomodRule::Spam,
+                punishment,
+                reason,
+                notice,
+                copies,
+            }
+        })
+        .collect()
+}
+
+/// Catches invites to other servers and links to domains that aren't allowed.
+async fn check_links(
+    msg: &MessageCreate,
+    guild_id: GuildId,
+    config: &GuildConfig,
+    ctx: &BotContext,
+) -> Vec<AutomodHit> {
+    let links = &config.automod.links;
+    if links.invites.is_none() && links.links.is_none() {
+        return vec![];
+    }
+
+    if ctx
+        .get_channel_permissions_for(msg.author.id, msg.channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        return vec![];
+    }
+
+    let mut tripped = vec![];
+    if let Some(action) = &links.invites {
+        if has_blocked_invite(&msg.content, guild_id, links, ctx).await {
+            let reason = ctx.translate(&config.language, GearBotString::SpamReasonInvite);
+            tripped.push((action, reason));
+        }
+    }
+    if let Some(action) = &links.links {
+        let blocked = matchers::get_link_domains(&msg.content)
+            .into_iter()
+            .find(|domain| !matchers::is_domain_allowed(domain, &links.allowed_domains));
+        if let Some(domain) = blocked {
+            let args = FluArgs::with_capacity(1).add("domain", domain).generate();
+            let reason = ctx.translate_with_args(&config.language, GearBotString::SpamReasonLink, &args);
+            tripped.push((action, reason));
+        }
+    }
+
+    tripped
+        .into_iter()
+        .map(|(action, reason)| {
+            let args = FluArgs::with_capacity(3)
+                .add("gearno", Emoji::No.for_chat())
+                .add("user_id", msg.author.id.to_string())
+                .add("rule", reason.clone())
+                .generate();
+            let notice = ctx.translate_with_args(&config.language, GearBotString::AutomodLinkNotice, &args);
+            AutomodHit {
+                rule: AutomodRule::Link,
+                punishment: Punishment::from(action),
+                reason,
+                notice: Some(notice),
+                copies: vec![],
+            }
+        })
+        .collect()
+}
+
+/// If any of the invites in the message lead to a server other than this one or the allowed ones.
+async fn has_blocked_invite(content: &str, guild_id: GuildId, links: &LinkFilterConfig, ctx: &BotContext) -> bool {
+    for invite in matchers::get_invite_links(content) {
+        let code = match invite {
+            InviteLink::Code(code) => code,
+            // can't be checked against the allowed servers
+            InviteLink::Redirect => return true,
+        };
+
+        match ctx.get_invite_guild(&code).await {
+            Ok(Some(target)) if target != guild_id && !links.allowed_invite_guilds.contains(&target) => return true,
+            // dead invites don't advertise anything
+            Ok(_) => {}
+            Err(e) => {
+                // better to remove an allowed invite than to let through one we couldn't check
+                debug!("Unable to look up where invite {} leads to: {}", code, e);
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Takes the heaviest action of all rules that tripped, so breaking several rules at once doesn't get
+/// someone punished twice, and logs it once with all of those rules.
+async fn act_on_hits(
+    msg: &MessageCreate,
+    guild_id: GuildId,
+    hits: Vec<AutomodHit>,
+    config: &GuildConfig,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    let hit = match hits
+        .iter()
+        .min_by_key(|hit| config.automod.action_rank(hit.punishment.kind()))
+    {
+        Some(hit) => hit,
+        None => return Ok(()),
+    };
+
+    let (applied, action) = punish(msg, guild_id, hit.punishment, &hit.reason, config, ctx).await?;
+    if applied != Punishment::Alert {
+        for (channel_id, message_id) in hits.iter().flat_map(|hit| &hit.copies) {
+            delete_message(*channel_id, *message_id, ctx).await?;
+        }
+    }
+
+    let log_type = match (hits.as_slice(), &hit.rule) {
+        // blocked attachments already get pointed out in the channel, there is nothing more to log about them
+        ([_], AutomodRule::BlockedAttachment) => None,
+        ([_], AutomodRule::AttachmentScan { files, scanned }) => {
+            let outcome = match (*scanned, applied) {
+                (false, _) => GearBotString::ScanOutcomeUnscanned,
+                (true, Punishment::Alert) => GearBotString::ScanOutcomeAlert,
+                (true, Punishment::Ban) => GearBotString::ScanOutcomeBan,
+                (true, _) => GearBotString::ScanOutcomeDelete,
+            };
+            Some(LogType::AttachmentScanned {
+                target: msg.author.id.0,
+                files: files.clone(),
+                action: ctx.translate(&config.language, outcome),
+            })
+        }
+        _ => {
+            let reasons: Vec<&str> = hits.iter().map(|hit| hit.reason.as_str()).collect();
+            Some(LogType::SpamDetected {
+                target: msg.author.id.0,
+                rule: reasons.join(", "),
+                action,
+            })
+        }
+    };
+    if let Some(log_type) = log_type {
+        ctx.log(LogData {
+            log_type,
+            guild: guild_id,
+            source_channel: Some(msg.channel_id),
+            source_user: ctx.bot_user.id,
+            failed_attempts: 0,
+        });
+    }
+
+    if let Some(notice) = &hit.notice {
+        if ctx
+            .get_bot_permissions_for_channel(msg.channel_id)
+            .contains(Permissions::SEND_MESSAGES)
+        {
+            // the rule in there can quote what was posted, like the domain of a link
+            let notice = text::truncate_with_ellipsis(notice, MAX_MESSAGE_LENGTH);
+            ctx.http.create_message(msg.channel_id).content(notice)?.await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes the message and applies the punishment, returning what was actually done and a description of it
+/// for the logs.
+///
+/// When we are not allowed to apply the punishment, removing the message is all that is done.
+async fn punish(
+    msg: &MessageCreate,
+    guild_id: GuildId,
+    punishment: Punishment,
+    reason: &str,
+    config: &GuildConfig,
+    ctx: &BotContext,
+) -> Result<(Punishment, String), EventHandlerError> {
+    if punishment == Punishment::Alert {
+        let description = ctx.translate(&config.language, GearBotString::SpamActionAlert);
+        return Ok((punishment, description));
+    }
+    remove_message(msg, ctx).await?;
+
+    let removed = ctx.translate(&config.language, GearBotString::SpamActionDelete);
+
+    let author = msg.author.id;
+    let audit_reason = format!("Automod: {}", text::truncate(reason, 400));
+    let outcome = match punishment {
+        Punishment::Alert | Punishment::Delete => (punishment, removed),
+        Punishment::Warn => {
+            ctx.datastore
+                .insert_history_entry(guild_id, author, ctx.bot_user.id, "warning", None)
+                .await?;
+            let description = ctx.translate(&config.language, GearBotString::SpamActionWarn);
+            (punishment, description)
+        }
+        Punishment::Mute(minutes) => {
+            let until = Utc::now() + chrono::Duration::minutes(minutes);
+            match ctx
+                .set_member_timeout(guild_id, author, Some(until), &audit_reason)
+                .await
+            {
+                Ok(_) => {
+                    ctx.datastore
+                        .insert_history_entry(guild_id, author, ctx.bot_user.id, "mute", Some(until))
+                        .await?;
+                    let args = FluArgs::with_capacity(1)
+                        .add("until", config.format_time(until))
+                        .generate();
+                    let description = ctx.translate_with_args(&config.language, GearBotString::SpamActionMute, &args);
+                    (punishment, description)
+                }
+                Err(Error::Response { status, .. }) if status == StatusCode::FORBIDDEN => {
+                    debug!("Unable to mute {} for breaking automod rules in {}", author, guild_id);
+                    (Punishment::Delete, removed)
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Punishment::Ban => match ctx.http.create_ban(guild_id, author).reason(audit_reason).await {
+            Ok(_) => {
+                ctx.datastore
+                    .insert_history_entry(guild_id, author, ctx.bot_user.id, "ban", None)
+                    .await?;
+                let description = ctx.translate(&config.language, GearBotString::SpamActionBan);
+                (punishment, description)
+            }
+            Err(Error::Response { status, .. }) if status == StatusCode::FORBIDDEN => {
+                debug!("Unable to ban {} for breaking automod rules in {}", author, guild_id);
+                (Punishment::Delete, removed)
+            }
+            Err(e) => return Err(e.into()),
+        },
+    };
+
+    Ok(outcome)
+}
+
+/// Deletes the message if we are allowed to.
+async fn remove_message(msg: &MessageCreate, ctx: &BotContext) -> Result<(), EventHandlerError> {
+    delete_message(msg.channel_id, msg.id, ctx).await
+}
+
+async fn delete_message(
+    channel_id: ChannelId,
+    message_id: MessageId,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    if ctx
+        .get_bot_permissions_for_channel(channel_id)
+        .contains(Permissions::MANAGE_MESSAGES)
+    {
+        match ctx.http.delete_message(channel_id, message_id).await {
+            Ok(_) => (),
+            // another check might have beaten us to it
+            Err(Error::Response { status, .. }) if status == StatusCode::NOT_FOUND => (),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Identifies messages with the same content, ignoring casing and surrounding whitespace.
+fn fingerprint(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.trim().to_lowercase().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Identifies messages that only differ in casing, spacing, punctuation, numbers or emoji, the things that get
+/// changed to slip past duplicate checks. Messages with too few letters left to tell apart get none.
+fn similarity_fingerprint(content: &str) -> Option<u64> {
+    let letters: String = content
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if letters.chars().count() < MIN_SIMILARITY_LETTERS {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    letters.hash(&mut hasher);
+    Some(hasher.finish())
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    match event {
+        Event::GuildCreate(guild) => {
+            let config = ctx.get_config(guild.id).await?;
+            if let Some(nickname) = &config.bot_nickname {
+                ctx.apply_bot_nickname(guild.id, Some(nickname)).await?;
+            }
+        }
+        // someone changed or reset our nickname, put the configured one back
+        Event::MemberUpdate(update) if update.user.id == ctx.bot_user.id => {
+            let config = ctx.get_config(update.guild_id).await?;
+            if let Some(nickname) = &config.bot_nickname {
+                if update.nick.as_ref() != Some(nickname) {
+                    ctx.apply_bot_nickname(update.guild_id, Some(nickname)).await?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
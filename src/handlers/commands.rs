@@ -1,53 +1,75 @@
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use log::{debug, trace};
 use twilight_gateway::Event;
+use twilight_model::gateway::payload::MessageCreate;
+use twilight_model::guild::Permissions;
+use twilight_model::id::{GuildId, UserId};
+use unic_langid::LanguageIdentifier;
 
+use crate::commands::meta::nodes::GearBotPermissions;
 use crate::core::BotContext;
 use crate::error::EventHandlerError;
+use crate::translation::{FluArgs, GearBotString, DEFAULT_LANG};
 use crate::Parser;
 
+/// How often a command that is waiting on its guild checks if it's ready.
+const RECOVERY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 pub async fn handle_event<'a>(shard_id: u64, event: Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
     match event {
         Event::MessageCreate(msg) if !msg.author.bot => {
             trace!("Received a message from {}, saying {}", msg.author.name, msg.content);
 
-            let p = match msg.guild_id {
+            let (p, language, case_insensitive, channel_allowed) = match msg.guild_id {
                 Some(guild_id) => {
-                    let guild = ctx.cache.get_guild(&guild_id);
-                    match guild {
-                        Some(g) => {
-                            if !g.complete.load(Ordering::SeqCst) {
-                                debug!("Message received in {} but the guild isn't fully cached yet!", g.id);
-                                return Ok(()); //not cached yet, just ignore for now
-                            }
-                        }
-                        None => return Ok(()), // we didn't even get a guild create yet
+                    if ctx.cache.get_guild(&guild_id).is_none() {
+                        return Ok(()); // we didn't even get a guild create yet
                     }
 
                     let config = ctx.get_config(guild_id).await?;
-                    config.prefix.clone()
+                    (
+                        config.prefix.clone(),
+                        config.language.clone(),
+                        config.case_insensitive_commands,
+                        config.command_channels.allows(msg.channel_id),
+                    )
                 }
-                None => String::from("!"),
+                None => (String::from("!"), DEFAULT_LANG, false, true),
             };
 
-            let prefix = if msg.content.starts_with(&p) {
-                Some(p)
-            } else {
-                let mention_1 = format!("<@{}>", ctx.bot_user.id);
-                let mention_2 = format!("<@!{}>", ctx.bot_user.id);
-                if msg.content.starts_with(&mention_1) {
-                    Some(mention_1)
-                } else if msg.content.starts_with(&mention_2) {
-                    Some(mention_2)
-                } else {
-                    None
+            let content = msg.content.trim();
+            let mentioned =
+                content == format!("<@{}>", ctx.bot_user.id) || content == format!("<@!{}>", ctx.bot_user.id);
+            let prefix = find_prefix(&msg.content, &p, case_insensitive, ctx.bot_user.id);
+            if !mentioned && prefix.is_none() {
+                return Ok(());
+            }
+
+            if let Some(guild_id) = msg.guild_id {
+                if !guild_ready(guild_id, &ctx).await {
+                    return Ok(());
                 }
-            };
+            }
+
+            // stay silent in channels the guild doesn't want commands in, unless they can change that
+            if !channel_allowed && !can_configure(&msg, &ctx).await? {
+                debug!(
+                    "Ignoring a command from {} in {}, commands aren't allowed there",
+                    msg.author.id, msg.channel_id
+                );
+                return Ok(());
+            }
+
+            // a mention without anything else, let them know how to actually use us
+            if mentioned {
+                return respond_to_mention(&msg, &p, &language, &ctx).await;
+            }
 
             if let Some(prefix) = prefix {
-                Parser::figure_it_out(&prefix, msg, ctx, shard_id).await?;
+                Parser::figure_it_out(&prefix, msg, ctx, shard_id, case_insensitive).await?;
             }
         }
         Event::MessageUpdate(update) => {
@@ -58,3 +80,120 @@ pub async fn handle_event<'a>(shard_id: u64, event: Event, ctx: Arc<BotContext>)
 
     Ok(())
 }
+
+/// Commands need the full guild, right after a restart or an outage it might still be getting cached.
+///
+/// Depending on the bot config they either wait (for a limited time) for it to be ready, or are dropped right away.
+/// Returns if the command can go ahead.
+async fn guild_ready(guild_id: GuildId, ctx: &BotContext) -> bool {
+    let is_complete = || {
+        ctx.cache
+            .get_guild(&guild_id)
+            .map_or(false, |guild| guild.complete.load(Ordering::SeqCst))
+    };
+    if is_complete() {
+        return true;
+    }
+
+    let load = &ctx.stats.command_load;
+    if let Some(wait) = ctx.recovery_command_wait {
+        debug!(
+            "Command received in {} but the guild isn't fully cached yet, waiting for it",
+            guild_id
+        );
+        let start = Instant::now();
+        while start.elapsed() < wait {
+            tokio::time::delay_for(RECOVERY_POLL_INTERVAL).await;
+            if is_complete() {
+                load.recovery_deferred.inc();
+                return true;
+            }
+        }
+    }
+
+    debug!(
+        "Command received in {} but the guild isn't fully cached yet, ignoring it",
+        guild_id
+    );
+    load.recovery_dropped.inc();
+    false
+}
+
+/// If the author can change the guild config, they can use commands anywhere so they can't lock themselves out.
+async fn can_configure(msg: &MessageCreate, ctx: &BotContext) -> Result<bool, EventHandlerError> {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(true),
+    };
+    let guild = match ctx.cache.get_guild(&guild_id) {
+        Some(guild) => guild,
+        None => return Ok(false),
+    };
+    let member = match ctx.fetch_member(guild_id, msg.author.id).await? {
+        Some(member) => member,
+        None => return Ok(false),
+    };
+
+    let config = ctx.get_config(guild_id).await?;
+    let permissions = ctx.get_permissions_for(&guild, &member, &config);
+    Ok(permissions.intersects(GearBotPermissions::WRITE_CONFIG | GearBotPermissions::BOT_ADMIN))
+}
+
+async fn respond_to_mention(
+    msg: &MessageCreate,
+    prefix: &str,
+    language: &LanguageIdentifier,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    if msg.guild_id.is_some()
+        && !ctx
+            .get_bot_permissions_for_channel(msg.channel_id)
+            .contains(Permissions::SEND_MESSAGES)
+    {
+        return Ok(());
+    }
+
+    if !ctx.check_mention_cooldown(msg.channel_id).await {
+        debug!("Not answering mention in {}, still on cooldown", msg.channel_id);
+        return Ok(());
+    }
+
+    let args = FluArgs::with_capacity(2)
+        .add("user_id", msg.author.id.0)
+        .add("prefix", prefix)
+        .generate();
+    let response = ctx.translate_with_args(language, GearBotString::MentionPrefix, &args);
+
+    ctx.http
+        .create_message(msg.channel_id)
+        .content(response)
+        .unwrap()
+        .await?;
+
+    Ok(())
+}
+
+/// The prefix a message starts with, either the configured one or a mention of the bot.
+pub fn find_prefix(content: &str, prefix: &str, case_insensitive: bool, bot_id: UserId) -> Option<String> {
+    let mention_1 = format!("<@{}>", bot_id);
+    let mention_2 = format!("<@!{}>", bot_id);
+
+    if content.starts_with(prefix) {
+        Some(prefix.to_string())
+    } else if case_insensitive && starts_with_ignore_case(content, prefix) {
+        // use the casing they typed so the prefix length still matches the content
+        Some(content[..prefix.len()].to_string())
+    } else if content.starts_with(&mention_1) {
+        Some(mention_1)
+    } else if content.starts_with(&mention_2) {
+        Some(mention_2)
+    } else {
+        None
+    }
+}
+
+fn starts_with_ignore_case(content: &str, prefix: &str) -> bool {
+    content
+        .get(..prefix.len())
+        .map_or(false, |start| start.to_lowercase() == prefix.to_lowercase())
+}
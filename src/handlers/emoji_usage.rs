@@ -0,0 +1,31 @@
+use std::iter;
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_model::channel::ReactionType;
+use twilight_model::id::EmojiId;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+use crate::utils::matchers;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    match event {
+        Event::MessageCreate(msg) if !msg.author.bot => {
+            if let Some(guild_id) = msg.guild_id {
+                let used = matchers::get_emoji_parts(&msg.content)
+                    .into_iter()
+                    .map(|emoji| EmojiId(emoji.id));
+                ctx.record_emoji_usage(guild_id, used).await?;
+            }
+        }
+        Event::ReactionAdd(reaction) => {
+            if let (Some(guild_id), ReactionType::Custom { id, .. }) = (reaction.guild_id, &reaction.emoji) {
+                ctx.record_emoji_usage(guild_id, iter::once(*id)).await?;
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_http::request::channel::reaction::RequestReactionType;
+use twilight_model::guild::Permissions;
+use twilight_model::id::EmojiId;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+use crate::utils::matchers;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let msg = match event {
+        Event::MessageCreate(msg) if !msg.author.bot && msg.webhook_id.is_none() => msg,
+        _ => return Ok(()),
+    };
+
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    let config = ctx.get_config(guild_id).await?;
+    let greeting = match &config.first_message_greeting {
+        Some(greeting) => greeting,
+        None => return Ok(()),
+    };
+
+    if !ctx
+        .datastore
+        .record_first_message(guild_id, msg.author.id, msg.id)
+        .await?
+    {
+        return Ok(());
+    }
+
+    let permissions = ctx.get_bot_permissions_for_channel(msg.channel_id);
+    if let Some(reaction) = &greeting.reaction {
+        if permissions.contains(Permissions::ADD_REACTIONS) {
+            ctx.http
+                .create_reaction(msg.channel_id, msg.id, to_reaction(reaction))
+                .await?;
+        }
+    }
+
+    if let Some(message) = &greeting.message {
+        if permissions.contains(Permissions::SEND_MESSAGES) {
+            let content = message.replace("{user}", &format!("<@{}>", msg.author.id));
+            let request = ctx.http.create_message(msg.channel_id).content(content)?;
+            ctx.queue_send(msg.channel_id, request).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn to_reaction(emoji: &str) -> RequestReactionType {
+    match matchers::get_emoji_parts(emoji).into_iter().next() {
+        Some(custom) => RequestReactionType::Custom {
+            id: EmojiId(custom.id),
+            name: Some(custom.name),
+        },
+        None => RequestReactionType::Unicode {
+            name: emoji.to_string(),
+        },
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use log::debug;
 use twilight_gateway::Event;
+use twilight_model::channel::{Channel, GuildChannel};
 
 use crate::core::reactors::reactor_controller;
 use crate::core::BotContext;
@@ -22,19 +23,32 @@ pub async fn handle_event(shard_id: u64, event: &Event, ctx: Arc<BotContext>) ->
                     shard_id
                 );
             } else {
-                return Err(EventHandlerError::InvalidSession(shard_id));
+                // also happens when a cold resume gets rejected, the shard identifies again and the
+                // ready + guild creates that follow replace whatever we restored for it
+                gearbot_warn!(
+                    "The gateway has invalidated our session for shard {}, it will re-identify instead",
+                    shard_id
+                );
             }
         }
         Event::GatewayReconnect => gearbot_info!("Gateway requested shard {} to reconnect!", shard_id),
         Event::GatewayHello(u) => {
             debug!("Registered with gateway {} on shard {}", u, shard_id);
         }
+        Event::GatewayHeartbeatAck => ctx.update_shard_latency(shard_id).await,
         Event::Resumed => {
             gearbot_info!("Shard {} successfully resumed", shard_id);
         }
+        Event::ChannelDelete(channel) => {
+            // only text channels can have a logging webhook
+            if let Channel::Guild(GuildChannel::Text(text)) = &channel.0 {
+                ctx.datastore.remove_webhook(text.id).await?;
+            }
+        }
         Event::ReactionAdd(reaction) => {
             reactor_controller::process_reaction(&ctx, reaction).await?;
         }
+        Event::MemberChunk(chunk) => ctx.receive_member_chunk(chunk).await,
 
         _ => (),
     }
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use twilight_gateway::Event;
+
+use crate::cache::{Cache, CachedMember};
+use crate::core::logpump::{LogData, LogType};
+use crate::core::{BotContext, LogCategory};
+use crate::error::EventHandlerError;
+use crate::translation::GearBotString;
+use crate::utils;
+
+/// The cache forgets members as soon as they leave, this holds on to them so their leave can still be
+/// logged with how long they were around and which roles they had. Needs to run before the cache update.
+pub fn departed_member(event: &Event, cache: &Cache) -> Option<Arc<CachedMember>> {
+    match event {
+        Event::MemberRemove(remove) => cache.get_member(&remove.guild_id, &remove.user.id),
+        _ => None,
+    }
+}
+
+pub async fn handle_event(
+    event: &Event,
+    departed: Option<Arc<CachedMember>>,
+    ctx: Arc<BotContext>,
+) -> Result<(), EventHandlerError> {
+    match event {
+        Event::MemberAdd(member) => {
+            let config = ctx.get_config(member.guild_id).await?;
+            if config.get_log_channels(&LogCategory::MEMBERS).is_empty() {
+                return Ok(());
+            }
+
+            let created_at = utils::snowflake_timestamp(member.user.id.0);
+            ctx.log(LogData {
+                log_type: LogType::MemberJoined {
+                    age: ctx.format_duration(&config.language, Utc::now() - created_at, 2),
+                },
+                guild: member.guild_id,
+                source_channel: None,
+                source_user: member.user.id,
+                failed_attempts: 0,
+            });
+        }
+        Event::MemberRemove(remove) => {
+            let config = ctx.get_config(remove.guild_id).await?;
+            if config.get_log_channels(&LogCategory::MEMBERS).is_empty() {
+                return Ok(());
+            }
+
+            let member_for = departed
+                .as_ref()
+                .and_then(|member| member.join_time())
+                .map(|joined| ctx.format_duration(&config.language, Utc::now() - joined, 2))
+                .unwrap_or_else(|| ctx.translate(&config.language, GearBotString::MemberLeftUnknownDuration));
+
+            let mut roles = departed.map_or_else(Vec::new, |member| member.roles.clone());
+            // highest roles first, the way discord shows them
+            if let Some(guild) = ctx.cache.get_guild(&remove.guild_id) {
+                roles.sort_by_key(|role_id| -guild.get_role(role_id).map_or(0, |role| role.position));
+            }
+
+            ctx.log(LogData {
+                log_type: LogType::MemberLeft {
+                    member_for,
+                    roles: roles.into_iter().map(|role_id| role_id.0).collect(),
+                },
+                guild: remove.guild_id,
+                source_channel: None,
+                source_user: remove.user.id,
+                failed_attempts: 0,
+            });
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
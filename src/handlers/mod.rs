@@ -1,3 +1,16 @@
+pub mod anti_raid;
+pub mod auto_pin;
+pub mod auto_response;
+pub mod automod;
+pub mod bot_nickname;
 pub mod commands;
+pub mod emoji_usage;
+pub mod first_message;
 pub mod general;
+pub mod member_logs;
 pub mod modlog;
+pub mod name_history;
+pub mod reaction_counts;
+pub mod role_menu;
+pub mod starboard;
+pub mod welcome_image;
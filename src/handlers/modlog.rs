@@ -1,13 +1,30 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use log::debug;
+use log::{debug, warn};
 use twilight_gateway::Event;
-use twilight_model::gateway::payload::RequestGuildMembers;
+use twilight_model::channel::message::MessageType;
+use twilight_model::gateway::payload::{MessageDelete, MessageDeleteBulk, MessageUpdate, RequestGuildMembers};
+use twilight_model::id::{AttachmentId, GuildId, MessageId};
 
-use crate::core::BotContext;
+use crate::core::logpump::{DeletedAttachment, LogData, LogType};
+use crate::core::LogCategory;
+use crate::core::{BotContext, DeletedContent, GuildConfig, OwnMessageStorage, SystemMessageStorage};
+use crate::database::structures::AttachmentBytes;
 use crate::error::EventHandlerError;
+use crate::handlers::commands;
+use crate::translation::GearBotString;
+use crate::utils;
 
 pub async fn handle_event(shard_id: u64, event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let message_event = matches!(
+        event,
+        Event::MessageCreate(_) | Event::MessageDelete(_) | Event::MessageUpdate(_) | Event::MessageDeleteBulk(_)
+    );
+    if message_event && ctx.message_logs_paused().await {
+        return Ok(());
+    }
+
     match &event {
         Event::MemberChunk(_chunk) => {}
         Event::UserUpdate(_) => {}
@@ -90,18 +107,72 @@ pub async fn handle_event(shard_id: u64, event: &Event, ctx: Arc<BotContext>) ->
 
         Event::MessageCreate(msg) => {
             if let Some(guild_id) = msg.guild_id {
-                let config = &ctx.get_config(guild_id).await?.message_logs;
+                ctx.cancel_reposted_deletes(guild_id, msg.channel_id, msg.author.id, &msg.content)
+                    .await?;
 
-                let should_log = config.enabled
-                    && !config.ignored_users.contains(&msg.author.id.0)
-                    && !(config.ignore_bots && msg.author.bot);
+                let guild_config = ctx.get_config(guild_id).await?;
+                let config = &guild_config.message_logs;
+
+                // sensitive channels stay out of storage entirely, before anything gets encrypted or hashed
+                let nsfw = ctx
+                    .cache
+                    .get_channel(msg.channel_id)
+                    .map_or(false, |channel| channel.is_nsfw());
+                let should_log = match ctx.own_messages {
+                    _ if config.excludes_channel(msg.channel_id, nsfw) => false,
+                    OwnMessageStorage::Audit if ctx.is_own(&msg.0) => true,
+                    OwnMessageStorage::Skip if ctx.is_own(&msg.0) => false,
+                    _ => {
+                        config.enabled
+                            && !config.ignored_users.contains(&msg.author.id.0)
+                            && !(config.ignore_bots && msg.author.bot)
+                            && !(config.ignore_commands && is_command(&msg.content, &guild_config, &ctx))
+                            && !(config.empty_system_messages == SystemMessageStorage::Skip
+                                && msg.kind != MessageType::Regular
+                                && msg.content.is_empty())
+                    }
+                };
 
                 if should_log {
-                    ctx.insert_message(&msg.0, guild_id).await?;
+                    let within_limit = match config.max_stored_per_second {
+                        Some(limit) => ctx.take_storage_slot(guild_id, limit).await,
+                        None => true,
+                    };
+
+                    if !within_limit {
+                        ctx.stats.database.rate_limited_messages.inc();
+                    } else if let Err(e) = ctx.store_message(shard_id, &msg.0, guild_id).await {
+                        ctx.stats.database.dropped_messages.inc();
+                        if !e.is_unavailable() {
+                            warn!("Failed to store message {}: {}", msg.id, e);
+                        }
+                    }
                 }
             }
         }
+        Event::MessageDelete(delete) => {
+            if let Some(guild_id) = delete.guild_id {
+                log_delete(delete, guild_id, &ctx).await?;
+            }
+        }
+        Event::MessageUpdate(update) => {
+            if let (Some(guild_id), Some(content)) = (update.guild_id, &update.content) {
+                log_edit(update, guild_id, content, &ctx).await?;
+            }
+        }
+        Event::MessageDeleteBulk(bulk) => {
+            if let Some(guild_id) = bulk.guild_id {
+                log_bulk_delete(bulk, guild_id, &ctx).await?;
+            }
+        }
         Event::GuildCreate(guild) => {
+            if ctx
+                .cache
+                .get_guild(&guild.id)
+                .map_or(false, |guild| guild.is_lazily_cached())
+            {
+                return Ok(());
+            }
             let c = ctx.cluster.clone();
             let data = RequestGuildMembers::builder(guild.id).query("", None);
             debug!("Requesting members for guild {}", guild.id);
@@ -111,3 +182,193 @@ pub async fn handle_event(shard_id: u64, event: &Event, ctx: Arc<BotContext>) ->
     }
     Ok(())
 }
+
+/// Uses the same prefix detection as the command handler, so only messages that would actually
+/// run (or attempt to run) a command count.
+fn is_command(content: &str, config: &GuildConfig, ctx: &BotContext) -> bool {
+    commands::find_prefix(
+        content,
+        &config.prefix,
+        config.case_insensitive_commands,
+        ctx.bot_user.id,
+    )
+    .is_some()
+}
+
+/// Logs the content of a deleted message, as far as we have it stored.
+async fn log_delete(delete: &MessageDelete, guild_id: GuildId, ctx: &BotContext) -> Result<(), EventHandlerError> {
+    let message = match ctx.fetch_user_message(delete.id, guild_id).await? {
+        Some(message) => message,
+        None => return Ok(()),
+    };
+    ctx.datastore.mark_messages_deleted(&[delete.id]).await?;
+
+    let config = ctx.get_config(guild_id).await?;
+    if config.get_log_channels(&LogCategory::MESSAGES).is_empty()
+        || config.message_logs.ignored_channels.contains(&delete.channel_id.0)
+    {
+        return Ok(());
+    }
+
+    let author = message.author;
+    let attachments = deleted_attachments(delete.id, guild_id, ctx).await?;
+    let log = move |content| LogData {
+        log_type: LogType::MessageDeleted { content, attachments },
+        guild: guild_id,
+        source_channel: Some(delete.channel_id),
+        source_user: author,
+        failed_attempts: 0,
+    };
+
+    let not_stored = || ctx.translate(&config.language, GearBotString::ContentNotStored);
+    match (message.content, message.content_hash, config.message_logs.delete_grace) {
+        (Some(content), _, Some(grace)) => {
+            let held = DeletedContent::Text(content.clone());
+            ctx.hold_delete_log(held, Duration::from_secs(grace), log(content))
+                .await
+        }
+        (Some(content), _, None) => ctx.log(log(content)),
+        // privacy mode still kept the hash, that is enough to recognize an exact repost
+        (None, Some(hash), Some(grace)) => {
+            ctx.hold_delete_log(
+                DeletedContent::Hash(hash),
+                Duration::from_secs(grace),
+                log(not_stored()),
+            )
+            .await
+        }
+        // there is no recognizing a repost without the content
+        (None, _, _) => ctx.log(log(not_stored())),
+    }
+
+    Ok(())
+}
+
+/// The attachments of a deleted message, along with the copies that were kept of them.
+async fn deleted_attachments(
+    message_id: MessageId,
+    guild_id: GuildId,
+    ctx: &BotContext,
+) -> Result<Vec<DeletedAttachment>, EventHandlerError> {
+    let mut deleted = vec![];
+    for attachment in ctx.datastore.get_attachments(&[message_id]).await? {
+        let attachment_id = AttachmentId(attachment.id as u64);
+        let copy = match ctx.datastore.get_attachment_bytes(attachment_id, guild_id).await? {
+            AttachmentBytes::Stored(bytes) => Some(bytes),
+            // it's still worth mentioning there was an attachment, even without anything to show
+            AttachmentBytes::NotStored => None,
+            // gone since we looked it up, there is nothing left to tell about it
+            AttachmentBytes::NotFound => continue,
+        };
+        deleted.push(DeletedAttachment {
+            filename: attachment.filename,
+            copy,
+        });
+    }
+    Ok(deleted)
+}
+
+/// Logs what changed in an edited message and keeps the stored copy up to date.
+async fn log_edit(
+    update: &MessageUpdate,
+    guild_id: GuildId,
+    content: &str,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    let message = match ctx.fetch_user_message(update.id, guild_id).await? {
+        Some(message) => message,
+        None => return Ok(()),
+    };
+
+    // discord also sends updates when embeds get resolved, those leave the content alone
+    if message.content.as_deref() == Some(content) {
+        return Ok(());
+    }
+
+    let config = ctx.get_config(guild_id).await?;
+    ctx.datastore
+        .update_message(update.id, guild_id, content, config.message_logs.content_storage())
+        .await?;
+
+    // without the old content there is no telling if it actually changed
+    let before = match message.content {
+        Some(before) => before,
+        None => return Ok(()),
+    };
+
+    if config.get_log_channels(&LogCategory::MESSAGES).is_empty()
+        || config.message_logs.ignored_channels.contains(&update.channel_id.0)
+    {
+        return Ok(());
+    }
+
+    ctx.log(LogData {
+        log_type: LogType::MessageEdited {
+            diff: utils::diff_lines(&before, content),
+        },
+        guild: guild_id,
+        source_channel: Some(update.channel_id),
+        source_user: message.author,
+        failed_attempts: 0,
+    });
+
+    Ok(())
+}
+
+/// Assembles everything we know about a bulk delete into a single log entry.
+async fn log_bulk_delete(
+    bulk: &MessageDeleteBulk,
+    guild_id: GuildId,
+    ctx: &BotContext,
+) -> Result<(), EventHandlerError> {
+    let mut ids = bulk.ids.clone();
+    ids.sort();
+
+    let config = ctx.get_config(guild_id).await?;
+    let mut archive = String::new();
+    for id in &ids {
+        if let Some(message) = ctx.fetch_user_message(*id, guild_id).await? {
+            let author = match ctx.cache.get_user(message.author) {
+                Some(user) => user.full_name_with_id(),
+                None => message.author.to_string(),
+            };
+
+            let reply = match message.referenced_message {
+                Some(referenced) => format!(" (in reply to {})", referenced),
+                None => String::new(),
+            };
+
+            let content = match message.content {
+                Some(content) => content,
+                None => ctx.translate(&config.language, GearBotString::ContentNotStored),
+            };
+
+            archive += &format!(
+                "[{}] {}{}: {}\n",
+                utils::snowflake_timestamp(id.0).format("%T"),
+                author,
+                reply,
+                content
+            );
+        }
+    }
+
+    ctx.datastore.mark_messages_deleted(&ids).await?;
+
+    if archive.is_empty() {
+        archive = String::from("-");
+    }
+
+    ctx.log(LogData {
+        log_type: LogType::MessagesBulkDeleted {
+            count: ids.len(),
+            archive,
+        },
+        guild: guild_id,
+        source_channel: Some(bulk.channel_id),
+        source_user: ctx.bot_user.id,
+        failed_attempts: 0,
+    });
+
+    Ok(())
+}
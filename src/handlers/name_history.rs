@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+
+use crate::cache::Cache;
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+/// What a member was called before an update.
+pub struct PreviousNames {
+    username: Option<String>,
+    nickname: Option<String>,
+}
+
+/// The cache already holds the new names by the time the handlers run, this grabs the old ones
+/// so the change can be recorded. Needs to run before the cache update.
+pub fn previous_names(event: &Event, cache: &Cache) -> Option<PreviousNames> {
+    match event {
+        Event::MemberUpdate(update) => {
+            let member = cache.get_member(&update.guild_id, &update.user.id)?;
+            Some(PreviousNames {
+                username: cache.get_user(update.user.id).map(|user| user.username.clone()),
+                nickname: member.nickname.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+pub async fn handle_event(
+    event: &Event,
+    previous: Option<PreviousNames>,
+    ctx: Arc<BotContext>,
+) -> Result<(), EventHandlerError> {
+    let (update, previous) = match (event, previous) {
+        (Event::MemberUpdate(update), Some(previous)) => (update, previous),
+        _ => return Ok(()),
+    };
+
+    // username changes come in for every mutual guild, but only the first one still finds the old name in the cache
+    if let Some(old_name) = &previous.username {
+        if old_name != &update.user.name {
+            ctx.datastore
+                .record_username_change(update.user.id, old_name, &update.user.name)
+                .await?;
+        }
+    }
+
+    if previous.nickname != update.nick {
+        ctx.datastore
+            .record_nickname_change(
+                update.guild_id,
+                update.user.id,
+                previous.nickname.as_deref(),
+                update.nick.as_deref(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_model::channel::Reaction;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    match event {
+        Event::ReactionAdd(reaction) => count_reaction(&reaction.0, 1, &ctx).await?,
+        Event::ReactionRemove(reaction) => count_reaction(&reaction.0, -1, &ctx).await?,
+        Event::ReactionRemoveAll(removed) => {
+            if let Some(guild_id) = removed.guild_id {
+                if ctx.get_config(guild_id).await?.track_reactions {
+                    ctx.clear_reactions(guild_id, removed.message_id).await?;
+                }
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+async fn count_reaction(reaction: &Reaction, change: i64, ctx: &BotContext) -> Result<(), EventHandlerError> {
+    // our own reactions are there for role menus and such, they say nothing about how a message was received
+    if reaction.user_id == ctx.bot_user.id {
+        return Ok(());
+    }
+
+    if let Some(guild_id) = reaction.guild_id {
+        if ctx.get_config(guild_id).await?.track_reactions {
+            ctx.record_reaction(guild_id, reaction.message_id, &reaction.emoji, change)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
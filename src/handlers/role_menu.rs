@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use hyper::StatusCode;
+use twilight_gateway::Event;
+use twilight_http::Error;
+use twilight_model::channel::Reaction;
+
+use crate::core::logpump::{LogData, LogType};
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    match event {
+        Event::ReactionAdd(reaction) => handle_reaction(&reaction.0, true, &ctx).await?,
+        Event::ReactionRemove(reaction) => handle_reaction(&reaction.0, false, &ctx).await?,
+        Event::MessageDelete(deleted) => {
+            if let Some(guild_id) = deleted.guild_id {
+                // a menu that is gone can't be clicked anymore
+                let menus = ctx.get_role_menus(guild_id).await?;
+                if menus.iter().any(|menu| menu.message_id == deleted.id) {
+                    ctx.datastore.remove_role_menu(guild_id, deleted.id).await?;
+                    ctx.forget_role_menus(guild_id).await;
+                }
+            }
+        }
+        Event::RoleDelete(deleted) => {
+            let count = ctx.remove_role_from_menus(deleted.guild_id, deleted.role_id).await?;
+            if count > 0 {
+                ctx.log(LogData {
+                    log_type: LogType::RoleMenusCleanedUp {
+                        role: deleted.role_id.0,
+                        count,
+                    },
+                    guild: deleted.guild_id,
+                    source_channel: None,
+                    source_user: ctx.bot_user.id,
+                    failed_attempts: 0,
+                });
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+async fn handle_reaction(reaction: &Reaction, added: bool, ctx: &Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let guild_id = match reaction.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    if reaction.user_id == ctx.bot_user.id {
+        return Ok(());
+    }
+
+    let menus = ctx.get_role_menus(guild_id).await?;
+    let option = match menus
+        .iter()
+        .find(|menu| menu.message_id == reaction.message_id)
+        .and_then(|menu| menu.get_option(&reaction.emoji))
+    {
+        Some(option) => option,
+        None => return Ok(()),
+    };
+
+    let result = if added {
+        ctx.http
+            .add_guild_member_role(guild_id, reaction.user_id, option.role_id)
+            .await
+    } else {
+        ctx.http
+            .remove_guild_member_role(guild_id, reaction.user_id, option.role_id)
+            .await
+    };
+
+    match result {
+        Ok(_) => Ok(()),
+        // the role got removed or moved above ours since the menu was made, nothing we can do about that here
+        Err(Error::Response { status, .. }) if status == StatusCode::FORBIDDEN || status == StatusCode::NOT_FOUND => {
+            log::debug!(
+                "Unable to update role {} from role menu {} in guild {}",
+                option.role_id,
+                reaction.message_id,
+                guild_id
+            );
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
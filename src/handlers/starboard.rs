@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_model::channel::Reaction;
+use twilight_model::id::GuildId;
+
+use crate::core::BotContext;
+use crate::error::EventHandlerError;
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    match event {
+        Event::ReactionAdd(reaction) => handle_reaction(&reaction.0, &ctx).await?,
+        Event::ReactionRemove(reaction) => handle_reaction(&reaction.0, &ctx).await?,
+        Event::ReactionRemoveAll(removed) => {
+            if let Some(guild_id) = removed.guild_id {
+                if has_starboard(guild_id, &ctx).await? {
+                    ctx.update_starboard(guild_id, removed.channel_id, removed.message_id)
+                        .await?;
+                }
+            }
+        }
+        Event::MessageUpdate(update) => {
+            if let Some(guild_id) = update.guild_id {
+                // only messages that already made it to the starboard need their post updated
+                if has_starboard(guild_id, &ctx).await? && ctx.datastore.get_starboard_entry(update.id).await?.is_some()
+                {
+                    ctx.update_starboard(guild_id, update.channel_id, update.id).await?;
+                }
+            }
+        }
+        Event::MessageDelete(deleted) => {
+            if deleted.guild_id.is_some() {
+                ctx.remove_from_starboard(deleted.id).await?;
+            }
+        }
+        Event::MessageDeleteBulk(bulk) => {
+            if bulk.guild_id.is_some() {
+                for message_id in &bulk.ids {
+                    ctx.remove_from_starboard(*message_id).await?;
+                }
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+async fn handle_reaction(reaction: &Reaction, ctx: &Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let guild_id = match reaction.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    let config = ctx.get_config(guild_id).await?;
+    if config.starboard.channel.is_some() && config.starboard.is_star(&reaction.emoji) {
+        ctx.update_starboard(guild_id, reaction.channel_id, reaction.message_id)
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn has_starboard(guild_id: GuildId, ctx: &BotContext) -> Result<bool, EventHandlerError> {
+    Ok(ctx.get_config(guild_id).await?.starboard.channel.is_some())
+}
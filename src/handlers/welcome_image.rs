@@ -0,0 +1,75 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use twilight_gateway::Event;
+use twilight_model::guild::Permissions;
+
+use crate::core::{AccountAgeAction, BotContext};
+use crate::error::EventHandlerError;
+use crate::handlers::anti_raid;
+use crate::translation::{FluArgs, GearBotString};
+
+pub async fn handle_event(event: &Event, ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
+    let member = match event {
+        Event::MemberAdd(member) if !member.user.bot => member,
+        _ => return Ok(()),
+    };
+
+    let config = ctx.get_config(member.guild_id).await?;
+    let channel_id = match config.welcome_image.channel {
+        Some(channel_id) => channel_id,
+        None => return Ok(()),
+    };
+
+    if !ctx
+        .get_bot_permissions_for_channel(channel_id)
+        .contains(Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES)
+    {
+        return Ok(());
+    }
+
+    // raiders don't get a warm welcome, nor do members the account age gate just kicked again
+    if ctx.is_raid_mode_active(member.guild_id).await {
+        return Ok(());
+    }
+    let gate = &config.account_age_gate;
+    if gate.action == AccountAgeAction::Kick
+        && anti_raid::fails_account_age_gate(member.user.id, gate)
+        && ctx
+            .get_guild_permissions_for(&member.guild_id, &ctx.bot_user.id)
+            .contains(Permissions::KICK_MEMBERS)
+    {
+        return Ok(());
+    }
+
+    let user = ctx
+        .cache
+        .get_user(member.user.id)
+        .ok_or(EventHandlerError::UnknownUser(member.user.id))?;
+    let guild = ctx
+        .cache
+        .get_guild(&member.guild_id)
+        .ok_or(EventHandlerError::UnknownGuild(member.guild_id))?;
+
+    // members can only be counted when all of them are cached
+    let member_number = if guild.complete.load(Ordering::SeqCst) && !guild.lazy.load(Ordering::SeqCst) {
+        let args = FluArgs::with_capacity(1)
+            .add("number", guild.member_count.load(Ordering::Relaxed))
+            .generate();
+        Some(ctx.translate_with_args(&config.language, GearBotString::WelcomeImageMemberNumber, &args))
+    } else {
+        None
+    };
+
+    let image = ctx
+        .generate_welcome_image(member.guild_id, &config.welcome_image, &user, member_number)
+        .await?;
+    let request = ctx
+        .http
+        .create_message(channel_id)
+        .content(format!("<@{}>", member.user.id))?
+        .attachment("welcome.png", image);
+    ctx.queue_send(channel_id, request).await?;
+
+    Ok(())
+}
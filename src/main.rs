@@ -2,7 +2,8 @@
 #![allow(dead_code)]
 
 use std::convert::{Infallible, TryFrom};
-use std::env;
+use std::net::SocketAddr;
+use std::ops::Range;
 use std::process;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -18,14 +19,17 @@ use twilight_model::{
     gateway::{
         payload::update_status::UpdateStatusInfo,
         presence::{ActivityType, Status},
-        Intents,
     },
     user::CurrentUser,
 };
 
 use prometheus::{Encoder, TextEncoder};
 
-use crate::core::{logging, logpump, status as bot_status, BotConfig, BotContext, BotStats, ColdRebootData};
+use crate::core::{
+    announcements, config_sync, emoji_usage, held_deletes, logging, logpump, maintenance, member_chunking,
+    reaction_counts, shard_watchdog, status as bot_status, status_rotation, BotConfig, BotContext, BotStats,
+    ColdRebootData, HandledEvent,
+};
 use crate::error::{EventHandlerError, StartupError};
 use commands::ROOT_NODE;
 use translation::Translations;
@@ -35,7 +39,7 @@ mod core;
 
 pub mod cache;
 
-use cache::Cache;
+use cache::{Cache, CachedMember};
 
 mod parser;
 
@@ -48,6 +52,8 @@ use database::DataStorage;
 mod error;
 mod handlers;
 
+use handlers::name_history::PreviousNames;
+
 mod translation;
 mod utils;
 
@@ -61,6 +67,13 @@ pub struct SchemeInfo {
     pub total_shards: u64,
 }
 
+impl SchemeInfo {
+    /// The ids of the shards this cluster runs.
+    pub fn shard_range(&self) -> Range<u64> {
+        self.cluster_id * self.shards_per_cluster..self.cluster_id * self.shards_per_cluster + self.shards_per_cluster
+    }
+}
+
 fn main() -> Result<(), StartupError> {
     let mut runtime = Runtime::new()?;
 
@@ -71,9 +84,17 @@ fn main() -> Result<(), StartupError> {
 }
 
 async fn real_main() -> Result<(), StartupError> {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("encrypt-secrets") {
+        let plaintext_file = args.next().unwrap_or_else(|| String::from("secrets.toml"));
+        let location = BotConfig::encrypt_secrets(&plaintext_file)?;
+        println!("Encrypted {} into {}", plaintext_file, location);
+        return Ok(());
+    }
+
     println!("Gearbot v{} starting!", VERSION);
     // Read config file
-    let config = BotConfig::new(&env::var("CONFIG_FILE").unwrap_or_else(|_| String::from("config.toml")))?;
+    let config = BotConfig::new(&BotConfig::file_location())?;
     println!("Loaded config file");
 
     let mut builder = HttpClient::builder()
@@ -101,20 +122,9 @@ async fn real_main() -> Result<(), StartupError> {
 
     gearbot_important!("Starting Gearbot v{}. Hello there, Ferris!", VERSION);
 
-    let translations = translation::load_translations();
+    let translations = translation::load_translations(&config.fallback_language);
     gearbot_info!("Loaded translations!");
 
-    let datastore = DataStorage::initalize(&config).await?;
-
-    {
-        info!("Populating command list");
-        ROOT_NODE.all_commands.get("something");
-        info!("Command list populated")
-    }
-
-    // end of the critical failure zone, everything from here on out should be properly wrapped
-    // and handled
-
     // Parse CLI arguments for sharding and cluster info
     let args = std::env::args().skip(1).collect::<Vec<String>>();
     let cluster_id = args
@@ -130,7 +140,20 @@ async fn real_main() -> Result<(), StartupError> {
         total_shards,
     };
 
-    if let Err(e) = run(scheme_info, config, http, bot_user, datastore, translations).await {
+    let stats = Arc::new(BotStats::new(&scheme_info));
+
+    let datastore = DataStorage::initalize(&config, Arc::clone(&stats)).await?;
+
+    {
+        info!("Populating command list");
+        ROOT_NODE.all_commands.get("something");
+        info!("Command list populated")
+    }
+
+    // end of the critical failure zone, everything from here on out should be properly wrapped
+    // and handled
+
+    if let Err(e) = run(scheme_info, config, http, bot_user, datastore, translations, stats).await {
         gearbot_error!("Failed to start the bot: {}", e)
     }
 
@@ -144,29 +167,20 @@ async fn run(
     bot_user: CurrentUser,
     datastore: DataStorage,
     translations: Translations,
+    stats: Arc<BotStats>,
 ) -> Result<(), StartupError> {
-    let sharding_scheme = ShardScheme::try_from((
-        scheme_info.cluster_id * scheme_info.shards_per_cluster
-            ..scheme_info.cluster_id * scheme_info.shards_per_cluster + scheme_info.shards_per_cluster,
-        scheme_info.total_shards,
-    ))
-    .unwrap();
-
-    let intents = Intents::GUILDS
-        | Intents::GUILD_MEMBERS
-        | Intents::GUILD_BANS
-        | Intents::GUILD_EMOJIS
-        | Intents::GUILD_INVITES
-        | Intents::GUILD_VOICE_STATES
-        | Intents::GUILD_MESSAGES
-        | Intents::GUILD_MESSAGE_REACTIONS
-        | Intents::DIRECT_MESSAGES
-        | Intents::DIRECT_MESSAGE_REACTIONS;
-
-    let stats = Arc::new(BotStats::new(scheme_info.cluster_id));
-    tokio::spawn(run_metrics_server(Arc::clone(&stats)));
-
-    let cache = Cache::new(scheme_info.cluster_id, Arc::clone(&stats));
+    let sharding_scheme = ShardScheme::try_from((scheme_info.shard_range(), scheme_info.total_shards)).unwrap();
+
+    let intents = config.get_intents()?;
+
+    tokio::spawn(run_metrics_server(Arc::clone(&stats), config.metrics_address));
+
+    let cache = Cache::new(
+        scheme_info.cluster_id,
+        Arc::clone(&stats),
+        config.member_chunking.lazy_threshold,
+        config.max_presences_per_guild,
+    );
 
     let mut cb = Cluster::builder(&config.tokens.discord, intents)
         .shard_scheme(sharding_scheme)
@@ -190,7 +204,13 @@ async fn run(
 
                 cache_pool.delete(&key).await?;
 
-                if (cold_cache.total_shards == scheme_info.total_shards)
+                if cold_cache.is_stale() {
+                    // the frozen cache chunks expire on their own, we get everything fresh from the gateway anyways
+                    gearbot_warn!(
+                        "Found cold resume data from {} seconds ago, too old to resume, identifying instead",
+                        cold_cache.age()
+                    );
+                } else if (cold_cache.total_shards == scheme_info.total_shards)
                     && (cold_cache.shard_count == scheme_info.shards_per_cluster)
                 {
                     let map = cold_cache
@@ -229,19 +249,40 @@ async fn run(
 
     let cluster = cb.build().await?;
 
-    let (sender, receiver) = mpsc::unbounded_channel();
+    let (sender, receiver) = mpsc::channel(config.logpump_capacity);
 
     let context = Arc::new(BotContext::new(
         (cache, cluster, scheme_info),
         (http, bot_user),
         datastore,
         translations,
-        config.global_admins,
+        &config,
         stats,
         sender,
     ));
+    // guilds that were locked down when we stopped still need to be unlocked later on
+    context.restore_raid_modes().await;
+
     let ctx = context.clone();
     let mut _logpump_task = tokio::spawn(logpump::run(ctx, receiver));
+    tokio::spawn(logpump::retry_dead_letters(context.clone()));
+    tokio::spawn(announcements::run(context.clone()));
+    tokio::spawn(emoji_usage::run(context.clone()));
+    tokio::spawn(reaction_counts::run(context.clone()));
+    tokio::spawn(held_deletes::run(context.clone()));
+    tokio::spawn(maintenance::run(context.clone()));
+    tokio::spawn(config_sync::run(context.clone()));
+    tokio::spawn(shard_watchdog::run(context.clone()));
+    if let Some(interval) = config.member_chunking.background_interval {
+        tokio::spawn(member_chunking::run(context.clone(), Duration::from_secs(interval)));
+    }
+    tokio::spawn(status_rotation::run(
+        context.clone(),
+        Duration::from_secs(config.status.interval),
+    ));
+    if scheme_info.cluster_id == 0 {
+        tokio::spawn(logpump::prune_webhooks(context.clone()));
+    }
 
     //establish api connection
     let c = context.clone();
@@ -271,10 +312,17 @@ async fn run(
     while let Some(event) = bot_events.next().await {
         let c = context.clone();
         context.update_stats(event.0, &event.1).await; //this is fine to await, only async for updating shard states, gona be extremely rare something else also has a lock on that
+        let departed = handlers::member_logs::departed_member(&event.1, &context.cache);
+        let previous_names = handlers::name_history::previous_names(&event.1, &context.cache);
         context.cache.update(event.0, &event.1, context.clone()).await; //we are awaiting this because cache needs ot be updated before it's safe to spawn off the handling, to avoid working with stale data
-        tokio::spawn(async {
-            if let Err(e) = handle_event(event, c).await {
-                gearbot_error!("{}", e);
+        tokio::spawn(async move {
+            let (shard_id, kind) = (event.0, HandledEvent::of(&event.1));
+            let start = Instant::now();
+            let result = handle_event(event, departed, previous_names, c.clone()).await;
+            c.stats.event_handled(shard_id, kind, start.elapsed());
+
+            if let Err(e) = result {
+                report_event_error(e, &c).await;
             }
         });
     }
@@ -286,9 +334,41 @@ async fn run(
     Ok(())
 }
 
-async fn handle_event(event: (u64, Event), ctx: Arc<BotContext>) -> Result<(), EventHandlerError> {
-    handlers::modlog::handle_event(event.0, &event.1, ctx.clone()).await?;
-    handlers::general::handle_event(event.0, &event.1, ctx.clone()).await?;
+async fn handle_event(
+    event: (u64, Event),
+    departed: Option<Arc<CachedMember>>,
+    previous_names: Option<PreviousNames>,
+    ctx: Arc<BotContext>,
+) -> Result<(), EventHandlerError> {
+    // automod and anti raid only act on events, their failed actions shouldn't keep the event from being stored
+    isolated(handlers::automod::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::anti_raid::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    // while the database or the discord api is down, the handlers that need it shouldn't keep the others from running
+    skip_if_unavailable(handlers::modlog::handle_event(event.0, &event.1, ctx.clone()).await)?;
+    isolated(
+        handlers::member_logs::handle_event(&event.1, departed, ctx.clone()).await,
+        &ctx,
+    )
+    .await;
+    isolated(handlers::emoji_usage::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(
+        handlers::reaction_counts::handle_event(&event.1, ctx.clone()).await,
+        &ctx,
+    )
+    .await;
+    skip_if_unavailable(handlers::general::handle_event(event.0, &event.1, ctx.clone()).await)?;
+    isolated(handlers::starboard::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::role_menu::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::auto_response::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::first_message::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::welcome_image::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::auto_pin::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(handlers::bot_nickname::handle_event(&event.1, ctx.clone()).await, &ctx).await;
+    isolated(
+        handlers::name_history::handle_event(&event.1, previous_names, ctx.clone()).await,
+        &ctx,
+    )
+    .await;
 
     // Bot stat handling "hooks". This can be converted into a match if we have more stats to register here.
     if let Event::MessageCreate(msg) = &event.1 {
@@ -300,7 +380,38 @@ async fn handle_event(event: (u64, Event), ctx: Arc<BotContext>) -> Result<(), E
     Ok(())
 }
 
-async fn run_metrics_server(stats: Arc<BotStats>) {
+/// Reports the failure of a handler that runs alongside the others, without keeping the rest from handling the event.
+async fn isolated(result: Result<(), EventHandlerError>, ctx: &BotContext) {
+    if let Err(e) = skip_if_unavailable(result) {
+        report_event_error(e, ctx).await;
+    }
+}
+
+async fn report_event_error(e: EventHandlerError, ctx: &BotContext) {
+    if let Some(http_error) = e.http_error() {
+        ctx.track_ratelimit(http_error).await;
+    }
+    ctx.record_event_error().await;
+    gearbot_error!("{}", e);
+}
+
+fn skip_if_unavailable(result: Result<(), EventHandlerError>) -> Result<(), EventHandlerError> {
+    match result {
+        Err(e) if e.is_database_unavailable() => {
+            log::debug!("Skipped an event handler, the database is unavailable: {}", e);
+            Ok(())
+        }
+        Err(e) if e.is_api_unavailable() => {
+            log::debug!("Skipped an event handler, the discord api is failing");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Serves the metrics for prometheus to scrape, these are all atomics so nothing here waits on the rest of the bot.
+async fn run_metrics_server(stats: Arc<BotStats>, addr: SocketAddr) {
+    use hyper::header::CONTENT_TYPE;
     use hyper::service::{make_service_fn, service_fn};
     use hyper::{Body, Response};
 
@@ -313,12 +424,16 @@ async fn run_metrics_server(stats: Arc<BotStats>) {
                 let metric_families = stats.registry.gather();
                 encoder.encode(&metric_families, &mut buffer).unwrap();
 
-                async move { Ok::<_, Infallible>(Response::new(Body::from(buffer))) }
+                let mut response = Response::new(Body::from(buffer));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, encoder.format_type().parse().unwrap());
+                async move { Ok::<_, Infallible>(response) }
             }))
         }
     });
 
-    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 9091));
+    log::info!("Serving metrics on {}", addr);
     let server = hyper::Server::bind(&addr).serve(metric_service);
     if let Err(e) = server.await {
         gearbot_error!("The metrics server failed: {}", e)
@@ -2,27 +2,73 @@ use std::cmp;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use chrono::Duration;
 use lazy_static::lazy_static;
 use log::{debug, info, trace};
+use twilight_model::channel::Message;
 use twilight_model::gateway::payload::MessageCreate;
 use twilight_model::guild::Permissions;
-use twilight_model::id::{GuildId, UserId};
+use twilight_model::id::{ChannelId, GuildId, RoleId, UserId};
 
-use crate::cache::{CachedGuild, CachedMember, CachedUser};
+use crate::cache::{Cache, CachedChannel, CachedGuild, CachedMember, CachedRole, CachedUser};
 use crate::commands::{
-    meta::nodes::{CommandNode, GearBotPermissions},
+    meta::nodes::{CommandGroup, CommandNode, GearBotPermissions},
     ROOT_NODE,
 };
-use crate::core::{BotContext, CommandContext, CommandMessage, GuildConfig};
-use crate::error::{CommandError, EventHandlerError, ParseError};
+use crate::core::logpump::{LogData, LogType};
+use crate::core::{BotContext, CommandContext, CommandMessage, ErrorVerbosity, GuildConfig};
+use crate::error::{ArgumentType, CommandError, EventHandlerError, ParseError};
 use crate::gearbot_error;
-use crate::translation::{FluArgs, GearBotString};
-use crate::utils::{matchers, Emoji};
+use crate::translation::{FluArgs, GearBotString, DEFAULT_LANG};
+use crate::utils::{self, matchers, text, Emoji};
+
+/// Unknown commands further than this from every known name don't get a suggestion.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
 
 lazy_static! {
     static ref BLANK_CONFIG: Arc<GuildConfig> = Arc::new(GuildConfig::default());
 }
 
+/// The cached members of the guild going by the (partial) name, a discriminator means only full names match.
+fn find_members_by_name(
+    guild: &CachedGuild,
+    cache: &Cache,
+    to_search: &str,
+    name: &str,
+    discriminator: Option<&str>,
+) -> Vec<Arc<CachedMember>> {
+    let mut matches = vec![];
+
+    let members = guild.members.read().unwrap();
+    for member in members.values() {
+        // If we have a discriminator, we have a full name, don't accept partials.
+        // note that this does not mean there can only be 1 match as # is valid for nicknames (but not usernames)
+        if let Some(nickname) = &member.nickname {
+            if nickname.starts_with(to_search) {
+                matches.push(Arc::clone(member));
+                // Pass early and don't incur a user lock below.
+                continue;
+            }
+        }
+
+        let user = member.user(cache);
+        match discriminator {
+            Some(discriminator) => {
+                if user.username == name && user.discriminator == discriminator {
+                    matches.push(Arc::clone(member))
+                }
+            }
+            None => {
+                if user.username.starts_with(name) {
+                    matches.push(Arc::clone(member));
+                }
+            }
+        }
+    }
+
+    matches
+}
+
 pub struct Parser {
     pub parts: Vec<String>,
     index: usize,
@@ -73,13 +119,20 @@ impl Parser {
         }
     }
 
-    pub fn get_command(&mut self) -> Vec<Arc<CommandNode>> {
+    /// Resolves the command nodes at the start of the message, command names are all lowercase so
+    /// `case_insensitive` lowercases the input for the lookup only, leaving the arguments untouched.
+    pub fn get_command(&mut self, case_insensitive: bool) -> Vec<Arc<CommandNode>> {
         let mut nodes = vec![];
         let mut to_search = &ROOT_NODE.all_commands;
         while self.index < self.parts.len() {
             let target = &self.parts[self.index];
+            let found = if case_insensitive {
+                to_search.get(&target.to_lowercase())
+            } else {
+                to_search.get(target)
+            };
 
-            match to_search.get(target) {
+            match found {
                 Some(node) => {
                     to_search = &node.sub_nodes;
                     debug!("Found a command node: {}", node.name);
@@ -92,11 +145,71 @@ impl Parser {
         nodes
     }
 
+    /// Points out the closest command name when the one that was used doesn't exist, as long as
+    /// it's close enough to likely be a typo.
+    async fn suggest_command(
+        &self,
+        prefix: &str,
+        message: &Message,
+        case_insensitive: bool,
+    ) -> Result<(), EventHandlerError> {
+        let typed = match self.parts.first() {
+            Some(typed) if case_insensitive => typed.to_lowercase(),
+            Some(typed) => typed.clone(),
+            None => return Ok(()),
+        };
+
+        let (disabled_commands, language) = match message.guild_id {
+            Some(guild_id) => {
+                if !self
+                    .ctx
+                    .get_bot_permissions_for_channel(message.channel_id)
+                    .contains(Permissions::SEND_MESSAGES)
+                {
+                    return Ok(());
+                }
+                let config = self.ctx.get_config(guild_id).await?;
+                (config.disabled_commands.clone(), config.language.clone())
+            }
+            None => (vec![], DEFAULT_LANG),
+        };
+
+        // the longer the name, the more typos we can still recognize it through, short words are left alone
+        let max_distance = (typed.chars().count() / 3).min(MAX_SUGGESTION_DISTANCE);
+        let suggestion = ROOT_NODE
+            .all_commands
+            .iter()
+            .filter(|(_, node)| !(node.can_be_disabled() && disabled_commands.contains(&node.name)))
+            .filter(|(_, node)| message.guild_id.is_some() || node.works_in_dms())
+            .map(|(name, _)| (utils::edit_distance(&typed, name), name))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min();
+
+        if let Some((_, name)) = suggestion {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("suggestion", format!("{}{}", prefix, name))
+                .generate();
+            let translated = self
+                .ctx
+                .translate_with_args(&language, GearBotString::CommandSuggestion, &args);
+            self.ctx
+                .http
+                .create_message(message.channel_id)
+                .content(translated)
+                .unwrap()
+                .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn figure_it_out(
         prefix: &str,
         message: Box<MessageCreate>,
         ctx: Arc<BotContext>,
         shard_id: u64,
+        case_insensitive: bool,
     ) -> Result<(), EventHandlerError> {
         let message = (*message).0;
 
@@ -105,12 +218,12 @@ impl Parser {
         trace!("Parser processing message: {:?}", message.content);
 
         // Parse the message to get the nodes
-        let command_nodes = parser.get_command();
+        let command_nodes = parser.get_command(case_insensitive);
 
         // Is there a valid node to execute?
         let node = match command_nodes.last() {
             Some(node) => node,
-            None => return Ok(()),
+            None => return parser.suggest_command(prefix, &message, case_insensitive).await,
         };
 
         // Assemble the command's name
@@ -123,6 +236,17 @@ impl Parser {
         let ctx = Arc::clone(&parser.ctx);
 
         let channel_id = message.channel_id;
+
+        // checked before the config is loaded, the database might be getting worked on
+        if command_nodes[0].group != CommandGroup::BotAdmin && ctx.is_in_maintenance().await {
+            let args = FluArgs::with_capacity(1)
+                .add("gearwarn", Emoji::Warn.for_chat())
+                .generate();
+            let translated = ctx.translate_with_args(&DEFAULT_LANG, GearBotString::UnderMaintenance, &args);
+            let _ = ctx.http.create_message(channel_id).content(translated).unwrap().await;
+            return Ok(());
+        }
+
         let channel = match ctx.cache.get_channel(channel_id) {
             Some(channel) => channel,
             None => return Err(EventHandlerError::UnknownChannel(channel_id)),
@@ -140,14 +264,29 @@ impl Parser {
                 None => return Err(EventHandlerError::UnknownGuild(message.guild_id.unwrap())),
             };
 
-            let member = match ctx.cache.get_member(&guild.id, &message.author.id) {
+            let member = match ctx.fetch_member(guild.id, message.author.id).await? {
                 Some(member) => member,
                 None => return Err(EventHandlerError::UnknownUser(message.author.id)),
             };
 
-            let config = ctx.get_config(guild.id).await?;
+            let config = match ctx.get_config(guild.id).await {
+                Ok(config) => config,
+                // commands that don't need the guild config keep working with the defaults while the database is down
+                Err(e) if e.is_unavailable() && command_nodes[0].works_in_dms() => Arc::clone(&BLANK_CONFIG),
+                Err(e) if e.is_unavailable() => {
+                    let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+                    let translated = ctx.translate_with_args(&DEFAULT_LANG, GearBotString::DatabaseUnavailable, &args);
+                    let _ = ctx.http.create_message(channel_id).content(translated).unwrap().await;
+                    return Ok(());
+                }
+                Err(e) => return Err(e.into()),
+            };
 
-            let permissions = ctx.get_permissions_for(&guild, &member, &config);
+            let mut permissions = ctx.get_permissions_for(&guild, &member, &config);
+            // guilds can pick who gets to use a command, regardless of what it normally needs
+            if let Some(allowed) = ctx.command_override_allows(&guild, &member, &config, &command_nodes[0]) {
+                permissions.set(node.command_permission, allowed);
+            }
 
             (Some(guild), Some(member), config, permissions)
         } else {
@@ -156,12 +295,7 @@ impl Parser {
             (None, None, Arc::clone(&BLANK_CONFIG), perms)
         };
 
-        // Silently ignore any DMs
-        // TODO: Maybe return an error?
-        let guild = match guild {
-            Some(g) => g,
-            None => return Ok(()),
-        };
+        let is_dm = guild.is_none();
 
         let cmdm = CommandMessage {
             id: message.id,
@@ -177,8 +311,57 @@ impl Parser {
             tts: message.tts,
         };
 
+        let root_node = &command_nodes[0];
+        let disabled = root_node.can_be_disabled() && config.disabled_commands.contains(&root_node.name);
+        let language = config.language.clone();
+        // guilds pick which commands leave a trace in their logs
+        let audit_log = match &guild {
+            Some(guild) if config.logged_commands.contains(&root_node.name) => Some(LogData {
+                log_type: LogType::CommandUsed {
+                    command: cmdm.content.clone(),
+                },
+                guild: guild.id,
+                source_channel: Some(channel_id),
+                source_user: cmdm.author.id,
+                failed_attempts: 0,
+            }),
+            _ => None,
+        };
+
+        let error_verbosity = config.error_verbosity;
+        // the command message id is unique already, and easy to find back in the logs
+        let error_id = cmdm.id;
+        let delete_command = match &guild {
+            Some(guild) if config.delete_command_messages => Some((guild.id, cmdm.id)),
+            _ => None,
+        };
         let context = CommandContext::new(Arc::clone(&ctx), config, cmdm, guild, shard_id, parser, permissions);
 
+        if is_dm && !root_node.works_in_dms() {
+            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+            let _ = context.reply(GearBotString::GuildOnlyCommand, args).await;
+            return Ok(());
+        }
+
+        // the bot operators pulling a command goes over anything the guild configured
+        if root_node.group != CommandGroup::BotAdmin && ctx.is_globally_disabled(&root_node.name).await {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("command", root_node.name.as_str())
+                .generate();
+            let _ = context.reply(GearBotString::CommandGloballyDisabled, args).await;
+            return Ok(());
+        }
+
+        if disabled {
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("command", root_node.name.as_str())
+                .generate();
+            let _ = context.reply(GearBotString::CommandDisabled, args).await;
+            return Ok(());
+        }
+
         if !permissions.contains(node.command_permission) {
             let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
             let _ = context.reply(GearBotString::MissingPermissions, args).await; //ignore result as there is nothing we can do if this fails
@@ -186,7 +369,7 @@ impl Parser {
         }
 
         //check if we can send a reply
-        if !context.bot_has_channel_permissions(Permissions::SEND_MESSAGES) {
+        if !is_dm && !context.bot_has_channel_permissions(Permissions::SEND_MESSAGES) {
             let msg = &context.message;
             info!(
                 "{}#{} ({}) tried to run the {} command in #{} ({}) but I lack send message permissions to execute the command",
@@ -222,20 +405,119 @@ impl Parser {
             return Ok(());
         }
 
+        // better to tell what we are missing up front than to fail halfway through
+        let missing = node.bot_permissions - context.get_bot_channel_permissions();
+        if !is_dm && !missing.is_empty() {
+            let key = if missing.bits().count_ones() == 1 {
+                GearBotString::MissingBotPermission
+            } else {
+                GearBotString::MissingBotPermissions
+            };
+            let args = FluArgs::with_capacity(2)
+                .add("gearno", Emoji::No.for_chat())
+                .add("permissions", utils::format_permissions(missing))
+                .generate();
+            let _ = context.reply(key, args).await;
+            return Ok(());
+        }
+
+        if let Some(cooldown) = node.cooldown.filter(|_| !context.bypasses_cooldowns()) {
+            let command = command_nodes
+                .iter()
+                .map(|node| node.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let author_id = context.message.author.id;
+            if let Some(remaining) = ctx.start_command_cooldown(author_id, &command, cooldown).await {
+                // rounded up, "try again in 0 seconds" isn't very helpful
+                let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                let args = FluArgs::with_capacity(3)
+                    .add("gearno", Emoji::No.for_chat())
+                    .add("command", command)
+                    .add(
+                        "duration",
+                        context.format_duration(chrono::Duration::seconds(seconds as i64), 2),
+                    )
+                    .generate();
+                let _ = context.reply(GearBotString::CommandOnCooldown, args).await;
+                return Ok(());
+            }
+        }
+
         match &node.handler {
             Some(handler) => {
-                if let Err(e) = handler(context).await {
+                let _slot = match ctx.acquire_command_slot().await {
+                    Some(slot) => slot,
+                    None => {
+                        let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+                        let _ = context.reply(GearBotString::CommandsBusy, args).await;
+                        return Ok(());
+                    }
+                };
+
+                let can_delete = context.bot_has_channel_permissions(Permissions::MANAGE_MESSAGES);
+                let result = handler(context).await;
+                if let Some((guild_id, message_id)) = delete_command {
+                    ctx.schedule_command_delete(guild_id, channel_id, message_id, can_delete)
+                        .await;
+                }
+                if let (Ok(_), Some(audit_log)) = (&result, audit_log) {
+                    ctx.log(audit_log);
+                }
+
+                if let Err(e) = result {
+                    if let Some(http_error) = e.http_error() {
+                        ctx.track_ratelimit(http_error).await;
+                    }
                     match e {
-                        CommandError::ParseError(e) => {
-                            ctx.http
-                                .create_message(channel_id)
-                                .content(format!(
-                                    "{} Something went wrong trying to parse that: {}",
-                                    Emoji::No.for_chat(),
-                                    e
-                                ))
-                                .unwrap()
-                                .await?;
+                        e if e.is_database_unavailable() => {
+                            let args = FluArgs::with_capacity(1).add("gearno", Emoji::No.for_chat()).generate();
+                            let translated =
+                                ctx.translate_with_args(&language, GearBotString::DatabaseUnavailable, &args);
+                            ctx.http.create_message(channel_id).content(translated).unwrap().await?;
+                            return Ok(());
+                        }
+                        // failures that came up while parsing are just as unexpected
+                        CommandError::OtherFailure(e) | CommandError::ParseError(ParseError::Other(e)) => {
+                            gearbot_error!("Command error (error id ``{}``, command ``{}``): {}", error_id, name, e);
+                            ctx.record_command_error().await;
+
+                            let mut args = FluArgs::with_capacity(3)
+                                .add("gearbug", Emoji::Bug.for_chat())
+                                .add("error_id", error_id.to_string());
+                            let key = match error_verbosity {
+                                ErrorVerbosity::Generic => GearBotString::CommandFailed,
+                                ErrorVerbosity::Detailed => {
+                                    // has to fit in the reply, the full error is in the logs
+                                    args = args.add("error", text::truncate_with_ellipsis(&e.to_string(), 1000));
+                                    GearBotString::CommandFailedDetailed
+                                }
+                            };
+                            let translated = ctx.translate_with_args(&language, key, &args.generate());
+                            ctx.http.create_message(channel_id).content(translated).unwrap().await?;
+                            return Ok(());
+                        }
+                        CommandError::ParseError(e) => match e.reply() {
+                            Some((key, args)) => {
+                                let translated = ctx.translate_with_args(&language, key, &args);
+                                ctx.http.create_message(channel_id).content(translated).unwrap().await?;
+                            }
+                            None => {
+                                ctx.http
+                                    .create_message(channel_id)
+                                    .content(format!(
+                                        "{} Something went wrong trying to parse that: {}",
+                                        Emoji::No.for_chat(),
+                                        e
+                                    ))
+                                    .unwrap()
+                                    .await?;
+                            }
+                        },
+                        CommandError::Moderation(e) => {
+                            let (key, args) = e.reply();
+                            let translated = ctx.translate_with_args(&language, key, &args);
+                            ctx.http.create_message(channel_id).content(translated).unwrap().await?;
                         }
                         CommandError::NoDM | CommandError::InvalidPermissions => {
                             ctx.http
@@ -244,15 +526,6 @@ impl Parser {
                                 .unwrap()
                                 .await?;
                         }
-                        CommandError::OtherFailure(e) => {
-                            ctx.http.create_message(channel_id)
-                                .content(format!("{} Something went very wrong trying to execute that command, please try again later or report this on the support server {}", Emoji::Bug.for_chat(), Emoji::Bug.for_chat())).unwrap()
-                                .await?;
-
-                            //TODO: better logging
-                            gearbot_error!("Command error: {}", e);
-                            return Ok(());
-                        }
                     }
                 }
 
@@ -286,14 +559,88 @@ impl Parser {
         self.index < self.parts.len()
     }
 
-    fn get_member(&mut self) -> Result<Arc<CachedMember>, ParseError> {
-        let cache = &Arc::clone(&self.ctx).cache;
+    /// Parses what comes next as a whole number
+    pub fn get_i64(&mut self) -> Result<i64, ParseError> {
+        let start = self.index;
+        let result = self.get_next().and_then(|input| {
+            input
+                .parse()
+                .map_err(|_| ParseError::InvalidArgument(ArgumentType::Number, input.to_string()))
+        });
+        self.restore_on_error(start, result)
+    }
+
+    /// Parses what comes next as a discord id, like the id of a message or guild
+    pub fn get_id(&mut self) -> Result<u64, ParseError> {
+        let start = self.index;
+        let result = self.get_next().and_then(|input| {
+            input
+                .parse()
+                .map_err(|_| ParseError::InvalidArgument(ArgumentType::Number, input.to_string()))
+        });
+        self.restore_on_error(start, result)
+    }
+
+    /// Parses what comes next as the number of a case, note or other listed entry, with or without the ``#`` these
+    /// are shown with
+    pub fn get_entry_number(&mut self) -> Result<i32, ParseError> {
+        let start = self.index;
+        let result = self.get_next().and_then(|input| {
+            input
+                .trim_start_matches('#')
+                .parse()
+                .map_err(|_| ParseError::InvalidArgument(ArgumentType::Number, input.to_string()))
+        });
+        self.restore_on_error(start, result)
+    }
+
+    /// Parses what comes next as a duration, like ``1d12h``
+    pub fn get_duration(&mut self) -> Result<Duration, ParseError> {
+        let start = self.index;
+        let result = self.get_next().and_then(Parser::parse_duration);
+        self.restore_on_error(start, result)
+    }
+
+    /// Reads a duration out of part of an argument, failing the same way [get_duration](Parser::get_duration) does
+    pub fn parse_duration(input: &str) -> Result<Duration, ParseError> {
+        utils::parse_duration(input)
+            .ok_or_else(|| ParseError::InvalidArgument(ArgumentType::Duration, input.to_string()))
+    }
+
+    /// The typed getters leave the cursor on the argument that failed to parse, so [peek](Parser::peek) shows which
+    /// one it was.
+    fn restore_on_error<T>(&mut self, start: usize, result: Result<T, ParseError>) -> Result<T, ParseError> {
+        if result.is_err() {
+            self.index = start;
+        }
+        result
+    }
+
+    /// Parses what comes next as a member of the current guild, either mentioned, by id or by name
+    pub async fn get_member(&mut self) -> Result<Arc<CachedMember>, ParseError> {
+        let start = self.index;
+        let result = self.parse_member().await;
+        self.restore_on_error(start, result)
+    }
+
+    async fn parse_member(&mut self) -> Result<Arc<CachedMember>, ParseError> {
+        let ctx = Arc::clone(&self.ctx);
+        let cache = &ctx.cache;
         let guild = self.get_guild()?;
 
         match self.get_affected_user()? {
-            Some(id) => cache
-                .get_member(&guild.id, &UserId(id))
-                .ok_or(ParseError::MemberNotFoundById(id)),
+            Some(id) => match self.ctx.fetch_member(guild.id, UserId(id)).await {
+                Ok(member) => member.ok_or(ParseError::MemberNotFoundById(id)),
+                Err(e) => {
+                    log::warn!(
+                        "Failed to fetch member {} of lazily cached guild {}: {}",
+                        id,
+                        guild.id,
+                        e
+                    );
+                    Err(ParseError::MemberNotFoundById(id))
+                }
+            },
             None => {
                 // Might be a (partial) name
                 let input = self.get_next()?;
@@ -306,37 +653,23 @@ impl Parser {
                     None => (to_search, None),
                 };
 
-                let mut matches = vec![];
-
-                let members = guild.members.read().unwrap();
-                for member in members.values() {
-                    // If we have a discriminator, we have a full name, don't accept partials.
-                    // note that this does not mean there can only be 1 match as # is valid for nicknames (but not usernames)
-                    if let Some(nickname) = &member.nickname {
-                        if nickname.starts_with(to_search) {
-                            matches.push(member);
-                            // Pass early and don't incur a user lock below.
-                            continue;
-                        }
-                    }
+                let mut matches = find_members_by_name(&guild, cache, to_search, name, discriminator);
 
-                    let user = member.user(cache);
-                    match discriminator {
-                        Some(discriminator) => {
-                            if user.username == name && user.discriminator == discriminator {
-                                matches.push(member)
-                            }
-                        }
-                        None => {
-                            if user.username.starts_with(name) {
-                                matches.push(member);
+                // not everyone is in the cache yet, ask discord for the ones with that name
+                if matches.is_empty() && guild.is_lazily_cached() {
+                    match ctx.request_members(guild.id, name, 100).await {
+                        Ok(members) => {
+                            for member in &members {
+                                cache.insert_member(&guild, member);
                             }
+                            matches = find_members_by_name(&guild, cache, to_search, name, discriminator);
                         }
+                        Err(e) => log::warn!("Failed to request members named {} of guild {}: {}", name, guild.id, e),
                     }
                 }
 
                 match matches.len().cmp(&1) {
-                    cmp::Ordering::Equal => Ok(Arc::clone(matches.remove(0))),
+                    cmp::Ordering::Equal => Ok(matches.remove(0)),
                     cmp::Ordering::Greater => Err(ParseError::MultipleMembersByName(input.to_string())),
                     cmp::Ordering::Less => Err(ParseError::MemberNotFoundByName(input.to_string())),
                 }
@@ -372,15 +705,75 @@ impl Parser {
     }
 
     /// Parses what comes next as discord user
-    async fn get_user(&mut self) -> Result<Arc<CachedUser>, ParseError> {
-        match self.get_affected_user()? {
-            Some(id) => Ok(self.ctx.get_user(UserId(id)).await?),
-            None => {
+    pub async fn get_user(&mut self) -> Result<Arc<CachedUser>, ParseError> {
+        let start = self.index;
+        let result = match self.get_affected_user() {
+            Ok(Some(id)) => self.ctx.get_user(UserId(id)).await,
+            Ok(None) if self.guild_id.is_some() => {
                 // reverse our get_next and make the member getter deal with it
                 self.index -= 1;
-                Ok(self.get_member()?.user(&self.ctx.cache))
+                self.parse_member().await.map(|member| member.user(&self.ctx.cache))
             }
+            // no guild to find them by name in
+            Ok(None) => Err(ParseError::InvalidArgument(
+                ArgumentType::User,
+                self.parts[start].clone(),
+            )),
+            Err(e) => Err(e),
+        };
+        self.restore_on_error(start, result)
+    }
+
+    /// Parses what comes next as a channel of the current guild, either mentioned or by id
+    pub fn get_channel(&mut self) -> Result<Arc<CachedChannel>, ParseError> {
+        let start = self.index;
+        let result = self.parse_channel();
+        self.restore_on_error(start, result)
+    }
+
+    fn parse_channel(&mut self) -> Result<Arc<CachedChannel>, ParseError> {
+        let input = self.get_next()?;
+        let channel_id = match matchers::get_channel_mention(input) {
+            Some(id) => id,
+            None => input
+                .parse()
+                .map_err(|_| ParseError::InvalidArgument(ArgumentType::Channel, input.to_string()))?,
+        };
+
+        let guild = self.get_guild()?;
+        let channel = guild
+            .channels
+            .read()
+            .expect("Guild inner channels cache got poisoned!")
+            .get(&ChannelId(channel_id))
+            .cloned();
+
+        channel.ok_or(ParseError::UnknownChannel(channel_id))
+    }
+
+    /// Parses what comes next as a role of the current guild, either mentioned, by id or by its exact name
+    pub fn get_role(&mut self) -> Result<Arc<CachedRole>, ParseError> {
+        let start = self.index;
+        let result = self.parse_role();
+        self.restore_on_error(start, result)
+    }
+
+    fn parse_role(&mut self) -> Result<Arc<CachedRole>, ParseError> {
+        let input = self.get_next()?.to_string();
+        let guild = self.get_guild()?;
+
+        let role_id = matchers::get_role_mention(&input).or_else(|| input.parse().ok());
+        if let Some(role) = role_id.and_then(|id| guild.get_role(&RoleId(id))) {
+            return Ok(role);
         }
+
+        let roles = guild.roles.read().expect("Guild inner roles cache got poisoned!");
+        let role = roles
+            .values()
+            .find(|role| role.name.eq_ignore_ascii_case(&input))
+            .cloned();
+
+        role.ok_or(ParseError::UnknownRole(input))
     }
 
     pub async fn get_user_or(&mut self, alternative: Arc<CachedUser>) -> Result<Arc<CachedUser>, ParseError> {
@@ -391,9 +784,9 @@ impl Parser {
         }
     }
 
-    pub fn get_member_or(&mut self, alternative: Arc<CachedMember>) -> Result<Arc<CachedMember>, ParseError> {
+    pub async fn get_member_or(&mut self, alternative: Arc<CachedMember>) -> Result<Arc<CachedMember>, ParseError> {
         if self.has_next() {
-            Ok(self.get_member()?)
+            Ok(self.get_member().await?)
         } else {
             Ok(alternative)
         }
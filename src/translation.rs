@@ -1,6 +1,8 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fs;
+use std::io;
 use std::sync::Arc;
 
 use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentError, FluentResource, FluentValue};
@@ -9,15 +11,17 @@ use unic_langid::{langid, LanguageIdentifier};
 use crate::gearbot_warn;
 
 const TRANSLATION_DIR: &str = "./lang";
-const FAILED_TRANSLATE_FALLBACK_MSG: &str =
-    "A translation error occured and no fallback could be found! Something may be wrong with the guild configuration!";
 
-/// The default language to fall back to if a string can't be translated in the requested language.
-/// This is also the language that new guild configs will default to.
+/// The language new guild configs default to, and where strings fall back to when no other fallback is configured.
+/// This is also the reference every other language gets validated against.
 pub const DEFAULT_LANG: LanguageIdentifier = langid!("en_US");
 
 /// The transations for all languages that the bot can handle.
-pub struct Translations(HashMap<LanguageIdentifier, Arc<FluentBundle<FluentResource>>>);
+pub struct Translations {
+    bundles: HashMap<LanguageIdentifier, Arc<FluentBundle<FluentResource>>>,
+    /// Where strings that aren't translated (yet) in the requested language are looked up next.
+    fallback: LanguageIdentifier,
+}
 
 pub struct FluArgs<'a>(FluentArgs<'a>);
 
@@ -43,39 +47,7 @@ impl Translations {
     /// Retreives a string key to use when sending a message to chat that *does not* require arguments and can be sent as fetched with no
     /// further modifications.
     pub fn get_text_plain(&self, lang_key: &LanguageIdentifier, string_key: GearBotString) -> Cow<str> {
-        // TODO: See how well this will work out in practice with unwrapping
-        let lang_bundle = self.0.get(lang_key).unwrap();
-
-        if let Some(expected_msg) = lang_bundle.get_message(string_key.as_str()) {
-            let mut errors = Vec::new();
-
-            let pattern = expected_msg.value.unwrap();
-
-            let value = lang_bundle.format_pattern(pattern, None, &mut errors);
-
-            handle_translation_error(&errors, string_key, false);
-
-            value
-        } else {
-            // If we can't find the key in the expected language, fallback to English
-            let fallback_bundle = self.0.get(&DEFAULT_LANG).unwrap();
-
-            if let Some(fallback_msg) = fallback_bundle.get_message(string_key.as_str()) {
-                let mut errors = Vec::new();
-
-                let pattern = fallback_msg.value.unwrap();
-
-                let value = lang_bundle.format_pattern(pattern, None, &mut errors);
-
-                handle_translation_error(&errors, string_key, true);
-
-                value
-            } else {
-                // Something really went wrong, error in chat and the logs
-                gearbot_warn!("{}", FAILED_TRANSLATE_FALLBACK_MSG);
-                Cow::Borrowed(FAILED_TRANSLATE_FALLBACK_MSG)
-            }
-        }
+        self.format(lang_key, string_key, None)
     }
 
     /// Retreives a string key to use when sending a message to chat that *does* require arguments and must have some fields
@@ -88,52 +60,82 @@ impl Translations {
         string_key: GearBotString,
         args: &'a FluentArgs<'a>,
     ) -> Cow<'a, str> {
-        let lang_bundle = self.0.get(lang_key).unwrap();
-
-        if let Some(expected_msg) = lang_bundle.get_message(string_key.as_str()) {
-            let mut errors = Vec::new();
+        self.format(lang_key, string_key, Some(args))
+    }
 
-            let pattern = expected_msg.value.unwrap();
+    /// Formats the string in the first language of the fallback chain that has it, falling back to the key itself
+    /// when not even the fallback language knows it.
+    fn format<'a>(
+        &'a self,
+        lang_key: &LanguageIdentifier,
+        string_key: GearBotString,
+        args: Option<&'a FluentArgs<'a>>,
+    ) -> Cow<'a, str> {
+        for lang in self.fallback_chain(lang_key) {
+            let bundle = match self.bundles.get(&lang) {
+                Some(bundle) => bundle,
+                None => continue,
+            };
 
-            let value = lang_bundle.format_pattern(pattern, Some(args), &mut errors);
+            if let Some(pattern) = bundle.get_message(string_key.as_str()).and_then(|msg| msg.value) {
+                let mut errors = Vec::new();
 
-            handle_translation_error(&errors, string_key, false);
+                let value = bundle.format_pattern(pattern, args, &mut errors);
 
-            value
-        } else {
-            // If we can't find the key in the expected language, fallback to English
-            let fallback_bundle = self.0.get(&DEFAULT_LANG).unwrap();
+                handle_translation_error(&errors, string_key, &lang, &lang != lang_key);
 
-            if let Some(fallback_msg) = fallback_bundle.get_message(string_key.as_str()) {
-                let mut errors = Vec::new();
+                return value;
+            }
+        }
 
-                let pattern = fallback_msg.value.unwrap();
+        // Something really went wrong, error in chat and the logs
+        gearbot_warn!(
+            "The **``{}``** key could not be found for ``{}`` nor any of its fallbacks!",
+            string_key.as_str(),
+            lang_key
+        );
+        Cow::Borrowed(string_key.as_str())
+    }
 
-                let value = lang_bundle.format_pattern(pattern, Some(args), &mut errors);
+    /// The languages to try in order when looking up a string: the language itself, the language without any
+    /// subtags (so ``pt-BR`` goes to ``pt``) and finally the fallback language.
+    fn fallback_chain(&self, lang: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+        let mut chain = vec![lang.clone()];
 
-                handle_translation_error(&errors, string_key, true);
+        let base = LanguageIdentifier::from_parts(lang.language, None, None, &[]);
+        if !chain.contains(&base) {
+            chain.push(base);
+        }
 
-                value
-            } else {
-                // Something really went wrong, error in chat and the logs
-                gearbot_warn!("{}", FAILED_TRANSLATE_FALLBACK_MSG);
-                Cow::Borrowed(FAILED_TRANSLATE_FALLBACK_MSG)
-            }
+        if !chain.contains(&self.fallback) {
+            chain.push(self.fallback.clone());
         }
+
+        chain
     }
 
     pub fn get_translator(&self, lang: &LanguageIdentifier) -> Arc<FluentBundle<FluentResource>> {
-        Arc::clone(self.0.get(lang).unwrap())
+        Arc::clone(self.bundles.get(lang).unwrap())
+    }
+
+    /// The languages there are translations for.
+    pub fn languages(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+        self.bundles.keys()
+    }
+
+    /// The language strings fall back to when they aren't translated.
+    pub fn fallback(&self) -> &LanguageIdentifier {
+        &self.fallback
     }
 }
 
-fn handle_translation_error(errors: &[FluentError], key: GearBotString, is_fallback: bool) {
+fn handle_translation_error(errors: &[FluentError], key: GearBotString, lang: &LanguageIdentifier, is_fallback: bool) {
     for error in errors {
         if is_fallback {
             gearbot_warn!(
                 "A translation error occured and had to fallback to '{}' while trying to translate the **``{}``** key: ``{:?}``",
+                lang,
                 key.as_str(),
-                DEFAULT_LANG,
                 error
             );
         } else {
@@ -149,34 +151,505 @@ fn handle_translation_error(errors: &[FluentError], key: GearBotString, is_fallb
 // This allows us to take full advantage of the type system to make sure that a key always exists in an
 // ergonomic way instead of checking a bunch of options.
 /// This is where *all* of the different things Gearbot can say should go.
+#[derive(Clone, Copy)]
 pub enum GearBotString {
     // Basic commands
     PingPong,
     CoinflipDefault,
     CoinflipYes,
     CoinflipNo,
+    SnowflakeInfo,
+    SnowflakeInvalid,
+    JumboInvalid,
+    HelpOverview,
+    HelpGroup,
+    HelpNothingAvailable,
+    HelpUnknownCommand,
+    HelpCommand,
+    HelpNoDescription,
+    HelpAliases,
+    HelpUsage,
+    HelpSubcommands,
+    HelpCooldown,
+    HelpGuildOnly,
+    AboutDescription,
+    CoinflipDescription,
+    HelpDescription,
+    JumboDescription,
+    PingDescription,
+    QuoteDescription,
+    SnowflakeDescription,
+    UidDescription,
+    HelpCommandUsage,
+    JumboUsage,
+    QuoteUsage,
+    SnowflakeUsage,
+    UidUsage,
+    TranscriptDescription,
+    TranscriptUsage,
+    AutomodTestDescription,
+    AutomodTestUsage,
+    ExportHistoryDescription,
+    ExportHistoryUsage,
+    CoinflipUsage,
+    ConfigDescription,
+    ConfigGetDescription,
+    ConfigGetPrettyDescription,
+    ConfigSetDescription,
+    ConfigSetUsage,
+    ConfigResetDescription,
+    ConfigColorDescription,
+    ConfigColorUsage,
+    ConfigFooterDescription,
+    ConfigFooterUsage,
+    ConfigFooterIconDescription,
+    ConfigFooterIconUsage,
+    ConfigTimezoneDescription,
+    ConfigTimezoneUsage,
+    ConfigCleanupDescription,
+    ConfigCleanupUsage,
+    ConfigNicknameDescription,
+    ConfigNicknameUsage,
+    ConfigPlaintextDescription,
+    ConfigPlaintextUsage,
+    ConfigQuietDescription,
+    ConfigQuietUsage,
+    ConfigDeleteCommandsDescription,
+    ConfigDeleteCommandsUsage,
+    ConfigErrorsDescription,
+    ConfigErrorsUsage,
+    ConfigSystemMessagesDescription,
+    ConfigSystemMessagesUsage,
+    ConfigContentStorageDescription,
+    ConfigContentStorageUsage,
+    ConfigWelcomeImageDescription,
+    ConfigWelcomeImageUsage,
+    ConfigWelcomeBackgroundDescription,
+    ConfigWelcomeBackgroundUsage,
+    ConfigWelcomeColorDescription,
+    ConfigWelcomeColorUsage,
+    ConfigStorageExcludeDescription,
+    ConfigStorageExcludeUsage,
+    ConfigRoleMenuMessagesDescription,
+    ConfigRoleMenuMessagesUsage,
+    ConfigDeleteGraceDescription,
+    ConfigDeleteGraceUsage,
+    ConfigLogCommandDescription,
+    ConfigLogCommandUsage,
+    ConfigCommandLevelDescription,
+    ConfigCommandLevelUsage,
+    ConfigAutomodOrderDescription,
+    ConfigAutomodOrderUsage,
+    ConfigCooldownBypassDescription,
+    ConfigCooldownBypassUsage,
+    ConfigCommandChannelsDescription,
+    ConfigCommandChannelsUsage,
+    ConfigJoinLogDescription,
+    ConfigJoinLogUsage,
+    ConfigMessageLogDescription,
+    ConfigMessageLogUsage,
+    ConfigExportDescription,
+    ConfigImportDescription,
+    ConfigImportUsage,
+    AnnouncementDescription,
+    AnnouncementAddDescription,
+    AnnouncementAddUsage,
+    AnnouncementListDescription,
+    AnnouncementRemoveDescription,
+    AnnouncementRemoveUsage,
+    AutoResponseDescription,
+    AutoResponseAddDescription,
+    AutoResponseAddUsage,
+    AutoResponseListDescription,
+    AutoResponseRemoveDescription,
+    AutoResponseRemoveUsage,
+    RoleMenuDescription,
+    RoleMenuCreateDescription,
+    RoleMenuCreateUsage,
+    RoleMenuListDescription,
+    RoleMenuRemoveDescription,
+    RoleMenuRemoveUsage,
+    DisableDescription,
+    DisableUsage,
+    EnableDescription,
+    EnableUsage,
+    ImportHistoryDescription,
+    SimulateJoinDescription,
+    SimulateJoinUsage,
+    UserinfoDescription,
+    UserinfoUsage,
+    PurgeDescription,
+    PurgeUsage,
+    PurgeUntilDescription,
+    PurgeUntilUsage,
+    DelwarnDescription,
+    DelwarnUsage,
+    ReasonDescription,
+    ReasonUsage,
+    ClearWarningsDescription,
+    ClearWarningsUsage,
+    AddNoteDescription,
+    AddNoteUsage,
+    NotesDescription,
+    NotesUsage,
+    DelNoteDescription,
+    DelNoteUsage,
+    AltsDescription,
+    AltsUsage,
+    NamesDescription,
+    NamesUsage,
+    ModStatsDescription,
+    ModStatsUsage,
+    MassBanDescription,
+    MassBanUsage,
+    MassUnbanDescription,
+    MassUnbanUsage,
+    PruneDescription,
+    PruneUsage,
+    RaidModeDescription,
+    RaidModeUsage,
+    TimeoutDescription,
+    TimeoutUsage,
+    UntimeoutDescription,
+    UntimeoutUsage,
+    ActivityDescription,
+    ActivityUsage,
+    ReactionsDescription,
+    ReactionsUsage,
+    StatsDescription,
+    StatsAttachmentsDescription,
+    CheckDescription,
+    CheckCacheDescription,
+    CacheStatsDescription,
+    CacheStatsUsage,
+    ClearCacheDescription,
+    ClearCacheUsage,
+    MaintenanceDescription,
+    MaintenanceUsage,
+    KillswitchDescription,
+    KillswitchDisableDescription,
+    KillswitchDisableUsage,
+    KillswitchEnableDescription,
+    KillswitchEnableUsage,
+    KillswitchListDescription,
+    ReloadLoggingDescription,
+    TestWebhooksDescription,
+    SetAvatarDescription,
+    SetUsernameDescription,
+    SetUsernameUsage,
+    RotateKeysDescription,
+    RotateGuildKeyDescription,
+    RotateGuildKeyUsage,
+    VerifyMessagesDescription,
+    VerifyMessagesUsage,
+    TranslationsDescription,
+    TranslationsUsage,
+    ReloadTranslationsDescription,
+    WebhookDescription,
+    WebhookUsage,
+    ShardsDescription,
+    HealthDescription,
+    RedisTestDescription,
+    PermsDescription,
+    PermsUsage,
+    TestDescription,
+    EmojiDescription,
+    EmojiListDescription,
+    EmojiStatsDescription,
+    OnlineDescription,
     UserinfoHeader,
     UserinfoNoRoles,
-    AboutDescription,
+    UserinfoNotes,
+    UserinfoStatus,
+    UserinfoStatusActivity,
+    AboutCommandDescription,
     QuoteNotFound,
+    QuoteContentNotStored,
 
     EmojiPageHeader,
     EmojiOverviewHeader,
     EmojiInfo,
+    EmojiStatsHeader,
+    EmojiStatsMostUsed,
+    EmojiStatsLeastUsed,
+    EmojiStatsNoEmoji,
+    OnlineCount,
+    OnlineNotTracked,
+    MentionPrefix,
 
     //General logs (Text)
     CommandUsedText,
+    MessagesBulkDeletedText,
+    RaidModeEnabledText,
+    RaidModeDisabledText,
+    MassBanText,
+    MassUnbanText,
+    ContentNotStored,
 
     //General logs (embed)
     CommandUsedEmbed,
     CommandUsedFooter,
+    MessagesBulkDeletedEmbed,
+    MessagesBulkDeletedFooter,
+    RaidModeEnabledEmbed,
+    RaidModeEnabledFooter,
+    RaidModeDisabledEmbed,
+    RaidModeDisabledFooter,
+    MassBanEmbed,
+    MassBanFooter,
+    MassUnbanEmbed,
+    MassUnbanFooter,
+    MemberTimedOutText,
+    MemberTimedOutEmbed,
+    MemberTimedOutFooter,
+    MemberTimeoutRemovedText,
+    MemberTimeoutRemovedEmbed,
+    MemberTimeoutRemovedFooter,
+    MessageDeletedText,
+    MessageDeletedEmbed,
+    MessageDeletedFooter,
+    DeletedAttachmentKept,
+    DeletedAttachmentNotKept,
+    MessageEditedText,
+    MessageEditedEmbed,
+    MessageEditedFooter,
+    NewAccountKickedText,
+    NewAccountKickedEmbed,
+    NewAccountRestrictedText,
+    NewAccountRestrictedEmbed,
+    NewAccountGatedFooter,
+    MembersPrunedText,
+    MembersPrunedEmbed,
+    MembersPrunedFooter,
+    SpamReasonDuplicates,
+    SpamReasonMentions,
+    SpamReasonRate,
+    SpamReasonCrossChannel,
+    SpamReasonBlockedAttachment,
+    SpamReasonScanFlagged,
+    SpamReasonScanFailed,
+    SpamReasonInvite,
+    SpamReasonLink,
+    SpamActionDelete,
+    SpamActionWarn,
+    SpamActionMute,
+    SpamActionAlert,
+    SpamActionBan,
+    SpamDetectedText,
+    SpamDetectedEmbed,
+    SpamDetectedFooter,
+    AttachmentScannedText,
+    AttachmentScannedEmbed,
+    AttachmentScannedFooter,
+    ScanOutcomeDelete,
+    ScanOutcomeAlert,
+    ScanOutcomeBan,
+    ScanOutcomeUnscanned,
+    MemberJoinedText,
+    MemberJoinedEmbed,
+    MemberJoinedFooter,
+    MemberLeftText,
+    MemberLeftEmbed,
+    MemberLeftFooter,
+    MemberLeftNoRoles,
+    MemberLeftUnknownDuration,
+    RoleMenusCleanedUpText,
+    RoleMenusCleanedUpEmbed,
+    RoleMenusCleanedUpFooter,
 
     //Errors
     MissingPermissions,
+    MissingBotPermissions,
+    MissingBotPermission,
+    CommandDisabled,
+    CommandGloballyDisabled,
+    GuildOnlyCommand,
+    ContentStorageDisabled,
+    CommandSuggestion,
+    UnderMaintenance,
+    OutputAsFile,
+    OutputTooLarge,
+    OutputNoAttachPermission,
+    BulkFailureReason,
+    BulkOtherFailures,
+    DatabaseUnavailable,
+    CommandsBusy,
+    CommandOnCooldown,
+    CommandFailed,
+    CommandFailedDetailed,
 
     //DM error strings
     UnableToReply,
     UnableToReplyForManager,
+
+    //Automod
+    AutomodBlockedAttachment,
+    NewAccountKickedNotice,
+    NewAccountRestrictedNotice,
+    AutomodSpamWarning,
+    AutomodLinkNotice,
+
+    //Moderation
+    PurgeDone,
+    PurgeDoneSkipped,
+    PurgeDryRun,
+    PurgeDryRunSent,
+    PurgeArchiveTranscript,
+    PurgeArchiveSent,
+    PurgeArchiveDmFailed,
+    PurgeFilterInvalid,
+    PurgeFilterTimeout,
+    ReactionsHeader,
+    ReactionsEmpty,
+    AltsHeader,
+    AltsEntry,
+    AltsEmpty,
+    NamesHeader,
+    NamesUsernames,
+    NamesNicknames,
+    NamesEntry,
+    NamesNoNickname,
+    NamesEmpty,
+    ModStatsTitle,
+    ModStatsEntry,
+    ModStatsEmpty,
+    PruneInvalidDays,
+    PruneNothing,
+    PrunePreview,
+    PruneNotPending,
+    PruneDone,
+    WarningNotFound,
+    WarningRemoved,
+    WarningsCleared,
+    ReasonCaseNotFound,
+    ReasonNotAllowed,
+    ReasonUpdated,
+    RaidModeOn,
+    RaidModeOff,
+    RaidModeAlreadyOn,
+    RaidModeAlreadyOff,
+    NoteAdded,
+    NoNotes,
+    NotesHeader,
+    NoteRemoved,
+    NoteNotFound,
+    AttachmentStats,
+    MassActionNoTargets,
+    MassActionTooManyTargets,
+    MassActionAttachmentInvalid,
+    MassActionProgress,
+    MassBanDone,
+    MassUnbanDone,
+    ModReasonRequired,
+    HierarchyTargetIsOwner,
+    HierarchyAuthorTooLow,
+    HierarchyBotTooLow,
+    ModerationBotMissingPermissions,
+    ModerationTargetNotFound,
+    ModerationRefused,
+    ParseMissingArgument,
+    ParseExpectedNumber,
+    ParseExpectedDuration,
+    ParseExpectedUser,
+    ParseExpectedChannel,
+    ParseExpectedRole,
+    TimeoutTooLong,
+    TimeoutApplied,
+    TimeoutRemoved,
+    ActivityHeader,
+    ActivityEmpty,
+    ActivityDay,
+    ActivityWeek,
+    NoReasonGiven,
+    MessageLogChannels,
+    NoMessageLogChannel,
+    MessageLogChannelSet,
+    LogChannelNoAccess,
+    MessageLogChannelDisabled,
+    JoinLogChannels,
+    NoJoinLogChannel,
+    JoinLogChannelSet,
+    JoinLogChannelDisabled,
+    TranscriptInvalidWindow,
+    TranscriptEmpty,
+    TranscriptTruncated,
+    TranscriptSizeTruncated,
+    TranscriptProgress,
+    TranscriptDone,
+
+    //Guild admin
+    AnnouncementAdded,
+    AnnouncementInvalidCron,
+    AnnouncementLimitReached,
+    NoAnnouncements,
+    AnnouncementsHeader,
+    AnnouncementRemoved,
+    AnnouncementNotFound,
+    UnknownCommand,
+    CommandCannotBeDisabled,
+    CommandAlreadyDisabled,
+    CommandNotDisabled,
+    CommandNowDisabled,
+    CommandNowEnabled,
+    AutoResponseAdded,
+    AutoResponseMissingSeparator,
+    AutoResponseLimitReached,
+    NoAutoResponses,
+    AutoResponsesHeader,
+    AutoResponseRemoved,
+    AutoResponseNotFound,
+    RoleMenuTooManyOptions,
+    RoleMenuDuplicateEmoji,
+    RoleMenuUnassignableRole,
+    RoleMenuRoleTooHigh,
+    RoleMenuInvalidEmoji,
+    RoleMenuTitle,
+    RoleMenuCreated,
+    NoRoleMenus,
+    RoleMenusHeader,
+    RoleMenuRemoved,
+    RoleMenuNotFound,
+    ConfigImportInvalid,
+    ConfigImported,
+    ConfigImportedWithSkips,
+    HistoryImportInvalid,
+    HistoryImportTooLarge,
+    HistoryImported,
+    HistoryImportRejectedEntry,
+    HistoryExported,
+    HistoryExportEmpty,
+    HistoryExportTooLarge,
+    AutomodTestNoMessages,
+    AutomodTestResult,
+    SimulateJoinResult,
+    SimulateJoinLogged,
+    SimulateJoinNotLogged,
+    SimulateJoinBot,
+    SimulateJoinGatePassed,
+    SimulateJoinGateKick,
+    SimulateJoinGateRole,
+    SimulateJoinMissingPermission,
+    SimulateJoinRaidKick,
+    SimulateJoinRaidAllowed,
+    SimulateJoinGreeting,
+    SimulateJoinGreetingReaction,
+    SimulateJoinWelcomeImage,
+
+    //Starboard
+    StarboardPost,
+    StarboardJumpLink,
+
+    //Welcome images
+    WelcomeImageMemberNumber,
+
+    //Durations
+    DurationYears,
+    DurationMonths,
+    DurationWeeks,
+    DurationDays,
+    DurationHours,
+    DurationMinutes,
+    DurationSeconds,
 }
 
 impl GearBotString {
@@ -199,6 +672,464 @@ impl GearBotString {
             GearBotString::CommandUsedText => "command_used_text",
             GearBotString::CommandUsedEmbed => "command_used_embed",
             GearBotString::CommandUsedFooter => "command_used_footer",
+            GearBotString::AutomodBlockedAttachment => "automod__blocked_attachment",
+            GearBotString::MessagesBulkDeletedText => "messages_bulk_deleted_text",
+            GearBotString::MessagesBulkDeletedEmbed => "messages_bulk_deleted_embed",
+            GearBotString::MessagesBulkDeletedFooter => "messages_bulk_deleted_footer",
+            GearBotString::PurgeDone => "moderation__purge_done",
+            GearBotString::PurgeDoneSkipped => "moderation__purge_done_skipped",
+            GearBotString::WarningNotFound => "moderation__warning_not_found",
+            GearBotString::WarningRemoved => "moderation__warning_removed",
+            GearBotString::WarningsCleared => "moderation__warnings_cleared",
+            GearBotString::OutputAsFile => "errors_output_as_file",
+            GearBotString::OutputTooLarge => "errors_output_too_large",
+            GearBotString::OutputNoAttachPermission => "errors_output_no_attach_permission",
+            GearBotString::MentionPrefix => "basic__mention_prefix",
+            GearBotString::RaidModeEnabledText => "raid_mode_enabled_text",
+            GearBotString::RaidModeDisabledText => "raid_mode_disabled_text",
+            GearBotString::RaidModeEnabledEmbed => "raid_mode_enabled_embed",
+            GearBotString::RaidModeEnabledFooter => "raid_mode_enabled_footer",
+            GearBotString::RaidModeDisabledEmbed => "raid_mode_disabled_embed",
+            GearBotString::RaidModeDisabledFooter => "raid_mode_disabled_footer",
+            GearBotString::RaidModeOn => "moderation__raid_mode_on",
+            GearBotString::RaidModeOff => "moderation__raid_mode_off",
+            GearBotString::RaidModeAlreadyOn => "moderation__raid_mode_already_on",
+            GearBotString::RaidModeAlreadyOff => "moderation__raid_mode_already_off",
+            GearBotString::NoteAdded => "moderation__note_added",
+            GearBotString::NoNotes => "moderation__no_notes",
+            GearBotString::NotesHeader => "moderation__notes_header",
+            GearBotString::NoteRemoved => "moderation__note_removed",
+            GearBotString::NoteNotFound => "moderation__note_not_found",
+            GearBotString::UserinfoNotes => "basic__userinfo_notes",
+            GearBotString::AnnouncementAdded => "guild_admin__announcement_added",
+            GearBotString::AnnouncementInvalidCron => "guild_admin__announcement_invalid_cron",
+            GearBotString::AnnouncementLimitReached => "guild_admin__announcement_limit_reached",
+            GearBotString::NoAnnouncements => "guild_admin__no_announcements",
+            GearBotString::AnnouncementsHeader => "guild_admin__announcements_header",
+            GearBotString::AnnouncementRemoved => "guild_admin__announcement_removed",
+            GearBotString::AnnouncementNotFound => "guild_admin__announcement_not_found",
+            GearBotString::AttachmentStats => "moderation__attachment_stats",
+            GearBotString::CommandDisabled => "errors_command_disabled",
+            GearBotString::UnknownCommand => "guild_admin__unknown_command",
+            GearBotString::CommandCannotBeDisabled => "guild_admin__command_cannot_be_disabled",
+            GearBotString::CommandAlreadyDisabled => "guild_admin__command_already_disabled",
+            GearBotString::CommandNotDisabled => "guild_admin__command_not_disabled",
+            GearBotString::CommandNowDisabled => "guild_admin__command_now_disabled",
+            GearBotString::CommandNowEnabled => "guild_admin__command_now_enabled",
+            GearBotString::EmojiStatsHeader => "basic__emoji_stats_header",
+            GearBotString::EmojiStatsMostUsed => "basic__emoji_stats_most_used",
+            GearBotString::EmojiStatsLeastUsed => "basic__emoji_stats_least_used",
+            GearBotString::EmojiStatsNoEmoji => "basic__emoji_stats_no_emoji",
+            GearBotString::GuildOnlyCommand => "errors_guild_only_command",
+            GearBotString::MassBanText => "mass_ban_text",
+            GearBotString::MassUnbanText => "mass_unban_text",
+            GearBotString::MassBanEmbed => "mass_ban_embed",
+            GearBotString::MassBanFooter => "mass_ban_footer",
+            GearBotString::MassUnbanEmbed => "mass_unban_embed",
+            GearBotString::MassUnbanFooter => "mass_unban_footer",
+            GearBotString::MassActionNoTargets => "moderation__mass_action_no_targets",
+            GearBotString::MassActionTooManyTargets => "moderation__mass_action_too_many_targets",
+            GearBotString::MassActionAttachmentInvalid => "moderation__mass_action_attachment_invalid",
+            GearBotString::MassActionProgress => "moderation__mass_action_progress",
+            GearBotString::MassBanDone => "moderation__mass_ban_done",
+            GearBotString::MassUnbanDone => "moderation__mass_unban_done",
+            GearBotString::StarboardPost => "starboard__post",
+            GearBotString::StarboardJumpLink => "starboard__jump_link",
+            GearBotString::BulkFailureReason => "errors_bulk_failure_reason",
+            GearBotString::BulkOtherFailures => "errors_bulk_other_failures",
+            GearBotString::QuoteContentNotStored => "basic__quote_content_not_stored",
+            GearBotString::ContentNotStored => "content_not_stored",
+            GearBotString::TranscriptInvalidWindow => "moderation__transcript_invalid_window",
+            GearBotString::TranscriptEmpty => "moderation__transcript_empty",
+            GearBotString::TranscriptTruncated => "moderation__transcript_truncated",
+            GearBotString::AutoResponseAdded => "guild_admin__auto_response_added",
+            GearBotString::AutoResponseMissingSeparator => "guild_admin__auto_response_missing_separator",
+            GearBotString::AutoResponseLimitReached => "guild_admin__auto_response_limit_reached",
+            GearBotString::NoAutoResponses => "guild_admin__no_auto_responses",
+            GearBotString::AutoResponsesHeader => "guild_admin__auto_responses_header",
+            GearBotString::AutoResponseRemoved => "guild_admin__auto_response_removed",
+            GearBotString::AutoResponseNotFound => "guild_admin__auto_response_not_found",
+            GearBotString::RoleMenuTooManyOptions => "guild_admin__role_menu_too_many_options",
+            GearBotString::RoleMenuDuplicateEmoji => "guild_admin__role_menu_duplicate_emoji",
+            GearBotString::RoleMenuUnassignableRole => "guild_admin__role_menu_unassignable_role",
+            GearBotString::RoleMenuRoleTooHigh => "guild_admin__role_menu_role_too_high",
+            GearBotString::RoleMenuInvalidEmoji => "guild_admin__role_menu_invalid_emoji",
+            GearBotString::RoleMenuTitle => "guild_admin__role_menu_title",
+            GearBotString::RoleMenuCreated => "guild_admin__role_menu_created",
+            GearBotString::NoRoleMenus => "guild_admin__no_role_menus",
+            GearBotString::RoleMenusHeader => "guild_admin__role_menus_header",
+            GearBotString::RoleMenuRemoved => "guild_admin__role_menu_removed",
+            GearBotString::RoleMenuNotFound => "guild_admin__role_menu_not_found",
+            GearBotString::DatabaseUnavailable => "errors_database_unavailable",
+            GearBotString::ConfigImportInvalid => "guild_admin__config_import_invalid",
+            GearBotString::ConfigImported => "guild_admin__config_imported",
+            GearBotString::ConfigImportedWithSkips => "guild_admin__config_imported_with_skips",
+            GearBotString::ModReasonRequired => "moderation__reason_required",
+            GearBotString::MissingBotPermissions => "errors_missing_bot_permissions",
+            GearBotString::MemberTimedOutText => "member_timed_out_text",
+            GearBotString::MemberTimedOutEmbed => "member_timed_out_embed",
+            GearBotString::MemberTimedOutFooter => "member_timed_out_footer",
+            GearBotString::MemberTimeoutRemovedText => "member_timeout_removed_text",
+            GearBotString::MemberTimeoutRemovedEmbed => "member_timeout_removed_embed",
+            GearBotString::MemberTimeoutRemovedFooter => "member_timeout_removed_footer",
+            GearBotString::TimeoutTooLong => "moderation__timeout_too_long",
+            GearBotString::TimeoutApplied => "moderation__timeout_applied",
+            GearBotString::TimeoutRemoved => "moderation__timeout_removed",
+            GearBotString::NoReasonGiven => "moderation__no_reason",
+            GearBotString::MessageDeletedText => "message_deleted_text",
+            GearBotString::MessageDeletedEmbed => "message_deleted_embed",
+            GearBotString::MessageDeletedFooter => "message_deleted_footer",
+            GearBotString::MessageEditedText => "message_edited_text",
+            GearBotString::MessageEditedEmbed => "message_edited_embed",
+            GearBotString::MessageEditedFooter => "message_edited_footer",
+            GearBotString::MessageLogChannels => "guild_admin__message_log_channels",
+            GearBotString::NoMessageLogChannel => "guild_admin__no_message_log_channel",
+            GearBotString::MessageLogChannelSet => "guild_admin__message_log_channel_set",
+            GearBotString::LogChannelNoAccess => "guild_admin__log_channel_no_access",
+            GearBotString::MessageLogChannelDisabled => "guild_admin__message_log_channel_disabled",
+            GearBotString::CommandSuggestion => "errors_command_suggestion",
+            GearBotString::ActivityHeader => "moderation__activity_header",
+            GearBotString::ActivityEmpty => "moderation__activity_empty",
+            GearBotString::ActivityDay => "moderation__activity_day",
+            GearBotString::ActivityWeek => "moderation__activity_week",
+            GearBotString::PurgeDryRun => "moderation__purge_dry_run",
+            GearBotString::PurgeDryRunSent => "moderation__purge_dry_run_sent",
+            GearBotString::NewAccountKickedText => "new_account_kicked_text",
+            GearBotString::NewAccountKickedEmbed => "new_account_kicked_embed",
+            GearBotString::NewAccountRestrictedText => "new_account_restricted_text",
+            GearBotString::NewAccountRestrictedEmbed => "new_account_restricted_embed",
+            GearBotString::NewAccountGatedFooter => "new_account_gated_footer",
+            GearBotString::NewAccountKickedNotice => "automod__new_account_kicked",
+            GearBotString::NewAccountRestrictedNotice => "automod__new_account_restricted",
+            GearBotString::ReactionsHeader => "moderation__reactions_header",
+            GearBotString::ReactionsEmpty => "moderation__reactions_empty",
+            GearBotString::PruneInvalidDays => "moderation__prune_invalid_days",
+            GearBotString::PruneNothing => "moderation__prune_nothing",
+            GearBotString::PrunePreview => "moderation__prune_preview",
+            GearBotString::PruneNotPending => "moderation__prune_not_pending",
+            GearBotString::PruneDone => "moderation__prune_done",
+            GearBotString::MembersPrunedText => "members_pruned_text",
+            GearBotString::MembersPrunedEmbed => "members_pruned_embed",
+            GearBotString::MembersPrunedFooter => "members_pruned_footer",
+            GearBotString::SpamReasonDuplicates => "spam_reason_duplicates",
+            GearBotString::SpamReasonMentions => "spam_reason_mentions",
+            GearBotString::SpamReasonRate => "spam_reason_rate",
+            GearBotString::SpamActionDelete => "spam_action_delete",
+            GearBotString::SpamActionWarn => "spam_action_warn",
+            GearBotString::SpamActionMute => "spam_action_mute",
+            GearBotString::SpamDetectedText => "spam_detected_text",
+            GearBotString::SpamDetectedEmbed => "spam_detected_embed",
+            GearBotString::SpamDetectedFooter => "spam_detected_footer",
+            GearBotString::AutomodSpamWarning => "automod__spam_warning",
+            GearBotString::UnderMaintenance => "errors_under_maintenance",
+            GearBotString::HierarchyTargetIsOwner => "moderation__hierarchy_target_is_owner",
+            GearBotString::HierarchyAuthorTooLow => "moderation__hierarchy_author_too_low",
+            GearBotString::HierarchyBotTooLow => "moderation__hierarchy_bot_too_low",
+            GearBotString::AltsHeader => "moderation__alts_header",
+            GearBotString::AltsEntry => "moderation__alts_entry",
+            GearBotString::AltsEmpty => "moderation__alts_empty",
+            GearBotString::JoinLogChannels => "guild_admin__join_log_channels",
+            GearBotString::NoJoinLogChannel => "guild_admin__no_join_log_channel",
+            GearBotString::JoinLogChannelSet => "guild_admin__join_log_channel_set",
+            GearBotString::JoinLogChannelDisabled => "guild_admin__join_log_channel_disabled",
+            GearBotString::MemberJoinedText => "member_joined_text",
+            GearBotString::MemberJoinedEmbed => "member_joined_embed",
+            GearBotString::MemberJoinedFooter => "member_joined_footer",
+            GearBotString::MemberLeftText => "member_left_text",
+            GearBotString::MemberLeftEmbed => "member_left_embed",
+            GearBotString::MemberLeftFooter => "member_left_footer",
+            GearBotString::MemberLeftNoRoles => "member_left_no_roles",
+            GearBotString::MemberLeftUnknownDuration => "member_left_unknown_duration",
+            GearBotString::SnowflakeInfo => "basic__snowflake_info",
+            GearBotString::SnowflakeInvalid => "basic__snowflake_invalid",
+            GearBotString::CommandsBusy => "errors_commands_busy",
+            GearBotString::PurgeArchiveTranscript => "moderation__purge_archive_transcript",
+            GearBotString::PurgeArchiveSent => "moderation__purge_archive_sent",
+            GearBotString::PurgeArchiveDmFailed => "moderation__purge_archive_dm_failed",
+            GearBotString::ModerationBotMissingPermissions => "moderation__bot_missing_permissions",
+            GearBotString::ModerationTargetNotFound => "moderation__target_not_found",
+            GearBotString::ModerationRefused => "moderation__refused",
+            GearBotString::ModStatsTitle => "moderation__modstats_title",
+            GearBotString::ModStatsEntry => "moderation__modstats_entry",
+            GearBotString::ModStatsEmpty => "moderation__modstats_empty",
+            GearBotString::HistoryImportInvalid => "guild_admin__history_import_invalid",
+            GearBotString::HistoryImportTooLarge => "guild_admin__history_import_too_large",
+            GearBotString::HistoryImported => "guild_admin__history_imported",
+            GearBotString::HistoryImportRejectedEntry => "guild_admin__history_import_rejected_entry",
+            GearBotString::PurgeFilterInvalid => "moderation__purge_filter_invalid",
+            GearBotString::PurgeFilterTimeout => "moderation__purge_filter_timeout",
+            GearBotString::NamesHeader => "moderation__names_header",
+            GearBotString::NamesUsernames => "moderation__names_usernames",
+            GearBotString::NamesNicknames => "moderation__names_nicknames",
+            GearBotString::NamesEntry => "moderation__names_entry",
+            GearBotString::NamesNoNickname => "moderation__names_no_nickname",
+            GearBotString::NamesEmpty => "moderation__names_empty",
+            GearBotString::AttachmentScannedText => "attachment_scanned_text",
+            GearBotString::AttachmentScannedEmbed => "attachment_scanned_embed",
+            GearBotString::AttachmentScannedFooter => "attachment_scanned_footer",
+            GearBotString::ScanOutcomeDelete => "scan_outcome_delete",
+            GearBotString::ScanOutcomeAlert => "scan_outcome_alert",
+            GearBotString::ScanOutcomeBan => "scan_outcome_ban",
+            GearBotString::ScanOutcomeUnscanned => "scan_outcome_unscanned",
+            GearBotString::ReasonCaseNotFound => "moderation__reason_case_not_found",
+            GearBotString::ReasonNotAllowed => "moderation__reason_not_allowed",
+            GearBotString::ReasonUpdated => "moderation__reason_updated",
+            GearBotString::UserinfoStatus => "basic__userinfo_status",
+            GearBotString::UserinfoStatusActivity => "basic__userinfo_status_activity",
+            GearBotString::OnlineCount => "basic__online_count",
+            GearBotString::OnlineNotTracked => "basic__online_not_tracked",
+            GearBotString::AutomodTestNoMessages => "guild_admin__automod_test_no_messages",
+            GearBotString::AutomodTestResult => "guild_admin__automod_test_result",
+            GearBotString::CommandFailed => "errors_command_failed",
+            GearBotString::CommandFailedDetailed => "errors_command_failed_detailed",
+            GearBotString::SimulateJoinResult => "guild_admin__simulate_join_result",
+            GearBotString::SimulateJoinLogged => "guild_admin__simulate_join_logged",
+            GearBotString::SimulateJoinNotLogged => "guild_admin__simulate_join_not_logged",
+            GearBotString::SimulateJoinBot => "guild_admin__simulate_join_bot",
+            GearBotString::SimulateJoinGatePassed => "guild_admin__simulate_join_gate_passed",
+            GearBotString::SimulateJoinGateKick => "guild_admin__simulate_join_gate_kick",
+            GearBotString::SimulateJoinGateRole => "guild_admin__simulate_join_gate_role",
+            GearBotString::SimulateJoinMissingPermission => "guild_admin__simulate_join_missing_permission",
+            GearBotString::SimulateJoinRaidKick => "guild_admin__simulate_join_raid_kick",
+            GearBotString::SimulateJoinRaidAllowed => "guild_admin__simulate_join_raid_allowed",
+            GearBotString::SimulateJoinGreeting => "guild_admin__simulate_join_greeting",
+            GearBotString::SimulateJoinGreetingReaction => "guild_admin__simulate_join_greeting_reaction",
+            GearBotString::JumboInvalid => "basic__jumbo_invalid",
+            GearBotString::ContentStorageDisabled => "errors_content_storage_disabled",
+            GearBotString::CommandOnCooldown => "errors_command_on_cooldown",
+            GearBotString::HelpOverview => "basic__help_overview",
+            GearBotString::HelpGroup => "basic__help_group",
+            GearBotString::HelpNothingAvailable => "basic__help_nothing_available",
+            GearBotString::HelpUnknownCommand => "basic__help_unknown_command",
+            GearBotString::HelpCommand => "basic__help_command",
+            GearBotString::HelpNoDescription => "basic__help_no_description",
+            GearBotString::HelpAliases => "basic__help_aliases",
+            GearBotString::HelpSubcommands => "basic__help_subcommands",
+            GearBotString::HelpCooldown => "basic__help_cooldown",
+            GearBotString::HelpGuildOnly => "basic__help_guild_only",
+            GearBotString::AboutCommandDescription => "basic__about_description",
+            GearBotString::CoinflipDescription => "basic__coinflip_description",
+            GearBotString::HelpDescription => "basic__help_description",
+            GearBotString::JumboDescription => "basic__jumbo_description",
+            GearBotString::PingDescription => "basic__ping_description",
+            GearBotString::QuoteDescription => "basic__quote_description",
+            GearBotString::SnowflakeDescription => "basic__snowflake_description",
+            GearBotString::UidDescription => "basic__uid_description",
+            GearBotString::TranscriptDescription => "moderation__transcript_description",
+            GearBotString::AutomodTestDescription => "guild_admin__automodtest_description",
+            GearBotString::SpamReasonCrossChannel => "spam_reason_cross_channel",
+            GearBotString::SpamReasonBlockedAttachment => "spam_reason_blocked_attachment",
+            GearBotString::SpamReasonScanFlagged => "spam_reason_scan_flagged",
+            GearBotString::SpamReasonScanFailed => "spam_reason_scan_failed",
+            GearBotString::SpamActionAlert => "spam_action_alert",
+            GearBotString::SpamActionBan => "spam_action_ban",
+            GearBotString::HistoryExported => "guild_admin__history_exported",
+            GearBotString::HistoryExportEmpty => "guild_admin__history_export_empty",
+            GearBotString::HistoryExportTooLarge => "guild_admin__history_export_too_large",
+            GearBotString::ExportHistoryDescription => "guild_admin__exporthistory_description",
+            GearBotString::DurationYears => "duration_years",
+            GearBotString::DurationMonths => "duration_months",
+            GearBotString::DurationWeeks => "duration_weeks",
+            GearBotString::DurationDays => "duration_days",
+            GearBotString::DurationHours => "duration_hours",
+            GearBotString::DurationMinutes => "duration_minutes",
+            GearBotString::DurationSeconds => "duration_seconds",
+            GearBotString::RoleMenusCleanedUpText => "role_menus_cleaned_up_text",
+            GearBotString::RoleMenusCleanedUpEmbed => "role_menus_cleaned_up_embed",
+            GearBotString::RoleMenusCleanedUpFooter => "role_menus_cleaned_up_footer",
+            GearBotString::TranscriptSizeTruncated => "moderation__transcript_size_truncated",
+            GearBotString::TranscriptProgress => "moderation__transcript_progress",
+            GearBotString::TranscriptDone => "moderation__transcript_done",
+            GearBotString::MissingBotPermission => "errors_missing_bot_permission",
+            GearBotString::CommandGloballyDisabled => "errors_command_globally_disabled",
+            GearBotString::SpamReasonInvite => "spam_reason_invite",
+            GearBotString::SpamReasonLink => "spam_reason_link",
+            GearBotString::AutomodLinkNotice => "automod__link_notice",
+            GearBotString::HelpUsage => "basic__help_usage",
+            GearBotString::JumboUsage => "basic__jumbo_usage",
+            GearBotString::HelpCommandUsage => "basic__help_command_usage",
+            GearBotString::QuoteUsage => "basic__quote_usage",
+            GearBotString::SnowflakeUsage => "basic__snowflake_usage",
+            GearBotString::UidUsage => "basic__uid_usage",
+            GearBotString::TranscriptUsage => "moderation__transcript_usage",
+            GearBotString::AutomodTestUsage => "guild_admin__automodtest_usage",
+            GearBotString::ExportHistoryUsage => "guild_admin__exporthistory_usage",
+            GearBotString::ParseMissingArgument => "parse__missing_argument",
+            GearBotString::ParseExpectedNumber => "parse__expected_number",
+            GearBotString::ParseExpectedDuration => "parse__expected_duration",
+            GearBotString::ParseExpectedUser => "parse__expected_user",
+            GearBotString::ParseExpectedChannel => "parse__expected_channel",
+            GearBotString::ParseExpectedRole => "parse__expected_role",
+            GearBotString::DeletedAttachmentKept => "message_deleted_attachment_kept",
+            GearBotString::DeletedAttachmentNotKept => "message_deleted_attachment_not_kept",
+            GearBotString::CoinflipUsage => "basic__coinflip_usage",
+            GearBotString::ConfigDescription => "guild_admin__config_description",
+            GearBotString::ConfigGetDescription => "guild_admin__config_get_description",
+            GearBotString::ConfigGetPrettyDescription => "guild_admin__config_get_pretty_description",
+            GearBotString::ConfigSetDescription => "guild_admin__config_set_description",
+            GearBotString::ConfigSetUsage => "guild_admin__config_set_usage",
+            GearBotString::ConfigResetDescription => "guild_admin__config_reset_description",
+            GearBotString::ConfigColorDescription => "guild_admin__config_color_description",
+            GearBotString::ConfigColorUsage => "guild_admin__config_color_usage",
+            GearBotString::ConfigFooterDescription => "guild_admin__config_footer_description",
+            GearBotString::ConfigFooterUsage => "guild_admin__config_footer_usage",
+            GearBotString::ConfigFooterIconDescription => "guild_admin__config_footericon_description",
+            GearBotString::ConfigFooterIconUsage => "guild_admin__config_footericon_usage",
+            GearBotString::ConfigTimezoneDescription => "guild_admin__config_timezone_description",
+            GearBotString::ConfigTimezoneUsage => "guild_admin__config_timezone_usage",
+            GearBotString::ConfigCleanupDescription => "guild_admin__config_cleanup_description",
+            GearBotString::ConfigCleanupUsage => "guild_admin__config_cleanup_usage",
+            GearBotString::ConfigNicknameDescription => "guild_admin__config_nickname_description",
+            GearBotString::ConfigNicknameUsage => "guild_admin__config_nickname_usage",
+            GearBotString::ConfigPlaintextDescription => "guild_admin__config_plaintext_description",
+            GearBotString::ConfigPlaintextUsage => "guild_admin__config_plaintext_usage",
+            GearBotString::ConfigQuietDescription => "guild_admin__config_quiet_description",
+            GearBotString::ConfigQuietUsage => "guild_admin__config_quiet_usage",
+            GearBotString::ConfigDeleteCommandsDescription => "guild_admin__config_deletecommands_description",
+            GearBotString::ConfigDeleteCommandsUsage => "guild_admin__config_deletecommands_usage",
+            GearBotString::ConfigErrorsDescription => "guild_admin__config_errors_description",
+            GearBotString::ConfigErrorsUsage => "guild_admin__config_errors_usage",
+            GearBotString::ConfigSystemMessagesDescription => "guild_admin__config_systemmessages_description",
+            GearBotString::ConfigSystemMessagesUsage => "guild_admin__config_systemmessages_usage",
+            GearBotString::ConfigContentStorageDescription => "guild_admin__config_contentstorage_description",
+            GearBotString::ConfigContentStorageUsage => "guild_admin__config_contentstorage_usage",
+            GearBotString::ConfigStorageExcludeDescription => "guild_admin__config_storageexclude_description",
+            GearBotString::ConfigStorageExcludeUsage => "guild_admin__config_storageexclude_usage",
+            GearBotString::ConfigRoleMenuMessagesDescription => "guild_admin__config_rolemenumessages_description",
+            GearBotString::ConfigRoleMenuMessagesUsage => "guild_admin__config_rolemenumessages_usage",
+            GearBotString::ConfigDeleteGraceDescription => "guild_admin__config_deletegrace_description",
+            GearBotString::ConfigDeleteGraceUsage => "guild_admin__config_deletegrace_usage",
+            GearBotString::ConfigLogCommandDescription => "guild_admin__config_logcommand_description",
+            GearBotString::ConfigLogCommandUsage => "guild_admin__config_logcommand_usage",
+            GearBotString::ConfigCommandLevelDescription => "guild_admin__config_commandlevel_description",
+            GearBotString::ConfigCommandLevelUsage => "guild_admin__config_commandlevel_usage",
+            GearBotString::ConfigAutomodOrderDescription => "guild_admin__config_automodorder_description",
+            GearBotString::ConfigAutomodOrderUsage => "guild_admin__config_automodorder_usage",
+            GearBotString::ConfigCooldownBypassDescription => "guild_admin__config_cooldownbypass_description",
+            GearBotString::ConfigCooldownBypassUsage => "guild_admin__config_cooldownbypass_usage",
+            GearBotString::ConfigCommandChannelsDescription => "guild_admin__config_commandchannels_description",
+            GearBotString::ConfigCommandChannelsUsage => "guild_admin__config_commandchannels_usage",
+            GearBotString::ConfigJoinLogDescription => "guild_admin__config_joinlog_description",
+            GearBotString::ConfigJoinLogUsage => "guild_admin__config_joinlog_usage",
+            GearBotString::ConfigMessageLogDescription => "guild_admin__config_messagelog_description",
+            GearBotString::ConfigMessageLogUsage => "guild_admin__config_messagelog_usage",
+            GearBotString::ConfigExportDescription => "guild_admin__config_export_description",
+            GearBotString::ConfigImportDescription => "guild_admin__config_import_description",
+            GearBotString::ConfigImportUsage => "guild_admin__config_import_usage",
+            GearBotString::AnnouncementDescription => "guild_admin__announcement_description",
+            GearBotString::AnnouncementAddDescription => "guild_admin__announcement_add_description",
+            GearBotString::AnnouncementAddUsage => "guild_admin__announcement_add_usage",
+            GearBotString::AnnouncementListDescription => "guild_admin__announcement_list_description",
+            GearBotString::AnnouncementRemoveDescription => "guild_admin__announcement_remove_description",
+            GearBotString::AnnouncementRemoveUsage => "guild_admin__announcement_remove_usage",
+            GearBotString::AutoResponseDescription => "guild_admin__autoresponse_description",
+            GearBotString::AutoResponseAddDescription => "guild_admin__autoresponse_add_description",
+            GearBotString::AutoResponseAddUsage => "guild_admin__autoresponse_add_usage",
+            GearBotString::AutoResponseListDescription => "guild_admin__autoresponse_list_description",
+            GearBotString::AutoResponseRemoveDescription => "guild_admin__autoresponse_remove_description",
+            GearBotString::AutoResponseRemoveUsage => "guild_admin__autoresponse_remove_usage",
+            GearBotString::RoleMenuDescription => "guild_admin__rolemenu_description",
+            GearBotString::RoleMenuCreateDescription => "guild_admin__rolemenu_create_description",
+            GearBotString::RoleMenuCreateUsage => "guild_admin__rolemenu_create_usage",
+            GearBotString::RoleMenuListDescription => "guild_admin__rolemenu_list_description",
+            GearBotString::RoleMenuRemoveDescription => "guild_admin__rolemenu_remove_description",
+            GearBotString::RoleMenuRemoveUsage => "guild_admin__rolemenu_remove_usage",
+            GearBotString::DisableDescription => "guild_admin__disable_description",
+            GearBotString::DisableUsage => "guild_admin__disable_usage",
+            GearBotString::EnableDescription => "guild_admin__enable_description",
+            GearBotString::EnableUsage => "guild_admin__enable_usage",
+            GearBotString::ImportHistoryDescription => "guild_admin__importhistory_description",
+            GearBotString::SimulateJoinDescription => "guild_admin__simulatejoin_description",
+            GearBotString::SimulateJoinUsage => "guild_admin__simulatejoin_usage",
+            GearBotString::UserinfoDescription => "moderation__userinfo_description",
+            GearBotString::UserinfoUsage => "moderation__userinfo_usage",
+            GearBotString::PurgeDescription => "moderation__purge_description",
+            GearBotString::PurgeUsage => "moderation__purge_usage",
+            GearBotString::PurgeUntilDescription => "moderation__purge_until_description",
+            GearBotString::PurgeUntilUsage => "moderation__purge_until_usage",
+            GearBotString::DelwarnDescription => "moderation__delwarn_description",
+            GearBotString::DelwarnUsage => "moderation__delwarn_usage",
+            GearBotString::ReasonDescription => "moderation__reason_description",
+            GearBotString::ReasonUsage => "moderation__reason_usage",
+            GearBotString::ClearWarningsDescription => "moderation__clearwarnings_description",
+            GearBotString::ClearWarningsUsage => "moderation__clearwarnings_usage",
+            GearBotString::AddNoteDescription => "moderation__addnote_description",
+            GearBotString::AddNoteUsage => "moderation__addnote_usage",
+            GearBotString::NotesDescription => "moderation__notes_description",
+            GearBotString::NotesUsage => "moderation__notes_usage",
+            GearBotString::DelNoteDescription => "moderation__delnote_description",
+            GearBotString::DelNoteUsage => "moderation__delnote_usage",
+            GearBotString::AltsDescription => "moderation__alts_description",
+            GearBotString::AltsUsage => "moderation__alts_usage",
+            GearBotString::NamesDescription => "moderation__names_description",
+            GearBotString::NamesUsage => "moderation__names_usage",
+            GearBotString::ModStatsDescription => "moderation__modstats_description",
+            GearBotString::ModStatsUsage => "moderation__modstats_usage",
+            GearBotString::MassBanDescription => "moderation__massban_description",
+            GearBotString::MassBanUsage => "moderation__massban_usage",
+            GearBotString::MassUnbanDescription => "moderation__massunban_description",
+            GearBotString::MassUnbanUsage => "moderation__massunban_usage",
+            GearBotString::PruneDescription => "moderation__prune_description",
+            GearBotString::PruneUsage => "moderation__prune_usage",
+            GearBotString::RaidModeDescription => "moderation__raidmode_description",
+            GearBotString::RaidModeUsage => "moderation__raidmode_usage",
+            GearBotString::TimeoutDescription => "moderation__timeout_description",
+            GearBotString::TimeoutUsage => "moderation__timeout_usage",
+            GearBotString::UntimeoutDescription => "moderation__untimeout_description",
+            GearBotString::UntimeoutUsage => "moderation__untimeout_usage",
+            GearBotString::ActivityDescription => "moderation__activity_description",
+            GearBotString::ActivityUsage => "moderation__activity_usage",
+            GearBotString::ReactionsDescription => "moderation__reactions_description",
+            GearBotString::ReactionsUsage => "moderation__reactions_usage",
+            GearBotString::StatsDescription => "moderation__stats_description",
+            GearBotString::StatsAttachmentsDescription => "moderation__stats_attachments_description",
+            GearBotString::CheckDescription => "bot_admin__check_description",
+            GearBotString::CheckCacheDescription => "bot_admin__check_cache_description",
+            GearBotString::CacheStatsDescription => "bot_admin__cachestats_description",
+            GearBotString::CacheStatsUsage => "bot_admin__cachestats_usage",
+            GearBotString::ClearCacheDescription => "bot_admin__clearcache_description",
+            GearBotString::ClearCacheUsage => "bot_admin__clearcache_usage",
+            GearBotString::MaintenanceDescription => "bot_admin__maintenance_description",
+            GearBotString::MaintenanceUsage => "bot_admin__maintenance_usage",
+            GearBotString::KillswitchDescription => "bot_admin__killswitch_description",
+            GearBotString::KillswitchDisableDescription => "bot_admin__killswitch_disable_description",
+            GearBotString::KillswitchDisableUsage => "bot_admin__killswitch_disable_usage",
+            GearBotString::KillswitchEnableDescription => "bot_admin__killswitch_enable_description",
+            GearBotString::KillswitchEnableUsage => "bot_admin__killswitch_enable_usage",
+            GearBotString::KillswitchListDescription => "bot_admin__killswitch_list_description",
+            GearBotString::ReloadLoggingDescription => "bot_admin__reload_logging_description",
+            GearBotString::TestWebhooksDescription => "bot_admin__test_webhooks_description",
+            GearBotString::SetAvatarDescription => "bot_admin__set_avatar_description",
+            GearBotString::SetUsernameDescription => "bot_admin__set_username_description",
+            GearBotString::SetUsernameUsage => "bot_admin__set_username_usage",
+            GearBotString::RotateKeysDescription => "bot_admin__rotate_keys_description",
+            GearBotString::RotateGuildKeyDescription => "bot_admin__rotate_guild_key_description",
+            GearBotString::RotateGuildKeyUsage => "bot_admin__rotate_guild_key_usage",
+            GearBotString::VerifyMessagesDescription => "bot_admin__verify_messages_description",
+            GearBotString::VerifyMessagesUsage => "bot_admin__verify_messages_usage",
+            GearBotString::TranslationsDescription => "bot_admin__translations_description",
+            GearBotString::TranslationsUsage => "bot_admin__translations_usage",
+            GearBotString::ReloadTranslationsDescription => "bot_admin__reload_translations_description",
+            GearBotString::WebhookDescription => "bot_admin__webhook_description",
+            GearBotString::WebhookUsage => "bot_admin__webhook_usage",
+            GearBotString::ShardsDescription => "bot_admin__shards_description",
+            GearBotString::HealthDescription => "bot_admin__health_description",
+            GearBotString::RedisTestDescription => "bot_admin__redis_test_description",
+            GearBotString::PermsDescription => "bot_admin__perms_description",
+            GearBotString::PermsUsage => "bot_admin__perms_usage",
+            GearBotString::TestDescription => "bot_admin__test_description",
+            GearBotString::EmojiDescription => "misc__emoji_description",
+            GearBotString::EmojiListDescription => "misc__emoji_list_description",
+            GearBotString::EmojiStatsDescription => "misc__emojistats_description",
+            GearBotString::OnlineDescription => "misc__online_description",
+            GearBotString::SimulateJoinWelcomeImage => "guild_admin__simulate_join_welcome_image",
+            GearBotString::WelcomeImageMemberNumber => "welcome_image__member_number",
+            GearBotString::ConfigWelcomeImageDescription => "guild_admin__config_welcomeimage_description",
+            GearBotString::ConfigWelcomeImageUsage => "guild_admin__config_welcomeimage_usage",
+            GearBotString::ConfigWelcomeBackgroundDescription => "guild_admin__config_welcomebackground_description",
+            GearBotString::ConfigWelcomeBackgroundUsage => "guild_admin__config_welcomebackground_usage",
+            GearBotString::ConfigWelcomeColorDescription => "guild_admin__config_welcomecolor_description",
+            GearBotString::ConfigWelcomeColorUsage => "guild_admin__config_welcomecolor_usage",
         }
     }
 
@@ -207,62 +1138,207 @@ impl GearBotString {
     // data up to date, but it probably isn't that bad for the nice compile time saftey it gives us.
 }
 
-pub fn load_translations() -> Translations {
-    let translation_files = fs::read_dir(TRANSLATION_DIR).expect("The translation directory was not found!");
+pub fn load_translations(fallback: &LanguageIdentifier) -> Translations {
+    read_translations(fallback).unwrap_or_else(|e| panic!("Failed to load the translations: {}", e))
+}
+
+/// Reads the translations of all languages from disk.
+///
+/// Anything broken is returned as an error instead of panicking, so they can also be reloaded while running.
+pub fn read_translations(fallback: &LanguageIdentifier) -> Result<Translations, String> {
+    let translation_files =
+        fs::read_dir(TRANSLATION_DIR).map_err(|e| format!("the translation directory could not be read: {}", e))?;
 
     let mut translations = HashMap::new();
 
     for lang_dir in translation_files {
-        let lang_dir = lang_dir.unwrap();
+        let lang_dir = lang_dir.map_err(|e| e.to_string())?;
 
-        if !lang_dir.file_type().unwrap().is_dir() {
-            panic!("Each language must be contained in its own directory!")
+        if !lang_dir.file_type().map_or(false, |file_type| file_type.is_dir()) {
+            return Err(String::from("each language must be contained in its own directory"));
         }
 
         let lang_dir_path = lang_dir.path();
 
-        let lang_dir_name = lang_dir_path.file_stem().unwrap().to_str().unwrap();
+        let lang_dir_name = lang_dir_path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
 
         let langid: LanguageIdentifier = lang_dir_name
             .parse()
-            .unwrap_or_else(|_| panic!("{} was not a valid language identifier!", lang_dir_name));
+            .map_err(|_| format!("{} is not a valid language identifier", lang_dir_name))?;
 
         // Make the bundle of the specific language
         let mut bundle = FluentBundle::new(&[langid.clone()]);
         bundle.set_use_isolating(false);
-        for t_file in fs::read_dir(lang_dir.path()).unwrap() {
-            let t_file = {
-                let tmp = t_file.unwrap();
-                fs::File::open(tmp.path()).expect("Failed to read a translation file in!")
-            };
+        for t_file in fs::read_dir(&lang_dir_path).map_err(|e| e.to_string())? {
+            let path = t_file.map_err(|e| e.to_string())?.path();
+            let location = format!(
+                "{}/{}",
+                lang_dir_name,
+                path.file_name().unwrap_or_default().to_string_lossy()
+            );
 
-            let translation_data: HashMap<String, String> = serde_json::from_reader(&t_file).unwrap();
+            let translation_data: HashMap<String, String> = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+                .map_err(|e| format!("{}: {}", location, e))?;
 
             // Then we add all the actual translations for said language
             for (translation_key, translation_string) in translation_data {
                 let tl_string = format!("{} = {}", translation_key, translation_string);
-                let res = FluentResource::try_new(tl_string).unwrap();
+                let res = FluentResource::try_new(tl_string)
+                    .map_err(|(_, errors)| format!("{}: {}: {:?}", location, translation_key, errors))?;
 
-                bundle.add_resource(res).unwrap();
+                bundle
+                    .add_resource(res)
+                    .map_err(|errors| format!("{}: {}: {:?}", location, translation_key, errors))?;
             }
         }
 
         translations.insert(langid, Arc::new(bundle));
     }
 
-    Translations(translations)
+    // everything falls back to this one
+    if !translations.contains_key(fallback) {
+        return Err(format!(
+            "there are no translations for the fallback language ({})",
+            fallback
+        ));
+    }
+
+    Ok(Translations {
+        bundles: translations,
+        fallback: fallback.clone(),
+    })
+}
+
+/// Everything that is wrong with the translation files of a single language.
+///
+/// Missing and extra keys are determined by comparing against the files of the default language.
+#[derive(Default)]
+pub struct LocaleReport {
+    /// Files that couldn't be read as a json object of strings, along with why.
+    pub broken_files: Vec<(String, String)>,
+    /// Keys of the default language without a translation, along with the file they belong in.
+    pub missing_keys: Vec<(String, String)>,
+    /// Keys that the default language doesn't have, along with the file they were found in.
+    pub extra_keys: Vec<(String, String)>,
+    /// Keys with a string fluent can't parse, along with the parser errors.
+    pub syntax_errors: Vec<(String, String)>,
+}
+
+impl LocaleReport {
+    pub fn is_clean(&self) -> bool {
+        self.broken_files.is_empty()
+            && self.missing_keys.is_empty()
+            && self.extra_keys.is_empty()
+            && self.syntax_errors.is_empty()
+    }
+}
+
+impl fmt::Display for LocaleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections = [
+            ("Broken files", &self.broken_files),
+            ("Missing keys", &self.missing_keys),
+            ("Extra keys", &self.extra_keys),
+            ("Syntax errors", &self.syntax_errors),
+        ];
+        for (title, entries) in sections.iter() {
+            if entries.is_empty() {
+                continue;
+            }
+            writeln!(f, "{} ({}):", title, entries.len())?;
+            for (location, detail) in entries.iter() {
+                writeln!(f, "    {}: {}", location, detail)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the translation files of a language on disk against those of the default language.
+///
+/// Unlike loading the translations this never panics, so it can be used to review contributions.
+pub fn validate_locale(lang: &LanguageIdentifier) -> Result<LocaleReport, io::Error> {
+    let mut report = LocaleReport::default();
+
+    let mut reference = HashMap::new();
+    for (file_name, strings) in read_locale_files(&DEFAULT_LANG)? {
+        if let Ok(strings) = strings {
+            for key in strings.keys() {
+                reference.insert(key.clone(), file_name.clone());
+            }
+        }
+    }
+
+    let mut found = HashSet::new();
+    for (file_name, strings) in read_locale_files(lang)? {
+        let strings = match strings {
+            Ok(strings) => strings,
+            Err(e) => {
+                report.broken_files.push((file_name, e));
+                continue;
+            }
+        };
+
+        for (key, value) in strings {
+            if !reference.contains_key(&key) {
+                report.extra_keys.push((file_name.clone(), key.clone()));
+            }
+            if let Err((_, errors)) = FluentResource::try_new(format!("{} = {}", key, value)) {
+                report
+                    .syntax_errors
+                    .push((format!("{}: {}", file_name, key), format!("{:?}", errors)));
+            }
+            found.insert(key);
+        }
+    }
+
+    let mut missing: Vec<(String, String)> = reference
+        .into_iter()
+        .filter(|(key, _)| !found.contains(key))
+        .map(|(key, file_name)| (file_name, key))
+        .collect();
+    missing.sort();
+    report.missing_keys = missing;
+
+    Ok(report)
+}
+
+/// Reads the translation files of a language, files that aren't valid json are returned as errors.
+fn read_locale_files(
+    lang: &LanguageIdentifier,
+) -> Result<Vec<(String, Result<BTreeMap<String, String>, String>)>, io::Error> {
+    let mut files = vec![];
+    for entry in fs::read_dir(format!("{}/{}", TRANSLATION_DIR, lang))? {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let strings = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+        files.push((file_name, strings));
+    }
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(files)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{GearBotString, TRANSLATION_DIR};
+    use super::{validate_locale, GearBotString, Translations, DEFAULT_LANG, TRANSLATION_DIR};
     use lazy_static::lazy_static;
     use serde_json;
     use std::collections::HashMap;
     use std::fs;
+    use unic_langid::langid;
 
     lazy_static! {
-        static ref ALL_TRANSLATION_STR_KEYS: [&'static str; 17] = [
+        static ref ALL_TRANSLATION_STR_KEYS: [&'static str; 475] = [
             GearBotString::PingPong.as_str(),
             GearBotString::CoinflipDefault.as_str(),
             GearBotString::CoinflipYes.as_str(),
@@ -280,6 +1356,464 @@ mod tests {
             GearBotString::CommandUsedText.as_str(),
             GearBotString::CommandUsedEmbed.as_str(),
             GearBotString::CommandUsedFooter.as_str(),
+            GearBotString::AutomodBlockedAttachment.as_str(),
+            GearBotString::MessagesBulkDeletedText.as_str(),
+            GearBotString::MessagesBulkDeletedEmbed.as_str(),
+            GearBotString::MessagesBulkDeletedFooter.as_str(),
+            GearBotString::PurgeDone.as_str(),
+            GearBotString::PurgeDoneSkipped.as_str(),
+            GearBotString::WarningNotFound.as_str(),
+            GearBotString::WarningRemoved.as_str(),
+            GearBotString::WarningsCleared.as_str(),
+            GearBotString::OutputAsFile.as_str(),
+            GearBotString::OutputTooLarge.as_str(),
+            GearBotString::OutputNoAttachPermission.as_str(),
+            GearBotString::MentionPrefix.as_str(),
+            GearBotString::RaidModeEnabledText.as_str(),
+            GearBotString::RaidModeDisabledText.as_str(),
+            GearBotString::RaidModeEnabledEmbed.as_str(),
+            GearBotString::RaidModeEnabledFooter.as_str(),
+            GearBotString::RaidModeDisabledEmbed.as_str(),
+            GearBotString::RaidModeDisabledFooter.as_str(),
+            GearBotString::RaidModeOn.as_str(),
+            GearBotString::RaidModeOff.as_str(),
+            GearBotString::RaidModeAlreadyOn.as_str(),
+            GearBotString::RaidModeAlreadyOff.as_str(),
+            GearBotString::NoteAdded.as_str(),
+            GearBotString::NoNotes.as_str(),
+            GearBotString::NotesHeader.as_str(),
+            GearBotString::NoteRemoved.as_str(),
+            GearBotString::NoteNotFound.as_str(),
+            GearBotString::UserinfoNotes.as_str(),
+            GearBotString::AnnouncementAdded.as_str(),
+            GearBotString::AnnouncementInvalidCron.as_str(),
+            GearBotString::AnnouncementLimitReached.as_str(),
+            GearBotString::NoAnnouncements.as_str(),
+            GearBotString::AnnouncementsHeader.as_str(),
+            GearBotString::AnnouncementRemoved.as_str(),
+            GearBotString::AnnouncementNotFound.as_str(),
+            GearBotString::AttachmentStats.as_str(),
+            GearBotString::CommandDisabled.as_str(),
+            GearBotString::UnknownCommand.as_str(),
+            GearBotString::CommandCannotBeDisabled.as_str(),
+            GearBotString::CommandAlreadyDisabled.as_str(),
+            GearBotString::CommandNotDisabled.as_str(),
+            GearBotString::CommandNowDisabled.as_str(),
+            GearBotString::CommandNowEnabled.as_str(),
+            GearBotString::EmojiStatsHeader.as_str(),
+            GearBotString::EmojiStatsMostUsed.as_str(),
+            GearBotString::EmojiStatsLeastUsed.as_str(),
+            GearBotString::EmojiStatsNoEmoji.as_str(),
+            GearBotString::GuildOnlyCommand.as_str(),
+            GearBotString::MassBanText.as_str(),
+            GearBotString::MassUnbanText.as_str(),
+            GearBotString::MassBanEmbed.as_str(),
+            GearBotString::MassBanFooter.as_str(),
+            GearBotString::MassUnbanEmbed.as_str(),
+            GearBotString::MassUnbanFooter.as_str(),
+            GearBotString::MassActionNoTargets.as_str(),
+            GearBotString::MassActionTooManyTargets.as_str(),
+            GearBotString::MassActionAttachmentInvalid.as_str(),
+            GearBotString::MassActionProgress.as_str(),
+            GearBotString::MassBanDone.as_str(),
+            GearBotString::MassUnbanDone.as_str(),
+            GearBotString::StarboardPost.as_str(),
+            GearBotString::StarboardJumpLink.as_str(),
+            GearBotString::BulkFailureReason.as_str(),
+            GearBotString::BulkOtherFailures.as_str(),
+            GearBotString::QuoteContentNotStored.as_str(),
+            GearBotString::ContentNotStored.as_str(),
+            GearBotString::TranscriptInvalidWindow.as_str(),
+            GearBotString::TranscriptEmpty.as_str(),
+            GearBotString::TranscriptTruncated.as_str(),
+            GearBotString::AutoResponseAdded.as_str(),
+            GearBotString::AutoResponseMissingSeparator.as_str(),
+            GearBotString::AutoResponseLimitReached.as_str(),
+            GearBotString::NoAutoResponses.as_str(),
+            GearBotString::AutoResponsesHeader.as_str(),
+            GearBotString::AutoResponseRemoved.as_str(),
+            GearBotString::AutoResponseNotFound.as_str(),
+            GearBotString::RoleMenuTooManyOptions.as_str(),
+            GearBotString::RoleMenuDuplicateEmoji.as_str(),
+            GearBotString::RoleMenuUnassignableRole.as_str(),
+            GearBotString::RoleMenuRoleTooHigh.as_str(),
+            GearBotString::RoleMenuInvalidEmoji.as_str(),
+            GearBotString::RoleMenuTitle.as_str(),
+            GearBotString::RoleMenuCreated.as_str(),
+            GearBotString::NoRoleMenus.as_str(),
+            GearBotString::RoleMenusHeader.as_str(),
+            GearBotString::RoleMenuRemoved.as_str(),
+            GearBotString::RoleMenuNotFound.as_str(),
+            GearBotString::DatabaseUnavailable.as_str(),
+            GearBotString::ConfigImportInvalid.as_str(),
+            GearBotString::ConfigImported.as_str(),
+            GearBotString::ConfigImportedWithSkips.as_str(),
+            GearBotString::ModReasonRequired.as_str(),
+            GearBotString::MissingBotPermissions.as_str(),
+            GearBotString::MemberTimedOutText.as_str(),
+            GearBotString::MemberTimedOutEmbed.as_str(),
+            GearBotString::MemberTimedOutFooter.as_str(),
+            GearBotString::MemberTimeoutRemovedText.as_str(),
+            GearBotString::MemberTimeoutRemovedEmbed.as_str(),
+            GearBotString::MemberTimeoutRemovedFooter.as_str(),
+            GearBotString::TimeoutTooLong.as_str(),
+            GearBotString::TimeoutApplied.as_str(),
+            GearBotString::TimeoutRemoved.as_str(),
+            GearBotString::NoReasonGiven.as_str(),
+            GearBotString::MessageDeletedText.as_str(),
+            GearBotString::MessageDeletedEmbed.as_str(),
+            GearBotString::MessageDeletedFooter.as_str(),
+            GearBotString::MessageEditedText.as_str(),
+            GearBotString::MessageEditedEmbed.as_str(),
+            GearBotString::MessageEditedFooter.as_str(),
+            GearBotString::MessageLogChannels.as_str(),
+            GearBotString::NoMessageLogChannel.as_str(),
+            GearBotString::MessageLogChannelSet.as_str(),
+            GearBotString::LogChannelNoAccess.as_str(),
+            GearBotString::MessageLogChannelDisabled.as_str(),
+            GearBotString::CommandSuggestion.as_str(),
+            GearBotString::ActivityHeader.as_str(),
+            GearBotString::ActivityEmpty.as_str(),
+            GearBotString::ActivityDay.as_str(),
+            GearBotString::ActivityWeek.as_str(),
+            GearBotString::PurgeDryRun.as_str(),
+            GearBotString::PurgeDryRunSent.as_str(),
+            GearBotString::NewAccountKickedText.as_str(),
+            GearBotString::NewAccountKickedEmbed.as_str(),
+            GearBotString::NewAccountRestrictedText.as_str(),
+            GearBotString::NewAccountRestrictedEmbed.as_str(),
+            GearBotString::NewAccountGatedFooter.as_str(),
+            GearBotString::NewAccountKickedNotice.as_str(),
+            GearBotString::NewAccountRestrictedNotice.as_str(),
+            GearBotString::ReactionsHeader.as_str(),
+            GearBotString::ReactionsEmpty.as_str(),
+            GearBotString::PruneInvalidDays.as_str(),
+            GearBotString::PruneNothing.as_str(),
+            GearBotString::PrunePreview.as_str(),
+            GearBotString::PruneNotPending.as_str(),
+            GearBotString::PruneDone.as_str(),
+            GearBotString::MembersPrunedText.as_str(),
+            GearBotString::MembersPrunedEmbed.as_str(),
+            GearBotString::MembersPrunedFooter.as_str(),
+            GearBotString::SpamReasonDuplicates.as_str(),
+            GearBotString::SpamReasonMentions.as_str(),
+            GearBotString::SpamReasonRate.as_str(),
+            GearBotString::SpamActionDelete.as_str(),
+            GearBotString::SpamActionWarn.as_str(),
+            GearBotString::SpamActionMute.as_str(),
+            GearBotString::SpamDetectedText.as_str(),
+            GearBotString::SpamDetectedEmbed.as_str(),
+            GearBotString::SpamDetectedFooter.as_str(),
+            GearBotString::AutomodSpamWarning.as_str(),
+            GearBotString::UnderMaintenance.as_str(),
+            GearBotString::HierarchyTargetIsOwner.as_str(),
+            GearBotString::HierarchyAuthorTooLow.as_str(),
+            GearBotString::HierarchyBotTooLow.as_str(),
+            GearBotString::AltsHeader.as_str(),
+            GearBotString::AltsEntry.as_str(),
+            GearBotString::AltsEmpty.as_str(),
+            GearBotString::JoinLogChannels.as_str(),
+            GearBotString::NoJoinLogChannel.as_str(),
+            GearBotString::JoinLogChannelSet.as_str(),
+            GearBotString::JoinLogChannelDisabled.as_str(),
+            GearBotString::MemberJoinedText.as_str(),
+            GearBotString::MemberJoinedEmbed.as_str(),
+            GearBotString::MemberJoinedFooter.as_str(),
+            GearBotString::MemberLeftText.as_str(),
+            GearBotString::MemberLeftEmbed.as_str(),
+            GearBotString::MemberLeftFooter.as_str(),
+            GearBotString::MemberLeftNoRoles.as_str(),
+            GearBotString::MemberLeftUnknownDuration.as_str(),
+            GearBotString::SnowflakeInfo.as_str(),
+            GearBotString::SnowflakeInvalid.as_str(),
+            GearBotString::CommandsBusy.as_str(),
+            GearBotString::PurgeArchiveTranscript.as_str(),
+            GearBotString::PurgeArchiveSent.as_str(),
+            GearBotString::PurgeArchiveDmFailed.as_str(),
+            GearBotString::ModerationBotMissingPermissions.as_str(),
+            GearBotString::ModerationTargetNotFound.as_str(),
+            GearBotString::ModerationRefused.as_str(),
+            GearBotString::ModStatsTitle.as_str(),
+            GearBotString::ModStatsEntry.as_str(),
+            GearBotString::ModStatsEmpty.as_str(),
+            GearBotString::HistoryImportInvalid.as_str(),
+            GearBotString::HistoryImportTooLarge.as_str(),
+            GearBotString::HistoryImported.as_str(),
+            GearBotString::HistoryImportRejectedEntry.as_str(),
+            GearBotString::PurgeFilterInvalid.as_str(),
+            GearBotString::PurgeFilterTimeout.as_str(),
+            GearBotString::NamesHeader.as_str(),
+            GearBotString::NamesUsernames.as_str(),
+            GearBotString::NamesNicknames.as_str(),
+            GearBotString::NamesEntry.as_str(),
+            GearBotString::NamesNoNickname.as_str(),
+            GearBotString::NamesEmpty.as_str(),
+            GearBotString::AttachmentScannedText.as_str(),
+            GearBotString::AttachmentScannedEmbed.as_str(),
+            GearBotString::AttachmentScannedFooter.as_str(),
+            GearBotString::ScanOutcomeDelete.as_str(),
+            GearBotString::ScanOutcomeAlert.as_str(),
+            GearBotString::ScanOutcomeBan.as_str(),
+            GearBotString::ScanOutcomeUnscanned.as_str(),
+            GearBotString::ReasonCaseNotFound.as_str(),
+            GearBotString::ReasonNotAllowed.as_str(),
+            GearBotString::ReasonUpdated.as_str(),
+            GearBotString::UserinfoStatus.as_str(),
+            GearBotString::UserinfoStatusActivity.as_str(),
+            GearBotString::OnlineCount.as_str(),
+            GearBotString::OnlineNotTracked.as_str(),
+            GearBotString::AutomodTestNoMessages.as_str(),
+            GearBotString::AutomodTestResult.as_str(),
+            GearBotString::CommandFailed.as_str(),
+            GearBotString::CommandFailedDetailed.as_str(),
+            GearBotString::SimulateJoinResult.as_str(),
+            GearBotString::SimulateJoinLogged.as_str(),
+            GearBotString::SimulateJoinNotLogged.as_str(),
+            GearBotString::SimulateJoinBot.as_str(),
+            GearBotString::SimulateJoinGatePassed.as_str(),
+            GearBotString::SimulateJoinGateKick.as_str(),
+            GearBotString::SimulateJoinGateRole.as_str(),
+            GearBotString::SimulateJoinMissingPermission.as_str(),
+            GearBotString::SimulateJoinRaidKick.as_str(),
+            GearBotString::SimulateJoinRaidAllowed.as_str(),
+            GearBotString::SimulateJoinGreeting.as_str(),
+            GearBotString::SimulateJoinGreetingReaction.as_str(),
+            GearBotString::JumboInvalid.as_str(),
+            GearBotString::ContentStorageDisabled.as_str(),
+            GearBotString::CommandOnCooldown.as_str(),
+            GearBotString::HelpOverview.as_str(),
+            GearBotString::HelpGroup.as_str(),
+            GearBotString::HelpNothingAvailable.as_str(),
+            GearBotString::HelpUnknownCommand.as_str(),
+            GearBotString::HelpCommand.as_str(),
+            GearBotString::HelpNoDescription.as_str(),
+            GearBotString::HelpAliases.as_str(),
+            GearBotString::HelpSubcommands.as_str(),
+            GearBotString::HelpCooldown.as_str(),
+            GearBotString::HelpGuildOnly.as_str(),
+            GearBotString::AboutCommandDescription.as_str(),
+            GearBotString::CoinflipDescription.as_str(),
+            GearBotString::HelpDescription.as_str(),
+            GearBotString::JumboDescription.as_str(),
+            GearBotString::PingDescription.as_str(),
+            GearBotString::QuoteDescription.as_str(),
+            GearBotString::SnowflakeDescription.as_str(),
+            GearBotString::UidDescription.as_str(),
+            GearBotString::TranscriptDescription.as_str(),
+            GearBotString::AutomodTestDescription.as_str(),
+            GearBotString::SpamReasonCrossChannel.as_str(),
+            GearBotString::SpamReasonBlockedAttachment.as_str(),
+            GearBotString::SpamReasonScanFlagged.as_str(),
+            GearBotString::SpamReasonScanFailed.as_str(),
+            GearBotString::SpamActionAlert.as_str(),
+            GearBotString::SpamActionBan.as_str(),
+            GearBotString::HistoryExported.as_str(),
+            GearBotString::HistoryExportEmpty.as_str(),
+            GearBotString::HistoryExportTooLarge.as_str(),
+            GearBotString::ExportHistoryDescription.as_str(),
+            GearBotString::DurationYears.as_str(),
+            GearBotString::DurationMonths.as_str(),
+            GearBotString::DurationWeeks.as_str(),
+            GearBotString::DurationDays.as_str(),
+            GearBotString::DurationHours.as_str(),
+            GearBotString::DurationMinutes.as_str(),
+            GearBotString::DurationSeconds.as_str(),
+            GearBotString::RoleMenusCleanedUpText.as_str(),
+            GearBotString::RoleMenusCleanedUpEmbed.as_str(),
+            GearBotString::RoleMenusCleanedUpFooter.as_str(),
+            GearBotString::TranscriptSizeTruncated.as_str(),
+            GearBotString::TranscriptProgress.as_str(),
+            GearBotString::TranscriptDone.as_str(),
+            GearBotString::MissingBotPermission.as_str(),
+            GearBotString::CommandGloballyDisabled.as_str(),
+            GearBotString::SpamReasonInvite.as_str(),
+            GearBotString::SpamReasonLink.as_str(),
+            GearBotString::AutomodLinkNotice.as_str(),
+            GearBotString::HelpUsage.as_str(),
+            GearBotString::JumboUsage.as_str(),
+            GearBotString::HelpCommandUsage.as_str(),
+            GearBotString::QuoteUsage.as_str(),
+            GearBotString::SnowflakeUsage.as_str(),
+            GearBotString::UidUsage.as_str(),
+            GearBotString::TranscriptUsage.as_str(),
+            GearBotString::AutomodTestUsage.as_str(),
+            GearBotString::ExportHistoryUsage.as_str(),
+            GearBotString::ParseMissingArgument.as_str(),
+            GearBotString::ParseExpectedNumber.as_str(),
+            GearBotString::ParseExpectedDuration.as_str(),
+            GearBotString::ParseExpectedUser.as_str(),
+            GearBotString::ParseExpectedChannel.as_str(),
+            GearBotString::ParseExpectedRole.as_str(),
+            GearBotString::DeletedAttachmentKept.as_str(),
+            GearBotString::DeletedAttachmentNotKept.as_str(),
+            GearBotString::CoinflipUsage.as_str(),
+            GearBotString::ConfigDescription.as_str(),
+            GearBotString::ConfigGetDescription.as_str(),
+            GearBotString::ConfigGetPrettyDescription.as_str(),
+            GearBotString::ConfigSetDescription.as_str(),
+            GearBotString::ConfigSetUsage.as_str(),
+            GearBotString::ConfigResetDescription.as_str(),
+            GearBotString::ConfigColorDescription.as_str(),
+            GearBotString::ConfigColorUsage.as_str(),
+            GearBotString::ConfigFooterDescription.as_str(),
+            GearBotString::ConfigFooterUsage.as_str(),
+            GearBotString::ConfigFooterIconDescription.as_str(),
+            GearBotString::ConfigFooterIconUsage.as_str(),
+            GearBotString::ConfigTimezoneDescription.as_str(),
+            GearBotString::ConfigTimezoneUsage.as_str(),
+            GearBotString::ConfigCleanupDescription.as_str(),
+            GearBotString::ConfigCleanupUsage.as_str(),
+            GearBotString::ConfigNicknameDescription.as_str(),
+            GearBotString::ConfigNicknameUsage.as_str(),
+            GearBotString::ConfigPlaintextDescription.as_str(),
+            GearBotString::ConfigPlaintextUsage.as_str(),
+            GearBotString::ConfigQuietDescription.as_str(),
+            GearBotString::ConfigQuietUsage.as_str(),
+            GearBotString::ConfigDeleteCommandsDescription.as_str(),
+            GearBotString::ConfigDeleteCommandsUsage.as_str(),
+            GearBotString::ConfigErrorsDescription.as_str(),
+            GearBotString::ConfigErrorsUsage.as_str(),
+            GearBotString::ConfigSystemMessagesDescription.as_str(),
+            GearBotString::ConfigSystemMessagesUsage.as_str(),
+            GearBotString::ConfigContentStorageDescription.as_str(),
+            GearBotString::ConfigContentStorageUsage.as_str(),
+            GearBotString::ConfigStorageExcludeDescription.as_str(),
+            GearBotString::ConfigStorageExcludeUsage.as_str(),
+            GearBotString::ConfigRoleMenuMessagesDescription.as_str(),
+            GearBotString::ConfigRoleMenuMessagesUsage.as_str(),
+            GearBotString::ConfigDeleteGraceDescription.as_str(),
+            GearBotString::ConfigDeleteGraceUsage.as_str(),
+            GearBotString::ConfigLogCommandDescription.as_str(),
+            GearBotString::ConfigLogCommandUsage.as_str(),
+            GearBotString::ConfigCommandLevelDescription.as_str(),
+            GearBotString::ConfigCommandLevelUsage.as_str(),
+            GearBotString::ConfigAutomodOrderDescription.as_str(),
+            GearBotString::ConfigAutomodOrderUsage.as_str(),
+            GearBotString::ConfigCooldownBypassDescription.as_str(),
+            GearBotString::ConfigCooldownBypassUsage.as_str(),
+            GearBotString::ConfigCommandChannelsDescription.as_str(),
+            GearBotString::ConfigCommandChannelsUsage.as_str(),
+            GearBotString::ConfigJoinLogDescription.as_str(),
+            GearBotString::ConfigJoinLogUsage.as_str(),
+            GearBotString::ConfigMessageLogDescription.as_str(),
+            GearBotString::ConfigMessageLogUsage.as_str(),
+            GearBotString::ConfigExportDescription.as_str(),
+            GearBotString::ConfigImportDescription.as_str(),
+            GearBotString::ConfigImportUsage.as_str(),
+            GearBotString::AnnouncementDescription.as_str(),
+            GearBotString::AnnouncementAddDescription.as_str(),
+            GearBotString::AnnouncementAddUsage.as_str(),
+            GearBotString::AnnouncementListDescription.as_str(),
+            GearBotString::AnnouncementRemoveDescription.as_str(),
+            GearBotString::AnnouncementRemoveUsage.as_str(),
+            GearBotString::AutoResponseDescription.as_str(),
+            GearBotString::AutoResponseAddDescription.as_str(),
+            GearBotString::AutoResponseAddUsage.as_str(),
+            GearBotString::AutoResponseListDescription.as_str(),
+            GearBotString::AutoResponseRemoveDescription.as_str(),
+            GearBotString::AutoResponseRemoveUsage.as_str(),
+            GearBotString::RoleMenuDescription.as_str(),
+            GearBotString::RoleMenuCreateDescription.as_str(),
+            GearBotString::RoleMenuCreateUsage.as_str(),
+            GearBotString::RoleMenuListDescription.as_str(),
+            GearBotString::RoleMenuRemoveDescription.as_str(),
+            GearBotString::RoleMenuRemoveUsage.as_str(),
+            GearBotString::DisableDescription.as_str(),
+            GearBotString::DisableUsage.as_str(),
+            GearBotString::EnableDescription.as_str(),
+            GearBotString::EnableUsage.as_str(),
+            GearBotString::ImportHistoryDescription.as_str(),
+            GearBotString::SimulateJoinDescription.as_str(),
+            GearBotString::SimulateJoinUsage.as_str(),
+            GearBotString::UserinfoDescription.as_str(),
+            GearBotString::UserinfoUsage.as_str(),
+            GearBotString::PurgeDescription.as_str(),
+            GearBotString::PurgeUsage.as_str(),
+            GearBotString::PurgeUntilDescription.as_str(),
+            GearBotString::PurgeUntilUsage.as_str(),
+            GearBotString::DelwarnDescription.as_str(),
+            GearBotString::DelwarnUsage.as_str(),
+            GearBotString::ReasonDescription.as_str(),
+            GearBotString::ReasonUsage.as_str(),
+            GearBotString::ClearWarningsDescription.as_str(),
+            GearBotString::ClearWarningsUsage.as_str(),
+            GearBotString::AddNoteDescription.as_str(),
+            GearBotString::AddNoteUsage.as_str(),
+            GearBotString::NotesDescription.as_str(),
+            GearBotString::NotesUsage.as_str(),
+            GearBotString::DelNoteDescription.as_str(),
+            GearBotString::DelNoteUsage.as_str(),
+            GearBotString::AltsDescription.as_str(),
+            GearBotString::AltsUsage.as_str(),
+            GearBotString::NamesDescription.as_str(),
+            GearBotString::NamesUsage.as_str(),
+            GearBotString::ModStatsDescription.as_str(),
+            GearBotString::ModStatsUsage.as_str(),
+            GearBotString::MassBanDescription.as_str(),
+            GearBotString::MassBanUsage.as_str(),
+            GearBotString::MassUnbanDescription.as_str(),
+            GearBotString::MassUnbanUsage.as_str(),
+            GearBotString::PruneDescription.as_str(),
+            GearBotString::PruneUsage.as_str(),
+            GearBotString::RaidModeDescription.as_str(),
+            GearBotString::RaidModeUsage.as_str(),
+            GearBotString::TimeoutDescription.as_str(),
+            GearBotString::TimeoutUsage.as_str(),
+            GearBotString::UntimeoutDescription.as_str(),
+            GearBotString::UntimeoutUsage.as_str(),
+            GearBotString::ActivityDescription.as_str(),
+            GearBotString::ActivityUsage.as_str(),
+            GearBotString::ReactionsDescription.as_str(),
+            GearBotString::ReactionsUsage.as_str(),
+            GearBotString::StatsDescription.as_str(),
+            GearBotString::StatsAttachmentsDescription.as_str(),
+            GearBotString::CheckDescription.as_str(),
+            GearBotString::CheckCacheDescription.as_str(),
+            GearBotString::CacheStatsDescription.as_str(),
+            GearBotString::CacheStatsUsage.as_str(),
+            GearBotString::ClearCacheDescription.as_str(),
+            GearBotString::ClearCacheUsage.as_str(),
+            GearBotString::MaintenanceDescription.as_str(),
+            GearBotString::MaintenanceUsage.as_str(),
+            GearBotString::KillswitchDescription.as_str(),
+            GearBotString::KillswitchDisableDescription.as_str(),
+            GearBotString::KillswitchDisableUsage.as_str(),
+            GearBotString::KillswitchEnableDescription.as_str(),
+            GearBotString::KillswitchEnableUsage.as_str(),
+            GearBotString::KillswitchListDescription.as_str(),
+            GearBotString::ReloadLoggingDescription.as_str(),
+            GearBotString::TestWebhooksDescription.as_str(),
+            GearBotString::SetAvatarDescription.as_str(),
+            GearBotString::SetUsernameDescription.as_str(),
+            GearBotString::SetUsernameUsage.as_str(),
+            GearBotString::RotateKeysDescription.as_str(),
+            GearBotString::RotateGuildKeyDescription.as_str(),
+            GearBotString::RotateGuildKeyUsage.as_str(),
+            GearBotString::VerifyMessagesDescription.as_str(),
+            GearBotString::VerifyMessagesUsage.as_str(),
+            GearBotString::TranslationsDescription.as_str(),
+            GearBotString::TranslationsUsage.as_str(),
+            GearBotString::ReloadTranslationsDescription.as_str(),
+            GearBotString::WebhookDescription.as_str(),
+            GearBotString::WebhookUsage.as_str(),
+            GearBotString::ShardsDescription.as_str(),
+            GearBotString::HealthDescription.as_str(),
+            GearBotString::RedisTestDescription.as_str(),
+            GearBotString::PermsDescription.as_str(),
+            GearBotString::PermsUsage.as_str(),
+            GearBotString::TestDescription.as_str(),
+            GearBotString::EmojiDescription.as_str(),
+            GearBotString::EmojiListDescription.as_str(),
+            GearBotString::EmojiStatsDescription.as_str(),
+            GearBotString::OnlineDescription.as_str(),
+            GearBotString::SimulateJoinWelcomeImage.as_str(),
+            GearBotString::WelcomeImageMemberNumber.as_str(),
+            GearBotString::ConfigWelcomeImageDescription.as_str(),
+            GearBotString::ConfigWelcomeImageUsage.as_str(),
+            GearBotString::ConfigWelcomeBackgroundDescription.as_str(),
+            GearBotString::ConfigWelcomeBackgroundUsage.as_str(),
+            GearBotString::ConfigWelcomeColorDescription.as_str(),
+            GearBotString::ConfigWelcomeColorUsage.as_str(),
         ];
     }
 
@@ -331,4 +1865,31 @@ mod tests {
         // Make sure we exhausted everything
         assert_eq!(covered, ALL_TRANSLATION_STR_KEYS.len())
     }
+
+    #[test]
+    fn default_locale_validates_clean() {
+        let report = validate_locale(&DEFAULT_LANG).unwrap();
+        assert!(report.is_clean(), "{}", report);
+    }
+
+    #[test]
+    fn fallback_chain_strips_subtags_first() {
+        let translations = Translations {
+            bundles: HashMap::new(),
+            fallback: DEFAULT_LANG,
+        };
+
+        assert_eq!(
+            translations.fallback_chain(&langid!("pt-BR")),
+            vec![langid!("pt-BR"), langid!("pt"), DEFAULT_LANG]
+        );
+        assert_eq!(
+            translations.fallback_chain(&langid!("nl")),
+            vec![langid!("nl"), DEFAULT_LANG]
+        );
+        assert_eq!(
+            translations.fallback_chain(&DEFAULT_LANG),
+            vec![DEFAULT_LANG, langid!("en")]
+        );
+    }
 }
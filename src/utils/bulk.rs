@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use hyper::StatusCode;
+use twilight_http::Error;
+
+use crate::core::CommandContext;
+use crate::translation::{FluArgs, GearBotString};
+use crate::utils::text;
+
+/// How many different failure reasons are spelled out in a report.
+const MAX_REPORTED_REASONS: usize = 3;
+/// How many of the targets that failed for a reason are listed with it.
+const MAX_TARGETS_PER_REASON: usize = 10;
+const MAX_REASON_LENGTH: usize = 100;
+
+/// Collects the outcome of every step of a command that works through many targets,
+/// so one failing halfway doesn't stop the rest and they can all be reported together.
+pub struct BulkOutcome<T> {
+    succeeded: Vec<T>,
+    failures: HashMap<String, Vec<T>>,
+}
+
+impl<T> Default for BulkOutcome<T> {
+    fn default() -> Self {
+        BulkOutcome {
+            succeeded: vec![],
+            failures: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Display> BulkOutcome<T> {
+    pub fn success(&mut self, target: T) {
+        self.succeeded.push(target);
+    }
+
+    pub fn failure(&mut self, target: T, reason: impl Display) {
+        let reason = text::truncate_with_ellipsis(&reason.to_string(), MAX_REASON_LENGTH);
+
+        self.failures.entry(reason).or_insert_with(Vec::new).push(target);
+    }
+
+    /// Records the result of a discord api call, failures are grouped by their status.
+    pub fn record_http<R>(&mut self, target: T, result: Result<R, Error>) {
+        match result {
+            Ok(_) => self.success(target),
+            Err(Error::Response { status, .. }) => self.failure(target, describe_status(status)),
+            Err(e) => self.failure(target, e),
+        }
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.succeeded.len()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failures.values().map(Vec::len).sum()
+    }
+
+    /// Lists the most common reasons things failed, with some of the targets they failed for.
+    ///
+    /// Returns an empty string when nothing failed, so it can always be appended to a summary.
+    pub fn failure_report(&self, ctx: &CommandContext) -> String {
+        let mut reasons: Vec<(&String, &Vec<T>)> = self.failures.iter().collect();
+        reasons.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+
+        let mut report = String::new();
+        for (reason, targets) in reasons.iter().take(MAX_REPORTED_REASONS) {
+            let mut listed = targets
+                .iter()
+                .take(MAX_TARGETS_PER_REASON)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            if targets.len() > MAX_TARGETS_PER_REASON {
+                listed += ", …";
+            }
+
+            let args = FluArgs::with_capacity(3)
+                .add("reason", reason.as_str())
+                .add("count", targets.len())
+                .add("targets", listed)
+                .generate();
+            report += "\n";
+            report += &ctx.translate_with_args(GearBotString::BulkFailureReason, &args);
+        }
+
+        if reasons.len() > MAX_REPORTED_REASONS {
+            let remaining: usize = reasons[MAX_REPORTED_REASONS..]
+                .iter()
+                .map(|(_, targets)| targets.len())
+                .sum();
+            let args = FluArgs::with_capacity(1).add("count", remaining).generate();
+            report += "\n";
+            report += &ctx.translate_with_args(GearBotString::BulkOtherFailures, &args);
+        }
+
+        report
+    }
+}
+
+fn describe_status(status: StatusCode) -> String {
+    match status.canonical_reason() {
+        Some(reason) => format!("{} {}", status.as_u16(), reason),
+        None => status.as_u16().to_string(),
+    }
+}
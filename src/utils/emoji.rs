@@ -27,6 +27,7 @@ define_emoji!(
     Left => "⬅️",
     Right => "➡️",
     Online => "🟢",
+    Trash => "🗑️",
 
     StaffBadge => "",
     PartnerBadge => "",
@@ -53,6 +54,22 @@ pub struct EmojiInfo {
     pub id: u64,
 }
 
+impl EmojiInfo {
+    /// The full size image of the emoji, animated ones as gif.
+    pub fn url(&self) -> String {
+        format!(
+            "https://cdn.discordapp.com/emojis/{}.{}",
+            self.id,
+            if self.animated { "gif" } else { "png" }
+        )
+    }
+
+    /// The emoji the way it's written in chat.
+    pub fn for_chat(&self) -> String {
+        format!("<{}:{}:{}>", if self.animated { "a" } else { "" }, self.name, self.id)
+    }
+}
+
 pub static EMOJI_OVERRIDES: OnceCell<HashMap<String, EmojiOverride>> = OnceCell::new();
 
 #[macro_use]
@@ -0,0 +1,162 @@
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageOutputFormat, Rgba, RgbaImage};
+use imageproc::drawing::draw_text_mut;
+use lazy_static::lazy_static;
+use rusttype::{point, Font, Scale};
+
+use crate::error::ImageError;
+
+pub const WELCOME_IMAGE_WIDTH: u32 = 1024;
+pub const WELCOME_IMAGE_HEIGHT: u32 = 450;
+
+const AVATAR_SIZE: u32 = 220;
+const AVATAR_TOP: u32 = 40;
+const NAME_TOP: u32 = 285;
+const NAME_SIZE: f32 = 64.0;
+const NUMBER_TOP: u32 = 370;
+const NUMBER_SIZE: f32 = 40.0;
+/// Text that doesn't fit gets smaller, down to this size, before it gets cut off at the edges.
+const MIN_TEXT_SIZE: f32 = 24.0;
+/// Space kept free on both sides of the text.
+const TEXT_MARGIN: u32 = 40;
+
+/// Drawn behind the avatar and text when the guild didn't pick a background.
+const PLAIN_BACKGROUND: Rgba<u8> = Rgba([0x2f, 0x31, 0x36, 0xff]);
+
+lazy_static! {
+    static ref FONT: Font<'static> = Font::try_from_bytes(include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/fonts/DejaVuSans-Bold.ttf"
+    )))
+    .expect("The bundled font is broken");
+}
+
+/// Reads an image in any of the formats discord hands out, like the avatars and attachments it serves.
+pub fn decode(data: &[u8]) -> Result<DynamicImage, ImageError> {
+    image::load_from_memory(data).map_err(ImageError::Decode)
+}
+
+/// Scales and crops an image to exactly cover a welcome image, ready to be used as its background.
+pub fn welcome_background(image: &DynamicImage) -> RgbaImage {
+    image
+        .resize_to_fill(WELCOME_IMAGE_WIDTH, WELCOME_IMAGE_HEIGHT, FilterType::Triangle)
+        .to_rgba()
+}
+
+pub fn plain_background() -> RgbaImage {
+    RgbaImage::from_pixel(WELCOME_IMAGE_WIDTH, WELCOME_IMAGE_HEIGHT, PLAIN_BACKGROUND)
+}
+
+/// Draws the avatar as a circle with the name and member number below it onto the background, as png.
+///
+/// Without an avatar a circle in the text color takes its place.
+pub fn render_welcome_image(
+    background: &RgbaImage,
+    avatar: Option<&DynamicImage>,
+    name: &str,
+    member_number: Option<&str>,
+    text_color: u32,
+) -> Result<Vec<u8>, ImageError> {
+    let mut canvas = background.clone();
+    let color = Rgba([
+        (text_color >> 16) as u8,
+        (text_color >> 8) as u8,
+        text_color as u8,
+        0xff,
+    ]);
+
+    let avatar = match avatar {
+        Some(avatar) => avatar
+            .resize_exact(AVATAR_SIZE, AVATAR_SIZE, FilterType::Triangle)
+            .to_rgba(),
+        None => RgbaImage::from_pixel(AVATAR_SIZE, AVATAR_SIZE, color),
+    };
+    imageops::overlay(
+        &mut canvas,
+        &circle(avatar),
+        (WELCOME_IMAGE_WIDTH - AVATAR_SIZE) / 2,
+        AVATAR_TOP,
+    );
+
+    draw_centered(&mut canvas, color, NAME_TOP, NAME_SIZE, name);
+    if let Some(member_number) = member_number {
+        draw_centered(&mut canvas, color, NUMBER_TOP, NUMBER_SIZE, member_number);
+    }
+
+    let mut png = vec![];
+    DynamicImage::ImageRgba8(canvas)
+        .write_to(&mut png, ImageOutputFormat::Png)
+        .map_err(ImageError::Encode)?;
+    Ok(png)
+}
+
+/// Makes everything outside of the circle that fits in the (square) image transparent.
+fn circle(mut image: RgbaImage) -> RgbaImage {
+    let radius = image.width() as f32 / 2.0;
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let dx = x as f32 + 0.5 - radius;
+        let dy = y as f32 + 0.5 - radius;
+        if dx * dx + dy * dy > radius * radius {
+            pixel[3] = 0;
+        }
+    }
+    image
+}
+
+fn draw_centered(canvas: &mut RgbaImage, color: Rgba<u8>, top: u32, preferred_size: f32, text: &str) {
+    let scale = fit(text, preferred_size);
+    let left = WELCOME_IMAGE_WIDTH.saturating_sub(text_width(text, scale)) / 2;
+    draw_text_mut(canvas, color, left, top, scale, &FONT, text);
+}
+
+/// The preferred size, unless the text is too wide for it, then the size at which it does fit.
+fn fit(text: &str, preferred_size: f32) -> Scale {
+    let available = (WELCOME_IMAGE_WIDTH - 2 * TEXT_MARGIN) as f32;
+    let width = text_width(text, Scale::uniform(preferred_size)) as f32;
+    if width <= available {
+        Scale::uniform(preferred_size)
+    } else {
+        Scale::uniform((preferred_size * available / width).max(MIN_TEXT_SIZE))
+    }
+}
+
+fn text_width(text: &str, scale: Scale) -> u32 {
+    FONT.layout(text, scale, point(0.0, 0.0))
+        .filter_map(|glyph| glyph.pixel_bounding_box())
+        .map(|bounds| bounds.max.x.max(0) as u32)
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welcome_image_is_a_png_of_the_right_size() {
+        let png = render_welcome_image(&plain_background(), None, "Someone", Some("Member #12"), 0xFF_FFFF).unwrap();
+        let image = decode(&png).unwrap();
+        assert_eq!(
+            image.to_rgba().dimensions(),
+            (WELCOME_IMAGE_WIDTH, WELCOME_IMAGE_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn long_names_get_smaller() {
+        assert_eq!(fit("Someone", NAME_SIZE), Scale::uniform(NAME_SIZE));
+
+        let long_name = "W".repeat(32);
+        let scale = fit(&long_name, NAME_SIZE);
+        assert!(scale.x < NAME_SIZE);
+        assert!(text_width(&long_name, scale) <= WELCOME_IMAGE_WIDTH - 2 * TEXT_MARGIN);
+    }
+
+    #[test]
+    fn avatars_are_cut_into_circles() {
+        let avatar = circle(RgbaImage::from_pixel(AVATAR_SIZE, AVATAR_SIZE, Rgba([0xff; 4])));
+        assert_eq!(avatar.get_pixel(0, 0)[3], 0);
+        assert_eq!(avatar.get_pixel(AVATAR_SIZE - 1, AVATAR_SIZE - 1)[3], 0);
+        assert_eq!(avatar.get_pixel(AVATAR_SIZE / 2, AVATAR_SIZE / 2)[3], 0xff);
+    }
+}
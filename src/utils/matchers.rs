@@ -21,10 +21,49 @@ pub fn contains_role_id(msg: &str) -> bool {
     ROLE_ID_MATCHER.is_match(msg)
 }
 
+/// Extracts the role id out of a message that is nothing but a role mention.
+pub fn get_role_mention(msg: &str) -> Option<u64> {
+    ROLE_MENTION_MATCHER_SOLO
+        .captures(msg)
+        .and_then(|c| c.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// How many users and roles the message mentions, every mention counts even when repeated.
+pub fn count_mentions(msg: &str) -> usize {
+    ID_MATCHER.find_iter(msg).count() + ROLE_ID_MATCHER.find_iter(msg).count()
+}
+
 pub fn contains_channel_id(msg: &str) -> bool {
     CHANNEL_ID_MATCHER.is_match(msg)
 }
 
+/// Extracts the channel id out of a message that is nothing but a channel mention.
+pub fn get_channel_mention(msg: &str) -> Option<u64> {
+    CHANNEL_MENTION_MATCHER_SOLO
+        .captures(msg)
+        .and_then(|c| c.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Collects every raw or mentioned snowflake in the text, for lists of users pasted in or uploaded as a file.
+pub fn get_ids(msg: &str) -> Vec<u64> {
+    RAW_ID_MATCHER
+        .find_iter(msg)
+        .filter_map(|id| id.as_str().parse().ok())
+        .collect()
+}
+
+/// Removes all raw and mentioned user ids from the text, leaving whatever else was said.
+pub fn strip_ids(msg: &str) -> String {
+    let without_mentions = MENTION_MATCHER.replace_all(msg, " ");
+    RAW_ID_MATCHER
+        .replace_all(&without_mentions, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn contains_mention(msg: &str) -> bool {
     MENTION_MATCHER.is_match(msg)
 }
@@ -61,34 +100,82 @@ pub fn starts_with_number(msg: &str) -> bool {
 }
 
 pub fn contains_invite_link(msg: &str) -> bool {
-    if let Some(url) = URL_MATCHER.captures(msg).and_then(|caps| caps.get(0)) {
-        let parsed = Url::parse(url.as_str()).unwrap();
-        let host = match parsed.host().unwrap() {
-            Host::Domain(host) => host,
-            // If it doesn't have a domain type host, then its not an invite link
-            _ => return false,
-        };
-
-        if KNOWN_INVITE_DOMAINS.contains(&host) {
-            // discordapp.com and discord.com
-            if host == KNOWN_INVITE_DOMAINS[0] || host == KNOWN_INVITE_DOMAINS[1] {
-                let mut segments = match parsed.path_segments() {
-                    Some(segs) => segs,
-                    None => return false,
-                };
-
-                if segments.any(|s| s.contains("invite")) {
-                    return true;
-                }
-            } else {
-                // The other links are used solely for inviting people to a server
-                // in one form or another
-                return true;
-            }
+    !get_invite_links(msg).is_empty()
+}
+
+/// An invite link found in a message.
+#[derive(Debug, PartialEq)]
+pub enum InviteLink {
+    /// Discord's own invite links, the code can be looked up to see which server it leads to.
+    Code(String),
+    /// Third party invite sites, there is no telling which server those lead to.
+    Redirect,
+}
+
+pub fn get_invite_links(msg: &str) -> Vec<InviteLink> {
+    URL_MATCHER
+        .find_iter(msg)
+        .filter_map(|url| get_invite_link(url.as_str()))
+        .collect()
+}
+
+fn get_invite_link(url: &str) -> Option<InviteLink> {
+    let parsed = Url::parse(url).ok()?;
+    let host = match parsed.host()? {
+        Host::Domain(host) => host.trim_start_matches("www."),
+        // If it doesn't have a domain type host, then its not an invite link
+        _ => return None,
+    };
+
+    if !KNOWN_INVITE_DOMAINS.contains(&host) {
+        return None;
+    }
+
+    let mut segments = parsed.path_segments()?.filter(|segment| !segment.is_empty());
+    // discordapp.com and discord.com
+    if host == KNOWN_INVITE_DOMAINS[0] || host == KNOWN_INVITE_DOMAINS[1] {
+        if segments.next()? != "invite" {
+            return None;
         }
+    } else if host != KNOWN_INVITE_DOMAINS[2] {
+        // The other links are used solely for inviting people to a server
+        // in one form or another
+        return Some(InviteLink::Redirect);
     }
 
-    false
+    segments.next().map(|code| InviteLink::Code(code.to_string()))
+}
+
+/// The domains of all links in the message, invite links are left out.
+pub fn get_link_domains(msg: &str) -> Vec<String> {
+    URL_MATCHER
+        .find_iter(msg)
+        .filter(|url| get_invite_link(url.as_str()).is_none())
+        .filter_map(|url| match Url::parse(url.as_str()).ok()?.host()? {
+            Host::Domain(host) => Some(host.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// If the domain is on the list, subdomains of domains on the list are allowed as well.
+pub fn is_domain_allowed(domain: &str, allowed: &[String]) -> bool {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    allowed.iter().any(|allowed| {
+        let allowed = allowed.trim_start_matches("*.").trim_matches('.').to_lowercase();
+        domain == allowed || domain.ends_with(&format!(".{}", allowed))
+    })
+}
+
+/// Returns the first extension of the filename that is on the blocklist, if any.
+///
+/// Every part after the first dot is checked so double extensions like ``file.pdf.exe`` can't sneak past.
+pub fn get_blocked_extension<'a>(filename: &'a str, blocked: &[String]) -> Option<&'a str> {
+    filename.split('.').skip(1).find(|ext| {
+        blocked
+            .iter()
+            .any(|b| b.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    })
 }
 
 pub fn get_emoji_parts(msg: &str) -> Vec<EmojiInfo> {
@@ -110,7 +197,10 @@ pub fn get_emoji_parts(msg: &str) -> Vec<EmojiInfo> {
 lazy_static! {
     static ref ID_MATCHER: Regex = Regex::new(r"<@!?([0-9]+)>").unwrap();
     static ref ROLE_ID_MATCHER: Regex = Regex::new(r"<@&([0-9]+)>").unwrap();
+    static ref ROLE_MENTION_MATCHER_SOLO: Regex = Regex::new(r"^<@&(\d+)>$").unwrap();
     static ref CHANNEL_ID_MATCHER: Regex = Regex::new(r"<#([0-9]+)>").unwrap();
+    static ref RAW_ID_MATCHER: Regex = Regex::new(r"\b[0-9]{15,20}\b").unwrap();
+    static ref CHANNEL_MENTION_MATCHER_SOLO: Regex = Regex::new(r"^<#(\d+)>$").unwrap();
     static ref MENTION_MATCHER: Regex = Regex::new(r"<@!?\d+>").unwrap();
     static ref MENTION_MATCHER_SOLO: Regex = Regex::new(r"^<@!?(\d+)>$").unwrap();
     static ref EMOJI_MATCHER: Regex = Regex::new(r"<(a?):([^:\n]+):([0-9]+)>").unwrap();
@@ -160,6 +250,13 @@ mod tests {
         assert_eq!(contains_role_id(control), false);
     }
 
+    #[test]
+    fn role_mention_extraction_works() {
+        assert_eq!(get_role_mention("<@&3892320392392>"), Some(3892320392392));
+        assert_eq!(get_role_mention("<@&3892320392392> hi"), None);
+        assert_eq!(get_role_mention("<@!3892320392392>"), None);
+    }
+
     #[test]
     fn channel_id_works() {
         let msg = "<#7012116760323232>";
@@ -169,6 +266,30 @@ mod tests {
         assert_eq!(contains_channel_id(control), false);
     }
 
+    #[test]
+    fn channel_mention_extraction_works() {
+        assert_eq!(get_channel_mention("<#7012116760323232>"), Some(7012116760323232));
+        assert_eq!(get_channel_mention("<#7012116760323232> hi"), None);
+        assert_eq!(get_channel_mention("<@!39238293809232>"), None);
+    }
+
+    #[test]
+    fn id_list_extraction_works() {
+        let list = "282830930237292, <@!383738338398392>\n7012116760323232 hello 1234";
+
+        assert_eq!(get_ids(list), vec![282830930237292, 383738338398392, 7012116760323232]);
+        assert_eq!(get_ids("99999999999999999999999"), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn id_stripping_works() {
+        assert_eq!(
+            strip_ids("282830930237292 <@!383738338398392> raid\nbots 1234"),
+            "raid bots 1234"
+        );
+        assert_eq!(strip_ids("282830930237292 7012116760323232"), "");
+    }
+
     #[test]
     fn mention_matcher_works() {
         // the regex differently.
@@ -181,6 +302,15 @@ mod tests {
         assert_eq!(contains_mention(control), false);
     }
 
+    #[test]
+    fn mentions_are_counted() {
+        assert_eq!(count_mentions("<@1234567890> <@!1234567890> and <@&987654321>"), 3);
+        assert_eq!(
+            count_mentions("<#1234567890> is a channel, 1234567890 just a number"),
+            0
+        );
+    }
+
     #[test]
     fn url_matcher_works() {
         let msg = "Hey, check out this not shady website: https://google.com";
@@ -247,4 +377,53 @@ mod tests {
 
         assert_eq!(contains_invite_link(control), false);
     }
+
+    #[test]
+    fn invite_extraction_works() {
+        let msg = "join https://example.com and https://discord.gg/vddW3D9 or https://www.discord.com/invite/abc/";
+        let msg2 = "https://discord.me/whoknowswhatshere";
+        let msg3 = "https://discord.com/channels/365498559174410241/365498559723732993/606145193766551552";
+
+        assert_eq!(
+            get_invite_links(msg),
+            vec![
+                InviteLink::Code(String::from("vddW3D9")),
+                InviteLink::Code(String::from("abc"))
+            ]
+        );
+        assert_eq!(get_invite_links(msg2), vec![InviteLink::Redirect]);
+        assert_eq!(get_invite_links(msg3), vec![]);
+        assert_eq!(contains_invite_link("https://discord.gg/"), false);
+    }
+
+    #[test]
+    fn link_domain_extraction_works() {
+        let msg = "https://discord.gg/vddW3D9 https://Example.com/page http://cdn.example.org:8080/file.png";
+
+        assert_eq!(get_link_domains(msg), vec!["example.com", "cdn.example.org"]);
+        assert_eq!(get_link_domains("example.com"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn domain_allowlist_works() {
+        let allowed = vec![String::from("example.com"), String::from("*.gearbot.rocks")];
+
+        assert_eq!(is_domain_allowed("example.com", &allowed), true);
+        assert_eq!(is_domain_allowed("cdn.example.com", &allowed), true);
+        assert_eq!(is_domain_allowed("gearbot.rocks", &allowed), true);
+        assert_eq!(is_domain_allowed("notexample.com", &allowed), false);
+        assert_eq!(is_domain_allowed("example.com.evil.io", &allowed), false);
+    }
+
+    #[test]
+    fn blocked_extension_matcher_works() {
+        let blocked = vec![String::from("exe"), String::from(".scr")];
+
+        assert_eq!(get_blocked_extension("virus.exe", &blocked), Some("exe"));
+        assert_eq!(get_blocked_extension("VIRUS.EXE", &blocked), Some("EXE"));
+        assert_eq!(get_blocked_extension("invoice.pdf.exe", &blocked), Some("exe"));
+        assert_eq!(get_blocked_extension("screensaver.scr", &blocked), Some("scr"));
+        assert_eq!(get_blocked_extension("exe", &blocked), None);
+        assert_eq!(get_blocked_extension("cat.png", &blocked), None);
+    }
 }
@@ -1,13 +1,27 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use cron::Schedule;
+use twilight_model::channel::embed::Embed;
+use twilight_model::guild::Permissions;
+use unic_langid::LanguageIdentifier;
+
+use crate::translation::{FluArgs, GearBotString, Translations};
 
 pub use emoji::*;
+pub mod bulk;
 pub mod emoji;
+pub mod images;
 pub mod matchers;
 pub mod pattern;
+pub mod text;
 
 const MARKDOWN_REPALCEMENTS: &[&str; 7] = &["\\", "*", "_", "~", "|", "{", ">"];
 const DISCORD_EPOCH: i64 = 1_420_070_400_000;
 
+/// Discord cuts off audit log reasons after this many characters.
+pub const MAX_AUDIT_REASON_LENGTH: usize = 512;
+
 fn replace_markdown(msg: &mut String) {
     for c in MARKDOWN_REPALCEMENTS.iter() {
         if let Some(pos) = msg.find(c) {
@@ -35,6 +49,57 @@ fn replace_lookalikes(msg: &mut String) -> String {
     msg.replace('`', "ˋ")
 }
 
+/// Lists permissions the way discord names them in its settings, in bold.
+pub fn format_permissions(permissions: Permissions) -> String {
+    format!("{:?}", permissions)
+        .split(" | ")
+        .map(|permission| format!("**{}**", permission_name(permission)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Turns a permission flag name like ``MANAGE_MESSAGES`` into ``Manage Messages``.
+fn permission_name(flag: &str) -> String {
+    flag.split('_')
+        .map(|word| {
+            let word = word.to_lowercase();
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Renders the content of an embed as regular message text, for guilds that don't want embeds.
+///
+/// Colors and thumbnails have no plaintext equivalent and are left out, images become their link.
+pub fn embed_to_plaintext(embed: &Embed) -> String {
+    let mut parts = vec![];
+    if let Some(name) = embed.author.as_ref().and_then(|author| author.name.as_ref()) {
+        parts.push(name.clone());
+    }
+    if let Some(title) = &embed.title {
+        parts.push(format!("**{}**", title));
+    }
+    if let Some(description) = &embed.description {
+        parts.push(description.clone());
+    }
+    for field in &embed.fields {
+        parts.push(format!("**{}**\n{}", field.name, field.value));
+    }
+    if let Some(url) = embed.image.as_ref().and_then(|image| image.url.as_ref()) {
+        parts.push(url.clone());
+    }
+    if let Some(footer) = &embed.footer {
+        parts.push(format!("*{}*", footer.text));
+    }
+
+    parts.join("\n\n")
+}
+
 /// Cleans a message with the specified options.
 ///
 /// If an option is set to true, then it will be scrubbed out.
@@ -69,73 +134,242 @@ pub fn snowflake_timestamp(snowflake: u64) -> DateTime<Utc> {
     )
 }
 
-pub fn age(old: DateTime<Utc>, new: DateTime<Utc>, max_parts: i8) -> String {
-    let mut seconds = new.signed_duration_since(old).num_seconds();
-    let mut parts = 0;
-    let mut output = String::with_capacity(20);
+/// Fetches a (text) file, like an attachment someone uploaded along with a command.
+pub async fn download_text(url: &str) -> Result<String, reqwest::Error> {
+    reqwest::get(url).await?.error_for_status()?.text().await
+}
 
-    let years = (seconds as f64 / (60.0 * 60.0 * 24.0 * 365.25)) as i64;
-    if years > 0 {
-        seconds -= (years as f64 * 60.0 * 60.0 * 24.0 * 365.25) as i64;
-        output += &format!("{} years ", years);
-        parts += 1;
+/// Fetches a file without interpreting it, for binary files like images.
+pub async fn download_bytes(url: &str) -> Result<Vec<u8>, reqwest::Error> {
+    Ok(reqwest::get(url).await?.error_for_status()?.bytes().await?.to_vec())
+}
+
+/// The lowest snowflake that can be generated at the given time, to use as bound when filtering by id.
+pub fn timestamp_snowflake(timestamp: DateTime<Utc>) -> u64 {
+    ((timestamp.timestamp_millis() - DISCORD_EPOCH).max(0) as u64) << 22
+}
 
-        if parts == max_parts {
-            return output;
+/// Parses a duration like ``1d12h`` or ``30m``, made up of numbers followed by a unit (w, d, h, m or s).
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let mut total = 0;
+    let mut number = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
         }
+
+        let unit = match c.to_ascii_lowercase() {
+            'w' => 60 * 60 * 24 * 7,
+            'd' => 60 * 60 * 24,
+            'h' => 60 * 60,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let amount: i64 = number.parse().ok()?;
+        total = amount.checked_mul(unit)?.checked_add(total)?;
+        number.clear();
     }
 
-    let months = seconds / (60 * 60 * 24 * 30);
-    if months > 0 {
-        seconds -= months * 60 * 60 * 24 * 30;
-        output += &format!("{} months ", months);
-        parts += 1;
+    // chrono can't represent durations that don't fit in milliseconds
+    if !number.is_empty() || total == 0 || total > i64::MAX / 1000 {
+        return None;
+    }
 
-        if parts == max_parts {
-            return output;
-        }
+    Some(Duration::seconds(total))
+}
+
+/// Parses a standard 5 field cron expression (minute hour day month weekday), evaluated in UTC.
+pub fn parse_cron(expression: &str) -> Option<Schedule> {
+    let mut fields: Vec<String> = expression.split_whitespace().map(String::from).collect();
+    if fields.len() != 5 {
+        return None;
     }
+    fields[4] = shift_weekdays(&fields[4])?;
+
+    // the cron crate wants seconds as well
+    Schedule::from_str(&format!("0 {}", fields.join(" "))).ok()
+}
 
-    let weeks = seconds / (60 * 60 * 24 * 7);
-    if weeks > 0 {
-        seconds -= weeks * 60 * 60 * 24 * 7;
-        output += &format!("{} weeks ", weeks);
-        parts += 1;
-        if parts == max_parts {
-            return output;
+/// The cron crate numbers weekdays from 1 (sunday) to 7, standard cron from 0 (sunday) to 6 with 7 as sunday again.
+fn shift_weekdays(field: &str) -> Option<String> {
+    let mut parts = vec![];
+    for part in field.split(',') {
+        let (days, step) = match part.find('/') {
+            Some(index) => part.split_at(index),
+            None => (part, ""),
+        };
+        let shifted = match days.find('-') {
+            Some(index) => {
+                let end = &days[index + 1..];
+                let start = shift_weekday(&days[..index])?;
+                // a range up to 7 runs into sunday, which comes first for the cron crate
+                if end == "7" {
+                    if !step.is_empty() {
+                        return None;
+                    }
+                    format!("{}-7,1", start)
+                } else {
+                    format!("{}-{}", start, shift_weekday(end)?)
+                }
+            }
+            None => shift_weekday(days)?,
+        };
+        parts.push(format!("{}{}", shifted, step));
+    }
+
+    Some(parts.join(","))
+}
+
+fn shift_weekday(day: &str) -> Option<String> {
+    match day.parse::<u8>() {
+        Ok(number) if number <= 7 => Some((number % 7 + 1).to_string()),
+        Ok(_) => None,
+        // names and wildcards mean the same to both
+        Err(_) => Some(day.to_string()),
+    }
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
         }
+        std::mem::swap(&mut previous, &mut current);
     }
 
-    let days = seconds / (60 * 60 * 24);
-    if days > 0 {
-        seconds -= days * 60 * 60 * 24;
-        output += &format!("{} days ", days);
-        parts += 1;
-        if parts == max_parts {
-            return output;
+    previous[b.len()]
+}
+
+/// Line based diff between two texts, meant to be shown in a ``diff`` code block.
+///
+/// Removed lines are prefixed with ``- ``, added ones with ``+ `` and unchanged ones with two spaces.
+pub fn diff_lines(before: &str, after: &str) -> String {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // length of the longest common subsequence of the remaining lines at every position
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
         }
     }
 
-    let hours = seconds / (60 * 60);
-    if hours > 0 {
-        seconds -= hours * 60 * 60;
-        output += &format!("{} hours ", hours);
-        parts += 1;
-        if parts == max_parts {
-            return output;
+    let mut output = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            output.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            output.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            output.push(format!("+ {}", new[j]));
+            j += 1;
         }
     }
 
-    let minutes = seconds / 60;
-    if minutes > 0 {
-        seconds -= minutes * 60;
-        output += &format!("{} minutes ", minutes);
-        parts += 1;
-        if parts == max_parts {
-            return output;
+    output.join("\n")
+}
+
+/// Seconds in each unit a duration gets split into, from large to small. Years and months are averages.
+const DURATION_UNITS: [(GearBotString, i64); 7] = [
+    (GearBotString::DurationYears, 31_557_600),
+    (GearBotString::DurationMonths, 60 * 60 * 24 * 30),
+    (GearBotString::DurationWeeks, 60 * 60 * 24 * 7),
+    (GearBotString::DurationDays, 60 * 60 * 24),
+    (GearBotString::DurationHours, 60 * 60),
+    (GearBotString::DurationMinutes, 60),
+    (GearBotString::DurationSeconds, 1),
+];
+
+/// Formats a duration like ``2 days, 3 hours`` in the given language, using at most ``max_parts`` units.
+///
+/// Units are only left out when they are zero, the smaller ones that don't fit anymore get cut off.
+pub fn format_duration(
+    translations: &Translations,
+    language: &LanguageIdentifier,
+    duration: Duration,
+    max_parts: usize,
+) -> String {
+    let mut seconds = duration.num_seconds().max(0);
+    let mut parts = vec![];
+    for (key, unit) in DURATION_UNITS.iter() {
+        let count = seconds / unit;
+        seconds -= count * unit;
+        // always say something, even when it was over in an instant
+        if count > 0 || (*unit == 1 && parts.is_empty()) {
+            let args = FluArgs::with_capacity(1).add("count", count).generate();
+            parts.push(translations.get_text_with_args(language, *key, &args).to_string());
+        }
+        if parts.len() == max_parts {
+            break;
         }
     }
 
-    output += &format!("{} seconds", seconds);
-    output
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone, Weekday};
+
+    fn weekdays(expression: &str) -> Vec<Weekday> {
+        // a sunday
+        let start = Utc.ymd(2021, 1, 3).and_hms(0, 0, 0);
+        parse_cron(expression)
+            .unwrap()
+            .after(&start)
+            .take(7)
+            .map(|time| time.weekday())
+            .collect()
+    }
+
+    #[test]
+    fn weekdays_are_counted_from_sunday() {
+        assert_eq!(
+            weekdays("0 9 * * 1-5"),
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Mon,
+                Weekday::Tue
+            ]
+        );
+        assert_eq!(weekdays("0 9 * * 0")[0], Weekday::Sun);
+        assert_eq!(weekdays("0 9 * * 7")[0], Weekday::Sun);
+        assert_eq!(weekdays("0 9 * * 6,0")[..2], [Weekday::Sun, Weekday::Sat]);
+        assert_eq!(weekdays("0 9 * * 5-7")[..3], [Weekday::Sun, Weekday::Fri, Weekday::Sat]);
+    }
+
+    #[test]
+    fn weekday_names_still_work() {
+        assert_eq!(weekdays("0 9 * * Mon")[0], Weekday::Mon);
+        assert_eq!(weekdays("0 9 * * *").len(), 7);
+    }
+
+    #[test]
+    fn invalid_weekdays_are_rejected() {
+        assert!(parse_cron("0 9 * * 8").is_none());
+        assert!(parse_cron("0 9 * *").is_none());
+    }
 }
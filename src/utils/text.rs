@@ -0,0 +1,96 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The longest start of the text that fits in ``max_chars`` characters, which is how discord counts its limits.
+///
+/// Cuts only happen between graphemes, so multi-byte characters, emoji with modifiers and flags stay whole.
+pub fn truncate(text: &str, max_chars: usize) -> &str {
+    let mut chars = 0;
+    for (start, grapheme) in text.grapheme_indices(true) {
+        chars += grapheme.chars().count();
+        if chars > max_chars {
+            return &text[..start];
+        }
+    }
+    text
+}
+
+/// Like [`truncate`], but ends with an ellipsis (that counts towards the limit) when something had to go.
+pub fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    format!("{}…", truncate(text, max_chars.saturating_sub(1)))
+}
+
+/// Makes text safe to put inside an html element or attribute value.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_untouched() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("", 5), "");
+        assert_eq!(truncate_with_ellipsis("hello", 10), "hello");
+    }
+
+    #[test]
+    fn cjk_is_counted_per_character() {
+        // three bytes each, a byte based cut would land in the middle of one
+        assert_eq!(truncate("日本語のテキスト", 3), "日本語");
+        assert_eq!(truncate_with_ellipsis("日本語のテキスト", 4), "日本語…");
+    }
+
+    #[test]
+    fn emoji_are_not_split() {
+        // thumbs up with a skin tone is two characters
+        assert_eq!(truncate("ok👍🏽", 3), "ok");
+        assert_eq!(truncate("ok👍🏽", 4), "ok👍🏽");
+        // flags are made of two regional indicators
+        assert_eq!(truncate("🇳🇱🇧🇪", 3), "🇳🇱");
+        // a family is joined together out of five characters
+        assert_eq!(truncate("hi 👨‍👩‍👧 there", 6), "hi ");
+        assert_eq!(truncate_with_ellipsis("hi 👨‍👩‍👧 there", 8), "hi 👨‍👩‍👧…");
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_letter() {
+        // e followed by a combining acute accent
+        assert_eq!(truncate("cafe\u{301}s", 4), "caf");
+        assert_eq!(truncate("cafe\u{301}s", 5), "cafe\u{301}");
+    }
+
+    #[test]
+    fn results_stay_within_the_limit() {
+        let text = "a👍🏽b🇳🇱c日本語d👨‍👩‍👧e".repeat(20);
+        for limit in 0..text.chars().count() {
+            assert!(truncate(&text, limit).chars().count() <= limit);
+            assert!(truncate_with_ellipsis(&text, limit.max(1)).chars().count() <= limit.max(1));
+        }
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        assert_eq!(escape_html("plain text"), "plain text");
+        assert_eq!(
+            escape_html("<b onclick=\"x('y')\">&</b>"),
+            "&lt;b onclick=&quot;x(&#39;y&#39;)&quot;&gt;&amp;&lt;/b&gt;"
+        );
+    }
+}